@@ -43,6 +43,26 @@ pub fn wasm_bindgen(attr: TokenStream, input: TokenStream) -> TokenStream {
     output.into()
 }
 
+/// Derive the wry-bindgen codec traits for a struct.
+///
+/// The codec traits only exist on desktop targets, so nothing is generated for wasm32.
+#[proc_macro_derive(BinaryCodec, attributes(codec))]
+pub fn binary_codec(input: TokenStream) -> TokenStream {
+    let wry_expansion = match wry_bindgen_macro_support::expand_binary_codec(input.into()) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    };
+
+    let output = quote! {
+        #[cfg(not(target_arch = "wasm32"))]
+        const _: () = {
+            #wry_expansion
+        };
+    };
+
+    output.into()
+}
+
 /// The link_to proc-macro for JavaScript module linking.
 ///
 /// This only works on wasm32 targets; on desktop it panics.
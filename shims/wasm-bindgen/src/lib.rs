@@ -12,6 +12,7 @@
 
 // Re-export the shim macro (works for both targets)
 pub use wasm_bindgen_macro::__wasm_bindgen_class_marker;
+pub use wasm_bindgen_macro::BinaryCodec;
 pub use wasm_bindgen_macro::link_to;
 pub use wasm_bindgen_macro::wasm_bindgen;
 
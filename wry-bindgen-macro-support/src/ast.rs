@@ -88,6 +88,8 @@ pub struct ImportType {
     pub vendor_prefixes: Vec<Ident>,
     /// Custom is_type_of expression for type checking
     pub is_type_of: Option<syn::Expr>,
+    /// Whether the type is marked `final`, which trusts casts without an instanceof check
+    pub is_final: bool,
 }
 
 /// An imported JavaScript function
@@ -224,6 +226,8 @@ pub struct ExportStruct {
     pub fields: Vec<StructField>,
     /// Whether to generate toJSON/toString methods
     pub is_inspectable: bool,
+    /// Whether to generate a `[Symbol.iterator]` method backed by `WryIterable`
+    pub is_iterable: bool,
     /// User-provided attributes (like #[derive(...)])
     pub rust_attrs: Vec<syn::Attribute>,
 }
@@ -252,6 +256,8 @@ pub struct StructField {
 pub struct ExportMethod {
     /// The struct this method belongs to
     pub class: Ident,
+    /// JavaScript class name of the struct (from `js_class`, defaults to the struct name)
+    pub js_class: String,
     /// Rust method name
     pub rust_name: Ident,
     /// JavaScript method name (may differ from rust_name)
@@ -264,6 +270,8 @@ pub struct ExportMethod {
     pub ret: Option<Type>,
     /// Whether to wrap in try-catch
     pub catch: bool,
+    /// Whether this is an `async fn`, which returns a promise in JS
+    pub is_async: bool,
     /// User-provided attributes (like #[cfg(...)] and #[doc = "..."])
     pub rust_attrs: Vec<syn::Attribute>,
     /// Method visibility
@@ -348,11 +356,13 @@ pub fn parse_item(program: &mut Program, item: syn::Item) -> syn::Result<()> {
 fn parse_struct(s: syn::ItemStruct, attrs: &BindgenAttrs) -> syn::Result<ExportStruct> {
     let rust_name = s.ident.clone();
     let js_name = attrs
-        .js_name()
+        .js_class()
+        .or(attrs.js_name())
         .map(|s| s.to_string())
         .unwrap_or_else(|| rust_name.to_string());
 
     let is_inspectable = attrs.inspectable.is_some();
+    let is_iterable = attrs.iterable.is_some();
 
     // Parse fields
     let mut fields = Vec::new();
@@ -412,6 +422,7 @@ fn parse_struct(s: syn::ItemStruct, attrs: &BindgenAttrs) -> syn::Result<ExportS
         js_name,
         fields,
         is_inspectable,
+        is_iterable,
         rust_attrs,
     })
 }
@@ -488,9 +499,13 @@ fn parse_impl_method(
     class: &Ident,
     method: &syn::ImplItemFn,
     method_attrs: BindgenAttrs,
-    _block_attrs: &BindgenAttrs,
+    block_attrs: &BindgenAttrs,
 ) -> syn::Result<ExportMethod> {
     let rust_name = method.sig.ident.clone();
+    let js_class = block_attrs
+        .js_class()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| class.to_string());
     let js_name = method_attrs
         .js_name()
         .map(|s| s.to_string())
@@ -538,7 +553,9 @@ fn parse_impl_method(
     let kind = if method_attrs.is_constructor() {
         ExportMethodKind::Constructor
     } else if let Some((_, name)) = &method_attrs.getter {
-        let property = name.clone().unwrap_or_else(|| js_name.clone());
+        let property = name
+            .clone()
+            .unwrap_or_else(|| js_name.strip_prefix("get_").unwrap_or(&js_name).to_string());
         ExportMethodKind::Getter { property }
     } else if let Some((_, name)) = &method_attrs.setter {
         let property = name
@@ -551,6 +568,19 @@ fn parse_impl_method(
         ExportMethodKind::StaticMethod
     };
 
+    let is_async = method.sig.asyncness.is_some();
+    if is_async
+        && !matches!(
+            kind,
+            ExportMethodKind::Method { .. } | ExportMethodKind::StaticMethod
+        )
+    {
+        return Err(syn::Error::new_spanned(
+            method.sig.asyncness,
+            "only methods and static methods can be async",
+        ));
+    }
+
     // Parse return type
     let ret = match &method.sig.output {
         syn::ReturnType::Default => None,
@@ -567,12 +597,14 @@ fn parse_impl_method(
 
     Ok(ExportMethod {
         class: class.clone(),
+        js_class,
         rust_name,
         js_name,
         kind,
         arguments,
         ret,
         catch: method_attrs.catch.is_some(),
+        is_async,
         rust_attrs,
         vis: method.vis.clone(),
         body: method.block.clone(),
@@ -684,6 +716,9 @@ fn extract_wasm_bindgen_attrs(attrs: &[syn::Attribute]) -> syn::Result<BindgenAt
             if let Some(v) = parsed.is_type_of {
                 combined.is_type_of = Some(v);
             }
+            if let Some(span) = parsed.final_ {
+                combined.final_ = Some(span);
+            }
             combined.vendor_prefixes.extend(parsed.vendor_prefixes);
         }
     }
@@ -848,6 +883,7 @@ fn parse_foreign_type(t: syn::ForeignItemType, attrs: BindgenAttrs) -> syn::Resu
     let typescript_type = attrs.typescript_type.map(|(_, t)| t);
     let vendor_prefixes: Vec<Ident> = attrs.vendor_prefixes.into_iter().map(|(_, i)| i).collect();
     let is_type_of = attrs.is_type_of.map(|(_, e)| e);
+    let is_final = attrs.final_.is_some();
 
     // Extract derive attributes (non-wasm_bindgen attributes that should be preserved)
     let derives: Vec<syn::Attribute> = t
@@ -866,6 +902,7 @@ fn parse_foreign_type(t: syn::ForeignItemType, attrs: BindgenAttrs) -> syn::Resu
         derives,
         vendor_prefixes,
         is_type_of,
+        is_final,
     })
 }
 
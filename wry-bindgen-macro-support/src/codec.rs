@@ -0,0 +1,291 @@
+//! Implementation of `#[derive(BinaryCodec)]`
+//!
+//! The derive implements `EncodeTypeDef`, `BinaryEncode`, `BinaryDecode` and
+//! `BatchableResult` for a struct. Structs with named fields are sent as a plain JS
+//! object keyed by field name, tuple structs as a JS array of their fields and newtypes
+//! (tuple structs with one field) exactly like the wrapped type.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Fields, FieldsUnnamed, Index, LitStr, Path};
+
+/// Options from `#[codec(...)]` on the struct itself
+#[derive(Default)]
+struct ContainerAttrs {
+    /// `#[codec(crate = path)]` - path to the wasm_bindgen crate
+    crate_path: Option<Path>,
+}
+
+impl ContainerAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut result = ContainerAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("codec")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("crate") {
+                    if result.crate_path.is_some() {
+                        return Err(meta.error("duplicate `crate` attribute"));
+                    }
+                    result.crate_path = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown codec attribute"))
+                }
+            })?;
+        }
+        Ok(result)
+    }
+
+    fn crate_path_tokens(&self) -> TokenStream {
+        match &self.crate_path {
+            Some(path) => quote!(#path),
+            None => quote!(wasm_bindgen),
+        }
+    }
+}
+
+/// Options from `#[codec(...)]` on a field
+#[derive(Default)]
+struct FieldAttrs {
+    /// `#[codec(rename = "jsName")]` - the key used for the field in JS
+    rename: Option<LitStr>,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut result = FieldAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("codec")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    if result.rename.is_some() {
+                        return Err(meta.error("duplicate `rename` attribute"));
+                    }
+                    result.rename = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown codec attribute"))
+                }
+            })?;
+        }
+        Ok(result)
+    }
+}
+
+/// Expand `#[derive(BinaryCodec)]`.
+pub fn expand_binary_codec(input: TokenStream) -> syn::Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+    let krate = ContainerAttrs::parse(&input.attrs)?.crate_path_tokens();
+    let name = &input.ident;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "BinaryCodec can only be derived for structs",
+        ));
+    };
+    if data.fields.len() > u8::MAX as usize {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "BinaryCodec supports at most 255 fields",
+        ));
+    }
+    let field_types = data
+        .fields
+        .iter()
+        .map(|field| &field.ty)
+        .collect::<Vec<_>>();
+
+    // Generic fields must implement the codec traits for the struct to implement them
+    let mut generics = input.generics.clone();
+    if !input.generics.params.is_empty() {
+        let where_clause = generics.make_where_clause();
+        for ty in &field_types {
+            where_clause.predicates.push(syn::parse_quote! {
+                #ty: #krate::EncodeTypeDef + #krate::BinaryEncode + #krate::BinaryDecode
+            });
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = match &data.fields {
+        Fields::Named(fields) => fields,
+        Fields::Unnamed(fields) => {
+            return expand_unnamed(&krate, name, &generics, fields, &field_types);
+        }
+        Fields::Unit => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "BinaryCodec can not be derived for unit structs",
+            ));
+        }
+    };
+
+    let mut field_idents = Vec::new();
+    let mut js_names = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        let js_name = attrs
+            .rename
+            .map(|rename| rename.value())
+            .unwrap_or_else(|| ident.unraw().to_string());
+        field_idents.push(ident);
+        js_names.push(js_name);
+    }
+    let field_count = field_idents.len() as u8;
+
+    let decoded = field_idents
+        .iter()
+        .map(|ident| format_ident!("__{}", ident.unraw().to_string()))
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #krate::EncodeTypeDef for #name #ty_generics #where_clause {
+            fn encode_type_def(buf: &mut #krate::alloc::vec::Vec<u8>) {
+                // Push Struct tag and field count
+                buf.push(#krate::encode::TypeTag::Struct as u8);
+                buf.push(#field_count);
+                // Push each field name (length as u32 + bytes) followed by its type
+                #(
+                    let name: &str = #js_names;
+                    buf.extend_from_slice(&(name.len() as u32).to_le_bytes());
+                    buf.extend_from_slice(name.as_bytes());
+                    <#field_types as #krate::EncodeTypeDef>::encode_type_def(buf);
+                )*
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #krate::BinaryEncode for #name #ty_generics #where_clause {
+            fn encode(self, encoder: &mut #krate::EncodedData) {
+                #(
+                    <#field_types as #krate::BinaryEncode>::encode(self.#field_idents, encoder);
+                )*
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #krate::BinaryDecode for #name #ty_generics #where_clause {
+            fn decode(decoder: &mut #krate::DecodedData) -> ::core::result::Result<Self, #krate::DecodeError> {
+                #(
+                    let #decoded = <#field_types as #krate::BinaryDecode>::decode(decoder)?;
+                )*
+                ::core::result::Result::Ok(Self {
+                    #(#field_idents: #decoded,)*
+                })
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #krate::BatchableResult for #name #ty_generics #where_clause {}
+    })
+}
+
+/// Expand `#[derive(BinaryCodec)]` for a tuple struct.
+///
+/// A newtype is transparent: it uses the type definition and encoding of its field, so
+/// `struct Id(u64)` looks like a plain number to JS. Tuple structs with several fields
+/// are sent as a JS array, the same way tuples are.
+fn expand_unnamed(
+    krate: &TokenStream,
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    fields: &FieldsUnnamed,
+    field_types: &[&syn::Type],
+) -> syn::Result<TokenStream> {
+    if let Some(field) = fields
+        .unnamed
+        .iter()
+        .find(|field| field.attrs.iter().any(|attr| attr.path().is_ident("codec")))
+    {
+        return Err(syn::Error::new(
+            field.span(),
+            "codec attributes are only supported on named fields",
+        ));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    if let [inner] = field_types {
+        // Forward placeholders so a newtype around a JsValue can be batched like one
+        let mut batchable_generics = generics.clone();
+        batchable_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! { #inner: #krate::BatchableResult });
+        let batchable_where_clause = &batchable_generics.where_clause;
+
+        return Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics #krate::EncodeTypeDef for #name #ty_generics #where_clause {
+                fn encode_type_def(buf: &mut #krate::alloc::vec::Vec<u8>) {
+                    <#inner as #krate::EncodeTypeDef>::encode_type_def(buf);
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #krate::BinaryEncode for #name #ty_generics #where_clause {
+                fn encode(self, encoder: &mut #krate::EncodedData) {
+                    <#inner as #krate::BinaryEncode>::encode(self.0, encoder);
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #krate::BinaryDecode for #name #ty_generics #where_clause {
+                fn decode(decoder: &mut #krate::DecodedData) -> ::core::result::Result<Self, #krate::DecodeError> {
+                    <#inner as #krate::BinaryDecode>::decode(decoder).map(Self)
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #krate::BatchableResult for #name #ty_generics #batchable_where_clause {
+                fn try_placeholder(runtime: &mut #krate::batch::Runtime) -> ::core::option::Option<Self> {
+                    <#inner as #krate::BatchableResult>::try_placeholder(runtime).map(Self)
+                }
+            }
+        });
+    }
+
+    let field_count = field_types.len() as u8;
+    let indices = (0..field_types.len()).map(Index::from).collect::<Vec<_>>();
+    let decoded = (0..field_types.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #krate::EncodeTypeDef for #name #ty_generics #where_clause {
+            fn encode_type_def(buf: &mut #krate::alloc::vec::Vec<u8>) {
+                // Push Tuple tag and field count, then each field's type in order
+                buf.push(#krate::encode::TypeTag::Tuple as u8);
+                buf.push(#field_count);
+                #(
+                    <#field_types as #krate::EncodeTypeDef>::encode_type_def(buf);
+                )*
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #krate::BinaryEncode for #name #ty_generics #where_clause {
+            fn encode(self, encoder: &mut #krate::EncodedData) {
+                #(
+                    <#field_types as #krate::BinaryEncode>::encode(self.#indices, encoder);
+                )*
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #krate::BinaryDecode for #name #ty_generics #where_clause {
+            fn decode(decoder: &mut #krate::DecodedData) -> ::core::result::Result<Self, #krate::DecodeError> {
+                #(
+                    let #decoded = <#field_types as #krate::BinaryDecode>::decode(decoder)?;
+                )*
+                ::core::result::Result::Ok(Self(#(#decoded),*))
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #krate::BatchableResult for #name #ty_generics #where_clause {}
+    })
+}
@@ -278,33 +278,16 @@ fn generate_type(ty: &ImportType, krate: &TokenStream) -> syn::Result<TokenStrea
     // Generate JsCast implementation with actual instanceof check
     let js_name = &ty.js_name;
 
-    // Generate JavaScript instanceof check code with vendor prefix fallback
-    // Always generate a safe check that returns false if the class doesn't exist,
-    // matching wasm-bindgen's try-catch behavior
-    let instanceof_js_code = if ty.vendor_prefixes.is_empty() {
-        // Simple case: check if class exists before instanceof
-        format!("(a0) => typeof {js_name} !== 'undefined' && a0 instanceof {js_name}")
-    } else {
-        // Generate vendor-prefixed fallback:
-        // (a0) => a0 instanceof (typeof Foo !== 'undefined' ? Foo : (typeof webkitFoo !== 'undefined' ? webkitFoo : ...))
-        let mut class_expr = format!("(typeof {js_name} !== 'undefined' ? {js_name} : ");
-        for (i, prefix) in ty.vendor_prefixes.iter().enumerate() {
-            let prefixed = format!("{prefix}{js_name}");
-            if i == ty.vendor_prefixes.len() - 1 {
-                // Last prefix - use Object as final fallback (which will make instanceof return false for non-objects)
-                class_expr.push_str(&format!(
-                    "(typeof {prefixed} !== 'undefined' ? {prefixed} : Object)"
-                ));
-            } else {
-                class_expr.push_str(&format!(
-                    "(typeof {prefixed} !== 'undefined' ? {prefixed} : "
-                ));
-            }
-        }
-        // Close all the parentheses
-        class_expr.push(')');
-        format!("(a0) => a0 instanceof {class_expr}")
-    };
+    // Check the global class and then each vendor-prefixed fallback by name. The JS helper
+    // returns false instead of throwing when a class does not exist, matching
+    // wasm-bindgen's try-catch behavior
+    let class_names: Vec<String> = std::iter::once(js_name.clone())
+        .chain(
+            ty.vendor_prefixes
+                .iter()
+                .map(|prefix| format!("{prefix}{js_name}")),
+        )
+        .collect();
 
     // Generate is_type_of implementation if provided
     let is_type_of_impl = ty.is_type_of.as_ref().map(|is_type_of| {
@@ -317,10 +300,22 @@ fn generate_type(ty: &ImportType, krate: &TokenStream) -> syn::Result<TokenStrea
         }
     });
 
+    // Types marked `final` trust the declaration and skip the instanceof round-trip entirely
+    let instanceof_body = if ty.is_final {
+        quote_spanned! {span=>
+            let _ = __val;
+            true
+        }
+    } else {
+        quote_spanned! {span=>
+            #(#krate::__rt::instanceof_global(__val, #class_names))||*
+        }
+    };
+
     let jscast_impl = quote_spanned! {span=>
         impl #krate::JsCast for #rust_name {
             fn instanceof(__val: &#krate::JsValue) -> bool {
-                #krate::__wry_call_js_function!(#instanceof_js_code, fn(&#krate::JsValue) -> bool, (__val))
+                #instanceof_body
             }
 
             #is_type_of_impl
@@ -385,10 +380,11 @@ fn generate_function(
     // For non-async functions, generate a simple closure that returns a constant string
     let js_code = generate_js_code(func, vendor_prefixes, prefix, false);
     let js_code_str = js_code.to_arrow_function();
+    let js_import_name = import_name(func);
 
     // Generate the function body
     let func_body = quote_spanned! {span=>
-        #krate::__wry_call_js_function!(#js_code_str, fn(#fn_types) -> #ret_type, (#call_values))
+        #krate::__wry_call_js_function!(name = #js_import_name, #js_code_str, fn(#fn_types) -> #ret_type, (#call_values))
     };
 
     // Get the rust attributes to forward (like #[cfg(...)] and #[doc = "..."])
@@ -493,6 +489,7 @@ fn generate_async_function(
     let fn_params = &args.fn_params;
     let fn_types = &args.fn_types;
     let call_values = &args.call_values;
+    let js_import_name = import_name(func);
 
     // Generate the async function body
     // - Call JS function which returns a Promise (as JsValue)
@@ -500,7 +497,7 @@ fn generate_async_function(
     // - Wrap in JsFuture and await
     let async_body = quote_spanned! {span=>
         // Call the function, get Promise as JsValue
-        let __promise_val = #krate::__wry_call_js_function!(#js_code_str, fn(#fn_types) -> #krate::JsValue, (#call_values));
+        let __promise_val = #krate::__wry_call_js_function!(name = #js_import_name, #js_code_str, fn(#fn_types) -> #krate::JsValue, (#call_values));
 
         // Cast to js_sys::Promise and wrap in JsFuture
         let __promise: ::wasm_bindgen_futures::js_sys::Promise =
@@ -644,6 +641,68 @@ fn generate_vendor_prefixed_constructor(class: &str, prefixes: &[String], prefix
     result
 }
 
+/// Join a JS namespace and name, as in `console.log`
+fn qualified_name(js_namespace: &Option<Vec<String>>, js_name: &str) -> String {
+    match js_namespace {
+        Some(ns) if !ns.is_empty() => format!("{}.{js_name}", ns.join(".")),
+        _ => js_name.to_string(),
+    }
+}
+
+/// The name an imported function is known by outside of its generated JS code, such as
+/// `add`, `Array.prototype.push` or `new Array`. The test harness matches handlers by it.
+fn import_name(func: &ImportFunction) -> String {
+    let receiver_class = |receiver: &syn::Type| {
+        let class = func.js_class.clone().unwrap_or_else(|| {
+            let receiver = match receiver {
+                syn::Type::Reference(reference) => &*reference.elem,
+                receiver => receiver,
+            };
+            match receiver {
+                syn::Type::Path(path) => path
+                    .path
+                    .segments
+                    .last()
+                    .map(|segment| segment.ident.to_string())
+                    .unwrap_or_default(),
+                _ => String::new(),
+            }
+        });
+        qualified_name(&func.js_namespace, &class)
+    };
+    match &func.kind {
+        ImportFunctionKind::Normal => qualified_name(&func.js_namespace, &func.js_name),
+        ImportFunctionKind::Method { receiver } => {
+            format!("{}.prototype.{}", receiver_class(receiver), func.js_name)
+        }
+        ImportFunctionKind::Getter { receiver, property } => {
+            format!("get {}.prototype.{property}", receiver_class(receiver))
+        }
+        ImportFunctionKind::Setter { receiver, property } => {
+            format!("set {}.prototype.{property}", receiver_class(receiver))
+        }
+        ImportFunctionKind::IndexingGetter { receiver } => {
+            format!("get {}[]", receiver_class(receiver))
+        }
+        ImportFunctionKind::IndexingSetter { receiver } => {
+            format!("set {}[]", receiver_class(receiver))
+        }
+        ImportFunctionKind::IndexingDeleter { receiver } => {
+            format!("delete {}[]", receiver_class(receiver))
+        }
+        ImportFunctionKind::Constructor { class } => {
+            format!("new {}", qualified_name(&func.js_namespace, class))
+        }
+        ImportFunctionKind::StaticMethod { class } => {
+            format!(
+                "{}.{}",
+                qualified_name(&func.js_namespace, class),
+                func.js_name
+            )
+        }
+    }
+}
+
 /// Generate JavaScript code for the function
 fn generate_js_code(
     func: &ImportFunction,
@@ -869,6 +928,7 @@ fn generate_static(
 
     // Generate JavaScript code to access the static
     let js_code = generate_static_js_code(st, prefix);
+    let js_import_name = qualified_name(&st.js_namespace, &st.js_name);
 
     assert!(st.thread_local_v2);
     let id = next_thread_local_id();
@@ -880,7 +940,7 @@ fn generate_static(
             // This can't be named __init for compat with older rustc versions
             // https://github.com/rust-lang/rust/issues/147006
             fn __init_wbg() -> #ty {
-                #krate::__wry_call_js_function!(#js_code, fn() -> #ty, ())
+                #krate::__wry_call_js_function!(name = #js_import_name, #js_code, fn() -> #ty, ())
             }
             #krate::JsThreadLocal::new(__init_wbg, #id)
         };
@@ -1075,7 +1135,7 @@ fn generate_export_struct(s: &ExportStruct, krate: &TokenStream) -> syn::Result<
     // Generate field getters and setters
     let mut field_impls = TokenStream::new();
     for field in &s.fields {
-        field_impls.extend(generate_field_accessor(rust_name, field, krate)?);
+        field_impls.extend(generate_field_accessor(rust_name, js_name, field, krate)?);
     }
 
     // Generate drop function
@@ -1108,6 +1168,13 @@ fn generate_export_struct(s: &ExportStruct, krate: &TokenStream) -> syn::Result<
         TokenStream::new()
     };
 
+    // Generate the iterator protocol if enabled
+    let iterable_impl = if s.is_iterable {
+        generate_iterable(rust_name, js_name, krate)?
+    } else {
+        TokenStream::new()
+    };
+
     // Generate From<StructName> for JsValue - inserts into object store and returns handle
     let into_jsvalue_impl = quote_spanned! {span=>
         impl ::core::convert::From<#rust_name> for #krate::JsValue {
@@ -1162,22 +1229,30 @@ fn generate_export_struct(s: &ExportStruct, krate: &TokenStream) -> syn::Result<
         impl #krate::BatchableResult for #rust_name {}
     };
 
+    // Mark the struct as exported so exported methods can take it by reference
+    let exported_class_impl = quote_spanned! {span=>
+        impl #krate::object_store::ExportedClass for #rust_name {}
+    };
+
     Ok(quote_spanned! {span=>
         #struct_def
         #field_impls
         #drop_impl
         #inspectable_impl
+        #iterable_impl
         #into_jsvalue_impl
         #encode_type_def_impl
         #binary_encode_impl
         #binary_decode_impl
         #batchable_result_impl
+        #exported_class_impl
     })
 }
 
 /// Generate getter and setter for a struct field
 fn generate_field_accessor(
     struct_name: &syn::Ident,
+    js_class_name: &str,
     field: &StructField,
     krate: &TokenStream,
 ) -> syn::Result<TokenStream> {
@@ -1191,28 +1266,30 @@ fn generate_field_accessor(
         return Ok(TokenStream::new());
     }
 
-    let struct_name_str = struct_name.to_string();
-    let getter_name = format!("{struct_name_str}::{js_field_name}_get");
-    let setter_name = format!("{struct_name_str}::{js_field_name}_set");
+    let getter_name = format!("{js_class_name}::{js_field_name}_get");
+    let setter_name = format!("{js_class_name}::{js_field_name}_set");
 
     // Generate getter
+    // Accessors return an error for dropped objects, so JS throws instead of Rust panicking
     let getter_body = if field.getter_with_clone {
         quote_spanned! {span=>
-            #krate::object_store::with_object::<#struct_name, _>(handle, |obj| {
+            let encoder = #krate::object_store::try_with_object::<#struct_name, _>(handle, |obj| {
                 let val = ::core::clone::Clone::clone(&obj.#field_name);
                 let mut encoder = #krate::EncodedData::new();
                 <#field_ty as #krate::BinaryEncode>::encode(val, &mut encoder);
-                Ok(encoder)
-            })
+                encoder
+            })?;
+            Ok(encoder)
         }
     } else {
         quote_spanned! {span=>
-            #krate::object_store::with_object::<#struct_name, _>(handle, |obj| {
+            let encoder = #krate::object_store::try_with_object::<#struct_name, _>(handle, |obj| {
                 let val = obj.#field_name;
                 let mut encoder = #krate::EncodedData::new();
                 <#field_ty as #krate::BinaryEncode>::encode(val, &mut encoder);
-                Ok(encoder)
-            })
+                encoder
+            })?;
+            Ok(encoder)
         }
     };
 
@@ -1243,9 +1320,9 @@ fn generate_field_accessor(
                     |decoder| {
                         let handle = <#krate::object_store::ObjectHandle as #krate::BinaryDecode>::decode(decoder)?;
                         let val = <#field_ty as #krate::BinaryDecode>::decode(decoder)?;
-                        #krate::object_store::with_object_mut::<#struct_name, _>(handle, |obj| {
+                        #krate::object_store::try_with_object_mut::<#struct_name, _>(handle, |obj| {
                             obj.#field_name = val;
-                        });
+                        })?;
                         Ok(#krate::EncodedData::new())
                     }
                 );
@@ -1260,7 +1337,6 @@ fn generate_field_accessor(
     };
 
     // Generate JsClassMemberSpec for the property getter
-    let js_class_name = struct_name.to_string();
     let getter_member_spec = quote_spanned! {span=>
         const _: () = {
             #[allow(non_upper_case_globals)]
@@ -1331,8 +1407,6 @@ fn generate_inspectable(
         .map(|f| &f.rust_name)
         .collect();
 
-    let struct_name_str = struct_name.to_string();
-
     let js_name_str = js_name.to_string();
 
     Ok(quote_spanned! {span=>
@@ -1387,7 +1461,7 @@ fn generate_inspectable(
                 |decoder| {
                     let handle = <#krate::object_store::ObjectHandle as #krate::BinaryDecode>::decode(decoder)?;
                     #krate::object_store::with_object::<#struct_name, _>(handle, |obj| {
-                        let s = ::alloc::format!("[object {}]", #struct_name_str);
+                        let s = ::alloc::format!("[object {}]", #js_name_str);
                         let mut encoder = #krate::EncodedData::new();
                         <::alloc::string::String as #krate::BinaryEncode>::encode(s, &mut encoder);
                         Ok(encoder)
@@ -1418,6 +1492,50 @@ fn generate_inspectable(
     })
 }
 
+/// Generate the `[Symbol.iterator]` method for iterable structs
+fn generate_iterable(
+    struct_name: &syn::Ident,
+    js_name: &str,
+    krate: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let span = struct_name.span();
+    let next_name = format!("{js_name}::__next");
+    let js_name_str = js_name.to_string();
+
+    Ok(quote_spanned! {span=>
+        const _: () = {
+            #[allow(non_upper_case_globals)]
+            static __NEXT_SPEC: #krate::JsExportSpec = #krate::JsExportSpec::new(
+                #next_name,
+                |decoder| {
+                    let handle = <#krate::object_store::ObjectHandle as #krate::BinaryDecode>::decode(decoder)?;
+                    #krate::object_store::iterable_next::<#struct_name>(handle)
+                }
+            );
+
+            #krate::inventory::submit! {
+                __NEXT_SPEC
+            }
+        };
+
+        // JsClassMemberSpec for the [Symbol.iterator] method
+        const _: () = {
+            #[allow(non_upper_case_globals)]
+            static __ITERATOR_MEMBER_SPEC: #krate::JsClassMemberSpec = #krate::JsClassMemberSpec::new(
+                #js_name_str,
+                "[Symbol.iterator]",
+                #next_name,
+                0,
+                #krate::JsClassMemberKind::Iterator
+            );
+
+            #krate::inventory::submit! {
+                __ITERATOR_MEMBER_SPEC
+            }
+        };
+    })
+}
+
 /// Generate code for an exported method
 fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Result<TokenStream> {
     let class = &method.class;
@@ -1425,26 +1543,81 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
     let js_name = &method.js_name;
     let span = rust_name.span();
 
-    let class_str = class.to_string();
+    let class_str = &method.js_class;
     let export_name = format!("{class_str}::{js_name}");
 
     // Generate argument decoding
-    let arg_names: Vec<_> = method.arguments.iter().map(|a| &a.name).collect();
-    let arg_types: Vec<_> = method.arguments.iter().map(|a| &a.ty).collect();
+    // References to other exported objects are passed as handles and borrowed from
+    // the object store for the duration of the call. Borrowing fails with a JS error if
+    // the object is of another class or is already in use, including when it is also
+    // the receiver of the call.
+    let has_receiver = matches!(method.kind, ExportMethodKind::Method { .. });
+    let decode_args: TokenStream = method
+        .arguments
+        .iter()
+        .map(|arg| {
+            let name = &arg.name;
+            let ty = &arg.ty;
+            match borrowed_export_type(ty) {
+                Some(inner) => {
+                    let check_receiver = has_receiver.then(|| {
+                        quote_spanned! {span=>
+                            if #name == handle {
+                                return Err(#krate::object_store::BorrowObjectError::AlreadyBorrowed(handle).into());
+                            }
+                        }
+                    });
+                    quote_spanned! {span=>
+                        let #name = <#krate::object_store::ObjectHandle as #krate::BinaryDecode>::decode(decoder)?;
+                        #check_receiver
+                        let #name = #krate::object_store::try_borrow_object::<#inner>(#name)?;
+                    }
+                }
+                None => quote_spanned! {span=>
+                    let #name = <#ty as #krate::BinaryDecode>::decode(decoder)?;
+                },
+            }
+        })
+        .collect();
+    let arg_names: Vec<_> = method
+        .arguments
+        .iter()
+        .map(|arg| {
+            let name = &arg.name;
+            if borrowed_export_type(&arg.ty).is_some() {
+                quote_spanned! {span=> &*#name }
+            } else {
+                quote_spanned! {span=> #name }
+            }
+        })
+        .collect();
 
-    let decode_args = quote_spanned! {span=>
-        #(
-            let #arg_names = <#arg_types as #krate::BinaryDecode>::decode(decoder)?;
-        )*
+    // Exports returning `Result<T, E>` encode the `Ok` value as a `T`, and the `Err`
+    // value is converted into a JsValue and thrown in JS
+    let ok_ty = method.ret.as_ref().and_then(extract_result_ok_type);
+    let unwrap_result = match &ok_ty {
+        Some(_) => quote_spanned! {span=>
+            let result = match result {
+                ::core::result::Result::Ok(value) => value,
+                ::core::result::Result::Err(err) => {
+                    return ::core::result::Result::Err(#krate::ExportError::throw(err));
+                }
+            };
+        },
+        None => quote! {},
     };
 
     // Generate the method call and return encoding based on kind
     let method_body = match &method.kind {
+        ExportMethodKind::Method { .. } | ExportMethodKind::StaticMethod if method.is_async => {
+            generate_async_export_body(method, krate, &decode_args, &arg_names, &export_name)
+        }
         ExportMethodKind::Constructor => {
             // Constructor: create new instance and store in object store
             quote_spanned! {span=>
                 #decode_args
                 let result = #class::#rust_name(#(#arg_names),*);
+                #unwrap_result
                 let handle = #krate::object_store::insert_object(result);
                 let mut encoder = #krate::EncodedData::new();
                 <#krate::object_store::ObjectHandle as #krate::BinaryEncode>::encode(handle, &mut encoder);
@@ -1452,27 +1625,30 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
             }
         }
         ExportMethodKind::Method { self_ty } => {
-            // Instance method: get object from store, call method
+            // Instance method: get object from store, call method. `&self` methods share the
+            // object with pending async `&self` methods, other calls on an object a pending
+            // async method holds throw in JS
             let call = match self_ty {
                 SelfType::RefShared => {
                     quote_spanned! {span=>
-                        #krate::object_store::with_object::<#class, _>(handle, |obj| {
+                        {
+                            let obj = #krate::object_store::try_share_object::<#class>(handle)?;
                             obj.#rust_name(#(#arg_names),*)
-                        })
+                        }
                     }
                 }
                 SelfType::RefMutable => {
                     quote_spanned! {span=>
-                        #krate::object_store::with_object_mut::<#class, _>(handle, |obj| {
+                        #krate::object_store::try_with_object_mut::<#class, _>(handle, |obj| {
                             obj.#rust_name(#(#arg_names),*)
-                        })
+                        })?
                     }
                 }
                 SelfType::ByValue => {
                     // Consuming method: remove from store
                     quote_spanned! {span=>
                         {
-                            let obj = #krate::object_store::remove_object::<#class>(handle);
+                            let obj = #krate::object_store::try_remove_object::<#class>(handle)?;
                             obj.#rust_name(#(#arg_names),*)
                         }
                     }
@@ -1480,11 +1656,12 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
             };
 
             if method.ret.is_some() {
-                let ret_ty = method.ret.as_ref().unwrap();
+                let ret_ty = ok_ty.as_ref().or(method.ret.as_ref()).unwrap();
                 quote_spanned! {span=>
                     let handle = <#krate::object_store::ObjectHandle as #krate::BinaryDecode>::decode(decoder)?;
                     #decode_args
                     let result = #call;
+                    #unwrap_result
                     let mut encoder = #krate::EncodedData::new();
                     <#ret_ty as #krate::BinaryEncode>::encode(result, &mut encoder);
                     Ok(encoder)
@@ -1500,10 +1677,11 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
         }
         ExportMethodKind::StaticMethod => {
             // Static method: just call directly
-            if let Some(ret_ty) = &method.ret {
+            if let Some(ret_ty) = ok_ty.as_ref().or(method.ret.as_ref()) {
                 quote_spanned! {span=>
                     #decode_args
                     let result = #class::#rust_name(#(#arg_names),*);
+                    #unwrap_result
                     let mut encoder = #krate::EncodedData::new();
                     <#ret_ty as #krate::BinaryEncode>::encode(result, &mut encoder);
                     Ok(encoder)
@@ -1521,12 +1699,13 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
             if let Some(ret_ty) = &method.ret {
                 quote_spanned! {span=>
                     let handle = <#krate::object_store::ObjectHandle as #krate::BinaryDecode>::decode(decoder)?;
-                    #krate::object_store::with_object::<#class, _>(handle, |obj| {
+                    let encoder = #krate::object_store::try_with_object::<#class, _>(handle, |obj| {
                         let result = obj.#rust_name();
                         let mut encoder = #krate::EncodedData::new();
                         <#ret_ty as #krate::BinaryEncode>::encode(result, &mut encoder);
-                        Ok(encoder)
-                    })
+                        encoder
+                    })?;
+                    Ok(encoder)
                 }
             } else {
                 return Err(syn::Error::new(span, "getter must have a return type"));
@@ -1544,9 +1723,9 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
             quote_spanned! {span=>
                 let handle = <#krate::object_store::ObjectHandle as #krate::BinaryDecode>::decode(decoder)?;
                 let #arg_name = <#arg_ty as #krate::BinaryDecode>::decode(decoder)?;
-                #krate::object_store::with_object_mut::<#class, _>(handle, |obj| {
+                #krate::object_store::try_with_object_mut::<#class, _>(handle, |obj| {
                     obj.#rust_name(#arg_name);
-                });
+                })?;
                 Ok(#krate::EncodedData::new())
             }
         }
@@ -1570,6 +1749,8 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
         None => quote_spanned! {span=> },
     };
 
+    let asyncness = method.is_async.then(|| quote_spanned! {span=> async });
+
     let allows = clippy_allows();
     let method_impl = match &method.kind {
         ExportMethodKind::Constructor | ExportMethodKind::StaticMethod => {
@@ -1578,7 +1759,7 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
                 impl #class {
                     #allows
                     #rust_attrs
-                    #vis fn #rust_name(#(#fn_args),*) #ret_type #body
+                    #vis #asyncness fn #rust_name(#(#fn_args),*) #ret_type #body
                 }
             }
         }
@@ -1597,7 +1778,7 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
                 impl #class {
                     #allows
                     #rust_attrs
-                    #vis fn #rust_name(#fn_args_with_self) #ret_type #body
+                    #vis #asyncness fn #rust_name(#fn_args_with_self) #ret_type #body
                 }
             }
         }
@@ -1628,10 +1809,18 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
             js_name.clone(),
             quote! { #krate::JsClassMemberKind::Constructor },
         ),
+        ExportMethodKind::Method { .. } if method.is_async => (
+            js_name.clone(),
+            quote! { #krate::JsClassMemberKind::AsyncMethod },
+        ),
         ExportMethodKind::Method { .. } => (
             js_name.clone(),
             quote! { #krate::JsClassMemberKind::Method },
         ),
+        ExportMethodKind::StaticMethod if method.is_async => (
+            js_name.clone(),
+            quote! { #krate::JsClassMemberKind::AsyncStaticMethod },
+        ),
         ExportMethodKind::StaticMethod => (
             js_name.clone(),
             quote! { #krate::JsClassMemberKind::StaticMethod },
@@ -1684,6 +1873,100 @@ fn generate_export_method(method: &ExportMethod, krate: &TokenStream) -> syn::Re
     })
 }
 
+/// Generate the export handler body for an `async` method or static method.
+///
+/// The receiver is lent out of the object store, or moved out for `self` methods, so the
+/// spawned future can own it. `&self` calls share the receiver and can overlap, while
+/// calls that need it exclusively throw in JS as long as another call holds it. The
+/// future's output is converted into the value that settles the promise JS receives.
+fn generate_async_export_body(
+    method: &ExportMethod,
+    krate: &TokenStream,
+    decode_args: &TokenStream,
+    arg_names: &[TokenStream],
+    export_name: &str,
+) -> TokenStream {
+    let class = &method.class;
+    let rust_name = &method.rust_name;
+    let span = rust_name.span();
+
+    let (prelude, call) = match &method.kind {
+        ExportMethodKind::Method { self_ty } => {
+            // The receiver stays out of the store until the future completes. Other `&self`
+            // calls can share it meanwhile, `&mut self` and `self` calls reject their promise
+            let take_receiver = match self_ty {
+                SelfType::RefShared => quote_spanned! {span=>
+                    let obj = #krate::object_store::try_share_object::<#class>(handle)?;
+                },
+                SelfType::RefMutable => quote_spanned! {span=>
+                    let mut obj = #krate::object_store::try_borrow_object::<#class>(handle)?;
+                },
+                SelfType::ByValue => quote_spanned! {span=>
+                    let obj = #krate::object_store::try_remove_object::<#class>(handle)?;
+                },
+            };
+            (
+                quote_spanned! {span=>
+                    let handle = <#krate::object_store::ObjectHandle as #krate::BinaryDecode>::decode(decoder)?;
+                    #decode_args
+                    #take_receiver
+                },
+                quote_spanned! {span=> obj.#rust_name(#(#arg_names),*).await },
+            )
+        }
+        _ => (
+            decode_args.clone(),
+            quote_spanned! {span=> #class::#rust_name(#(#arg_names),*).await },
+        ),
+    };
+
+    // `Err` values reject the promise, everything else resolves it
+    let settle = match &method.ret {
+        None => quote_spanned! {span=>
+            #call;
+            ::core::result::Result::Ok(#krate::JsValue::UNDEFINED)
+        },
+        Some(ty) => match extract_result_ok_type(ty) {
+            Some(ok_ty) if is_unit_type(&ok_ty) => quote_spanned! {span=>
+                #call
+                    .map(|()| #krate::JsValue::UNDEFINED)
+                    .map_err(::core::convert::Into::<#krate::JsValue>::into)
+            },
+            Some(_) => quote_spanned! {span=>
+                #call
+                    .map(::core::convert::Into::<#krate::JsValue>::into)
+                    .map_err(::core::convert::Into::<#krate::JsValue>::into)
+            },
+            None => quote_spanned! {span=>
+                ::core::result::Result::Ok(::core::convert::Into::<#krate::JsValue>::into(#call))
+            },
+        },
+    };
+
+    quote_spanned! {span=>
+        #prelude
+        #krate::runtime::spawn_export(#export_name, async move { #settle })
+    }
+}
+
+/// Get the referenced type of an exported method argument that borrows another
+/// exported object (e.g. `other: &Counter`), or None for arguments passed by value.
+///
+/// The referenced type must implement `ExportedClass`, so references to types that are
+/// not exported structs fail to compile instead of being looked up in the object store.
+fn borrowed_export_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Reference(reference) = ty else {
+        return None;
+    };
+    if reference.mutability.is_some() {
+        return None;
+    }
+    match &*reference.elem {
+        syn::Type::Path(path) if !path.path.is_ident("str") => Some(&reference.elem),
+        _ => None,
+    }
+}
+
 /// Extract the Ok type from a Result<T, E> type, or None if not a Result
 fn extract_result_ok_type(ty: &syn::Type) -> Option<syn::Type> {
     if let syn::Type::Path(type_path) = ty {
@@ -1,15 +1,18 @@
 //! wry-bindgen-macro-support - Implementation of the wasm_bindgen attribute macro
 //!
 //! This crate contains the parsing, AST, and code generation logic for the
-//! `#[wasm_bindgen]` attribute macro that targets Wry's WebView.
+//! `#[wasm_bindgen]` attribute macro that targets Wry's WebView, and of the
+//! `#[derive(BinaryCodec)]` macro.
 
 mod ast;
+mod codec;
 mod codegen;
 mod parser;
 
 use proc_macro2::TokenStream;
 
 pub use ast::*;
+pub use codec::expand_binary_codec;
 pub use parser::BindgenAttrs;
 
 /// Expand the wasm_bindgen attribute macro.
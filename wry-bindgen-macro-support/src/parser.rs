@@ -72,6 +72,8 @@ pub struct BindgenAttrs {
     pub vendor_prefixes: Vec<(Span, Ident)>,
     /// The `inspectable` attribute - generate toJSON/toString for structs
     pub inspectable: Option<Span>,
+    /// The `iterable` attribute - generate `[Symbol.iterator]` for structs implementing `WryIterable`
+    pub iterable: Option<Span>,
     /// The `skip` attribute - skip field from export
     pub skip: Option<Span>,
     /// The `getter_with_clone` attribute - clone value in getter (for non-Copy types)
@@ -174,6 +176,7 @@ enum BindgenAttr {
     Crate(Span, Path),
     VendorPrefix(Span, Ident),
     Inspectable(Span),
+    Iterable(Span),
     Skip(Span),
     GetterWithClone(Span),
     Module(Span, String),
@@ -301,6 +304,7 @@ impl Parse for BindgenAttr {
             "final" => Ok(BindgenAttr::Final(span)),
             "readonly" => Ok(BindgenAttr::Readonly(span)),
             "inspectable" => Ok(BindgenAttr::Inspectable(span)),
+            "iterable" => Ok(BindgenAttr::Iterable(span)),
             "skip" => Ok(BindgenAttr::Skip(span)),
             "getter_with_clone" => Ok(BindgenAttr::GetterWithClone(span)),
 
@@ -526,6 +530,12 @@ pub fn parse_attrs(attr: TokenStream) -> syn::Result<BindgenAttrs> {
                 }
                 result.inspectable = Some(span);
             }
+            BindgenAttr::Iterable(span) => {
+                if result.iterable.is_some() {
+                    return Err(syn::Error::new(span, "duplicate `iterable` attribute"));
+                }
+                result.iterable = Some(span);
+            }
             BindgenAttr::Skip(span) => {
                 if result.skip.is_some() {
                     return Err(syn::Error::new(span, "duplicate `skip` attribute"));
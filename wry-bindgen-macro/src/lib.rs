@@ -34,6 +34,32 @@ use proc_macro::TokenStream;
 ///     pub fn new() -> Element;
 /// }
 /// ```
+///
+/// # Final types
+///
+/// Marking an imported type with `#[wasm_bindgen(final)]` makes its `instanceof`
+/// check always return `true` without an IPC round-trip, so `dyn_into` and
+/// `dyn_ref` always succeed. This trusts the declaration: if a value of another
+/// type is cast, the mistake only shows up when a later JS call fails.
+///
+/// # Iterable structs
+///
+/// Marking an exported struct with `#[wasm_bindgen(iterable)]` gives its JS class a
+/// `[Symbol.iterator]` method, so JS can use `for...of` or spread syntax on it. The
+/// struct must implement `WryIterable`; each step pulls the next element from Rust.
+///
+/// # JS class names
+///
+/// Exported structs are presented to JS as a class named after the Rust struct. Use
+/// `#[wasm_bindgen(js_class = "MyWidget")]` on both the struct and its exported impl
+/// blocks to choose a different name. The name is used for the wrapper's
+/// `constructor.name`, for `instanceof` checks against `window.MyWidget` and in devtools.
+///
+/// # Fallible exports
+///
+/// Exported constructors and methods may return `Result<T, E>` where `E: Into<JsValue>`.
+/// `Ok` values are returned to JS as a `T`, and `Err` values are thrown as a JS exception
+/// instead of panicking.
 #[proc_macro_attribute]
 pub fn wasm_bindgen(attr: TokenStream, input: TokenStream) -> TokenStream {
     match wry_bindgen_macro_support::expand(attr.into(), input.into()) {
@@ -42,6 +68,42 @@ pub fn wasm_bindgen(attr: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+/// Derive the binary codec traits for a struct so it can be passed to and from JS.
+///
+/// This implements `EncodeTypeDef`, `BinaryEncode`, `BinaryDecode` and
+/// `BatchableResult`. A struct with named fields is sent to JS as a plain object
+/// keyed by field name, and decoded from one on the way back. A tuple struct is sent
+/// as an array of its fields, and a newtype such as `struct Id(u64)` is sent exactly
+/// like the type it wraps.
+///
+/// # Example
+///
+/// ```ignore
+/// use wry_bindgen::BinaryCodec;
+///
+/// #[derive(BinaryCodec)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+///     // Appears in JS as `point.isVisible`
+///     #[codec(rename = "isVisible")]
+///     is_visible: bool,
+/// }
+/// ```
+///
+/// # Attributes
+///
+/// - `#[codec(rename = "jsName")]` on a named field sets the key used in JS.
+/// - `#[codec(crate = path)]` on the struct sets the path to the bindgen crate,
+///   which defaults to `wasm_bindgen`.
+#[proc_macro_derive(BinaryCodec, attributes(codec))]
+pub fn binary_codec(input: TokenStream) -> TokenStream {
+    match wry_bindgen_macro_support::expand_binary_codec(input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Link to a JS file for use with workers/worklets.
 ///
 /// This macro is only meaningful in WASM contexts. When running outside of WASM,
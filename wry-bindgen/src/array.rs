@@ -0,0 +1,143 @@
+//! A binding to the JavaScript `Array` type
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::batch::Runtime;
+use crate::encode::{BatchableResult, BinaryDecode, EncodeTypeDef};
+use crate::ipc::{DecodeError, DecodedData};
+use crate::js_helpers::{
+    js_array_get, js_array_length, js_array_of, js_array_push, js_array_set, js_array_sort_by,
+    js_array_to_vec, js_is_array, js_new_array,
+};
+use crate::{JsValue, wasm_bindgen};
+
+#[wasm_bindgen(crate = crate)]
+extern "C" {
+    /// A handle to a JavaScript `Array` living in the webview.
+    ///
+    /// Casting checks `Array.isArray`, so arrays from other realms are accepted too.
+    #[wasm_bindgen(js_name = Array, is_type_of = js_is_array)]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub type JsArray;
+}
+
+impl JsArray {
+    /// Create a new, empty JS array.
+    pub fn new() -> JsArray {
+        js_new_array()
+    }
+
+    /// Build a JS array from Rust values that convert into [`JsValue`].
+    ///
+    /// The conversions and the array construction run in a single batch, so a
+    /// `Vec<String>` or `Vec<f64>` becomes a JS array in one IPC round-trip.
+    pub fn from_rust<T: Into<JsValue>>(iter: impl IntoIterator<Item = T>) -> JsArray {
+        crate::batch::batch(|| {
+            let values: Vec<JsValue> = iter.into_iter().map(Into::into).collect();
+            js_array_of(&values)
+        })
+    }
+
+    /// Append a value to the end of the array, returning the new length.
+    ///
+    /// Inside [`batch`](crate::batch::batch) the call is queued rather than flushed, so
+    /// pushing in a loop is sent to JS in one go. The length is only read back when
+    /// [`ArrayLength::get`] is called.
+    pub fn push(&self, val: &JsValue) -> ArrayLength {
+        js_array_push(self, val)
+    }
+
+    /// Get the element at `index`, or `undefined` if it is out of bounds.
+    pub fn get(&self, index: u32) -> JsValue {
+        js_array_get(self, index)
+    }
+
+    /// Set the element at `index`, growing the array with holes if it is out of bounds.
+    pub fn set(&self, index: u32, val: &JsValue) {
+        js_array_set(self, index, val)
+    }
+
+    /// The number of elements in the array.
+    pub fn length(&self) -> u32 {
+        js_array_length(self)
+    }
+
+    /// Sort the array in place with a Rust comparator.
+    ///
+    /// This drives `Array.prototype.sort` in JS, calling back into `compare` for
+    /// each comparison and translating the returned [`Ordering`] to -1/0/1.
+    pub fn sort_by(&self, mut compare: impl FnMut(&JsValue, &JsValue) -> Ordering) {
+        js_array_sort_by(self, &mut |a: JsValue, b: JsValue| match compare(&a, &b) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        });
+    }
+
+    /// Copy every element of the array into a Rust [`Vec`].
+    ///
+    /// The whole array is transferred in a single IPC round-trip instead of
+    /// reading each index separately.
+    pub fn to_vec(&self) -> Vec<JsValue> {
+        js_array_to_vec(self)
+    }
+}
+
+impl Default for JsArray {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Into<JsValue>> FromIterator<T> for JsArray {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_rust(iter)
+    }
+}
+
+/// The length of a [`JsArray`] returned by [`JsArray::push`].
+///
+/// Like [`JsValue`], this is a handle to the number in JS, so it can be returned from
+/// a batched call without waiting for JS to run it.
+#[derive(Clone, Debug)]
+pub struct ArrayLength(JsValue);
+
+impl ArrayLength {
+    /// Read the length, flushing the current batch if the push is still queued.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `push` did not return a number, which can only happen when the value
+    /// was cast to a [`JsArray`] without being an array.
+    pub fn get(&self) -> u32 {
+        match self.0.as_f64() {
+            Some(length) => length as u32,
+            None => panic!("push returned {:?} instead of a length", self.0),
+        }
+    }
+}
+
+impl PartialEq<u32> for ArrayLength {
+    fn eq(&self, other: &u32) -> bool {
+        self.get() == *other
+    }
+}
+
+impl EncodeTypeDef for ArrayLength {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        JsValue::encode_type_def(buf);
+    }
+}
+
+impl BinaryDecode for ArrayLength {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        JsValue::decode(decoder).map(ArrayLength)
+    }
+}
+
+impl BatchableResult for ArrayLength {
+    fn try_placeholder(batch: &mut Runtime) -> Option<Self> {
+        JsValue::try_placeholder(batch).map(ArrayLength)
+    }
+}
@@ -4,16 +4,21 @@
 //! JS operations to be grouped together for efficient execution.
 
 use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
 use alloc::vec::Vec;
 use core::any::Any;
-use core::cell::{Ref, RefCell, RefMut};
+use core::cell::{Cell, Ref, RefCell, RefMut};
+use core::sync::atomic::{AtomicU32, Ordering};
+use core::task::Waker;
+use futures_util::stream::FuturesUnordered;
 use std::boxed::Box;
 
-use crate::encode::{BatchableResult, BinaryDecode};
-use crate::ipc::DecodedData;
-use crate::ipc::{EncodedData, IPCMessage, MessageType};
+use crate::encode::{BatchableResult, BinaryDecode, WriteBack};
+use crate::ipc::{DecodeError, DecodedData};
+use crate::ipc::{EncodedData, HEADER_LEN, IPCMessage, MessageType};
 use crate::lazy::ThreadLocalKey;
-use crate::runtime::WryIPC;
+use crate::object_store::{BorrowObjectError, ObjectHandle};
+use crate::runtime::{LocalTask, WryIPC};
 use crate::value::{JSIDX_OFFSET, JSIDX_RESERVED};
 
 /// State for batching operations and object storage.
@@ -31,6 +36,8 @@ pub struct Runtime {
     /// A stack of ongoing function encodings with the ids
     /// that need to be freed after each one is done
     ids_to_free: Vec<Vec<u64>>,
+    /// Mutable slices passed to the operations being encoded, in argument order
+    write_backs: Vec<WriteBack>,
     /// Whether we're inside a batch() call
     is_batching: bool,
     /// Borrow stack pointer - uses indices 1-127, growing downward from JSIDX_OFFSET (128) to 1
@@ -49,12 +56,61 @@ pub struct Runtime {
     objects: BTreeMap<u32, Box<dyn Any>>,
     /// Next handle to assign for exported objects
     next_object_handle: u32,
+    /// Exported objects currently lent out of the store
+    lent_objects: BTreeMap<u32, LentObject>,
+    /// The app whose code is running, 0 for the main app and 1.. for attached apps
+    app_scope: u32,
+    /// The attached app each object it put in the store belongs to
+    scoped_objects: BTreeMap<u32, u32>,
     /// The ipc layer used to communicate with the JS runtime
     ipc: WryIPC,
     /// The id of the webview this is associated with
     webview_id: u64,
+    /// The webview generation the heap and type cache were built against
+    webview_generation: u64,
+    /// The stamp of the current heap, unique per runtime and webview generation
+    heap_stamp: u64,
     /// Thread locals associated with the runtime
     thread_locals: BTreeMap<ThreadLocalKey<'static>, Box<dyn Any>>,
+    /// Heap IDs released by dropped JsValues that JS has not been told to free yet
+    pending_drops: Vec<u64>,
+    /// Number of calls encoded into the current message
+    pending_calls: u64,
+    /// Batching statistics collected since the runtime started
+    metrics: BatchMetrics,
+    /// Futures started with `spawn_local`, or `None` while they are being polled
+    tasks: Option<FuturesUnordered<LocalTask>>,
+    /// Futures spawned while `tasks` was being polled
+    spawned_tasks: Vec<LocalTask>,
+    /// Waker of the future polling `tasks`, woken when a future is spawned
+    task_waker: Option<Waker>,
+}
+
+/// Statistics about how well batching is reducing IPC traffic.
+///
+/// Returned by [`metrics`]. Counters accumulate for the lifetime of the runtime.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchMetrics {
+    /// Total number of JS calls encoded.
+    pub calls: u64,
+    /// Number of calls that were sent in the same message as at least one other call.
+    pub calls_coalesced: u64,
+    /// Number of messages flushed to JS.
+    pub flushes: u64,
+}
+
+impl BatchMetrics {
+    /// Estimated number of IPC round-trips avoided compared to sending every call on its own.
+    pub fn round_trips_saved(&self) -> u64 {
+        self.calls.saturating_sub(self.flushes)
+    }
+
+    /// Estimated number of bytes avoided by not repeating the message header for every call.
+    pub fn bytes_saved(&self) -> u64 {
+        // Each message carries the buffer header, the message type and the reserved placeholder count
+        const MESSAGE_OVERHEAD: u64 = HEADER_LEN as u64 + 1 + 4;
+        self.round_trips_saved() * MESSAGE_OVERHEAD
+    }
 }
 
 impl Runtime {
@@ -65,6 +121,7 @@ impl Runtime {
             // Start allocating heap IDs from JSIDX_RESERVED to match JS heap
             max_id: JSIDX_RESERVED,
             ids_to_free: Vec::new(),
+            write_backs: Vec::new(),
             is_batching: false,
             // Borrow stack starts at JSIDX_OFFSET (128) and grows downward to 1
             borrow_stack_pointer: JSIDX_OFFSET,
@@ -78,11 +135,22 @@ impl Runtime {
             next_type_id: 0,
             // Object store starts empty
             objects: BTreeMap::new(),
-            // Object handles start at 0
-            next_object_handle: 0,
+            // Object handles start at 1 because JS wrappers use 0 to mark a freed object
+            next_object_handle: 1,
+            lent_objects: BTreeMap::new(),
+            app_scope: 0,
+            scoped_objects: BTreeMap::new(),
             ipc,
             webview_id,
+            webview_generation: 0,
+            heap_stamp: register_heap(),
             thread_locals: BTreeMap::new(),
+            pending_drops: Vec::new(),
+            pending_calls: 0,
+            metrics: BatchMetrics::default(),
+            tasks: Some(FuturesUnordered::new()),
+            spawned_tasks: Vec::new(),
+            task_waker: None,
         }
     }
 
@@ -93,7 +161,10 @@ impl Runtime {
     }
 
     /// Get the next heap ID for placeholder allocation.
-    /// Uses free-list strategy: reuses freed IDs first, then allocates new ones.
+    ///
+    /// IDs are allocated monotonically and never reused, matching the JS heap. A drop
+    /// that is still queued in the batch can therefore never free a slot that a newer
+    /// value has been assigned to.
     pub fn get_next_heap_id(&mut self) -> u64 {
         let id = self.max_id;
         self.max_id += 1;
@@ -161,10 +232,21 @@ impl Runtime {
         }
     }
 
+    /// Check if a heap ID has already been released by a dropped JsValue.
+    #[cfg(debug_assertions)]
+    pub(crate) fn is_heap_id_released(&self, id: u64) -> bool {
+        self.free_ids.contains(&id) || self.ids_to_free.iter().any(|ids| ids.contains(&id))
+    }
+
     /// Take the message data and reset the batch for reuse.
     /// Includes any pending drops at the start of the message.
     /// Prepends the reserved placeholder count so JS can skip those IDs during nested allocations.
     pub(crate) fn take_message(&mut self) -> IPCMessage {
+        let calls = core::mem::take(&mut self.pending_calls);
+        self.metrics.flushes += 1;
+        if calls > 1 {
+            self.metrics.calls_coalesced += calls;
+        }
         let reserved_count = self.take_reserved_placeholder_count();
         let mut encoder = self.take_encoder();
         encoder.prepend_u32(reserved_count);
@@ -172,8 +254,8 @@ impl Runtime {
     }
 
     pub(crate) fn is_empty(&self) -> bool {
-        // 12 bytes for offsets + 1 byte for message type
-        self.encoder.byte_len() <= 13
+        // Header + 1 byte for message type
+        self.encoder.byte_len() <= HEADER_LEN + 1
     }
 
     pub(crate) fn push_ids_to_free(&mut self) {
@@ -192,6 +274,37 @@ impl Runtime {
         to_free
     }
 
+    /// Remember a mutable slice argument of the operation being encoded.
+    pub(crate) fn push_write_back(&mut self, write_back: WriteBack) {
+        self.write_backs.push(write_back);
+    }
+
+    /// Take the mutable slice arguments encoded since there were `start` of them.
+    fn take_write_backs(&mut self, start: usize) -> Vec<WriteBack> {
+        self.write_backs.split_off(start)
+    }
+
+    /// Queue a released heap ID to be freed in JS along with the other pending drops.
+    pub(crate) fn queue_drop(&mut self, id: u64) {
+        self.pending_drops.push(id);
+    }
+
+    /// Take every heap ID waiting to be freed in JS.
+    pub(crate) fn take_pending_drops(&mut self) -> Vec<u64> {
+        core::mem::take(&mut self.pending_drops)
+    }
+
+    /// Record that a call was encoded into the current message.
+    pub(crate) fn record_call(&mut self) {
+        self.pending_calls += 1;
+        self.metrics.calls += 1;
+    }
+
+    /// Get the batching statistics collected so far.
+    pub fn metrics(&self) -> BatchMetrics {
+        self.metrics
+    }
+
     pub(crate) fn set_batching(&mut self, batching: bool) {
         self.is_batching = batching;
     }
@@ -234,11 +347,151 @@ impl Runtime {
     /// Insert an exported object and return its handle.
     pub(crate) fn insert_object<T: 'static>(&mut self, obj: T) -> u32 {
         let handle = self.next_object_handle;
-        self.next_object_handle = self.next_object_handle.wrapping_add(1);
+        self.next_object_handle = self.next_object_handle.wrapping_add(1).max(1);
         self.objects.insert(handle, Box::new(RefCell::new(obj)));
+        if self.app_scope != 0 {
+            self.scoped_objects.insert(handle, self.app_scope);
+        }
         handle
     }
 
+    /// Run the code of another app until the returned scope is entered again. Objects
+    /// put in the store in the meantime belong to that app.
+    pub(crate) fn enter_app_scope(&mut self, scope: u32) -> u32 {
+        core::mem::replace(&mut self.app_scope, scope)
+    }
+
+    /// Get the app the object with this handle belongs to, 0 for the main app.
+    pub(crate) fn object_app_scope(&self, handle: u32) -> u32 {
+        self.scoped_objects.get(&handle).copied().unwrap_or(0)
+    }
+
+    /// Remove every object that belongs to a finished attached app. Lent out objects are
+    /// dropped when they are given back. The removed objects are returned so the caller
+    /// can drop them outside the runtime borrow.
+    pub(crate) fn release_app_scope(&mut self, scope: u32) -> Vec<Box<dyn Any>> {
+        let mut handles = Vec::new();
+        self.scoped_objects.retain(|&handle, &mut owner| {
+            if owner == scope {
+                handles.push(handle);
+            }
+            owner != scope
+        });
+        let mut released = Vec::new();
+        for handle in handles {
+            match self.objects.remove(&handle) {
+                Some(object) => released.push(object),
+                None => {
+                    self.free_lent_object(handle);
+                }
+            }
+        }
+        released
+    }
+
+    /// Put an exported object back into the store under an existing handle.
+    ///
+    /// If JS freed the object while it was lent out, it is returned instead so the caller
+    /// can drop it outside the runtime borrow.
+    pub(crate) fn restore_object<T: 'static>(&mut self, handle: u32, obj: T) -> Option<T> {
+        let lent = self.lent_objects.remove(&handle).unwrap_or_default();
+        if lent.freed {
+            self.scoped_objects.remove(&handle);
+            return Some(obj);
+        }
+        self.objects.insert(handle, Box::new(RefCell::new(obj)));
+        None
+    }
+
+    /// Take an exported object out of the store until it is restored with [`Self::restore_object`].
+    /// The store is left unchanged if the object cannot be taken, see [`Self::try_remove_object`].
+    pub(crate) fn lend_object<T: 'static>(&mut self, handle: u32) -> Result<T, BorrowObjectError> {
+        let obj = self.try_remove_object(handle)?;
+        self.lent_objects.insert(handle, LentObject::default());
+        Ok(obj)
+    }
+
+    /// Lend an exported object out for shared use. Every share of the same handle gets the
+    /// same value until the last one is returned with [`Self::unshare_object`].
+    ///
+    /// Fails if the object is lent out exclusively, see [`Self::lend_object`].
+    pub(crate) fn share_object<T: 'static>(
+        &mut self,
+        handle: u32,
+    ) -> Result<Rc<T>, BorrowObjectError> {
+        if let Some(lent) = self.lent_objects.get(&handle) {
+            let Some(shared) = lent.shared.clone() else {
+                return Err(BorrowObjectError::AlreadyBorrowed(ObjectHandle(handle)));
+            };
+            return shared
+                .downcast::<T>()
+                .map_err(|_| BorrowObjectError::WrongType {
+                    handle: ObjectHandle(handle),
+                    expected: core::any::type_name::<T>(),
+                });
+        }
+        let shared = Rc::new(self.try_remove_object::<T>(handle)?);
+        self.lent_objects.insert(
+            handle,
+            LentObject {
+                shared: Some(shared.clone()),
+                ..LentObject::default()
+            },
+        );
+        Ok(shared)
+    }
+
+    /// Give back a share taken with [`Self::share_object`]. Returns the object once the
+    /// last share is given back, which the caller must then restore.
+    pub(crate) fn unshare_object<T: 'static>(&mut self, handle: u32, shared: Rc<T>) -> Option<T> {
+        drop(shared);
+        let lent = self.lent_objects.get_mut(&handle)?;
+        if lent
+            .shared
+            .as_ref()
+            .is_some_and(|rc| Rc::strong_count(rc) > 1)
+        {
+            return None;
+        }
+        let shared = lent.shared.take()?.downcast::<T>().ok()?;
+        Rc::try_unwrap(shared).ok()
+    }
+
+    /// Mark a lent out object as freed so it is dropped instead of restored when it comes
+    /// back. Returns false if the object is not lent out.
+    pub(crate) fn free_lent_object(&mut self, handle: u32) -> bool {
+        match self.lent_objects.get_mut(&handle) {
+            Some(lent) if !lent.freed => {
+                lent.freed = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Queue a mutation for a lent out object. Returns the mutation back if the object is not lent out.
+    pub(crate) fn queue_object_mutation(
+        &mut self,
+        handle: u32,
+        mutation: QueuedMutation,
+    ) -> Result<(), QueuedMutation> {
+        match self.lent_objects.get_mut(&handle) {
+            Some(lent) => {
+                lent.mutations.push(mutation);
+                Ok(())
+            }
+            None => Err(mutation),
+        }
+    }
+
+    /// Take the mutations queued for a lent out object.
+    pub(crate) fn take_object_mutations(&mut self, handle: u32) -> Vec<QueuedMutation> {
+        self.lent_objects
+            .get_mut(&handle)
+            .map(|lent| core::mem::take(&mut lent.mutations))
+            .unwrap_or_default()
+    }
+
     /// Get a thread-local variable.
     pub(crate) fn take_thread_local<T: 'static>(&mut self, key: ThreadLocalKey<'static>) -> T {
         *self
@@ -265,27 +518,64 @@ impl Runtime {
 
     /// Get a reference to an exported object.
     pub(crate) fn get_object<T: 'static>(&self, handle: u32) -> Ref<'_, T> {
-        let boxed = self.objects.get(&handle).expect("invalid handle");
+        self.try_get_object(handle).expect("invalid handle")
+    }
+
+    /// Get a reference to an exported object, or `None` if the handle is not live.
+    pub(crate) fn try_get_object<T: 'static>(&self, handle: u32) -> Option<Ref<'_, T>> {
+        let boxed = self.objects.get(&handle)?;
         let cell = boxed.downcast_ref::<RefCell<T>>().expect("type mismatch");
-        cell.borrow()
+        Some(cell.borrow())
     }
 
     /// Get a mutable reference to an exported object.
     pub(crate) fn get_object_mut<T: 'static>(&self, handle: u32) -> RefMut<'_, T> {
-        let boxed = self.objects.get(&handle).expect("invalid handle");
+        self.try_get_object_mut(handle).expect("invalid handle")
+    }
+
+    /// Get a mutable reference to an exported object, or `None` if the handle is not live.
+    pub(crate) fn try_get_object_mut<T: 'static>(&self, handle: u32) -> Option<RefMut<'_, T>> {
+        let boxed = self.objects.get(&handle)?;
         let cell = boxed.downcast_ref::<RefCell<T>>().expect("type mismatch");
-        cell.borrow_mut()
+        Some(cell.borrow_mut())
     }
 
     /// Remove an exported object and return it.
     pub(crate) fn remove_object<T: 'static>(&mut self, handle: u32) -> T {
+        self.scoped_objects.remove(&handle);
         let boxed = self.objects.remove(&handle).expect("invalid handle");
         let cell = boxed.downcast::<RefCell<T>>().expect("type mismatch");
         cell.into_inner()
     }
 
+    /// Remove an exported object from the store. The store is left unchanged if the object
+    /// is lent out, the handle does not refer to an object in the store, or the object is
+    /// not a `T`.
+    pub(crate) fn try_remove_object<T: 'static>(
+        &mut self,
+        handle: u32,
+    ) -> Result<T, BorrowObjectError> {
+        if self.lent_objects.contains_key(&handle) {
+            return Err(BorrowObjectError::AlreadyBorrowed(ObjectHandle(handle)));
+        }
+        match self.objects.get(&handle) {
+            None => return Err(BorrowObjectError::InvalidHandle(ObjectHandle(handle))),
+            Some(boxed) if !boxed.is::<RefCell<T>>() => {
+                return Err(BorrowObjectError::WrongType {
+                    handle: ObjectHandle(handle),
+                    expected: core::any::type_name::<T>(),
+                });
+            }
+            Some(_) => {}
+        }
+        let boxed = self.objects.remove(&handle).expect("object checked above");
+        let cell = boxed.downcast::<RefCell<T>>().expect("type checked above");
+        Ok(cell.into_inner())
+    }
+
     /// Remove an exported object without returning it.
     pub(crate) fn remove_object_untyped(&mut self, handle: u32) -> Option<Box<dyn Any>> {
+        self.scoped_objects.remove(&handle);
         self.objects.remove(&handle)
     }
 
@@ -294,18 +584,164 @@ impl Runtime {
         &self.ipc
     }
 
+    /// Forget the JS-side state of a webview that has been recreated.
+    ///
+    /// The new webview starts with an empty heap and type cache, so heap IDs are
+    /// allocated from the start again and every type definition is sent in full.
+    pub(crate) fn sync_webview_generation(&mut self) {
+        let generation = self.ipc.webview_generation();
+        if generation == self.webview_generation {
+            return;
+        }
+        self.webview_generation = generation;
+        unregister_heap(self.heap_stamp);
+        self.heap_stamp = register_heap();
+        set_heap_stamp(self.heap_stamp);
+        self.type_cache.clear();
+        self.next_type_id = 0;
+        self.free_ids.clear();
+        self.max_id = JSIDX_RESERVED;
+        // The old heap is gone, so there is nothing left to free
+        self.pending_drops.clear();
+    }
+
+    /// Add a future spawned with `spawn_local`. Returns the waker of the future that
+    /// polls spawned futures, which must be woken to start polling it.
+    pub(crate) fn spawn_task(&mut self, task: LocalTask) -> Option<Waker> {
+        match &mut self.tasks {
+            Some(tasks) => tasks.push(task),
+            None => self.spawned_tasks.push(task),
+        }
+        self.task_waker.clone()
+    }
+
+    /// Take the spawned futures to poll them, remembering the waker of the caller.
+    /// Returns `None` if they are already being polled further up the stack.
+    pub(crate) fn take_tasks(&mut self, waker: &Waker) -> Option<FuturesUnordered<LocalTask>> {
+        let tasks = self.tasks.take()?;
+        if !self.task_waker.as_ref().is_some_and(|w| w.will_wake(waker)) {
+            self.task_waker = Some(waker.clone());
+        }
+        Some(tasks)
+    }
+
+    /// Put the spawned futures back after polling them. Returns true if more futures were
+    /// spawned while they were being polled.
+    pub(crate) fn restore_tasks(&mut self, mut tasks: FuturesUnordered<LocalTask>) -> bool {
+        let spawned = !self.spawned_tasks.is_empty();
+        tasks.extend(self.spawned_tasks.drain(..));
+        self.tasks = Some(tasks);
+        spawned
+    }
+
+    /// Get the waker of the future that polls spawned futures, if it has been polled.
+    pub(crate) fn task_waker(&self) -> Option<Waker> {
+        self.task_waker.clone()
+    }
+
     /// Get the webview ID associated with this runtime.
     pub(crate) fn webview_id(&self) -> u64 {
         self.webview_id
     }
+
+    /// Queue the drops of this heap's values that happened while another runtime was
+    /// on top of the stack.
+    fn take_foreign_drops(&mut self) {
+        let drops = LIVE_HEAPS.with(|heaps| {
+            heaps
+                .borrow_mut()
+                .get_mut(&self.heap_stamp)
+                .map(core::mem::take)
+                .unwrap_or_default()
+        });
+        for id in drops {
+            if let Some(id) = self.release_heap_id(id) {
+                self.queue_drop(id);
+            }
+        }
+    }
+}
+
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        unregister_heap(self.heap_stamp);
+    }
+}
+
+/// A mutation of an exported object deferred until the object is returned to the store
+pub(crate) type QueuedMutation = Box<dyn FnOnce(&mut dyn Any)>;
+
+/// An exported object that is out of the store while a call uses it.
+#[derive(Default)]
+struct LentObject {
+    /// Mutations callbacks queued while the object was unavailable
+    mutations: Vec<QueuedMutation>,
+    /// Whether JS freed the object while it was lent out
+    freed: bool,
+    /// The object, while it is shared between calls that only read it
+    shared: Option<Rc<dyn Any>>,
 }
 
 thread_local! {
     /// Thread-local runtime state - always exists, reset after each flush
     pub(crate) static RUNTIME: RefCell<Vec<Runtime>> = const { RefCell::new(Vec::new()) };
+
+    /// The heap stamp of the runtime on top of the stack. Kept outside `RUNTIME` so
+    /// JsValues can check it while the runtime is borrowed.
+    static HEAP_STAMP: Cell<u64> = const { Cell::new(0) };
+
+    /// The heaps of the runtimes on this thread, keyed by heap stamp, with the heap IDs
+    /// of their values dropped while another runtime was on top of the stack.
+    static LIVE_HEAPS: RefCell<BTreeMap<u64, Vec<u64>>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// The last heap stamp handed out. Stamp 0 means no runtime.
+static LAST_HEAP_STAMP: AtomicU32 = AtomicU32::new(0);
+
+/// Allocate a stamp for a new heap and register it as live on this thread.
+fn register_heap() -> u64 {
+    // JsValue stamps its heap into the upper 32 bits of its index
+    let stamp = loop {
+        let stamp = LAST_HEAP_STAMP
+            .fetch_add(1, Ordering::Relaxed)
+            .wrapping_add(1);
+        if stamp != 0 {
+            break u64::from(stamp);
+        }
+    };
+    LIVE_HEAPS.with(|heaps| heaps.borrow_mut().insert(stamp, Vec::new()));
+    stamp
 }
 
-fn push_runtime(runtime: Runtime) {
+/// Forget a heap that no longer exists. Drops queued for it are discarded.
+fn unregister_heap(stamp: u64) {
+    // The thread local is gone if the runtime is dropped during thread shutdown
+    let _ = LIVE_HEAPS.try_with(|heaps| heaps.borrow_mut().remove(&stamp));
+}
+
+fn set_heap_stamp(stamp: u64) {
+    HEAP_STAMP.with(|current| current.set(stamp));
+}
+
+/// The heap stamp that new JsValues are stamped with.
+pub(crate) fn heap_stamp() -> u64 {
+    HEAP_STAMP.with(|current| current.get())
+}
+
+/// Remember the drop of a value from a heap other than the current one, so it is freed
+/// the next time that heap's runtime is on top of the stack. Values from heaps that no
+/// longer exist are ignored.
+pub(crate) fn queue_foreign_drop(stamp: u64, id: u64) {
+    let _ = LIVE_HEAPS.try_with(|heaps| {
+        if let Some(drops) = heaps.borrow_mut().get_mut(&stamp) {
+            drops.push(id);
+        }
+    });
+}
+
+fn push_runtime(mut runtime: Runtime) {
+    set_heap_stamp(runtime.heap_stamp);
+    runtime.take_foreign_drops();
     RUNTIME.with(|state| {
         state.borrow_mut().push(runtime);
     });
@@ -313,10 +749,13 @@ fn push_runtime(runtime: Runtime) {
 
 fn pop_runtime() -> Runtime {
     RUNTIME.with(|state| {
-        state
-            .borrow_mut()
-            .pop()
-            .expect("No runtime available to pop")
+        let mut state = state.borrow_mut();
+        let runtime = state.pop().expect("No runtime available to pop");
+        set_heap_stamp(state.last().map_or(0, |next| next.heap_stamp));
+        if let Some(next) = state.last_mut() {
+            next.take_foreign_drops();
+        }
+        runtime
     })
 }
 
@@ -334,13 +773,79 @@ pub(crate) fn with_runtime<R>(f: impl FnOnce(&mut Runtime) -> R) -> R {
     })
 }
 
+/// Get the webview id of the runtime on top of the stack, or `None` if there is no runtime.
+pub(crate) fn current_webview_id() -> Option<u64> {
+    RUNTIME.with(|state| state.borrow().last().map(|runtime| runtime.webview_id()))
+}
+
+/// Check if a heap ID has already been released in the current runtime.
+/// Returns false if there is no runtime or it is currently borrowed.
+#[cfg(debug_assertions)]
+pub(crate) fn is_heap_id_released(id: u64) -> bool {
+    RUNTIME.with(|state| {
+        let Ok(state) = state.try_borrow() else {
+            return false;
+        };
+        state
+            .last()
+            .is_some_and(|runtime| runtime.is_heap_id_released(id))
+    })
+}
+
 /// Check if we're currently inside a batch() call
 pub fn is_batching() -> bool {
     with_runtime(|state| state.is_batching())
 }
 
+/// How many times the current app's webview has reloaded or been recreated.
+///
+/// Every reload starts a new generation with an empty JS heap, so compare this against
+/// a value saved earlier to find out whether JS-side state needs to be set up again.
+pub fn webview_generation() -> u64 {
+    with_runtime(|state| state.ipc().webview_generation())
+}
+
+/// Wait until the current app's webview reloads or is recreated after this is called,
+/// and return the new [`webview_generation`].
+///
+/// Every `JsValue` created before then is stale by the time this resolves, so an app can
+/// await this in a task to set its JS-side state up again.
+///
+/// ```ignore
+/// spawn_local(async {
+///     loop {
+///         webview_reloaded().await;
+///         install_event_listeners();
+///     }
+/// });
+/// ```
+pub fn webview_reloaded() -> impl core::future::Future<Output = u64> {
+    let seen = webview_generation();
+    core::future::poll_fn(move |cx| {
+        with_runtime(|state| {
+            let generation = core::task::ready!(state.ipc().poll_webview_generation(seen, cx));
+            state.sync_webview_generation();
+            core::task::Poll::Ready(generation)
+        })
+    })
+}
+
+/// Get batching statistics for the current runtime.
+///
+/// Use this to check whether [`batch`] is actually reducing the number of IPC
+/// round-trips for a workload.
+pub fn metrics() -> BatchMetrics {
+    with_runtime(|state| state.metrics())
+}
+
 /// Queue a JS drop operation for a heap ID.
 /// This is called when a JsValue is dropped.
+///
+/// Drops are coalesced: consecutive drops are collected and sent to JS as a single
+/// operation freeing all of them, encoded in front of the next call, sent when a call
+/// from JS into Rust returns, or sent by [`flush_drops`]. Heap IDs are never reused, so freeing a value later than it was
+/// dropped can not affect newer values. Drops that happen while an operation is being
+/// encoded are deferred until that operation has been added to the batch.
 pub(crate) fn queue_js_drop(id: u64) {
     debug_assert!(
         id >= JSIDX_RESERVED,
@@ -353,9 +858,47 @@ pub(crate) fn queue_js_drop(id: u64) {
         return;
     }
 
-    let id = with_runtime(|state| state.release_heap_id(id));
-    if let Some(id) = id {
-        crate::js_helpers::js_drop_heap_ref(id);
+    with_runtime(|state| {
+        if let Some(id) = state.release_heap_id(id) {
+            state.queue_drop(id);
+        }
+    });
+}
+
+/// Encode one operation freeing every pending drop into the current batch.
+///
+/// The operation is queued like a batched call, so it is sent with the next message
+/// instead of costing a round-trip of its own.
+fn encode_pending_drops() {
+    let drops = with_runtime(|state| state.take_pending_drops());
+    if drops.is_empty() {
+        return;
+    }
+    let was_batching = with_runtime(|state| {
+        let was_batching = state.is_batching();
+        state.set_batching(true);
+        was_batching
+    });
+    crate::js_helpers::js_drop_heap_refs(&drops);
+    with_runtime(|state| state.set_batching(was_batching));
+}
+
+/// Send every pending JsValue drop to JS now.
+///
+/// Dropped values are normally freed in JS together with the next call. Call this when
+/// no further calls are coming soon and the JS objects should be released right away.
+pub fn flush_drops() {
+    encode_pending_drops();
+    force_flush();
+}
+
+/// Free queued JS values now, unless a batch is open.
+///
+/// Inside a batch the drops are encoded into it and sent when the batch flushes.
+pub(crate) fn flush_pending_drops_unless_batching() {
+    encode_pending_drops();
+    if !with_runtime(|state| state.is_batching()) {
+        force_flush();
     }
 }
 
@@ -379,16 +922,27 @@ pub(crate) fn run_js_sync<R: BatchableResult>(
     fn_id: u32,
     add_args: impl FnOnce(&mut EncodedData),
 ) -> R {
+    // Start from a clean slate if the webview was recreated since the last call
+    with_runtime(|state| state.sync_webview_generation());
+
+    // Free values dropped since the last call before running this one
+    encode_pending_drops();
+
     // Step 1: Encode the operation into the batch and get placeholder for non-flush types
     // We take the current encoder out of the thread-local state to avoid borrowing issues
     // and then put it back after adding the operation. Drops or other calls may happen while
     // we are encoding, but they should be queued after this operation.
-    let mut batch = with_runtime(|state| {
+    let (mut batch, write_back_start) = with_runtime(|state| {
         // Push a new operation into the batch
         state.push_ids_to_free();
-        state.take_encoder()
+        state.record_call();
+        (state.take_encoder(), state.write_backs.len())
     });
     add_operation(&mut batch, fn_id, add_args);
+    // Mutable slices of this operation. Nested calls made while encoding took their own.
+    let write_backs = Cell::new(with_runtime(|state| {
+        state.take_write_backs(write_back_start)
+    }));
 
     // Check if any encoded argument requires immediate flush (e.g., stack-allocated callbacks)
     let needs_flush = batch.needs_flush;
@@ -406,50 +960,85 @@ pub(crate) fn run_js_sync<R: BatchableResult>(
     // (e.g., stack-allocated callbacks that must be invoked before returning)
     let result = if !is_batching() || needs_flush {
         flush_and_then(|mut data| {
-            let response = get_placeholder()
-                .unwrap_or_else(|| R::decode(&mut data).expect("Failed to decode return value"));
+            let response = match get_placeholder() {
+                Some(placeholder) => placeholder,
+                // The rest of the response cannot be read, so the slices keep their contents
+                None => R::decode(&mut data)?,
+            };
+            // JS sends mutable slices back after the return value
+            for write_back in write_backs.take() {
+                // SAFETY: the slices are borrowed by the caller until this call returns
+                unsafe { write_back.apply(&mut data) }
+                    .expect("Failed to decode mutable slice contents");
+            }
             assert!(
                 data.is_empty(),
                 "Extra data remaining after decoding response"
             );
-            response
+            Ok(response)
         })
     } else {
-        get_placeholder().unwrap_or_else(|| flush_and_return::<R>())
+        get_placeholder().map_or_else(try_flush_and_return::<R>, Ok)
     };
 
-    // After running, free any queued IDs for this operation
-    let ids = with_runtime(|state| state.pop_and_release_ids());
-    for id in ids {
-        crate::js_helpers::js_drop_heap_ref(id);
-    }
+    // After running, queue any IDs dropped during this operation to be freed
+    with_runtime(|state| {
+        for id in state.pop_and_release_ids() {
+            state.queue_drop(id);
+        }
+    });
 
-    result
+    // Only report a decode error once this call is done, since it may call into JS
+    result.unwrap_or_else(return_value_error)
+}
+
+/// Turn a return value that failed to decode into a value of the return type, or panic.
+fn return_value_error<R: BinaryDecode>(error: DecodeError) -> R {
+    R::from_decode_error(&error)
+        .unwrap_or_else(|| panic!("Failed to decode the value returned from JS: {error}"))
 }
 
 /// Flush the current batch and return the decoded result.
 pub(crate) fn flush_and_return<R: BinaryDecode>() -> R {
+    try_flush_and_return().unwrap_or_else(return_value_error)
+}
+
+/// Flush the current batch and decode the result, failing if it does not decode.
+fn try_flush_and_return<R: BinaryDecode>() -> Result<R, DecodeError> {
     flush_and_then(|mut data| {
-        let response = R::decode(&mut data).expect("Failed to decode return value");
+        let response = R::decode(&mut data)?;
         assert!(
             data.is_empty(),
             "Extra data remaining after decoding response"
         );
-        response
+        Ok(response)
     })
 }
 
-pub(crate) fn flush_and_then<R>(then: impl for<'a> Fn(DecodedData<'a>) -> R) -> R {
+pub(crate) fn flush_and_then<R>(
+    then: impl for<'a> Fn(DecodedData<'a>) -> Result<R, DecodeError>,
+) -> Result<R, DecodeError> {
     use crate::runtime::WryBindgenEvent;
 
     let batch_msg = with_runtime(|state| state.take_message());
+    log::trace!("Sending {} byte batch to JS", batch_msg.data().len());
 
     // Send and wait for result
-    with_runtime(|runtime| {
-        (runtime.ipc().proxy)(WryBindgenEvent::ipc(runtime.webview_id(), batch_msg))
+    let generation = with_runtime(|runtime| {
+        let generation = runtime.ipc().webview_generation();
+        (runtime.ipc().proxy)(WryBindgenEvent::ipc(runtime.webview_id(), batch_msg));
+        generation
     });
+    let then = |data: DecodedData<'_>| {
+        // A recreated webview answers the calls the old one never did with an empty response
+        if with_runtime(|runtime| runtime.ipc().webview_generation()) != generation {
+            return Err(DecodeError::WebviewRecreated);
+        }
+        then(data)
+    };
     loop {
         if let Some(result) = crate::runtime::progress_js_with(&then) {
+            log::trace!("Got response from JS");
             return result;
         }
     }
@@ -102,6 +102,21 @@ pub trait JsCast: AsRef<JsValue> + Into<JsValue> + Sized {
     fn unchecked_ref<T: JsCast>(&self) -> &T {
         T::unchecked_from_js_ref(self.as_ref())
     }
+
+    /// Cast to type T, only checking the type in debug builds.
+    ///
+    /// This is a fast path for hot code where the type is statically known.
+    /// In debug builds it behaves like [`JsCast::dyn_into`] and panics if the
+    /// check fails. In release builds it skips the `is_type_of` IPC round-trip
+    /// entirely, so a wrong type is not caught and later calls on the value
+    /// may fail in JS.
+    fn unchecked_dyn_into<T: JsCast>(self) -> T {
+        debug_assert!(
+            T::is_type_of(self.as_ref()),
+            "unchecked_dyn_into called with a value of the wrong type"
+        );
+        T::unchecked_from_js(self.into())
+    }
 }
 
 /// Implement JsCast for JsValue itself (identity cast)
@@ -0,0 +1,82 @@
+//! Encoding for `chrono` date and time types.
+//!
+//! `DateTime<Utc>` is sent as a JS `Date` using milliseconds since the Unix epoch.
+//! `NaiveDate` and `NaiveDateTime` have no time zone, so they are sent as ISO 8601 strings.
+
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+use crate::encode::{BatchableResult, BinaryDecode, BinaryEncode, EncodeTypeDef, TypeTag};
+use crate::ipc::{DecodeError, DecodedData, EncodedData};
+
+impl EncodeTypeDef for DateTime<Utc> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        buf.push(TypeTag::Date as u8);
+    }
+}
+
+impl BinaryEncode for DateTime<Utc> {
+    fn encode(self, encoder: &mut EncodedData) {
+        (self.timestamp_millis() as f64).encode(encoder);
+    }
+}
+
+impl BinaryDecode for DateTime<Utc> {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        let millis = f64::decode(decoder)?;
+        // Invalid JS dates have a NaN timestamp
+        if !millis.is_finite() {
+            return Err(DecodeError::Custom("invalid date".to_string()));
+        }
+        DateTime::from_timestamp_millis(millis as i64)
+            .ok_or_else(|| DecodeError::Custom(format!("date out of range: {millis}ms")))
+    }
+}
+
+impl BatchableResult for DateTime<Utc> {}
+
+impl EncodeTypeDef for NaiveDate {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        buf.push(TypeTag::String as u8);
+    }
+}
+
+impl BinaryEncode for NaiveDate {
+    fn encode(self, encoder: &mut EncodedData) {
+        encoder.push_str(&self.format("%Y-%m-%d").to_string());
+    }
+}
+
+impl BinaryDecode for NaiveDate {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        let s = decoder.take_str()?;
+        s.parse()
+            .map_err(|_| DecodeError::Custom(format!("invalid date: {s:?}")))
+    }
+}
+
+impl BatchableResult for NaiveDate {}
+
+impl EncodeTypeDef for NaiveDateTime {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        buf.push(TypeTag::String as u8);
+    }
+}
+
+impl BinaryEncode for NaiveDateTime {
+    fn encode(self, encoder: &mut EncodedData) {
+        encoder.push_str(&self.format("%Y-%m-%dT%H:%M:%S%.f").to_string());
+    }
+}
+
+impl BinaryDecode for NaiveDateTime {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        let s = decoder.take_str()?;
+        s.parse()
+            .map_err(|_| DecodeError::Custom(format!("invalid date time: {s:?}")))
+    }
+}
+
+impl BatchableResult for NaiveDateTime {}
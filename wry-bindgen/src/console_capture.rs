@@ -0,0 +1,109 @@
+//! Capturing the webview's console output in Rust.
+//!
+//! Console output normally only shows up in the webview devtools. Installing a
+//! `ConsoleSink` with `WryBindgen::set_console_sink` forwards every `console.debug`,
+//! `log`, `info`, `warn` and `error` call to Rust as well, so tests can assert on what
+//! was logged.
+//!
+//! Capturing is meant for tests, so everything except [`ConsoleLevel`] is only
+//! available with the `test-harness` feature.
+
+#[cfg(feature = "test-harness")]
+use {
+    alloc::{boxed::Box, string::String, sync::Arc, vec::Vec},
+    std::sync::Mutex,
+};
+
+/// The console method a message was logged with.
+///
+/// Levels are ordered from least to most severe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConsoleLevel {
+    Debug,
+    Log,
+    Info,
+    Warn,
+    Error,
+}
+
+#[cfg(feature = "test-harness")]
+impl ConsoleLevel {
+    /// Parse the name of a console method, e.g. `"warn"`.
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "debug" => Some(ConsoleLevel::Debug),
+            "log" => Some(ConsoleLevel::Log),
+            "info" => Some(ConsoleLevel::Info),
+            "warn" => Some(ConsoleLevel::Warn),
+            "error" => Some(ConsoleLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "test-harness")]
+/// A receiver for console output forwarded from the webview.
+///
+/// The sink is called on the thread that runs the event loop, while the webview
+/// waits for it to return, so a message is always recorded before the JS call that
+/// logged it completes.
+pub trait ConsoleSink {
+    /// Record one console call. The arguments are joined with spaces, with objects
+    /// formatted as JSON.
+    fn write(&self, level: ConsoleLevel, message: &str);
+}
+
+#[cfg(feature = "test-harness")]
+impl<S: ConsoleSink + ?Sized> ConsoleSink for Box<S> {
+    fn write(&self, level: ConsoleLevel, message: &str) {
+        (**self).write(level, message);
+    }
+}
+
+#[cfg(feature = "test-harness")]
+/// A message captured by a [`ConsoleBuffer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsoleMessage {
+    /// The console method the message was logged with
+    pub level: ConsoleLevel,
+    /// The formatted message
+    pub message: String,
+}
+
+#[cfg(feature = "test-harness")]
+/// A [`ConsoleSink`] that stores messages in memory.
+///
+/// Clones share the same buffer, so one clone can be installed as the sink while
+/// another is kept to read the captured messages from any thread.
+#[derive(Clone, Debug, Default)]
+pub struct ConsoleBuffer {
+    messages: Arc<Mutex<Vec<ConsoleMessage>>>,
+}
+
+#[cfg(feature = "test-harness")]
+impl ConsoleBuffer {
+    /// Create an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a copy of every message captured so far.
+    pub fn messages(&self) -> Vec<ConsoleMessage> {
+        self.messages.lock().unwrap().clone()
+    }
+
+    /// Remove and return every message captured so far.
+    pub fn take(&self) -> Vec<ConsoleMessage> {
+        core::mem::take(&mut *self.messages.lock().unwrap())
+    }
+}
+
+#[cfg(feature = "test-harness")]
+impl ConsoleSink for ConsoleBuffer {
+    fn write(&self, level: ConsoleLevel, message: &str) {
+        self.messages.lock().unwrap().push(ConsoleMessage {
+            level,
+            message: message.into(),
+        });
+    }
+}
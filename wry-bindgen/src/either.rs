@@ -0,0 +1,85 @@
+//! A value that is one of two types.
+
+use alloc::vec::Vec;
+
+use crate::encode::{BatchableResult, BinaryDecode, BinaryEncode, EncodeTypeDef, TypeTag};
+use crate::ipc::{DecodeError, DecodedData, EncodedData};
+
+/// A value of one of two types, for JS APIs that accept or return either.
+///
+/// Unlike [`Result`], neither side means failure. JS sees the bare value. When JS
+/// passes a value to Rust, it becomes [`Either::Left`] if it has the shape of `L`
+/// (for example a number for `u32` or a string for `String`) and [`Either::Right`]
+/// otherwise, so put the more specific type on the left.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Either<L, R> {
+    /// A value of the left type
+    Left(L),
+    /// A value of the right type
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    /// Whether this is a [`Either::Left`].
+    pub fn is_left(&self) -> bool {
+        matches!(self, Either::Left(_))
+    }
+
+    /// Whether this is a [`Either::Right`].
+    pub fn is_right(&self) -> bool {
+        matches!(self, Either::Right(_))
+    }
+
+    /// The left value, if this is a [`Either::Left`].
+    pub fn left(self) -> Option<L> {
+        match self {
+            Either::Left(left) => Some(left),
+            Either::Right(_) => None,
+        }
+    }
+
+    /// The right value, if this is a [`Either::Right`].
+    pub fn right(self) -> Option<R> {
+        match self {
+            Either::Left(_) => None,
+            Either::Right(right) => Some(right),
+        }
+    }
+}
+
+impl<L: EncodeTypeDef, R: EncodeTypeDef> EncodeTypeDef for Either<L, R> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        // Either encodes as: [Either tag] [left type] [right type]
+        buf.push(TypeTag::Either as u8);
+        L::encode_type_def(buf);
+        R::encode_type_def(buf);
+    }
+}
+
+impl<L: BinaryEncode, R: BinaryEncode> BinaryEncode for Either<L, R> {
+    fn encode(self, encoder: &mut EncodedData) {
+        match self {
+            Either::Left(left) => {
+                encoder.push_u8(0);
+                left.encode(encoder);
+            }
+            Either::Right(right) => {
+                encoder.push_u8(1);
+                right.encode(encoder);
+            }
+        }
+    }
+}
+
+impl<L: BinaryDecode, R: BinaryDecode> BinaryDecode for Either<L, R> {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        let is_right = decoder.take_u8()? != 0;
+        if is_right {
+            Ok(Either::Right(R::decode(decoder)?))
+        } else {
+            Ok(Either::Left(L::decode(decoder)?))
+        }
+    }
+}
+
+impl<L: BinaryDecode, R: BinaryDecode> BatchableResult for Either<L, R> {}
@@ -2,11 +2,34 @@
 //!
 //! This module provides traits for serializing and deserializing Rust types
 //! to/from the binary IPC protocol.
-
+//!
+//! # Integer ranges
+//!
+//! JS numbers are f64, so decoding one into a Rust integer can lose information.
+//! Decoding never saturates:
+//!
+//! - `u8`, `u16`, `i8` and `i16` fail with [`DecodeError::OutOfRange`] when the JS
+//!   value is not an integer in the range of the type.
+//! - [`Wrapping`] versions of those types wrap the value like an `as` cast instead,
+//!   so 300 decodes as `Wrapping(44u8)`.
+//! - Sequences of numbers other than `u8` are sent as packed typed arrays. JS flags
+//!   packed `u16`, `i8` and `i16` arrays that hold a value out of range, so decoding
+//!   them fails the same way. A `Vec<u8>` is a plain JS array of numbers, use
+//!   [`Uint8Array`](crate::typed_array::Uint8Array) for bytes.
+//! - 32 bit and wider integers are not range checked and wrap on the JS side.
+
+use alloc::borrow::Cow;
 use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash};
 use core::marker::PhantomData;
+use core::num::Wrapping;
+use core::ops::{Range, RangeInclusive};
+use core::str::FromStr;
+use core::time::Duration;
+use std::collections::HashMap;
 
 use crate::Closure;
 use crate::WasmClosureFnOnce;
@@ -20,12 +43,45 @@ use crate::value::JsValue;
 /// Each type specifies how to serialize itself.
 pub trait BinaryEncode<P = ()> {
     fn encode(self, encoder: &mut EncodedData);
+
+    /// Encode a sequence of values, such as the elements of a `Vec<Self>`.
+    ///
+    /// By default this is the length followed by each element. Numeric primitives
+    /// override it to send the elements as one packed block of bytes.
+    fn encode_sequence(values: impl ExactSizeIterator<Item = Self>, encoder: &mut EncodedData)
+    where
+        Self: Sized,
+    {
+        encoder.push_u32(values.len() as u32);
+        for value in values {
+            value.encode(encoder);
+        }
+    }
 }
 
 /// Trait for decoding values from the binary protocol.
 /// Each type specifies how to deserialize itself.
 pub trait BinaryDecode: Sized {
     fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError>;
+
+    /// Decode a sequence encoded with [`BinaryEncode::encode_sequence`].
+    fn decode_sequence(decoder: &mut DecodedData) -> Result<Vec<Self>, DecodeError> {
+        let len = decoder.take_u32()? as usize;
+        let mut vec = Vec::with_capacity(len);
+        for _ in 0..len {
+            vec.push(Self::decode(decoder)?);
+        }
+        Ok(vec)
+    }
+
+    /// Stand in for a value returned from JS that could not be decoded.
+    ///
+    /// Imports panic when their return value fails to decode, unless the return type can
+    /// hold the error. `Result<T, JsValue>`, which `catch` imports return, becomes an
+    /// `Err` holding a JS `Error` that describes the failure.
+    fn from_decode_error(_error: &DecodeError) -> Option<Self> {
+        None
+    }
 }
 
 /// Trait for converting a closure into a Closure wrapper.
@@ -37,6 +93,14 @@ pub trait IntoClosure<M, Output> {
     fn into_closure(self) -> Output;
 }
 
+/// Trait for Rust closures that can be turned directly into a JS function value.
+///
+/// Unlike [`IntoClosure`], there is only one implementation per argument list, so
+/// the closure type alone is enough to pick it. Used by [`JsValue::from_closure`].
+pub trait IntoJsFunction<M> {
+    fn into_js_function(self) -> JsValue;
+}
+
 /// Trait for return types that can be used in batched JS calls.
 /// Determines how the type behaves during batching.
 pub trait BatchableResult: BinaryDecode {
@@ -67,7 +131,8 @@ pub(crate) const TYPE_FULL: u8 = 0xFE;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TypeTag {
     // Primitive types
-    Null = 0,
+    /// Unit type: encodes no data. JS represents it as `undefined`.
+    Void = 0,
     Bool = 1,
     U8 = 2,
     U16 = 3,
@@ -104,6 +169,73 @@ pub enum TypeTag {
     /// Format: [StringEnum tag] [variant_count: u8] [for each: string_len: u32, string_bytes...]
     /// Values encode as u32 discriminant. JS decodes using the lookup array.
     StringEnum = 24,
+    /// Tuple type: followed by u8 element count and each element's TypeDef in order.
+    /// Values encode as each element back to back. JS represents tuples as arrays.
+    Tuple = 25,
+    /// Date type: encodes as f64 milliseconds since the Unix epoch. JS represents it as a `Date`.
+    Date = 26,
+    /// Packed array of a numeric primitive: followed by the element's TypeTag.
+    /// Encodes as u32 length + the little-endian element bytes in the u8 buffer.
+    /// JS represents it as the matching typed array, e.g. `Float64Array`.
+    PackedArray = 27,
+    /// Struct with named fields: followed by u8 field count and, for each field, its name
+    /// (u32 length + UTF-8 bytes) and TypeDef. Values encode as each field back to back.
+    /// JS represents it as a plain object keyed by field name.
+    Struct = 28,
+    /// String-keyed map: followed by the value TypeDef. Encodes as u32 length + each key
+    /// as a string followed by its value. JS represents it as a plain object.
+    Record = 29,
+    /// One of two types: followed by left TypeDef and right TypeDef. Encodes as u8 flag
+    /// (0=Left, 1=Right) + value. JS represents it as the bare value of either type.
+    Either = 30,
+    /// Mutable slice: followed by the TypeDef of the slice as a sequence. Encodes like the
+    /// sequence. After the call returns, JS sends the slice's contents back encoded the
+    /// same way, following the return value, and Rust copies them into the slice.
+    MutSlice = 31,
+}
+
+impl TryFrom<u8> for TypeTag {
+    type Error = DecodeError;
+
+    fn try_from(tag: u8) -> Result<Self, Self::Error> {
+        const TAGS: [TypeTag; 32] = [
+            TypeTag::Void,
+            TypeTag::Bool,
+            TypeTag::U8,
+            TypeTag::U16,
+            TypeTag::U32,
+            TypeTag::U64,
+            TypeTag::U128,
+            TypeTag::I8,
+            TypeTag::I16,
+            TypeTag::I32,
+            TypeTag::I64,
+            TypeTag::I128,
+            TypeTag::F32,
+            TypeTag::F64,
+            TypeTag::Usize,
+            TypeTag::Isize,
+            TypeTag::String,
+            TypeTag::HeapRef,
+            TypeTag::Callback,
+            TypeTag::Option,
+            TypeTag::Result,
+            TypeTag::Array,
+            TypeTag::BorrowedRef,
+            TypeTag::U8Clamped,
+            TypeTag::StringEnum,
+            TypeTag::Tuple,
+            TypeTag::Date,
+            TypeTag::PackedArray,
+            TypeTag::Struct,
+            TypeTag::Record,
+            TypeTag::Either,
+            TypeTag::MutSlice,
+        ];
+        TAGS.get(tag as usize)
+            .copied()
+            .ok_or_else(|| DecodeError::Custom(format!("unknown type tag {tag}")))
+    }
 }
 
 /// Trait for types that can encode their type definition into the binary protocol.
@@ -113,6 +245,18 @@ pub trait EncodeTypeDef {
     /// For primitives, this is just the TypeTag byte.
     /// For callbacks, this includes param count, param types, and return type.
     fn encode_type_def(buf: &mut Vec<u8>);
+
+    /// Encode the type definition of a sequence of this type, such as `Vec<Self>`.
+    ///
+    /// This must match how [`BinaryEncode::encode_sequence`] sends the elements.
+    fn encode_sequence_type_def(buf: &mut Vec<u8>)
+    where
+        Self: Sized,
+    {
+        // Array type tag followed by element type
+        buf.push(TypeTag::Array as u8);
+        Self::encode_type_def(buf);
+    }
 }
 
 // Unit type implementations
@@ -125,7 +269,7 @@ impl BatchableResult for () {
 
 impl EncodeTypeDef for () {
     fn encode_type_def(buf: &mut Vec<u8>) {
-        buf.push(TypeTag::Null as u8);
+        buf.push(TypeTag::Void as u8);
     }
 }
 
@@ -141,6 +285,30 @@ impl BinaryDecode for () {
     }
 }
 
+// PhantomData carries no data, so like unit it is skipped on the wire. This lets
+// types that hold a marker field encode their real fields without wasting bytes.
+impl<T: ?Sized> BatchableResult for PhantomData<T> {
+    fn try_placeholder(_: &mut Runtime) -> Option<Self> {
+        Some(PhantomData)
+    }
+}
+
+impl<T: ?Sized> EncodeTypeDef for PhantomData<T> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        buf.push(TypeTag::Void as u8);
+    }
+}
+
+impl<T: ?Sized> BinaryEncode for PhantomData<T> {
+    fn encode(self, _encoder: &mut EncodedData) {}
+}
+
+impl<T: ?Sized> BinaryDecode for PhantomData<T> {
+    fn decode(_decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        Ok(PhantomData)
+    }
+}
+
 impl EncodeTypeDef for bool {
     fn encode_type_def(buf: &mut Vec<u8>) {
         buf.push(TypeTag::Bool as u8);
@@ -159,6 +327,33 @@ impl BinaryDecode for bool {
     }
 }
 
+/// Sent before every 8 and 16 bit integer whose value fits its type.
+///
+/// JS numbers are f64, so JS wraps narrow integers to their width and clears this flag
+/// when the number was not an integer in the range of the type. Rust always sets it.
+const NARROW_IN_RANGE: u8 = 1;
+
+/// Read the range flag and the wrapped value of an 8 or 16 bit integer.
+fn take_narrow_int<T>(
+    decoder: &mut DecodedData,
+    take_value: impl FnOnce(&mut DecodedData) -> Result<T, DecodeError>,
+) -> Result<(T, bool), DecodeError> {
+    let in_range = decoder.take_u8()? == NARROW_IN_RANGE;
+    Ok((take_value(decoder)?, in_range))
+}
+
+/// Decode an 8 or 16 bit integer, failing if JS flagged it as out of range.
+fn decode_narrow_int<T>(
+    decoder: &mut DecodedData,
+    take_value: impl FnOnce(&mut DecodedData) -> Result<T, DecodeError>,
+    ty: &'static str,
+) -> Result<T, DecodeError> {
+    match take_narrow_int(decoder, take_value)? {
+        (value, true) => Ok(value),
+        (_, false) => Err(DecodeError::OutOfRange { ty }),
+    }
+}
+
 impl EncodeTypeDef for u8 {
     fn encode_type_def(buf: &mut Vec<u8>) {
         buf.push(TypeTag::U8 as u8);
@@ -167,13 +362,14 @@ impl EncodeTypeDef for u8 {
 
 impl BinaryEncode for u8 {
     fn encode(self, encoder: &mut EncodedData) {
+        encoder.push_u8(NARROW_IN_RANGE);
         encoder.push_u8(self);
     }
 }
 
 impl BinaryDecode for u8 {
     fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
-        decoder.take_u8()
+        decode_narrow_int(decoder, |decoder| decoder.take_u8(), "u8")
     }
 }
 
@@ -181,17 +377,30 @@ impl EncodeTypeDef for u16 {
     fn encode_type_def(buf: &mut Vec<u8>) {
         buf.push(TypeTag::U16 as u8);
     }
+
+    fn encode_sequence_type_def(buf: &mut Vec<u8>) {
+        packed_type_def::<Self>(buf);
+    }
 }
 
 impl BinaryEncode for u16 {
     fn encode(self, encoder: &mut EncodedData) {
+        encoder.push_u8(NARROW_IN_RANGE);
         encoder.push_u16(self);
     }
+
+    fn encode_sequence(values: impl ExactSizeIterator<Item = Self>, encoder: &mut EncodedData) {
+        encode_packed(values, encoder);
+    }
 }
 
 impl BinaryDecode for u16 {
     fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
-        decoder.take_u16()
+        decode_narrow_int(decoder, |decoder| decoder.take_u16(), "u16")
+    }
+
+    fn decode_sequence(decoder: &mut DecodedData) -> Result<Vec<Self>, DecodeError> {
+        decode_packed(decoder)
     }
 }
 
@@ -199,18 +408,30 @@ impl EncodeTypeDef for u32 {
     fn encode_type_def(buf: &mut Vec<u8>) {
         buf.push(TypeTag::U32 as u8);
     }
+
+    fn encode_sequence_type_def(buf: &mut Vec<u8>) {
+        packed_type_def::<Self>(buf);
+    }
 }
 
 impl BinaryEncode for u32 {
     fn encode(self, encoder: &mut EncodedData) {
         encoder.push_u32(self);
     }
+
+    fn encode_sequence(values: impl ExactSizeIterator<Item = Self>, encoder: &mut EncodedData) {
+        encode_packed(values, encoder);
+    }
 }
 
 impl BinaryDecode for u32 {
     fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
         decoder.take_u32()
     }
+
+    fn decode_sequence(decoder: &mut DecodedData) -> Result<Vec<Self>, DecodeError> {
+        decode_packed(decoder)
+    }
 }
 
 impl EncodeTypeDef for u64 {
@@ -253,17 +474,30 @@ impl EncodeTypeDef for i8 {
     fn encode_type_def(buf: &mut Vec<u8>) {
         buf.push(TypeTag::I8 as u8);
     }
+
+    fn encode_sequence_type_def(buf: &mut Vec<u8>) {
+        packed_type_def::<Self>(buf);
+    }
 }
 
 impl BinaryEncode for i8 {
     fn encode(self, encoder: &mut EncodedData) {
+        encoder.push_u8(NARROW_IN_RANGE);
         encoder.push_u8(self as u8);
     }
+
+    fn encode_sequence(values: impl ExactSizeIterator<Item = Self>, encoder: &mut EncodedData) {
+        encode_packed(values, encoder);
+    }
 }
 
 impl BinaryDecode for i8 {
     fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
-        Ok(decoder.take_u8()? as i8)
+        decode_narrow_int(decoder, |decoder| Ok(decoder.take_u8()? as i8), "i8")
+    }
+
+    fn decode_sequence(decoder: &mut DecodedData) -> Result<Vec<Self>, DecodeError> {
+        decode_packed(decoder)
     }
 }
 
@@ -271,17 +505,30 @@ impl EncodeTypeDef for i16 {
     fn encode_type_def(buf: &mut Vec<u8>) {
         buf.push(TypeTag::I16 as u8);
     }
+
+    fn encode_sequence_type_def(buf: &mut Vec<u8>) {
+        packed_type_def::<Self>(buf);
+    }
 }
 
 impl BinaryEncode for i16 {
     fn encode(self, encoder: &mut EncodedData) {
+        encoder.push_u8(NARROW_IN_RANGE);
         encoder.push_u16(self as u16);
     }
+
+    fn encode_sequence(values: impl ExactSizeIterator<Item = Self>, encoder: &mut EncodedData) {
+        encode_packed(values, encoder);
+    }
 }
 
 impl BinaryDecode for i16 {
     fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
-        Ok(decoder.take_u16()? as i16)
+        decode_narrow_int(decoder, |decoder| Ok(decoder.take_u16()? as i16), "i16")
+    }
+
+    fn decode_sequence(decoder: &mut DecodedData) -> Result<Vec<Self>, DecodeError> {
+        decode_packed(decoder)
     }
 }
 
@@ -289,18 +536,30 @@ impl EncodeTypeDef for i32 {
     fn encode_type_def(buf: &mut Vec<u8>) {
         buf.push(TypeTag::I32 as u8);
     }
+
+    fn encode_sequence_type_def(buf: &mut Vec<u8>) {
+        packed_type_def::<Self>(buf);
+    }
 }
 
 impl BinaryEncode for i32 {
     fn encode(self, encoder: &mut EncodedData) {
         encoder.push_u32(self as u32);
     }
+
+    fn encode_sequence(values: impl ExactSizeIterator<Item = Self>, encoder: &mut EncodedData) {
+        encode_packed(values, encoder);
+    }
 }
 
 impl BinaryDecode for i32 {
     fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
         Ok(decoder.take_u32()? as i32)
     }
+
+    fn decode_sequence(decoder: &mut DecodedData) -> Result<Vec<Self>, DecodeError> {
+        decode_packed(decoder)
+    }
 }
 
 impl EncodeTypeDef for i64 {
@@ -343,38 +602,243 @@ impl EncodeTypeDef for f32 {
     fn encode_type_def(buf: &mut Vec<u8>) {
         buf.push(TypeTag::F32 as u8);
     }
+
+    fn encode_sequence_type_def(buf: &mut Vec<u8>) {
+        packed_type_def::<Self>(buf);
+    }
 }
 
 impl BinaryEncode for f32 {
     fn encode(self, encoder: &mut EncodedData) {
         encoder.push_u32(self.to_bits());
     }
+
+    fn encode_sequence(values: impl ExactSizeIterator<Item = Self>, encoder: &mut EncodedData) {
+        encode_packed(values, encoder);
+    }
 }
 
 impl BinaryDecode for f32 {
     fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
         Ok(f32::from_bits(decoder.take_u32()?))
     }
+
+    fn decode_sequence(decoder: &mut DecodedData) -> Result<Vec<Self>, DecodeError> {
+        decode_packed(decoder)
+    }
 }
 
 impl EncodeTypeDef for f64 {
     fn encode_type_def(buf: &mut Vec<u8>) {
         buf.push(TypeTag::F64 as u8);
     }
+
+    fn encode_sequence_type_def(buf: &mut Vec<u8>) {
+        packed_type_def::<Self>(buf);
+    }
 }
 
 impl BinaryEncode for f64 {
     fn encode(self, encoder: &mut EncodedData) {
         encoder.push_u64(self.to_bits());
     }
+
+    fn encode_sequence(values: impl ExactSizeIterator<Item = Self>, encoder: &mut EncodedData) {
+        encode_packed(values, encoder);
+    }
 }
 
 impl BinaryDecode for f64 {
     fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
         Ok(f64::from_bits(decoder.take_u64()?))
     }
+
+    fn decode_sequence(decoder: &mut DecodedData) -> Result<Vec<Self>, DecodeError> {
+        decode_packed(decoder)
+    }
 }
 
+// Duration implementations (sent as f64 milliseconds, like JS timestamps)
+
+impl EncodeTypeDef for Duration {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        buf.push(TypeTag::F64 as u8);
+    }
+}
+
+impl BinaryEncode for Duration {
+    fn encode(self, encoder: &mut EncodedData) {
+        (self.as_secs_f64() * 1000.0).encode(encoder);
+    }
+}
+
+impl BinaryDecode for Duration {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        let millis = f64::decode(decoder)?;
+        Duration::try_from_secs_f64(millis / 1000.0)
+            .map_err(|_| DecodeError::Custom(format!("invalid duration: {millis}ms")))
+    }
+}
+
+impl BatchableResult for Duration {}
+
+// Range implementations (sent as `{ start, end }` objects, with `inclusive: true` for
+// RangeInclusive). Decoding does not check that start <= end: like in Rust, such a
+// range is simply empty.
+
+/// Push the name of a struct field in the format expected after [`TypeTag::Struct`].
+fn push_field_name(buf: &mut Vec<u8>, name: &str) {
+    buf.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    buf.extend_from_slice(name.as_bytes());
+}
+
+impl<T: EncodeTypeDef> EncodeTypeDef for Range<T> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        buf.push(TypeTag::Struct as u8);
+        buf.push(2);
+        push_field_name(buf, "start");
+        T::encode_type_def(buf);
+        push_field_name(buf, "end");
+        T::encode_type_def(buf);
+    }
+}
+
+impl<T: BinaryEncode> BinaryEncode for Range<T> {
+    fn encode(self, encoder: &mut EncodedData) {
+        self.start.encode(encoder);
+        self.end.encode(encoder);
+    }
+}
+
+impl<T: BinaryDecode> BinaryDecode for Range<T> {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        let start = T::decode(decoder)?;
+        let end = T::decode(decoder)?;
+        Ok(start..end)
+    }
+}
+
+impl<T: BinaryDecode> BatchableResult for Range<T> {}
+
+impl<T: EncodeTypeDef> EncodeTypeDef for RangeInclusive<T> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        buf.push(TypeTag::Struct as u8);
+        buf.push(3);
+        push_field_name(buf, "start");
+        T::encode_type_def(buf);
+        push_field_name(buf, "end");
+        T::encode_type_def(buf);
+        push_field_name(buf, "inclusive");
+        bool::encode_type_def(buf);
+    }
+}
+
+impl<T: BinaryEncode> BinaryEncode for RangeInclusive<T> {
+    fn encode(self, encoder: &mut EncodedData) {
+        let (start, end) = self.into_inner();
+        start.encode(encoder);
+        end.encode(encoder);
+        true.encode(encoder);
+    }
+}
+
+impl<T: BinaryDecode> BinaryDecode for RangeInclusive<T> {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        let start = T::decode(decoder)?;
+        let end = T::decode(decoder)?;
+        // The type decides inclusivity, so the flag only describes the range to JS
+        let _inclusive = bool::decode(decoder)?;
+        Ok(start..=end)
+    }
+}
+
+impl<T: BinaryDecode> BatchableResult for RangeInclusive<T> {}
+
+// Map implementations (sent as plain JS objects). Keys must convert to and from strings,
+// so maps with other key types fail to compile. Keys that parse to the same Rust key,
+// like "1" and "01" for a numeric key type, keep the value read last.
+
+fn encode_record<K: Into<String>, V: BinaryEncode>(
+    len: usize,
+    entries: impl Iterator<Item = (K, V)>,
+    encoder: &mut EncodedData,
+) {
+    encoder.push_u32(len as u32);
+    for (key, value) in entries {
+        encoder.push_str(&key.into());
+        value.encode(encoder);
+    }
+}
+
+fn decode_record<K: FromStr, V: BinaryDecode>(
+    decoder: &mut DecodedData,
+    mut insert: impl FnMut(K, V),
+) -> Result<(), DecodeError> {
+    let len = decoder.take_u32()?;
+    for _ in 0..len {
+        let key = decoder.take_str()?;
+        let parsed = K::from_str(key)
+            .map_err(|_| DecodeError::Custom(format!("invalid map key: {key:?}")))?;
+        insert(parsed, V::decode(decoder)?);
+    }
+    Ok(())
+}
+
+impl<K: Into<String>, V: EncodeTypeDef, S> EncodeTypeDef for HashMap<K, V, S> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        buf.push(TypeTag::Record as u8);
+        V::encode_type_def(buf);
+    }
+}
+
+impl<K: Into<String>, V: BinaryEncode, S> BinaryEncode for HashMap<K, V, S> {
+    fn encode(self, encoder: &mut EncodedData) {
+        encode_record(self.len(), self.into_iter(), encoder);
+    }
+}
+
+impl<K: FromStr + Eq + Hash, V: BinaryDecode, S: BuildHasher + Default> BinaryDecode
+    for HashMap<K, V, S>
+{
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        let mut map = HashMap::default();
+        decode_record(decoder, |key, value| {
+            map.insert(key, value);
+        })?;
+        Ok(map)
+    }
+}
+
+impl<K: FromStr + Eq + Hash, V: BinaryDecode, S: BuildHasher + Default> BatchableResult
+    for HashMap<K, V, S>
+{
+}
+
+impl<K: Into<String>, V: EncodeTypeDef> EncodeTypeDef for BTreeMap<K, V> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        buf.push(TypeTag::Record as u8);
+        V::encode_type_def(buf);
+    }
+}
+
+impl<K: Into<String>, V: BinaryEncode> BinaryEncode for BTreeMap<K, V> {
+    fn encode(self, encoder: &mut EncodedData) {
+        encode_record(self.len(), self.into_iter(), encoder);
+    }
+}
+
+impl<K: FromStr + Ord, V: BinaryDecode> BinaryDecode for BTreeMap<K, V> {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        let mut map = BTreeMap::new();
+        decode_record(decoder, |key, value| {
+            map.insert(key, value);
+        })?;
+        Ok(map)
+    }
+}
+
+impl<K: FromStr + Ord, V: BinaryDecode> BatchableResult for BTreeMap<K, V> {}
+
 // usize implementations (uses u64 for portability)
 
 impl EncodeTypeDef for usize {
@@ -415,6 +879,55 @@ impl BinaryDecode for isize {
     }
 }
 
+// Wrapping integers use the wire format of the integer they wrap. Narrow types wrap
+// values that would otherwise be rejected as out of range, like an `as` cast.
+
+impl<T: EncodeTypeDef> EncodeTypeDef for Wrapping<T> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        T::encode_type_def(buf);
+    }
+}
+
+impl<T: BinaryEncode> BinaryEncode for Wrapping<T> {
+    fn encode(self, encoder: &mut EncodedData) {
+        self.0.encode(encoder);
+    }
+}
+
+macro_rules! impl_wrapping_decode {
+    (narrow: $($ty:ty => $take:expr),*; wide: $($wide:ty),*) => {
+        $(
+            impl BinaryDecode for Wrapping<$ty> {
+                fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+                    // JS already wrapped the value, so the range flag is ignored
+                    let (value, _) = take_narrow_int(decoder, $take)?;
+                    Ok(Wrapping(value))
+                }
+            }
+
+            impl BatchableResult for Wrapping<$ty> {}
+        )*
+        $(
+            impl BinaryDecode for Wrapping<$wide> {
+                fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+                    <$wide>::decode(decoder).map(Wrapping)
+                }
+            }
+
+            impl BatchableResult for Wrapping<$wide> {}
+        )*
+    };
+}
+
+impl_wrapping_decode!(
+    narrow:
+        u8 => |decoder| decoder.take_u8(),
+        u16 => |decoder| decoder.take_u16(),
+        i8 => |decoder| Ok(decoder.take_u8()? as i8),
+        i16 => |decoder| Ok(decoder.take_u16()? as i16);
+    wide: u32, u64, u128, i32, i64, i128, usize, isize
+);
+
 // String/str implementations
 
 impl EncodeTypeDef for str {
@@ -516,10 +1029,56 @@ impl<T: BinaryDecode, E: BinaryDecode> BinaryDecode for Result<T, E> {
             Ok(Err(E::decode(decoder)?))
         }
     }
+
+    fn from_decode_error(error: &DecodeError) -> Option<Self> {
+        E::from_decode_error(error).map(Err)
+    }
 }
 
 impl<T: BinaryDecode, E: BinaryDecode> BatchableResult for Result<T, E> {}
 
+macro_rules! impl_tuple {
+    ($len:literal; $($ty:ident),*) => {
+        impl<$($ty: EncodeTypeDef,)*> EncodeTypeDef for ($($ty,)*) {
+            fn encode_type_def(buf: &mut Vec<u8>) {
+                // Tuple encodes as: [Tuple tag] [element count] [element types...]
+                buf.push(TypeTag::Tuple as u8);
+                buf.push($len);
+                $($ty::encode_type_def(buf);)*
+            }
+        }
+
+        impl<$($ty: BinaryEncode,)*> BinaryEncode for ($($ty,)*) {
+            #[allow(non_snake_case)]
+            fn encode(self, encoder: &mut EncodedData) {
+                let ($($ty,)*) = self;
+                $($ty.encode(encoder);)*
+            }
+        }
+
+        impl<$($ty: BinaryDecode,)*> BinaryDecode for ($($ty,)*) {
+            fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+                // Tuple expressions evaluate left to right, so elements are read in order
+                Ok(($($ty::decode(decoder)?,)*))
+            }
+        }
+
+        impl<$($ty: BinaryDecode,)*> BatchableResult for ($($ty,)*) {}
+    };
+}
+
+impl_tuple!(2; A, B);
+impl_tuple!(3; A, B, C);
+impl_tuple!(4; A, B, C, D);
+impl_tuple!(5; A, B, C, D, E);
+impl_tuple!(6; A, B, C, D, E, F);
+impl_tuple!(7; A, B, C, D, E, F, G);
+impl_tuple!(8; A, B, C, D, E, F, G, H);
+impl_tuple!(9; A, B, C, D, E, F, G, H, I);
+impl_tuple!(10; A, B, C, D, E, F, G, H, I, J);
+impl_tuple!(11; A, B, C, D, E, F, G, H, I, J, K);
+impl_tuple!(12; A, B, C, D, E, F, G, H, I, J, K, L);
+
 impl EncodeTypeDef for JsValue {
     fn encode_type_def(buf: &mut Vec<u8>) {
         buf.push(TypeTag::HeapRef as u8);
@@ -528,6 +1087,7 @@ impl EncodeTypeDef for JsValue {
 
 impl BinaryEncode for JsValue {
     fn encode(self, encoder: &mut EncodedData) {
+        self.debug_assert_alive();
         encoder.push_u64(self.id());
     }
 }
@@ -539,6 +1099,11 @@ impl BinaryDecode for JsValue {
         // called for callback parameters from JS, not for return value placeholders.
         with_runtime(|runtime| Ok(JsValue::from_id(runtime.get_next_heap_id())))
     }
+
+    fn from_decode_error(error: &DecodeError) -> Option<Self> {
+        let message = format!("Failed to decode the value returned from JS: {error}");
+        Some(crate::JsError::new(&message).into())
+    }
 }
 
 impl BatchableResult for JsValue {
@@ -592,11 +1157,17 @@ impl<T: BinaryEncode + CloneForEncode> BinaryEncode for &T {
     fn encode(self, encoder: &mut EncodedData) {
         self.clone().encode(encoder);
     }
+
+    fn encode_sequence(values: impl ExactSizeIterator<Item = Self>, encoder: &mut EncodedData) {
+        // Let the element type pick the wire format, so `&[f64]` packs like `Vec<f64>`
+        T::encode_sequence(values.cloned(), encoder);
+    }
 }
 
 // When encoding JsValue references, encode the underlying ID
 impl BinaryEncode for &JsValue {
     fn encode(self, encoder: &mut EncodedData) {
+        self.debug_assert_alive();
         encoder.push_u64(self.id());
     }
 }
@@ -634,7 +1205,7 @@ macro_rules! decode_args {
     // Main entry: decode each arg and call body
     ($decoder:expr; [$first:ident, $($ty:ident,)*] => $body:expr) => {{
         #[allow(non_snake_case)]
-        let $first = <$first as BinaryDecode>::decode($decoder).unwrap();
+        let $first = <$first as BinaryDecode>::decode($decoder)?;
         decode_args!($decoder; [$($ty,)*] => $body);
     }};
     // Nothing to decode, just execute body
@@ -684,6 +1255,7 @@ macro_rules! impl_fnmut_stub {
                             let result = boxed($($arg),*);
                             result.encode(encoder);
                         });
+                        Ok(())
                     },
                 )
             }
@@ -706,6 +1278,7 @@ macro_rules! impl_fnmut_stub {
                             let result = boxed($($arg),*);
                             result.encode(encoder);
                         });
+                        Ok(())
                     }
                 )
             }
@@ -727,11 +1300,24 @@ macro_rules! impl_fnmut_stub {
                             let result = self($($arg),*);
                             result.encode(encoder);
                         });
+                        Ok(())
                     },
                 )
             }
         }
 
+        // IntoJsFunction for F: FnMut, going through Closure<dyn FnMut>
+        impl<R, F, $($arg,)*> IntoJsFunction<fn($($arg),*) -> R> for F
+            where F: FnMut($($arg),*) -> R + 'static,
+            $($arg: BinaryDecode + EncodeTypeDef + 'static, )*
+            R: BinaryEncode + EncodeTypeDef + 'static,
+        {
+            fn into_js_function(self) -> JsValue {
+                let closure: crate::Closure<dyn FnMut($($arg),*) -> R> = self.into_closure();
+                closure.into_js_value()
+            }
+        }
+
         // IntoClosure for F: Fn -> Closure<dyn Fn>
         impl<R, F, $($arg,)*> IntoClosure<fn($($arg),*) -> R, crate::Closure<dyn Fn($($arg),*) -> R>> for F
             where F: Fn($($arg),*) -> R + 'static,
@@ -748,6 +1334,7 @@ macro_rules! impl_fnmut_stub {
                             let result = self($($arg),*);
                             result.encode(encoder);
                         });
+                        Ok(())
                     },
                 )
             }
@@ -814,9 +1401,10 @@ macro_rules! impl_closure_ref_encode {
                         };
                         let f: &mut dyn FnMut($($arg),*) -> R = unsafe { &mut *ptr };
                         // Decode arguments and call the closure
-                        $(let $arg = <$arg as BinaryDecode>::decode(decoder).unwrap();)*
+                        $(let $arg = <$arg as BinaryDecode>::decode(decoder)?;)*
                         let result = f($($arg),*);
                         result.encode(encoder);
+                        Ok(())
                     },
                 );
                 let key: CallbackKey<fn($($arg),*) -> R> = CallbackKey::new(crate::object_store::insert_object(callback));
@@ -877,9 +1465,10 @@ macro_rules! impl_closure_ref_encode {
                         };
                         let f: &dyn Fn($($arg),*) -> R = unsafe { &*ptr };
                         // Decode arguments and call the closure
-                        $(let $arg = <$arg as BinaryDecode>::decode(decoder).unwrap();)*
+                        $(let $arg = <$arg as BinaryDecode>::decode(decoder)?;)*
                         let result = f($($arg),*);
                         result.encode(encoder);
+                        Ok(())
                     },
                 );
                 let key: CallbackKey<fn($($arg),*) -> R> = CallbackKey::new(crate::object_store::insert_object(callback));
@@ -941,9 +1530,10 @@ macro_rules! impl_closure_ref_encode {
                         };
                         let f: &dyn Fn($($arg),*) -> R = unsafe { &*ptr };
                         // Decode arguments and call the closure
-                        $(let $arg = <$arg as BinaryDecode>::decode(decoder).unwrap();)*
+                        $(let $arg = <$arg as BinaryDecode>::decode(decoder)?;)*
                         let result = f($($arg),*);
                         result.encode(encoder);
+                        Ok(())
                     },
                 );
                 let key: CallbackKey<fn($($arg),*) -> R> = CallbackKey::new(crate::object_store::insert_object(callback));
@@ -1016,10 +1606,11 @@ macro_rules! impl_fnmut_stub_ref {
             fn into_js_closure(mut boxed: Box<Self>) -> crate::Closure<Self> {
                 crate::Closure::wrap_encode_decode_mut::<fn(&$first, $($rest),*) -> R>(
                     move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
-                        let anchor = <$first as RefFromBinaryDecode>::ref_decode(decoder).unwrap();
-                        $(let $rest = <$rest as BinaryDecode>::decode(decoder).unwrap();)*
+                        let anchor = <$first as RefFromBinaryDecode>::ref_decode(decoder)?;
+                        $(let $rest = <$rest as BinaryDecode>::decode(decoder)?;)*
                         let result = boxed(&*anchor, $($rest),*);
                         result.encode(encoder);
+                        Ok(())
                     },
                 )
             }
@@ -1037,10 +1628,11 @@ macro_rules! impl_fnmut_stub_ref {
             fn into_js_closure(boxed: Box<Self>) -> crate::Closure<Self> {
                 crate::Closure::wrap_encode_decode::<fn(&$first, $($rest),*) -> R>(
                     move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
-                        let anchor = <$first as RefFromBinaryDecode>::ref_decode(decoder).unwrap();
-                        $(let $rest = <$rest as BinaryDecode>::decode(decoder).unwrap();)*
+                        let anchor = <$first as RefFromBinaryDecode>::ref_decode(decoder)?;
+                        $(let $rest = <$rest as BinaryDecode>::decode(decoder)?;)*
                         let result = boxed(&*anchor, $($rest),*);
                         result.encode(encoder);
+                        Ok(())
                     },
                 )
             }
@@ -1058,10 +1650,11 @@ macro_rules! impl_fnmut_stub_ref {
             fn into_closure(mut self) -> crate::Closure<dyn FnMut(&$first, $($rest),*) -> R> {
                 crate::Closure::wrap_encode_decode_mut::<fn(&$first, $($rest),*) -> R>(
                     move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
-                        let anchor = <$first as RefFromBinaryDecode>::ref_decode(decoder).unwrap();
-                        $(let $rest = <$rest as BinaryDecode>::decode(decoder).unwrap();)*
+                        let anchor = <$first as RefFromBinaryDecode>::ref_decode(decoder)?;
+                        $(let $rest = <$rest as BinaryDecode>::decode(decoder)?;)*
                         let result = self(&*anchor, $($rest),*);
                         result.encode(encoder);
+                        Ok(())
                     },
                 )
             }
@@ -1079,10 +1672,11 @@ macro_rules! impl_fnmut_stub_ref {
             fn into_closure(self) -> crate::Closure<dyn Fn(&$first, $($rest),*) -> R> {
                 crate::Closure::wrap_encode_decode::<fn($first, $($rest),*) -> R>(
                     move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
-                        let anchor = <$first as RefFromBinaryDecode>::ref_decode(decoder).unwrap();
-                        $(let $rest = <$rest as BinaryDecode>::decode(decoder).unwrap();)*
+                        let anchor = <$first as RefFromBinaryDecode>::ref_decode(decoder)?;
+                        $(let $rest = <$rest as BinaryDecode>::decode(decoder)?;)*
                         let result = self(&*anchor, $($rest),*);
                         result.encode(encoder);
+                        Ok(())
                     },
                 )
             }
@@ -1098,6 +1692,267 @@ impl_fnmut_stub_ref!(A1, A2, A3, A4, A5);
 impl_fnmut_stub_ref!(A1, A2, A3, A4, A5, A6);
 impl_fnmut_stub_ref!(A1, A2, A3, A4, A5, A6, A7);
 
+/// Marker type for closures that borrow every argument.
+pub struct BorrowedArgs;
+
+/// Macro to implement WasmClosure and IntoClosure for closures that borrow every argument,
+/// like `dyn FnMut(&JsValue, &JsValue) -> JsValue`. Each argument is decoded with
+/// RefFromBinaryDecode in order, so JS pushes them onto the borrow stack in the same order.
+/// Single argument closures are covered by `impl_fnmut_stub_ref`.
+macro_rules! impl_fnmut_stub_all_ref {
+    ($($arg:ident),+) => {
+        // Implement EncodeTypeDef for fn(borrowed+) -> R
+        #[allow(coherence_leak_check)]
+        impl<R, $($arg,)+> EncodeTypeDef for CallbackKey<fn($(&$arg),+) -> R>
+            where
+            $($arg: EncodeTypeDef + 'static, )+
+            R: EncodeTypeDef + 'static,
+        {
+            fn encode_type_def(buf: &mut Vec<u8>) {
+                buf.push(TypeTag::Callback as u8);
+                // Encode arg count
+                let mut count: u8 = 0;
+                $(
+                    let _ = PhantomData::<$arg>;
+                    count += 1;
+                )+
+                buf.push(count);
+                // Encode each argument type
+                $(
+                    let _ = PhantomData::<$arg>;
+                    buf.push(TypeTag::BorrowedRef as u8);
+                )+
+                // Encode return type
+                <R as EncodeTypeDef>::encode_type_def(buf);
+            }
+        }
+
+        // WasmClosure for dyn FnMut(&A1, &A2, ...) -> R
+        impl<R, $($arg,)+> crate::WasmClosure<(BorrowedArgs, fn($(&$arg),+) -> R)> for dyn FnMut($(&$arg),+) -> R
+            where
+            $($arg: RefFromBinaryDecode + EncodeTypeDef + 'static,)+
+            R: BinaryEncode + EncodeTypeDef + 'static,
+        {
+            #[allow(non_snake_case)]
+            fn into_js_closure(mut boxed: Box<Self>) -> crate::Closure<Self> {
+                crate::Closure::wrap_encode_decode_mut::<fn($(&$arg),+) -> R>(
+                    move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
+                        $(let $arg = <$arg as RefFromBinaryDecode>::ref_decode(decoder)?;)+
+                        let result = boxed($(&*$arg),+);
+                        result.encode(encoder);
+                        Ok(())
+                    },
+                )
+            }
+        }
+
+        // WasmClosure for dyn Fn(&A1, &A2, ...) -> R (supports reentrant calls)
+        impl<R, $($arg,)+> crate::WasmClosure<(BorrowedArgs, fn($(&$arg),+) -> R)> for dyn Fn($(&$arg),+) -> R
+            where
+            $($arg: RefFromBinaryDecode + EncodeTypeDef + 'static,)+
+            R: BinaryEncode + EncodeTypeDef + 'static,
+        {
+            #[allow(non_snake_case)]
+            fn into_js_closure(boxed: Box<Self>) -> crate::Closure<Self> {
+                crate::Closure::wrap_encode_decode::<fn($(&$arg),+) -> R>(
+                    move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
+                        $(let $arg = <$arg as RefFromBinaryDecode>::ref_decode(decoder)?;)+
+                        let result = boxed($(&*$arg),+);
+                        result.encode(encoder);
+                        Ok(())
+                    },
+                )
+            }
+        }
+
+        // IntoClosure for F: FnMut(&A1, &A2, ...) -> R -> Closure<dyn FnMut(&A1, &A2, ...) -> R>
+        impl<R, F, $($arg,)+> IntoClosure<(BorrowedArgs, fn($(&$arg),+) -> R), crate::Closure<dyn FnMut($(&$arg),+) -> R>> for F
+            where F: FnMut($(&$arg),+) -> R + 'static,
+            $($arg: RefFromBinaryDecode + EncodeTypeDef + 'static,)+
+            R: BinaryEncode + EncodeTypeDef + 'static,
+        {
+            #[allow(non_snake_case)]
+            fn into_closure(mut self) -> crate::Closure<dyn FnMut($(&$arg),+) -> R> {
+                crate::Closure::wrap_encode_decode_mut::<fn($(&$arg),+) -> R>(
+                    move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
+                        $(let $arg = <$arg as RefFromBinaryDecode>::ref_decode(decoder)?;)+
+                        let result = self($(&*$arg),+);
+                        result.encode(encoder);
+                        Ok(())
+                    },
+                )
+            }
+        }
+
+        // IntoClosure for F: Fn(&A1, &A2, ...) -> R -> Closure<dyn Fn(&A1, &A2, ...) -> R>
+        impl<R, F, $($arg,)+> IntoClosure<(BorrowedArgs, fn($(&$arg),+) -> R), crate::Closure<dyn Fn($(&$arg),+) -> R>> for F
+            where F: Fn($(&$arg),+) -> R + 'static,
+            $($arg: RefFromBinaryDecode + EncodeTypeDef + 'static,)+
+            R: BinaryEncode + EncodeTypeDef + 'static,
+        {
+            #[allow(non_snake_case)]
+            fn into_closure(self) -> crate::Closure<dyn Fn($(&$arg),+) -> R> {
+                crate::Closure::wrap_encode_decode::<fn($(&$arg),+) -> R>(
+                    move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
+                        $(let $arg = <$arg as RefFromBinaryDecode>::ref_decode(decoder)?;)+
+                        let result = self($(&*$arg),+);
+                        result.encode(encoder);
+                        Ok(())
+                    },
+                )
+            }
+        }
+    };
+}
+
+impl_fnmut_stub_all_ref!(A1, A2);
+impl_fnmut_stub_all_ref!(A1, A2, A3);
+impl_fnmut_stub_all_ref!(A1, A2, A3, A4);
+
+/// Marker type for closures whose first argument is an optional borrowed reference.
+pub struct OptionalBorrowedFirstArg;
+
+/// Decode an optional borrowed reference: a u8 flag (0=None, 1=Some) and the reference if Some.
+fn optional_ref_decode<T: RefFromBinaryDecode>(
+    decoder: &mut DecodedData,
+) -> Result<Option<T::Anchor>, DecodeError> {
+    if decoder.take_u8()? != 0 {
+        Ok(Some(T::ref_decode(decoder)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Macro to implement WasmClosure and IntoClosure for closures whose first argument is
+/// `Option<&First>`. JS passes `null` or `undefined` as `None`, and any other value on the
+/// borrow stack like a plain `&First` argument.
+macro_rules! impl_fnmut_stub_optional_ref {
+    ($first:ident $(, $rest:ident)*) => {
+        // Implement EncodeTypeDef for fn(Option<borrowed>, owned*) -> R
+        #[allow(coherence_leak_check)]
+        impl<R, $first, $($rest,)*> EncodeTypeDef for CallbackKey<fn(Option<&$first>, $($rest),*) -> R>
+            where
+            $first: EncodeTypeDef + 'static,
+            $($rest: EncodeTypeDef + 'static, )*
+            R: EncodeTypeDef + 'static,
+        {
+            #[allow(unused)]
+            fn encode_type_def(buf: &mut Vec<u8>) {
+                buf.push(TypeTag::Callback as u8);
+                // Encode arg count
+                let mut count: u8 = 1;
+                $(
+                    let _ = PhantomData::<$rest>;
+                    count += 1;
+                )*
+                buf.push(count);
+                // Encode each argument type
+                buf.push(TypeTag::Option as u8);
+                buf.push(TypeTag::BorrowedRef as u8);
+                $(<$rest as EncodeTypeDef>::encode_type_def(buf);)*
+                // Encode return type
+                <R as EncodeTypeDef>::encode_type_def(buf);
+            }
+        }
+
+        // WasmClosure for dyn FnMut(Option<&First>, ...) -> R
+        impl<R, $first, $($rest,)*> crate::WasmClosure<(OptionalBorrowedFirstArg, fn(Option<&$first>, $($rest),*) -> R)> for dyn FnMut(Option<&$first>, $($rest),*) -> R
+            where
+            $first: RefFromBinaryDecode + EncodeTypeDef + 'static,
+            $($rest: BinaryDecode + EncodeTypeDef + 'static,)*
+            R: BinaryEncode + EncodeTypeDef + 'static,
+        {
+            #[allow(non_snake_case)]
+            #[allow(unused)]
+            fn into_js_closure(mut boxed: Box<Self>) -> crate::Closure<Self> {
+                crate::Closure::wrap_encode_decode_mut::<fn(Option<&$first>, $($rest),*) -> R>(
+                    move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
+                        let anchor = optional_ref_decode::<$first>(decoder)?;
+                        $(let $rest = <$rest as BinaryDecode>::decode(decoder)?;)*
+                        let result = boxed(anchor.as_deref(), $($rest),*);
+                        result.encode(encoder);
+                        Ok(())
+                    },
+                )
+            }
+        }
+
+        // WasmClosure for dyn Fn(Option<&First>, ...) -> R (supports reentrant calls)
+        impl<R, $first, $($rest,)*> crate::WasmClosure<(OptionalBorrowedFirstArg, fn(Option<&$first>, $($rest),*) -> R)> for dyn Fn(Option<&$first>, $($rest),*) -> R
+            where
+            $first: RefFromBinaryDecode + EncodeTypeDef + 'static,
+            $($rest: BinaryDecode + EncodeTypeDef + 'static,)*
+            R: BinaryEncode + EncodeTypeDef + 'static,
+        {
+            #[allow(non_snake_case)]
+            #[allow(unused)]
+            fn into_js_closure(boxed: Box<Self>) -> crate::Closure<Self> {
+                crate::Closure::wrap_encode_decode::<fn(Option<&$first>, $($rest),*) -> R>(
+                    move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
+                        let anchor = optional_ref_decode::<$first>(decoder)?;
+                        $(let $rest = <$rest as BinaryDecode>::decode(decoder)?;)*
+                        let result = boxed(anchor.as_deref(), $($rest),*);
+                        result.encode(encoder);
+                        Ok(())
+                    },
+                )
+            }
+        }
+
+        // IntoClosure for F: FnMut(Option<&First>, ...) -> R -> Closure<dyn FnMut(Option<&First>, ...) -> R>
+        impl<R, F, $first, $($rest,)*> IntoClosure<(OptionalBorrowedFirstArg, fn(Option<&$first>, $($rest),*) -> R), crate::Closure<dyn FnMut(Option<&$first>, $($rest),*) -> R>> for F
+            where F: FnMut(Option<&$first>, $($rest),*) -> R + 'static,
+            $first: RefFromBinaryDecode + EncodeTypeDef + 'static,
+            $($rest: BinaryDecode + EncodeTypeDef + 'static,)*
+            R: BinaryEncode + EncodeTypeDef + 'static,
+        {
+            #[allow(non_snake_case)]
+            #[allow(unused)]
+            fn into_closure(mut self) -> crate::Closure<dyn FnMut(Option<&$first>, $($rest),*) -> R> {
+                crate::Closure::wrap_encode_decode_mut::<fn(Option<&$first>, $($rest),*) -> R>(
+                    move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
+                        let anchor = optional_ref_decode::<$first>(decoder)?;
+                        $(let $rest = <$rest as BinaryDecode>::decode(decoder)?;)*
+                        let result = self(anchor.as_deref(), $($rest),*);
+                        result.encode(encoder);
+                        Ok(())
+                    },
+                )
+            }
+        }
+
+        // IntoClosure for F: Fn(Option<&First>, ...) -> R -> Closure<dyn Fn(Option<&First>, ...) -> R>
+        impl<R, F, $first, $($rest,)*> IntoClosure<(OptionalBorrowedFirstArg, fn(Option<&$first>, $($rest),*) -> R), crate::Closure<dyn Fn(Option<&$first>, $($rest),*) -> R>> for F
+            where F: Fn(Option<&$first>, $($rest),*) -> R + 'static,
+            $first: RefFromBinaryDecode + EncodeTypeDef + 'static,
+            $($rest: BinaryDecode + EncodeTypeDef + 'static,)*
+            R: BinaryEncode + EncodeTypeDef + 'static,
+        {
+            #[allow(non_snake_case)]
+            #[allow(unused)]
+            fn into_closure(self) -> crate::Closure<dyn Fn(Option<&$first>, $($rest),*) -> R> {
+                crate::Closure::wrap_encode_decode::<fn(Option<&$first>, $($rest),*) -> R>(
+                    move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
+                        let anchor = optional_ref_decode::<$first>(decoder)?;
+                        $(let $rest = <$rest as BinaryDecode>::decode(decoder)?;)*
+                        let result = self(anchor.as_deref(), $($rest),*);
+                        result.encode(encoder);
+                        Ok(())
+                    },
+                )
+            }
+        }
+    };
+}
+
+impl_fnmut_stub_optional_ref!(A1);
+impl_fnmut_stub_optional_ref!(A1, A2);
+impl_fnmut_stub_optional_ref!(A1, A2, A3);
+impl_fnmut_stub_optional_ref!(A1, A2, A3, A4);
+impl_fnmut_stub_optional_ref!(A1, A2, A3, A4, A5);
+impl_fnmut_stub_optional_ref!(A1, A2, A3, A4, A5, A6);
+impl_fnmut_stub_optional_ref!(A1, A2, A3, A4, A5, A6, A7);
+
 /// Macro to implement WasmClosureFnOnce for FnOnce closures of various arities.
 /// This wraps an FnOnce in an FnMut that panics if called more than once.
 macro_rules! impl_fn_once {
@@ -1116,11 +1971,13 @@ macro_rules! impl_fn_once {
                 // Register the callback using the same pattern as impl_fnmut_stub
                 crate::Closure::wrap_encode_decode_mut::<fn($($arg),*) -> R>(
                     move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
-                        let f = me.take().expect("FnOnce closure called more than once");
+                        // Decode first so a malformed call does not use up the closure
                         decode_args!(decoder; [$($arg,)*] => {
+                            let f = me.take().expect("FnOnce closure called more than once");
                             let result = f($($arg),*);
                             result.encode(encoder);
                         });
+                        Ok(())
                     },
                 )
             }
@@ -1154,11 +2011,12 @@ macro_rules! impl_fn_once_ref {
                 let mut me = Some(self);
                 crate::Closure::wrap_encode_decode_mut::<fn(&$first, $($rest),*) -> R>(
                     move |decoder: &mut DecodedData, encoder: &mut EncodedData| {
+                        let anchor = <$first as RefFromBinaryDecode>::ref_decode(decoder)?;
+                        $(let $rest = <$rest as BinaryDecode>::decode(decoder)?;)*
                         let f = me.take().expect("FnOnce closure called more than once");
-                        let anchor = <$first as RefFromBinaryDecode>::ref_decode(decoder).unwrap();
-                        $(let $rest = <$rest as BinaryDecode>::decode(decoder).unwrap();)*
                         let result = f(&*anchor, $($rest),*);
                         result.encode(encoder);
+                        Ok(())
                     },
                 )
             }
@@ -1201,62 +2059,69 @@ impl<F: ?Sized> BinaryEncode for &crate::Closure<F> {
 
 impl<T: EncodeTypeDef> EncodeTypeDef for Vec<T> {
     fn encode_type_def(buf: &mut Vec<u8>) {
-        // Array type tag followed by element type
-        buf.push(TypeTag::Array as u8);
-        T::encode_type_def(buf);
+        T::encode_sequence_type_def(buf);
     }
 }
 
 impl<T: EncodeTypeDef> EncodeTypeDef for &[T] {
     fn encode_type_def(buf: &mut Vec<u8>) {
-        // Array type tag followed by element type
-        buf.push(TypeTag::Array as u8);
-        T::encode_type_def(buf);
+        T::encode_sequence_type_def(buf);
     }
 }
 
 impl<T: EncodeTypeDef> EncodeTypeDef for &mut [T] {
     fn encode_type_def(buf: &mut Vec<u8>) {
-        // Array type tag followed by element type
-        buf.push(TypeTag::Array as u8);
-        T::encode_type_def(buf);
+        buf.push(TypeTag::MutSlice as u8);
+        T::encode_sequence_type_def(buf);
     }
 }
 
 impl<T: EncodeTypeDef> EncodeTypeDef for Box<[T]> {
     fn encode_type_def(buf: &mut Vec<u8>) {
-        // Array type tag followed by element type
-        buf.push(TypeTag::Array as u8);
-        T::encode_type_def(buf);
+        T::encode_sequence_type_def(buf);
     }
 }
 
 impl<T: BinaryEncode> BinaryEncode for Box<[T]> {
     fn encode(self, encoder: &mut EncodedData) {
-        encoder.push_u32(self.len() as u32);
-        for val in self.into_vec() {
-            val.encode(encoder);
-        }
+        T::encode_sequence(self.into_vec().into_iter(), encoder);
+    }
+}
+
+// A box is just an owned pointer, so it is sent exactly like the value it holds
+impl<T: EncodeTypeDef> EncodeTypeDef for Box<T> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        T::encode_type_def(buf);
+    }
+}
+
+impl<T: BinaryEncode<P>, P> BinaryEncode<P> for Box<T> {
+    fn encode(self, encoder: &mut EncodedData) {
+        (*self).encode(encoder);
+    }
+}
+
+impl<T: BinaryDecode> BinaryDecode for Box<T> {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        T::decode(decoder).map(Box::new)
+    }
+}
+
+impl<T: BatchableResult> BatchableResult for Box<T> {
+    fn try_placeholder(batch: &mut Runtime) -> Option<Self> {
+        T::try_placeholder(batch).map(Box::new)
     }
 }
 
 impl<T: BinaryEncode> BinaryEncode for Vec<T> {
     fn encode(self, encoder: &mut EncodedData) {
-        encoder.push_u32(self.len() as u32);
-        for val in self {
-            val.encode(encoder);
-        }
+        T::encode_sequence(self.into_iter(), encoder);
     }
 }
 
 impl<T: BinaryDecode> BinaryDecode for Vec<T> {
     fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
-        let len = decoder.take_u32()? as usize;
-        let mut vec = Vec::with_capacity(len);
-        for _ in 0..len {
-            vec.push(T::decode(decoder)?);
-        }
-        Ok(vec)
+        T::decode_sequence(decoder)
     }
 }
 
@@ -1267,25 +2132,178 @@ where
     for<'a> &'a T: BinaryEncode,
 {
     fn encode(self, encoder: &mut EncodedData) {
-        encoder.push_u32(self.len() as u32);
-        for val in self {
-            val.encode(encoder);
-        }
+        <&T>::encode_sequence(self.iter(), encoder);
     }
 }
 
+// A mutable slice is sent like a shared one, and JS sends its contents back once the
+// call returns. The call must run before the slice's borrow ends, so it is never batched.
 impl<T> BinaryEncode for &mut [T]
 where
     for<'a> &'a T: BinaryEncode,
+    T: BinaryDecode,
 {
     fn encode(self, encoder: &mut EncodedData) {
-        encoder.push_u32(self.len() as u32);
-        for val in self {
-            val.encode(encoder);
+        <&T>::encode_sequence(self.iter(), encoder);
+        encoder.mark_needs_flush();
+        with_runtime(|state| state.push_write_back(WriteBack::new(self)));
+    }
+}
+
+/// A mutable slice passed to JS, waiting for its contents to be sent back.
+pub(crate) struct WriteBack {
+    ptr: *mut (),
+    len: usize,
+    apply: unsafe fn(*mut (), usize, &mut DecodedData) -> Result<(), DecodeError>,
+}
+
+impl WriteBack {
+    fn new<T: BinaryDecode>(slice: &mut [T]) -> Self {
+        /// # Safety
+        /// `ptr` and `len` must describe a live `[T]` that nothing else is accessing.
+        unsafe fn apply<T: BinaryDecode>(
+            ptr: *mut (),
+            len: usize,
+            data: &mut DecodedData,
+        ) -> Result<(), DecodeError> {
+            let values = T::decode_sequence(data)?;
+            // SAFETY: guaranteed by the caller
+            let slice = unsafe { core::slice::from_raw_parts_mut(ptr.cast::<T>(), len) };
+            // JS can not resize typed arrays. Elements added to plain arrays are ignored.
+            for (slot, value) in slice.iter_mut().zip(values) {
+                *slot = value;
+            }
+            Ok(())
+        }
+
+        Self {
+            ptr: slice.as_mut_ptr().cast(),
+            len: slice.len(),
+            apply: apply::<T>,
+        }
+    }
+
+    /// Copy the contents JS sent back into the slice.
+    ///
+    /// # Safety
+    /// The slice this was created from must still be mutably borrowed by the caller,
+    /// which holds while the JS call it was passed to is running.
+    pub(crate) unsafe fn apply(self, data: &mut DecodedData) -> Result<(), DecodeError> {
+        // SAFETY: guaranteed by the caller
+        unsafe { (self.apply)(self.ptr, self.len, data) }
+    }
+}
+
+// A Cow slice is sent exactly like a Vec<T>. A borrowed slice is encoded in place
+// without cloning it, and decoding always produces an owned Vec.
+impl<T: EncodeTypeDef + Clone> EncodeTypeDef for Cow<'_, [T]> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        T::encode_sequence_type_def(buf);
+    }
+}
+
+impl<T> BinaryEncode for Cow<'_, [T]>
+where
+    T: BinaryEncode + Clone,
+    for<'a> &'a T: BinaryEncode,
+{
+    fn encode(self, encoder: &mut EncodedData) {
+        match self {
+            Cow::Borrowed(slice) => slice.encode(encoder),
+            Cow::Owned(vec) => vec.encode(encoder),
         }
     }
 }
 
+impl<T: BinaryDecode + Clone> BinaryDecode for Cow<'_, [T]> {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        T::decode_sequence(decoder).map(Cow::Owned)
+    }
+}
+
+impl<T: BinaryDecode + Clone> BatchableResult for Cow<'_, [T]> {}
+
+// ============ Packed numeric sequences ============
+
+/// A numeric primitive that sequences send as one block of little-endian bytes
+/// instead of element by element. JS reads the block as a typed array.
+trait PackedPrimitive: Sized {
+    const SIZE: usize;
+
+    /// The type's name for [`DecodeError::OutOfRange`], if blocks of it start with a
+    /// range flag like single 8 and 16 bit integers do
+    const RANGE_CHECKED: Option<&'static str>;
+
+    fn write_le(self, out: &mut Vec<u8>);
+
+    fn read_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_packed_primitive {
+    ($($ty:ty => $range_checked:expr),*) => {
+        $(
+            impl PackedPrimitive for $ty {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+
+                const RANGE_CHECKED: Option<&'static str> = $range_checked;
+
+                fn write_le(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_le(bytes: &[u8]) -> Self {
+                    <$ty>::from_le_bytes(bytes.try_into().expect("chunk has the element size"))
+                }
+            }
+        )*
+    };
+}
+
+impl_packed_primitive!(
+    u16 => Some("u16"),
+    i8 => Some("i8"),
+    i16 => Some("i16"),
+    u32 => None,
+    i32 => None,
+    f32 => None,
+    f64 => None
+);
+
+fn packed_type_def<T: PackedPrimitive + EncodeTypeDef>(buf: &mut Vec<u8>) {
+    buf.push(TypeTag::PackedArray as u8);
+    T::encode_type_def(buf);
+}
+
+fn encode_packed<T: PackedPrimitive>(
+    values: impl ExactSizeIterator<Item = T>,
+    encoder: &mut EncodedData,
+) {
+    if T::RANGE_CHECKED.is_some() {
+        encoder.push_u8(NARROW_IN_RANGE);
+    }
+    encoder.push_u32(values.len() as u32);
+    let mut bytes = Vec::with_capacity(values.len() * T::SIZE);
+    for value in values {
+        value.write_le(&mut bytes);
+    }
+    encoder.push_bytes(&bytes);
+}
+
+fn decode_packed<T: PackedPrimitive>(decoder: &mut DecodedData) -> Result<Vec<T>, DecodeError> {
+    match T::RANGE_CHECKED {
+        Some(ty) if decoder.take_u8()? != NARROW_IN_RANGE => {
+            return Err(DecodeError::OutOfRange { ty });
+        }
+        _ => {}
+    }
+    let len = decoder.take_u32()? as usize;
+    let byte_len = len.checked_mul(T::SIZE).ok_or_else(|| {
+        DecodeError::Custom(format!("packed array of {len} elements is too large"))
+    })?;
+    let bytes = decoder.take_bytes_ref(byte_len)?;
+    Ok(bytes.chunks_exact(T::SIZE).map(T::read_le).collect())
+}
+
 // ============ Clamped<T> implementations ============
 
 use crate::Clamped;
@@ -13,8 +13,7 @@ use core::marker::PhantomData;
 
 use crate::batch::{force_flush, run_js_sync, with_runtime};
 use crate::encode::{BatchableResult, BinaryEncode, EncodeTypeDef, TYPE_CACHED, TYPE_FULL};
-use crate::ipc::DecodedData;
-use crate::ipc::EncodedData;
+use crate::ipc::{DecodeError, DecodedData, EncodedData};
 
 /// Reserved function ID for dropping native Rust refs when JS objects are GC'd.
 /// JS sends this when a FinalizationRegistry callback fires for a RustFunction.
@@ -148,20 +147,27 @@ impl_js_function_call!(32, T1 P1 arg1, T2 P2 arg2, T3 P3 arg3, T4 P4 arg4, T5 P5
 /// Always stores as `Rc<dyn Fn(...)>` for uniform handling.
 /// - For `Fn` closures: stored directly, supports reentrant calls
 /// - For `FnMut` closures: wrapped in RefCell internally, panics on reentrant calls
+///
+/// Callbacks return an error instead of panicking when JS sent arguments they cannot decode.
 pub(crate) struct RustCallback {
-    f: alloc::rc::Rc<dyn Fn(&mut DecodedData, &mut EncodedData)>,
+    f: alloc::rc::Rc<RustCallbackFn>,
 }
 
+/// The type-erased body of a [`RustCallback`].
+pub(crate) type RustCallbackFn =
+    dyn Fn(&mut DecodedData, &mut EncodedData) -> Result<(), DecodeError>;
+
 impl RustCallback {
     /// Create a callback from an `Fn` closure (supports reentrant calls)
     pub fn new_fn<F>(f: F) -> Self
     where
-        F: Fn(&mut DecodedData, &mut EncodedData) + 'static,
+        F: Fn(&mut DecodedData, &mut EncodedData) -> Result<(), DecodeError> + 'static,
     {
         Self {
             f: alloc::rc::Rc::new(move |data: &mut DecodedData, encoder: &mut EncodedData| {
-                f(data, encoder);
+                let result = f(data, encoder);
                 force_flush();
+                result
             }),
         }
     }
@@ -169,23 +175,24 @@ impl RustCallback {
     /// Create a callback from an `FnMut` closure (panics on reentrant calls)
     pub fn new_fn_mut<F>(f: F) -> Self
     where
-        F: FnMut(&mut DecodedData, &mut EncodedData) + 'static,
+        F: FnMut(&mut DecodedData, &mut EncodedData) -> Result<(), DecodeError> + 'static,
     {
         // Wrap the FnMut in a RefCell, then create an Fn wrapper
         let cell = RefCell::new(f);
         Self {
             f: alloc::rc::Rc::new(move |data: &mut DecodedData, encoder: &mut EncodedData| {
-                {
+                let result = {
                     let mut f = cell.borrow_mut();
-                    f(data, encoder);
-                }
+                    f(data, encoder)
+                };
                 force_flush();
+                result
             }),
         }
     }
 
     /// Get a cloned Rc to the callback
-    pub fn clone_rc(&self) -> alloc::rc::Rc<dyn Fn(&mut DecodedData, &mut EncodedData)> {
+    pub fn clone_rc(&self) -> alloc::rc::Rc<RustCallbackFn> {
         self.f.clone()
     }
 }
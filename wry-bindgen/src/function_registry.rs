@@ -10,19 +10,34 @@ use core::fmt::Write;
 use core::ops::Deref;
 use once_cell::sync::{Lazy, OnceCell};
 
+use crate::JsValue;
 use crate::function::JSFunction;
-use crate::ipc::{DecodedData, EncodedData};
+use crate::ipc::{DecodeError, DecodedData, EncodedData};
+use crate::object_store::{BorrowObjectError, InvalidHandleError};
 
 /// Function specification for the registry
 #[derive(Clone, Copy)]
 pub struct JsFunctionSpec {
+    /// The name of the import in JS, such as `add` or `Array.prototype.push`, or empty
+    /// for functions that are not imported with `#[wasm_bindgen]`
+    name: &'static str,
     /// Function that generates the JS code
     js_code: fn() -> String,
 }
 
 impl JsFunctionSpec {
     pub const fn new(js_code: fn() -> String) -> Self {
-        Self { js_code }
+        Self { name: "", js_code }
+    }
+
+    /// Create the spec of a function imported under `name`.
+    pub const fn named(name: &'static str, js_code: fn() -> String) -> Self {
+        Self { name, js_code }
+    }
+
+    /// Get the name of the import in JS, or an empty string if it has none
+    pub const fn name(&self) -> &'static str {
+        self.name
     }
 
     /// Get the JS code generator function
@@ -111,10 +126,16 @@ pub enum JsClassMemberKind {
     Method,
     /// Static method on class (e.g., `Counter.staticMethod`)
     StaticMethod,
+    /// `async` instance method, which returns a promise in JS
+    AsyncMethod,
+    /// `async` static method, which returns a promise in JS
+    AsyncStaticMethod,
     /// Property getter (e.g., `get count()`)
     Getter,
     /// Property setter (e.g., `set count(v)`)
     Setter,
+    /// `[Symbol.iterator]` method backed by [`WryIterable`](crate::WryIterable)
+    Iterator,
 }
 
 /// Specification for a member of an exported Rust class
@@ -189,13 +210,13 @@ pub struct JsExportSpec {
     /// The export name (e.g., "MyStruct::new", "MyStruct::method")
     pub name: &'static str,
     /// Handler function that decodes arguments, calls the Rust function, and encodes the result
-    pub handler: fn(&mut DecodedData) -> Result<EncodedData, alloc::string::String>,
+    pub handler: fn(&mut DecodedData) -> Result<EncodedData, ExportError>,
 }
 
 impl JsExportSpec {
     pub const fn new(
         name: &'static str,
-        handler: fn(&mut DecodedData) -> Result<EncodedData, alloc::string::String>,
+        handler: fn(&mut DecodedData) -> Result<EncodedData, ExportError>,
     ) -> Self {
         Self { name, handler }
     }
@@ -203,6 +224,43 @@ impl JsExportSpec {
 
 inventory::collect!(JsExportSpec);
 
+/// Why a call to an exported Rust function did not produce a return value.
+///
+/// Both variants are thrown in JS by the caller of the export instead of panicking.
+pub enum ExportError {
+    /// The arguments sent by JS could not be decoded, or referred to an exported object
+    /// that was already dropped or could not be borrowed. Thrown as an `Error` with this
+    /// message.
+    Decode(String),
+    /// The exported function returned `Err`. The value is thrown as is.
+    Throw(JsValue),
+}
+
+impl ExportError {
+    /// Throw `err` in JS, for exported functions returning `Result<T, E>` with `E: Into<JsValue>`.
+    pub fn throw(err: impl Into<JsValue>) -> Self {
+        ExportError::Throw(err.into())
+    }
+}
+
+impl From<DecodeError> for ExportError {
+    fn from(err: DecodeError) -> Self {
+        ExportError::Decode(err.to_string())
+    }
+}
+
+impl From<InvalidHandleError> for ExportError {
+    fn from(err: InvalidHandleError) -> Self {
+        ExportError::Decode(err.to_string())
+    }
+}
+
+impl From<BorrowObjectError> for ExportError {
+    fn from(err: BorrowObjectError) -> Self {
+        ExportError::Decode(err.to_string())
+    }
+}
+
 /// Registry of JS functions collected via inventory
 pub(crate) struct FunctionRegistry {
     functions: String,
@@ -216,6 +274,16 @@ pub(crate) struct FunctionRegistry {
 pub(crate) static FUNCTION_REGISTRY: Lazy<FunctionRegistry> =
     Lazy::new(FunctionRegistry::collect_from_inventory);
 
+/// The global JS function that calls an export of this kind
+fn call_export_fn(kind: JsClassMemberKind) -> &'static str {
+    match kind {
+        JsClassMemberKind::AsyncMethod | JsClassMemberKind::AsyncStaticMethod => {
+            "__wryCallAsyncExport"
+        }
+        _ => "__wryCallExport",
+    }
+}
+
 /// Generate argument names for JS function (a0, a1, a2, ...)
 fn generate_args(count: usize) -> String {
     (0..count)
@@ -316,7 +384,7 @@ impl FunctionRegistry {
             // Generate methods inside the class body
             for member in members {
                 match member.kind() {
-                    JsClassMemberKind::Method => {
+                    JsClassMemberKind::Method | JsClassMemberKind::AsyncMethod => {
                         // Instance method
                         let args = generate_args(member.arg_count());
                         let args_with_handle = if member.arg_count() > 0 {
@@ -326,9 +394,10 @@ impl FunctionRegistry {
                         };
                         writeln!(
                             &mut script,
-                            r#"    {}({}) {{ return window.__wryCallExport("{}", {}); }}"#,
+                            r#"    {}({}) {{ return window.{}("{}", {}); }}"#,
                             member.member_name(),
                             args,
+                            call_export_fn(member.kind()),
                             member.export_name(),
                             args_with_handle
                         )
@@ -340,6 +409,14 @@ impl FunctionRegistry {
                     JsClassMemberKind::Setter => {
                         setters.insert(member.member_name(), member);
                     }
+                    JsClassMemberKind::Iterator => {
+                        writeln!(
+                            &mut script,
+                            r#"    *[Symbol.iterator]() {{ while (true) {{ const r = window.__wryIterNext("{}", this.__handle); if (r.done) return; yield r.value; }} }}"#,
+                            member.export_name()
+                        )
+                        .unwrap();
+                    }
                     _ => {} // Constructor and static handled separately
                 }
             }
@@ -403,15 +480,16 @@ impl FunctionRegistry {
                         )
                         .unwrap();
                     }
-                    JsClassMemberKind::StaticMethod => {
+                    JsClassMemberKind::StaticMethod | JsClassMemberKind::AsyncStaticMethod => {
                         let args = generate_args(member.arg_count());
                         let args_call = if member.arg_count() > 0 { &args } else { "" };
                         writeln!(
                             &mut script,
-                            r#"  {class_name}.{method_name} = function({args}) {{ return window.__wryCallExport("{export_name}", {args_call}); }};"#,
+                            r#"  {class_name}.{method_name} = function({args}) {{ return window.{call_export}("{export_name}", {args_call}); }};"#,
                             class_name = class_name,
                             method_name = member.member_name(),
                             args = args,
+                            call_export = call_export_fn(member.kind()),
                             export_name = member.export_name(),
                             args_call = args_call
                         )
@@ -447,6 +525,12 @@ impl FunctionRegistry {
         Some(JSFunction::new(index as _))
     }
 
+    /// Get the spec of the function with the given id
+    #[cfg(feature = "test-harness")]
+    pub fn function_spec(&self, id: u32) -> Option<JsFunctionSpec> {
+        self.function_specs.get(id as usize).copied()
+    }
+
     /// Get the initialization script
     pub fn script(&self) -> &str {
         &self.functions
@@ -33,7 +33,7 @@ pub(crate) fn unsafe_get_str(s: &str) -> Option<u64> {
     CACHE.with(|cache| {
         let cache = cache.entries.borrow();
 
-        cache.get(s).map(|x| x.idx)
+        cache.get(s).map(|x| x.id())
     })
 }
 
@@ -1,8 +1,9 @@
 //! Binary IPC protocol types for communicating between Rust and JavaScript.
 //!
 //! The binary format uses aligned buffers for efficient memory access:
-//! - First 12 bytes: three u32 offsets (u16_offset, u8_offset, str_offset)
-//! - u32 buffer: from byte 12 to u16_offset
+//! - First 4 bytes: the magic bytes `WRY` followed by the protocol version
+//! - Next 12 bytes: three u32 offsets (u16_offset, u8_offset, str_offset)
+//! - u32 buffer: from byte 16 to u16_offset
 //! - u16 buffer: from u16_offset to u8_offset
 //! - u8 buffer: from u8_offset to str_offset
 //! - string buffer: from str_offset to end
@@ -12,23 +13,43 @@ use alloc::vec::Vec;
 use base64::Engine;
 use core::fmt;
 
+/// Magic bytes at the start of every IPC buffer.
+const IPC_MAGIC: [u8; 3] = *b"WRY";
+
+/// Version of the binary IPC wire format.
+///
+/// This must match `IPC_VERSION` in `encoding.ts` and must be bumped whenever
+/// the wire format changes so mismatched Rust and JS halves fail loudly.
+pub const IPC_VERSION: u8 = 9;
+
+/// Length of the buffer header: magic, version and three u32 offsets.
+pub(crate) const HEADER_LEN: usize = 16;
+
 /// Error type for decoding binary IPC messages.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DecodeError {
-    /// The message is too short (less than 12 bytes for header)
+    /// The message is too short (less than 16 bytes for header)
     MessageTooShort { expected: usize, actual: usize },
+    /// The message does not start with the expected magic bytes
+    InvalidMagic { got: [u8; 3] },
+    /// The message was encoded with a different protocol version
+    VersionMismatch { expected: u8, got: u8 },
     /// The u8 buffer is empty when trying to read
     U8BufferEmpty,
     /// The u16 buffer is empty when trying to read
     U16BufferEmpty,
     /// The u32 buffer is empty when trying to read
     U32BufferEmpty,
+    /// The u8 buffer doesn't have enough bytes for a byte slice
+    U8BufferTooShort { expected: usize, actual: usize },
     /// The string buffer doesn't have enough bytes
     StringBufferTooShort { expected: usize, actual: usize },
     /// Invalid UTF-8 in string buffer
     InvalidUtf8 { position: usize },
     /// Invalid message type byte
     InvalidMessageType { value: u8 },
+    /// The message is larger than the configured maximum message size
+    MessageTooLarge { limit: usize, actual: usize },
     /// Header offsets are invalid (e.g., overlapping or out of bounds)
     InvalidHeaderOffsets {
         u16_offset: u32,
@@ -36,6 +57,10 @@ pub enum DecodeError {
         str_offset: u32,
         total_len: usize,
     },
+    /// A JS number did not fit the integer type it was decoded as
+    OutOfRange { ty: &'static str },
+    /// The webview was recreated or reloaded before it answered the call
+    WebviewRecreated,
     /// Generic decode failure with context
     Custom(String),
 }
@@ -49,9 +74,27 @@ impl fmt::Display for DecodeError {
                     "message too short: expected at least {expected} bytes, got {actual}"
                 )
             }
+            DecodeError::InvalidMagic { got } => {
+                write!(
+                    f,
+                    "invalid magic bytes: expected {IPC_MAGIC:?}, got {got:?}"
+                )
+            }
+            DecodeError::VersionMismatch { expected, got } => {
+                write!(
+                    f,
+                    "IPC protocol version mismatch: expected {expected}, got {got}"
+                )
+            }
             DecodeError::U8BufferEmpty => write!(f, "u8 buffer empty when trying to read"),
             DecodeError::U16BufferEmpty => write!(f, "u16 buffer empty when trying to read"),
             DecodeError::U32BufferEmpty => write!(f, "u32 buffer empty when trying to read"),
+            DecodeError::U8BufferTooShort { expected, actual } => {
+                write!(
+                    f,
+                    "u8 buffer too short: expected {expected} bytes, got {actual}"
+                )
+            }
             DecodeError::StringBufferTooShort { expected, actual } => {
                 write!(
                     f,
@@ -64,6 +107,12 @@ impl fmt::Display for DecodeError {
             DecodeError::InvalidMessageType { value } => {
                 write!(f, "invalid message type: {value}")
             }
+            DecodeError::MessageTooLarge { limit, actual } => {
+                write!(
+                    f,
+                    "message too large: {actual} bytes exceeds the limit of {limit} bytes"
+                )
+            }
             DecodeError::InvalidHeaderOffsets {
                 u16_offset,
                 u8_offset,
@@ -75,6 +124,12 @@ impl fmt::Display for DecodeError {
                     "invalid header offsets: u16={u16_offset}, u8={u8_offset}, str={str_offset}, total_len={total_len}"
                 )
             }
+            DecodeError::OutOfRange { ty } => {
+                write!(f, "JS number is not an integer in the range of {ty}")
+            }
+            DecodeError::WebviewRecreated => {
+                write!(f, "the webview was recreated before it answered the call")
+            }
             DecodeError::Custom(msg) => write!(f, "{msg}"),
         }
     }
@@ -91,7 +146,7 @@ impl From<DecodeError> for String {
 /// Message type identifier for IPC protocol.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum MessageType {
+pub enum MessageType {
     /// Rust calling JS (supports batching - multiple operations in one message)
     Evaluate = 0,
     /// JS/Rust responding to a call
@@ -115,7 +170,7 @@ pub(crate) enum MessageType {
 /// - For each operation result:
 ///   - encoded return value (varies by function)
 #[derive(Debug, Clone)]
-pub(crate) struct IPCMessage {
+pub struct IPCMessage {
     data: Vec<u8>,
 }
 
@@ -147,7 +202,7 @@ impl IPCMessage {
     }
 
     /// Decode the message into its variant form.
-    pub fn decoded(&self) -> Result<DecodedVariant<'_>, DecodeError> {
+    pub(crate) fn decoded(&self) -> Result<DecodedVariant<'_>, DecodeError> {
         let mut decoded = DecodedData::from_bytes(&self.data)?;
         let message_type = decoded.take_u8()?;
         let message_type = match message_type {
@@ -189,15 +244,29 @@ pub struct DecodedData<'a> {
 
 impl<'a> DecodedData<'a> {
     /// Parse decoded data from raw bytes.
-    pub(crate) fn from_bytes(bytes: &'a [u8]) -> Result<Self, DecodeError> {
-        if bytes.len() < 12 {
+    ///
+    /// Fails with [`DecodeError::VersionMismatch`] if the buffer was encoded
+    /// with a different [`IPC_VERSION`].
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        if bytes.len() < HEADER_LEN {
             return Err(DecodeError::MessageTooShort {
-                expected: 12,
+                expected: HEADER_LEN,
                 actual: bytes.len(),
             });
         }
 
-        let header: [u32; 3] = bytemuck::cast_slice(&bytes[0..12])
+        let magic = [bytes[0], bytes[1], bytes[2]];
+        if magic != IPC_MAGIC {
+            return Err(DecodeError::InvalidMagic { got: magic });
+        }
+        if bytes[3] != IPC_VERSION {
+            return Err(DecodeError::VersionMismatch {
+                expected: IPC_VERSION,
+                got: bytes[3],
+            });
+        }
+
+        let header: [u32; 3] = bytemuck::cast_slice(&bytes[4..HEADER_LEN])
             .try_into()
             .map_err(|_| DecodeError::Custom("failed to parse header".to_string()))?;
         let [u16_offset, u8_offset, str_offset] = header;
@@ -207,7 +276,7 @@ impl<'a> DecodedData<'a> {
         if u16_offset as usize > total_len
             || u8_offset as usize > total_len
             || str_offset as usize > total_len
-            || u16_offset < 12
+            || (u16_offset as usize) < HEADER_LEN
             || u8_offset < u16_offset
             || str_offset < u8_offset
         {
@@ -219,7 +288,7 @@ impl<'a> DecodedData<'a> {
             });
         }
 
-        let u32_buf = bytemuck::cast_slice(&bytes[12..u16_offset as usize]);
+        let u32_buf = bytemuck::cast_slice(&bytes[HEADER_LEN..u16_offset as usize]);
         let u16_buf = bytemuck::cast_slice(&bytes[u16_offset as usize..u8_offset as usize]);
         let u8_buf = &bytes[u8_offset as usize..str_offset as usize];
         let str_buf = &bytes[str_offset as usize..];
@@ -259,6 +328,12 @@ impl<'a> DecodedData<'a> {
         Ok(*first)
     }
 
+    /// Check if there are more u32s to read, which marks another batched operation.
+    #[cfg(feature = "test-harness")]
+    pub(crate) fn has_more_u32(&self) -> bool {
+        !self.u32_buf.is_empty()
+    }
+
     /// Take a u64 from the buffer (stored as two u32s).
     pub(crate) fn take_u64(&mut self) -> Result<u64, DecodeError> {
         let low = self.take_u32()? as u64;
@@ -273,6 +348,22 @@ impl<'a> DecodedData<'a> {
         Ok((high << 64) | low)
     }
 
+    /// Take `len` bytes from the u8 buffer without copying them.
+    ///
+    /// The returned slice borrows the underlying message buffer, so large binary
+    /// payloads can be read by codecs without an intermediate allocation.
+    pub fn take_bytes_ref(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let actual_len = self.u8_buf.len();
+        let Some((bytes, rem)) = self.u8_buf.split_at_checked(len) else {
+            return Err(DecodeError::U8BufferTooShort {
+                expected: len,
+                actual: actual_len,
+            });
+        };
+        self.u8_buf = rem;
+        Ok(bytes)
+    }
+
     /// Take a string from the buffer.
     pub(crate) fn take_str(&mut self) -> Result<&'a str, DecodeError> {
         let len = self.take_u32()? as usize;
@@ -331,7 +422,8 @@ impl EncodedData {
 
     /// Get the total byte length of the encoded data.
     pub(crate) fn byte_len(&self) -> usize {
-        12 + self.u32_buf.len() * 4
+        HEADER_LEN
+            + self.u32_buf.len() * 4
             + self.u16_buf.len() * 2
             + self.u8_buf.len()
             + self.str_buf.len()
@@ -370,21 +462,32 @@ impl EncodedData {
         self.push_u64((value >> 64) as u64);
     }
 
+    /// Push a slice of bytes to the u8 buffer in one copy.
+    ///
+    /// The bytes can be read back with [`DecodedData::take_bytes_ref`].
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        self.u8_buf.extend_from_slice(bytes);
+    }
+
     /// Push a string to the buffer.
     pub(crate) fn push_str(&mut self, value: &str) {
         self.push_u32(value.len() as u32);
         self.str_buf.extend_from_slice(value.as_bytes());
     }
 
-    /// Convert the encoded data to bytes.
-    pub(crate) fn to_bytes(&self) -> Vec<u8> {
-        let u16_offset = 12 + self.u32_buf.len() * 4;
+    /// Convert the encoded data to bytes, including the versioned header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let u16_offset = HEADER_LEN + self.u32_buf.len() * 4;
         let u8_offset = u16_offset + self.u16_buf.len() * 2;
         let str_offset = u8_offset + self.u8_buf.len();
 
         let total_len = str_offset + self.str_buf.len();
         let mut bytes = Vec::with_capacity(total_len);
 
+        // Write magic and protocol version
+        bytes.extend_from_slice(&IPC_MAGIC);
+        bytes.push(IPC_VERSION);
+
         // Write header offsets
         bytes.extend_from_slice(&(u16_offset as u32).to_le_bytes());
         bytes.extend_from_slice(&(u8_offset as u32).to_le_bytes());
@@ -418,9 +521,19 @@ impl EncodedData {
     }
 }
 
-/// Decode base64-encoded IPC data.
-pub(crate) fn decode_data(bytes: &[u8]) -> Option<IPCMessage> {
+/// Decode base64-encoded IPC data, rejecting messages larger than `max_size` bytes.
+pub(crate) fn decode_data(bytes: &[u8], max_size: usize) -> Result<IPCMessage, DecodeError> {
+    // Check the decoded size before allocating so oversized messages are cheap to reject
+    let decoded_len = base64::decoded_len_estimate(bytes.len());
+    if decoded_len > max_size {
+        return Err(DecodeError::MessageTooLarge {
+            limit: max_size,
+            actual: decoded_len,
+        });
+    }
     let engine = base64::engine::general_purpose::STANDARD;
-    let data = engine.decode(bytes).ok()?;
-    Some(IPCMessage { data })
+    let data = engine
+        .decode(bytes)
+        .map_err(|e| DecodeError::Custom(format!("invalid base64 message: {e}")))?;
+    Ok(IPCMessage { data })
 }
@@ -0,0 +1,28 @@
+//! A binding to JavaScript iterators
+
+use crate::js_helpers::{js_is_iterator, js_iterator_next};
+use crate::{JsValue, wasm_bindgen};
+
+#[wasm_bindgen(crate = crate)]
+extern "C" {
+    /// A handle to a JavaScript iterator living in the webview.
+    ///
+    /// Any object with a `next` method is accepted, so iterators returned by
+    /// generators, `Map.prototype.entries` or `[Symbol.iterator]()` all cast to this type.
+    #[wasm_bindgen(js_name = Iterator, is_type_of = js_is_iterator)]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub type JsIterator;
+}
+
+impl Iterator for JsIterator {
+    type Item = JsValue;
+
+    /// Advance the JS iterator, returning `None` once it reports `done`.
+    ///
+    /// Each step is a single IPC round-trip. Yielded `undefined` values are returned
+    /// as `Some(JsValue::UNDEFINED)` and do not end iteration.
+    fn next(&mut self) -> Option<JsValue> {
+        let (done, value) = js_iterator_next(self);
+        if done { None } else { Some(value) }
+    }
+}
@@ -1 +1 @@
-function is_undefined(x){return x===void 0}function is_null(x){return x===null}function is_true(x){return x===!0}function is_false(x){return x===!1}function get_typeof(x){return typeof x}function is_falsy(x){return!x}function is_truthy(x){return!!x}function is_object(x){return typeof x==="object"&&x!==null}function is_function(x){return typeof x==="function"}function is_string(x){return typeof x==="string"}function is_symbol(x){return typeof x==="symbol"}function is_bigint(x){return typeof x==="bigint"}function as_string(x){return typeof x==="string"?x:null}function as_f64(x){return typeof x==="number"?x:null}function debug_string(x){try{return x.toString()}catch{return"[unrepresentable]"}}function js_checked_div(a,b){try{return a/b}catch(e){return e}}function js_pow(a,b){return a**b}function js_add(a,b){return a+b}function js_sub(a,b){return a-b}function js_mul(a,b){return a*b}function js_div(a,b){return a/b}function js_rem(a,b){return a%b}function js_neg(a){return-a}function js_bit_and(a,b){return a&b}function js_bit_or(a,b){return a|b}function js_bit_xor(a,b){return a^b}function js_bit_not(a){return~a}function js_shl(a,b){return a<<b}function js_shr(a,b){return a>>b}function js_unsigned_shr(a,b){return a>>>b}function js_lt(a,b){return a<b}function js_le(a,b){return a<=b}function js_gt(a,b){return a>b}function js_ge(a,b){return a>=b}function js_loose_eq(a,b){return a==b}function js_in(prop,obj){return prop in obj}function is_error(x){return x instanceof Error}function clone_heap_ref(heapId){return window.jsHeap.get(heapId)}function drop_heap_ref(heapId){window.jsHeap.remove(heapId)}function create_rust_object_wrapper(handle,className){return window.rustExports.createWrapper(handle,className)}function extract_rust_handle(obj){return obj&&typeof obj.__handle==="number"?obj.__handle:null}export{js_unsigned_shr,js_sub,js_shr,js_shl,js_rem,js_pow,js_neg,js_mul,js_lt,js_loose_eq,js_le,js_in,js_gt,js_ge,js_div,js_checked_div,js_bit_xor,js_bit_or,js_bit_not,js_bit_and,js_add,is_undefined,is_truthy,is_true,is_symbol,is_string,is_object,is_null,is_function,is_falsy,is_false,is_error,is_bigint,get_typeof,extract_rust_handle,drop_heap_ref,debug_string,create_rust_object_wrapper,clone_heap_ref,as_string,as_f64};
+function is_undefined(x){return x===undefined;}function is_null(x){return x===null;}function get_typeof(x){return typeof x;}function is_falsy(x){return!x;}function is_truthy(x){return!!x;}function is_object(x){return typeof x==="object"&&x!==null;}function is_function(x){return typeof x==="function";}function is_string(x){return typeof x==="string";}function is_symbol(x){return typeof x==="symbol";}function is_bigint(x){return typeof x==="bigint";}function is_array_buffer(x){return x instanceof ArrayBuffer;}function is_date(x){return x instanceof Date;}function is_typed_array(x){return ArrayBuffer.isView(x)&&!(x instanceof DataView);}function as_string(x){return typeof x==="string"?x:null;}function as_f64(x){return typeof x==="number"?x:null;}function as_bool(x){return typeof x==="boolean"?x:null;}function debug_string(x){try{return x.toString();}catch{return"[unrepresentable]";}}function js_checked_div(a,b){try{return a/b;}catch(e){return e;}}function js_pow(a,b){return a**b;}function js_add(a,b){return a+b;}function js_sub(a,b){return a-b;}function js_mul(a,b){return a*b;}function js_div(a,b){return a/b;}function js_rem(a,b){return a%b;}function js_neg(a){return-a;}function js_bit_and(a,b){return a&b;}function js_bit_or(a,b){return a|b;}function js_bit_xor(a,b){return a^b;}function js_bit_not(a){return~a;}function js_shl(a,b){return a<<b;}function js_shr(a,b){return a>>b;}function js_unsigned_shr(a,b){return a>>>b;}function js_lt(a,b){return a<b;}function js_le(a,b){return a<=b;}function js_gt(a,b){return a>b;}function js_ge(a,b){return a>=b;}function js_loose_eq(a,b){return a==b;}function js_deep_equal(a,b){return deepEqual(a,b,new Set(),new Map());}function deepEqual(a,b,ancestors,equal){if(a===b||(a!==a&&b!==b)){return true;}if(typeof a!=="object"||typeof b!=="object"||a===null||b===null){return false;}if(equal.get(a)?.has(b)){return true;}if(ancestors.has(a)||ancestors.has(b)){return false;}if(Object.getPrototypeOf(a)!==Object.getPrototypeOf(b)){return false;}if(a instanceof Date){return a.getTime()===b.getTime();}if(a instanceof Set){return a.size===b.size&&[...a].every((item)=>b.has(item));}let result;ancestors.add(a).add(b);if(a instanceof Map){result=a.size===b.size&&[...a].every(([key,value])=>b.has(key)&&deepEqual(value,b.get(key),ancestors,equal));}else{const keysA=Object.keys(a);const keysB=Object.keys(b);result=keysA.length===keysB.length&&keysA.every((key)=>Object.prototype.hasOwnProperty.call(b,key)&&deepEqual(a[key],b[key],ancestors,equal));}ancestors.delete(a);ancestors.delete(b);if(result){let seen=equal.get(a);if(!seen){seen=new Set();equal.set(a,seen);}seen.add(b);}return result;}function js_in(prop,obj){return prop in obj;}function is_error(x){return x instanceof Error;}function js_instanceof(x,ctor){try{return x instanceof ctor;}catch{return false;}}function instanceof_global(x,name){const ctor=(globalThis)[name];if(typeof ctor!=="function"){return false;}try{if(x instanceof ctor){return true;}}catch{return false;}if((typeof x!=="object"&&typeof x!=="function")||x===null){return false;}const chain=[];for(let proto=Object.getPrototypeOf(x);proto!==null;proto=Object.getPrototypeOf(proto)){chain.push(proto);}if(chain.length===0||chain[chain.length-1]===Object.prototype){return false;}return chain.some((proto)=>Object.prototype.hasOwnProperty.call(proto,"constructor")&&proto.constructor.name===name);}function clone_heap_ref(heapId){return window.jsHeap.get(heapId);}function drop_heap_refs(heapIds){for(const heapId of heapIds){window.jsHeap.remove(heapId);}}function create_rust_object_wrapper(handle,className){return window.rustExports.createWrapper(handle,className);}function extract_rust_handle(obj){return(obj&&typeof obj.__handle==='number')?obj.__handle:null;}function json_parse(json){return JSON.parse(json);}function json_stringify(x){return JSON.stringify(x===undefined?null:x);}function snapshot_json(x){const json=JSON.stringify(x);if(json===undefined){throw new TypeError(`${typeof x} values cannot be snapshotted`);}return json;}function structured_clone(x){return structuredClone(x);}function function_apply(f,thisArg,args){return Reflect.apply(f,thisArg,args);}function function_call(f,thisArg,...args){return Reflect.apply(f,thisArg,args);}function construct(ctor,args){return Reflect.construct(ctor,args);}const objectTokens=new WeakMap();let nextObjectToken=2;function hashText(text){let low=0x811c9dc5;let high=0x050c5d1f;for(let i=0;i<text.length;i++){const code=text.charCodeAt(i);low=Math.imul(low^code,0x01000193);high=Math.imul(high^code,0x01000193);}return(high>>>12)*0x100000000+(low>>>0);}function identity_token(x){const isObject=(typeof x==="object"&&x!==null)||typeof x==="function";if(!isObject){return hashText(typeof x+":"+String(x))*2+1;}let token=objectTokens.get(x);if(token===undefined){token=nextObjectToken;nextObjectToken+=2;objectTokens.set(x,token);}return token;}function new_object(){return{};}function new_array(){return[];}function array_of(values){return values;}function array_sort_by(arr,compare){arr.sort((a,b)=>compare(a,b));}function array_to_vec(arr){return arr;}function is_array(x){return Array.isArray(x);}function array_push(arr,value){return arr.push(value);}function array_get(arr,index){return arr[index];}function array_set(arr,index,value){arr[index]=value;}function array_length(arr){return arr.length;}function try_iter(x){if(x===null||x===undefined||typeof x[Symbol.iterator]!=="function"){return null;}return x[Symbol.iterator]();}function is_iterator(x){return typeof x==="object"&&x!==null&&typeof x.next==="function";}function iterator_next(iter){const result=iter.next();return[!!result.done,result.value];}function object_entries(obj){const keys=Object.keys(obj);return[keys,keys.map((key)=>obj[key])];}function is_uint8_array(x){return x instanceof Uint8Array;}function uint8_array_from_bytes(bytes){return bytes;}function uint8_array_to_bytes(array){return array;}function value_kind(x){switch(typeof x){case"undefined":return[0,0];case"boolean":return[2,x?1:0];case"number":return[3,x];case"string":return[4,x];case"function":return[6,0];case"object":if(x===null){return[1,0];}return[Array.isArray(x)?5:7,0];default:return[8,0];}}function new_deferred(){const deferred={};deferred.promise=new Promise((resolve,reject)=>{deferred.resolve=resolve;deferred.reject=reject;});return deferred;}function deferred_promise(deferred){return deferred.promise;}function settle_deferred(deferred,ok,value){if(ok){deferred.resolve(value);}else{deferred.reject(value);}}function reject_deferred_with_error(deferred,message){deferred.reject(new Error(message));}export{is_undefined,is_null,get_typeof,is_falsy,is_truthy,is_object,is_function,is_string,is_symbol,is_bigint,is_array_buffer,is_date,is_typed_array,as_string,as_f64,as_bool,debug_string,js_checked_div,js_pow,js_add,js_sub,js_mul,js_div,js_rem,js_neg,js_bit_and,js_bit_or,js_bit_xor,js_bit_not,js_shl,js_shr,js_unsigned_shr,js_lt,js_le,js_gt,js_ge,js_loose_eq,js_deep_equal,js_in,is_error,js_instanceof,instanceof_global,clone_heap_ref,drop_heap_refs,create_rust_object_wrapper,extract_rust_handle,json_parse,json_stringify,snapshot_json,structured_clone,function_apply,function_call,construct,identity_token,new_object,new_array,array_of,array_sort_by,array_to_vec,is_array,array_push,array_get,array_set,array_length,try_iter,is_iterator,iterator_next,object_entries,is_uint8_array,uint8_array_from_bytes,uint8_array_to_bytes,value_kind,new_deferred,deferred_promise,settle_deferred,reject_deferred_with_error};
@@ -1 +1 @@
-class JSHeap{slots;freeIds;maxId;borrowStackPointer;borrowFrameStack;reservationStack;constructor(){this.slots=[],this.slots[129]=null,this.slots[130]=!0,this.slots[131]=!1,this.slots[128]=void 0,this.freeIds=[],this.maxId=132,this.borrowStackPointer=128,this.borrowFrameStack=[],this.reservationStack=[]}insert(value){let id=this.maxId;return this.maxId++,this.slots[id]=value,id}pushReservationScope(count){let start=this.maxId;this.reservationStack.push({start,count,nextIndex:0}),this.maxId+=count}popReservationScope(){this.reservationStack.pop()}fillNextReserved(value){let scope=this.reservationStack[this.reservationStack.length-1];if(!scope||scope.nextIndex>=scope.count)throw new Error("No reserved slots available");let id=scope.start+scope.nextIndex;scope.nextIndex++,this.slots[id]=value}get(id){return this.slots[id]}remove(id){if(id<132)return this.slots[id];let value=this.slots[id];return delete this.slots[id],this.freeIds.push(id),value}has(id){return this.freeIds.indexOf(id)===-1&&id<this.slots.length}heapObjectsAlive(){return this.slots.length-this.freeIds.length-132}addBorrowedRef(obj){if(this.borrowStackPointer<=1)throw new Error("Borrow stack overflow: too many borrowed references in a single operation");return this.borrowStackPointer--,this.slots[this.borrowStackPointer]=obj,this.borrowStackPointer}pushBorrowFrame(){this.borrowFrameStack.push(this.borrowStackPointer)}popBorrowFrame(){let savedPointer=this.borrowFrameStack.pop();if(savedPointer!==void 0){for(let i=this.borrowStackPointer;i<savedPointer;i++)delete this.slots[i];this.borrowStackPointer=savedPointer}}getBorrowStackPointer(){return this.borrowStackPointer}}class DataEncoder{u8Buf;u16Buf;u32Buf;strBuf;constructor(){this.u8Buf=[],this.u16Buf=[],this.u32Buf=[],this.strBuf=[]}pushU8(value){this.u8Buf.push(value&255)}pushU16(value){this.u16Buf.push(value&65535)}pushU32(value){this.u32Buf.push(value>>>0)}pushU64(value){let low=value>>>0,high=Math.floor(value/4294967296)>>>0;this.pushU32(low),this.pushU32(high)}pushU128(value){let low=value>>>0,high=Math.floor(value/18446744073709552000)>>>0;this.pushU64(low),this.pushU64(high)}pushF32(value){let floatBuf=new Float32Array(1);floatBuf[0]=value;let intBuf=new Uint32Array(floatBuf.buffer);this.pushU32(intBuf[0])}pushF64(value){let floatBuf=new Float64Array(1);floatBuf[0]=value;let intBuf=new Uint32Array(floatBuf.buffer);this.pushU32(intBuf[0]),this.pushU32(intBuf[1])}pushStr(value){let encoded=new TextEncoder().encode(value);this.pushU32(encoded.length);for(let i=0;i<encoded.length;i++)this.strBuf.push(encoded[i])}finalize(){let u16Offset=12+this.u32Buf.length*4,u8Offset=u16Offset+this.u16Buf.length*2,strOffset=u8Offset+this.u8Buf.length,totalSize=strOffset+this.strBuf.length,buffer=new ArrayBuffer(totalSize),dataView=new DataView(buffer);dataView.setUint32(0,u16Offset,!0),dataView.setUint32(4,u8Offset,!0),dataView.setUint32(8,strOffset,!0);let offset=12;for(let val of this.u32Buf)dataView.setUint32(offset,val,!0),offset+=4;for(let val of this.u16Buf)dataView.setUint16(offset,val,!0),offset+=2;return new Uint8Array(buffer,u8Offset,this.u8Buf.length).set(this.u8Buf),new Uint8Array(buffer,strOffset,this.strBuf.length).set(this.strBuf),buffer}}class DataDecoder{u8Buf;u8Offset;u16Buf;u16Offset;u32Buf;u32Offset;strBuf;strOffset;constructor(data){let headerView=new DataView(data,0,12),u16ByteOffset=headerView.getUint32(0,!0),u8ByteOffset=headerView.getUint32(4,!0),strByteOffset=headerView.getUint32(8,!0),u32ByteLength=u16ByteOffset-12;this.u32Buf=new Uint32Array(data,12,u32ByteLength/4),this.u32Offset=0;let u16ByteLength=u8ByteOffset-u16ByteOffset;this.u16Buf=new Uint16Array(data,u16ByteOffset,u16ByteLength/2),this.u16Offset=0;let u8ByteLength=strByteOffset-u8ByteOffset;this.u8Buf=new Uint8Array(data,u8ByteOffset,u8ByteLength),this.u8Offset=0;let strBuf=new Uint8Array(data,strByteOffset);this.strBuf=new TextDecoder("utf-8").decode(strBuf),this.strOffset=0}takeU8(){return this.u8Buf[this.u8Offset++]}takeU16(){return this.u16Buf[this.u16Offset++]}takeU32(){return this.u32Buf[this.u32Offset++]}hasMoreU32(){return this.u32Offset<this.u32Buf.length}takeU64(){let low=this.takeU32(),high=this.takeU32();return low+high*4294967296}takeU128(){let low=this.takeU64(),high=this.takeU64();return low+high*18446744073709552000}takeF32(){let intVal=this.takeU32(),intBuf=new Uint32Array(1);return intBuf[0]=intVal,new Float32Array(intBuf.buffer)[0]}takeF64(){let low=this.takeU32(),high=this.takeU32(),intBuf=new Uint32Array(2);return intBuf[0]=low,intBuf[1]=high,new Float64Array(intBuf.buffer)[0]}takeStr(){let len=this.takeU32(),str=this.strBuf.substring(this.strOffset,this.strOffset+len);return this.strOffset+=len,str}takeI8(){let unsigned=this.takeU8();return unsigned>127?unsigned-256:unsigned}takeI16(){let unsigned=this.takeU16();return unsigned>32767?unsigned-65536:unsigned}takeI32(){return this.takeU32()|0}takeI64(){let low=this.takeU32(),signedHigh=this.takeU32()|0;return low+signedHigh*4294967296}takeI128(){let low=this.takeU64(),signedHigh=this.takeU64()|0;return low+signedHigh*18446744073709552000}getRemainingBytes(){return this.u8Buf.subarray(this.u8Offset)}skipBytes(count){this.u8Offset+=count}isEmpty(){return this.u8Offset>=this.u8Buf.length&&this.u16Offset>=this.u16Buf.length&&this.u32Offset>=this.u32Buf.length&&this.strOffset>=this.strBuf.length}}var functionRegistry=null,typeCache=new Map;function getFunctionRegistry(){return functionRegistry}function setFunctionRegistry(registry){functionRegistry=registry}function getTypeCache(){return typeCache}var nativeRefRegistry=new FinalizationRegistry((fnId)=>{let encoder=new DataEncoder;encoder.pushU8(0),encoder.pushU32(DROP_NATIVE_REF_FN_ID),encoder.pushU32(fnId);let response=sync_request_binary("/__wbg__/handler",encoder.finalize());handleBinaryResponse(response)});class RustFunction{fnId;paramTypes;returnType;constructor(fnId,paramTypes,returnType){this.fnId=fnId,this.paramTypes=paramTypes,this.returnType=returnType,nativeRefRegistry.register(this,fnId)}call(...args){window.jsHeap.pushBorrowFrame();let encoder=new DataEncoder;encoder.pushU8(0),encoder.pushU32(0),encoder.pushU32(this.fnId);for(let i=0;i<this.paramTypes.length;i++)this.paramTypes[i].encode(encoder,args[i]);let response=sync_request_binary("/__wbg__/handler",encoder.finalize()),result=handleBinaryResponse(response);window.jsHeap.popBorrowFrame();let decoded=this.returnType.decode(result);if(result&&!result.isEmpty())throw new Error("Unprocessed data remaining after RustFunction call");return decoded}}class BoolType{encode(encoder,value){encoder.pushU8(value?1:0)}decode(decoder){return decoder.takeU8()!==0}}class HeapRefType{encode(encoder,obj){window.jsHeap.insert(obj)}decode(decoder){let id=decoder.takeU64();return window.jsHeap.get(id)}}class BorrowedRefType{encode(encoder,obj){window.jsHeap.addBorrowedRef(obj)}decode(decoder){let id=decoder.takeU64();return window.jsHeap.get(id)}}class StringType{encode(encoder,value){encoder.pushStr(value)}decode(decoder){return decoder.takeStr()}}class StringEnumType{lookupArray;constructor(lookupArray){this.lookupArray=lookupArray}encode(encoder,value){let index=this.lookupArray.indexOf(value),encoded=index>=0?index:this.lookupArray.length;encoder.pushU32(encoded)}decode(decoder){let index=decoder.takeU32();return this.lookupArray[index]}}class CallbackType{paramTypes;returnType;constructor(paramTypes,returnType){this.paramTypes=paramTypes,this.returnType=returnType}encode(encoder,fnId){encoder.pushU32(fnId)}decode(decoder){let fnId=decoder.takeU32(),f=new RustFunction(fnId,this.paramTypes,this.returnType);return(...args)=>f.call(...args)}}class NullType{encode(encoder,value){}decode(decoder){return null}}class NumericType{size;constructor(size){this.size=size}encode(encoder,value){switch(this.size){case"u8":encoder.pushU8(value);break;case"u16":encoder.pushU16(value);break;case"u32":encoder.pushU32(value);break;case"u64":encoder.pushU64(value);break;case"u128":encoder.pushU128(value);break;case"i8":encoder.pushU8(value&255);break;case"i16":encoder.pushU16(value&65535);break;case"i32":encoder.pushU32(value>>>0);break;case"i64":encoder.pushU64(value);break;case"i128":encoder.pushU128(value);break;case"usize":encoder.pushU64(value);break;case"isize":encoder.pushU64(value);break;case"f32":encoder.pushF32(value);break;case"f64":encoder.pushF64(value);break}}decode(decoder){switch(this.size){case"u8":return decoder.takeU8();case"u16":return decoder.takeU16();case"u32":return decoder.takeU32();case"u64":return decoder.takeU64();case"u128":return decoder.takeU128();case"i8":return decoder.takeI8();case"i16":return decoder.takeI16();case"i32":return decoder.takeI32();case"i64":return decoder.takeI64();case"i128":return decoder.takeI128();case"usize":return decoder.takeU64();case"isize":return decoder.takeI64();case"f32":return decoder.takeF32();case"f64":return decoder.takeF64()}}}class OptionType{wrappedType;constructor(wrappedType){this.wrappedType=wrappedType}encode(encoder,value){if(value===null||value===void 0)encoder.pushU8(0);else encoder.pushU8(1),this.wrappedType.encode(encoder,value)}decode(decoder){if(decoder.takeU8()===0)return null;else return this.wrappedType.decode(decoder)}}class ResultType{okType;errType;constructor(okType,errType){this.okType=okType,this.errType=errType}encode(encoder,value){let result=value;if("ok"in result)encoder.pushU8(1),this.okType.encode(encoder,result.ok);else if("err"in result)encoder.pushU8(0),this.errType.encode(encoder,result.err);else throw new Error("Invalid RustType value: must be Ok or Err")}decode(decoder){if(decoder.takeU8()===1)return{ok:this.okType.decode(decoder)};else return{err:this.errType.decode(decoder)}}}class ArrayType{elementType;constructor(elementType){this.elementType=elementType}encode(encoder,value){encoder.pushU32(value.length);for(let element of value)this.elementType.encode(encoder,element)}decode(decoder){let length=decoder.takeU32(),result=[];for(let i=0;i<length;i++)result.push(this.elementType.decode(decoder));return result}}class U8ClampedType{encode(encoder,value){encoder.pushU32(value.length);for(let i=0;i<value.length;i++)encoder.pushU8(value[i])}decode(decoder){let length=decoder.takeU32(),result=new Uint8ClampedArray(length);for(let i=0;i<length;i++)result[i]=decoder.takeU8();return result}}var u8ClampedTypeInstance=new U8ClampedType,U8Type=new NumericType("u8"),U16Type=new NumericType("u16"),U32Type=new NumericType("u32"),U64Type=new NumericType("u64"),U128Type=new NumericType("u128"),I8Type=new NumericType("i8"),I16Type=new NumericType("i16"),I32Type=new NumericType("i32"),I64Type=new NumericType("i64"),I128Type=new NumericType("i128"),UsizeType=new NumericType("usize"),IsizeType=new NumericType("isize"),F32Type=new NumericType("f32"),F64Type=new NumericType("f64"),strType=new StringType,boolTypeInstance=new BoolType,nullTypeInstance=new NullType,heapRefTypeInstance=new HeapRefType,borrowedRefTypeInstance=new BorrowedRefType,stringTypeInstance=new StringType;function parseTypeDef(bytes,offset){let tag=bytes[offset.value++];switch(tag){case 0:return nullTypeInstance;case 1:return boolTypeInstance;case 2:return U8Type;case 3:return U16Type;case 4:return U32Type;case 5:return U64Type;case 6:return U128Type;case 7:return I8Type;case 8:return I16Type;case 9:return I32Type;case 10:return I64Type;case 11:return I128Type;case 12:return F32Type;case 13:return F64Type;case 14:return UsizeType;case 15:return IsizeType;case 16:return stringTypeInstance;case 17:return heapRefTypeInstance;case 22:return borrowedRefTypeInstance;case 18:{let paramCount=bytes[offset.value++],paramTypes=[];for(let i=0;i<paramCount;i++)paramTypes.push(parseTypeDef(bytes,offset));let returnType=parseTypeDef(bytes,offset);return new CallbackType(paramTypes,returnType)}case 19:{let innerType=parseTypeDef(bytes,offset);return new OptionType(innerType)}case 20:{let okType=parseTypeDef(bytes,offset),errType=parseTypeDef(bytes,offset);return new ResultType(okType,errType)}case 21:{let elementType=parseTypeDef(bytes,offset);return new ArrayType(elementType)}case 23:return u8ClampedTypeInstance;case 24:{let variantCount=bytes[offset.value++],lookupArray=[];for(let i=0;i<variantCount;i++){let len=bytes[offset.value]|bytes[offset.value+1]<<8|bytes[offset.value+2]<<16|bytes[offset.value+3]<<24;offset.value+=4;let strBytes=bytes.subarray(offset.value,offset.value+len);offset.value+=len,lookupArray.push(new TextDecoder().decode(strBytes))}return new StringEnumType(lookupArray)}default:throw new Error(`Unknown TypeTag: ${tag}`)}}var TYPE_CACHED=255,TYPE_FULL=254,DROP_NATIVE_REF_FN_ID=4294967295,CALL_EXPORT_FN_ID=4294967294;function sync_request_binary(endpoint,data){let xhr=new XMLHttpRequest;xhr.open("POST",endpoint,!1);let bytes=new Uint8Array(data),binary="";for(let i=0;i<bytes.length;i++)binary+=String.fromCharCode(bytes[i]);let base64=btoa(binary);if(xhr.setRequestHeader("dioxus-data",base64),xhr.send(),xhr.status===200&&xhr.responseText){let responseBinary=atob(xhr.responseText),responseBytes=new Uint8Array(responseBinary.length);for(let i=0;i<responseBinary.length;i++)responseBytes[i]=responseBinary.charCodeAt(i);return responseBytes.buffer}return null}function evaluate_from_rust_binary(dataBase64){let binary=atob(dataBase64),bytes=new Uint8Array(binary.length);for(let i=0;i<binary.length;i++)bytes[i]=binary.charCodeAt(i);if(handleBinaryResponse(bytes.buffer))throw new Error("Unprocessed data remaining after Evaluate handling")}function parseTypeInfo(decoder){let typeCache2=getTypeCache(),typeMarker=decoder.takeU8();if(typeMarker===TYPE_CACHED){let typeId=decoder.takeU32(),cached=typeCache2.get(typeId);if(!cached)throw new Error(`Unknown cached type ID: ${typeId}`);return cached}else if(typeMarker===TYPE_FULL){let typeId=decoder.takeU32(),paramCount=decoder.takeU8(),typeBytes=decoder.getRemainingBytes(),offset={value:0},paramTypes=[];for(let i=0;i<paramCount;i++)paramTypes.push(parseTypeDef(typeBytes,offset));let returnType=parseTypeDef(typeBytes,offset);decoder.skipBytes(offset.value);let cached={paramTypes,returnType};return typeCache2.set(typeId,cached),cached}else throw new Error(`Unknown type marker: ${typeMarker}`)}function handleBinaryResponse(response){if(!response||response.byteLength===0)return null;let decoder=new DataDecoder(response),msgType=decoder.takeU8();if(msgType===1)return decoder;else if(msgType===0){let reservedCount=decoder.takeU32();window.jsHeap.pushReservationScope(reservedCount);let encoder=new DataEncoder;encoder.pushU8(1),window.jsHeap.pushBorrowFrame();while(decoder.hasMoreU32()){let fnId=decoder.takeU32(),typeInfo=parseTypeInfo(decoder),jsFunction=getFunctionRegistry()[fnId];if(!jsFunction)throw new Error("Unknown function ID in response: "+fnId);let params=typeInfo.paramTypes.map((paramType)=>paramType.decode(decoder)),result=jsFunction(...params);if(typeInfo.returnType instanceof HeapRefType&&reservedCount>0)window.jsHeap.fillNextReserved(result);else typeInfo.returnType.encode(encoder,result)}window.jsHeap.popBorrowFrame(),window.jsHeap.popReservationScope();let nextResponse=sync_request_binary("/__wbg__/handler",encoder.finalize());return handleBinaryResponse(nextResponse)}if(!decoder.isEmpty())throw new Error("Unprocessed data remaining after Evaluate handling");return null}var exportRegistry=new FinalizationRegistry((info)=>{let encoder=new DataEncoder;encoder.pushU8(0),encoder.pushU32(CALL_EXPORT_FN_ID);let dropName=`${info.className}::__drop`;encoder.pushStr(dropName),encoder.pushU32(info.handle);let response=sync_request_binary("/__wbg__/handler",encoder.finalize());handleBinaryResponse(response)});function callExport(exportName,...args){window.jsHeap.pushBorrowFrame();let encoder=new DataEncoder;encoder.pushU8(0),encoder.pushU32(CALL_EXPORT_FN_ID),encoder.pushStr(exportName);for(let arg of args)if(typeof arg==="number")encoder.pushU32(arg);else throw new Error(`Unsupported argument type: ${typeof arg}`);let response=sync_request_binary("/__wbg__/handler",encoder.finalize()),decoder=handleBinaryResponse(response);if(window.jsHeap.popBorrowFrame(),decoder&&decoder.hasMoreU32())return decoder.takeI32();return}function createWrapper(handle,className){let ClassConstructor=window[className];if(ClassConstructor&&typeof ClassConstructor.__wrap==="function")return ClassConstructor.__wrap(handle);let proxy=new Proxy({__handle:handle,__className:className},{get(target,prop){if(prop==="__handle"||prop==="__className")return target[prop];if(typeof prop==="symbol"||prop==="then"||prop==="toJSON")return;return(...args)=>{let exportName=`${className}::${String(prop)}`;return callExport(exportName,handle,...args)}}});return exportRegistry.register(proxy,{handle,className}),proxy}window.__wryCallExport=callExport;window.__wryExportRegistry=exportRegistry;var rustExports={createWrapper,callExport};window.setFunctionRegistry=setFunctionRegistry;window.evaluate_from_rust_binary=evaluate_from_rust_binary;window.jsHeap=new JSHeap;window.rustExports=rustExports;
+const JSIDX_OFFSET=128;const JSIDX_UNDEFINED=JSIDX_OFFSET;const JSIDX_NULL=JSIDX_OFFSET+1;const JSIDX_TRUE=JSIDX_OFFSET+2;const JSIDX_FALSE=JSIDX_OFFSET+3;const JSIDX_RESERVED=JSIDX_OFFSET+4;class JSHeap{slots;freeIds;maxId;borrowStackPointer;borrowFrameStack;reservationStack;constructor(){this.slots=[];this.slots[JSIDX_NULL]=null;this.slots[JSIDX_TRUE]=true;this.slots[JSIDX_FALSE]=false;this.slots[JSIDX_UNDEFINED]=undefined;this.freeIds=[];this.maxId=JSIDX_RESERVED;this.borrowStackPointer=JSIDX_OFFSET;this.borrowFrameStack=[];this.reservationStack=[];}insert(value){const id=this.maxId;this.maxId++;this.slots[id]=value;return id;}pushReservationScope(count){const start=this.maxId;this.reservationStack.push({start,count,nextIndex:0});this.maxId+=count;}popReservationScope(){this.reservationStack.pop();}fillNextReserved(value){const scope=this.reservationStack[this.reservationStack.length-1];if(!scope||scope.nextIndex>=scope.count){throw new Error("No reserved slots available");}const id=scope.start+scope.nextIndex;scope.nextIndex++;this.slots[id]=value;}get(id){return this.slots[id];}remove(id){if(id<JSIDX_RESERVED){return this.slots[id];}const value=this.slots[id];delete this.slots[id];this.freeIds.push(id);return value;}has(id){return this.freeIds.indexOf(id)===-1&&id<this.slots.length;}heapObjectsAlive(){return this.slots.length-this.freeIds.length-JSIDX_RESERVED;}addBorrowedRef(obj){if(this.borrowStackPointer<=1){throw new Error("Borrow stack overflow: too many borrowed references in a single operation");}this.borrowStackPointer--;this.slots[this.borrowStackPointer]=obj;return this.borrowStackPointer;}pushBorrowFrame(){this.borrowFrameStack.push(this.borrowStackPointer);}popBorrowFrame(){const savedPointer=this.borrowFrameStack.pop();if(savedPointer!==undefined){for(let i=this.borrowStackPointer;i<savedPointer;i++){delete this.slots[i];}this.borrowStackPointer=savedPointer;}}getBorrowStackPointer(){return this.borrowStackPointer;}}const TypeTag={Void:0,Bool:1,U8:2,U16:3,U32:4,U64:5,U128:6,I8:7,I16:8,I32:9,I64:10,I128:11,F32:12,F64:13,Usize:14,Isize:15,String:16,HeapRef:17,Callback:18,Option:19,Result:20,Array:21,BorrowedRef:22,U8Clamped:23,StringEnum:24,Tuple:25,Date:26,PackedArray:27,Struct:28,Record:29,Either:30,MutSlice:31,};class BoolType{encode(encoder,value){encoder.pushU8(value?1:0);}decode(decoder){const val=decoder.takeU8();return val!==0;}}class HeapRefType{encode(encoder,obj){window.jsHeap.insert(obj);}decode(decoder){const id=decoder.takeU64();return window.jsHeap.get(id);}}class BorrowedRefType{encode(encoder,obj){window.jsHeap.addBorrowedRef(obj);}decode(decoder){const id=decoder.takeU64();return window.jsHeap.get(id);}}class StringType{encode(encoder,value){encoder.pushStr(value);}decode(decoder){return decoder.takeStr();}}class StringEnumType{lookupArray;constructor(lookupArray){this.lookupArray=lookupArray;}encode(encoder,value){const index=this.lookupArray.indexOf(value);const encoded=index>=0?index:this.lookupArray.length;encoder.pushU32(encoded);}decode(decoder){const index=decoder.takeU32();return this.lookupArray[index];}}class CallbackType{paramTypes;returnType;constructor(paramTypes,returnType){this.paramTypes=paramTypes;this.returnType=returnType;}encode(encoder,fnId){encoder.pushU32(fnId);}decode(decoder){const fnId=decoder.takeU32();const f=new RustFunction(fnId,this.paramTypes,this.returnType);return(...args)=>f.call(...args);}}class VoidType{encode(encoder,value){}decode(decoder){return undefined;}}const NARROW_IN_RANGE=1;const NARROW_OUT_OF_RANGE=0;function pushNarrowRangeFlag(encoder,value,min,max){const inRange=Number.isInteger(value)&&value>=min&&value<=max;encoder.pushU8(inRange?NARROW_IN_RANGE:NARROW_OUT_OF_RANGE);}class NumericType{size;constructor(size){this.size=size;}encode(encoder,value){switch(this.size){case"u8":pushNarrowRangeFlag(encoder,value,0,0xff);encoder.pushU8(value);break;case"u16":pushNarrowRangeFlag(encoder,value,0,0xffff);encoder.pushU16(value);break;case"i8":pushNarrowRangeFlag(encoder,value,-0x80,0x7f);encoder.pushU8(value);break;case"i16":pushNarrowRangeFlag(encoder,value,-0x8000,0x7fff);encoder.pushU16(value);break;case"u32":encoder.pushU32(value);break;case"u64":encoder.pushU64(value);break;case"u128":encoder.pushU128(value);break;case"i32":encoder.pushU32(value>>>0);break;case"i64":encoder.pushU64(value);break;case"i128":encoder.pushU128(value);break;case"usize":encoder.pushU64(value);break;case"isize":encoder.pushU64(value);break;case"f32":encoder.pushF32(value);break;case"f64":encoder.pushF64(value);break;}}decode(decoder){if(this.size==="u8"||this.size==="u16"||this.size==="i8"||this.size==="i16"){decoder.takeU8();}switch(this.size){case"u8":return decoder.takeU8();case"u16":return decoder.takeU16();case"u32":return decoder.takeU32();case"u64":return decoder.takeU64();case"u128":return decoder.takeU128();case"i8":return decoder.takeI8();case"i16":return decoder.takeI16();case"i32":return decoder.takeI32();case"i64":return decoder.takeI64();case"i128":return decoder.takeI128();case"usize":return decoder.takeU64();case"isize":return decoder.takeI64();case"f32":return decoder.takeF32();case"f64":return decoder.takeF64();}}}class OptionType{wrappedType;constructor(wrappedType){this.wrappedType=wrappedType;}encode(encoder,value){if(value===null||value===undefined){encoder.pushU8(0);}else{encoder.pushU8(1);this.wrappedType.encode(encoder,value);}}decode(decoder){const isPresent=decoder.takeU8();if(isPresent===0){return null;}else{return this.wrappedType.decode(decoder);}}}class ResultType{okType;errType;constructor(okType,errType){this.okType=okType;this.errType=errType;}encode(encoder,value){const result=value;if("ok"in result){encoder.pushU8(1);this.okType.encode(encoder,result.ok);}else if("err"in result){encoder.pushU8(0);this.errType.encode(encoder,result.err);}else{throw new Error("Invalid RustType value: must be Ok or Err");}}decode(decoder){const isOk=decoder.takeU8();if(isOk===1){const okValue=this.okType.decode(decoder);return{ok:okValue};}else{const errValue=this.errType.decode(decoder);return{err:errValue};}}}function acceptsValue(type,value){if(type instanceof BoolType){return typeof value==="boolean";}if(type instanceof NumericType){return typeof value==="number"||typeof value==="bigint";}if(type instanceof StringType||type instanceof StringEnumType){return typeof value==="string";}if(type instanceof VoidType){return value===null||value===undefined;}if(type instanceof OptionType){return value===null||value===undefined||acceptsValue(type.wrappedType,value);}if(type instanceof DateType){return value instanceof Date;}if(type instanceof ArrayType||type instanceof TupleType){return Array.isArray(value);}if(type instanceof PackedArrayType||type instanceof U8ClampedType){return ArrayBuffer.isView(value);}if(type instanceof StructType||type instanceof RecordType){return typeof value==="object"&&value!==null&&!Array.isArray(value);}return true;}class EitherType{leftType;rightType;constructor(leftType,rightType){this.leftType=leftType;this.rightType=rightType;}encode(encoder,value){if(acceptsValue(this.leftType,value)){encoder.pushU8(0);this.leftType.encode(encoder,value);}else{encoder.pushU8(1);this.rightType.encode(encoder,value);}}decode(decoder){const isRight=decoder.takeU8();if(isRight===0){return this.leftType.decode(decoder);}else{return this.rightType.decode(decoder);}}}class ArrayType{elementType;constructor(elementType){this.elementType=elementType;}encode(encoder,value){encoder.pushU32(value.length);for(const element of value){this.elementType.encode(encoder,element);}}decode(decoder){const length=decoder.takeU32();const result=[];for(let i=0;i<length;i++){result.push(this.elementType.decode(decoder));}return result;}}const packedArrayConstructors={[TypeTag.I8]:Int8Array,[TypeTag.U16]:Uint16Array,[TypeTag.I16]:Int16Array,[TypeTag.U32]:Uint32Array,[TypeTag.I32]:Int32Array,[TypeTag.F32]:Float32Array,[TypeTag.F64]:Float64Array,};const packedArrayRanges=new Map([[Int8Array,[-0x80,0x7f]],[Uint16Array,[0,0xffff]],[Int16Array,[-0x8000,0x7fff]],]);class PackedArrayType{ctor;range;constructor(ctor){this.ctor=ctor;this.range=packedArrayRanges.get(ctor);}encode(encoder,value){if(this.range){const[min,max]=this.range;let inRange=true;if(!(value instanceof this.ctor)){for(let i=0;i<value.length&&inRange;i++){const element=value[i];inRange=Number.isInteger(element)&&element>=min&&element<=max;}}encoder.pushU8(inRange?NARROW_IN_RANGE:NARROW_OUT_OF_RANGE);}const typed=value instanceof this.ctor?value:this.ctor.from(value);encoder.pushU32(typed.length);encoder.pushBytes(new Uint8Array(typed.buffer,typed.byteOffset,typed.byteLength));}decode(decoder){if(this.range){decoder.takeU8();}const length=decoder.takeU32();const bytes=decoder.takeBytes(length*this.ctor.BYTES_PER_ELEMENT);const copy=new Uint8Array(bytes);return new this.ctor(copy.buffer,0,length);}}let mutableSlices=[];function takeMutableSlices(){const slices=mutableSlices;mutableSlices=[];return slices;}class MutSliceType{sequenceType;constructor(sequenceType){this.sequenceType=sequenceType;}encode(encoder,value){this.sequenceType.encode(encoder,value);}decode(decoder){const value=this.sequenceType.decode(decoder);mutableSlices.push({sequenceType:this.sequenceType,value});return value;}}class TupleType{elementTypes;constructor(elementTypes){this.elementTypes=elementTypes;}encode(encoder,value){for(let i=0;i<this.elementTypes.length;i++){this.elementTypes[i].encode(encoder,value[i]);}}decode(decoder){const result=[];for(const elementType of this.elementTypes){result.push(elementType.decode(decoder));}return result;}}class StructType{fieldNames;fieldTypes;constructor(fieldNames,fieldTypes){this.fieldNames=fieldNames;this.fieldTypes=fieldTypes;}encode(encoder,value){for(let i=0;i<this.fieldTypes.length;i++){this.fieldTypes[i].encode(encoder,value[this.fieldNames[i]]);}}decode(decoder){const result={};for(let i=0;i<this.fieldTypes.length;i++){result[this.fieldNames[i]]=this.fieldTypes[i].decode(decoder);}return result;}}class RecordType{valueType;constructor(valueType){this.valueType=valueType;}encode(encoder,value){const keys=Object.keys(value);encoder.pushU32(keys.length);for(const key of keys){encoder.pushStr(key);this.valueType.encode(encoder,value[key]);}}decode(decoder){const length=decoder.takeU32();const result={};for(let i=0;i<length;i++){const key=decoder.takeStr();Object.defineProperty(result,key,{value:this.valueType.decode(decoder),enumerable:true,writable:true,configurable:true,});}return result;}}class DateType{encode(encoder,value){encoder.pushF64(value.getTime());}decode(decoder){return new Date(decoder.takeF64());}}class U8ClampedType{encode(encoder,value){encoder.pushU32(value.length);for(let i=0;i<value.length;i++){encoder.pushU8(value[i]);}}decode(decoder){const length=decoder.takeU32();const result=new Uint8ClampedArray(length);for(let i=0;i<length;i++){result[i]=decoder.takeU8();}return result;}}const u8ClampedTypeInstance=new U8ClampedType();const U8Type=new NumericType("u8");const U16Type=new NumericType("u16");const U32Type=new NumericType("u32");const U64Type=new NumericType("u64");const U128Type=new NumericType("u128");const I8Type=new NumericType("i8");const I16Type=new NumericType("i16");const I32Type=new NumericType("i32");const I64Type=new NumericType("i64");const I128Type=new NumericType("i128");const UsizeType=new NumericType("usize");const IsizeType=new NumericType("isize");const F32Type=new NumericType("f32");const F64Type=new NumericType("f64");const strType=new StringType();const boolTypeInstance=new BoolType();const voidTypeInstance=new VoidType();const heapRefTypeInstance=new HeapRefType();const borrowedRefTypeInstance=new BorrowedRefType();const stringTypeInstance=new StringType();const dateTypeInstance=new DateType();function parseTypeDef(bytes,offset){const tag=bytes[offset.value++];switch(tag){case TypeTag.Void:return voidTypeInstance;case TypeTag.Bool:return boolTypeInstance;case TypeTag.U8:return U8Type;case TypeTag.U16:return U16Type;case TypeTag.U32:return U32Type;case TypeTag.U64:return U64Type;case TypeTag.U128:return U128Type;case TypeTag.I8:return I8Type;case TypeTag.I16:return I16Type;case TypeTag.I32:return I32Type;case TypeTag.I64:return I64Type;case TypeTag.I128:return I128Type;case TypeTag.F32:return F32Type;case TypeTag.F64:return F64Type;case TypeTag.Usize:return UsizeType;case TypeTag.Isize:return IsizeType;case TypeTag.String:return stringTypeInstance;case TypeTag.HeapRef:return heapRefTypeInstance;case TypeTag.BorrowedRef:return borrowedRefTypeInstance;case TypeTag.Callback:{const paramCount=bytes[offset.value++];const paramTypes=[];for(let i=0;i<paramCount;i++){paramTypes.push(parseTypeDef(bytes,offset));}const returnType=parseTypeDef(bytes,offset);return new CallbackType(paramTypes,returnType);}case TypeTag.Option:{const innerType=parseTypeDef(bytes,offset);return new OptionType(innerType);}case TypeTag.Result:{const okType=parseTypeDef(bytes,offset);const errType=parseTypeDef(bytes,offset);return new ResultType(okType,errType);}case TypeTag.Array:{const elementType=parseTypeDef(bytes,offset);return new ArrayType(elementType);}case TypeTag.U8Clamped:return u8ClampedTypeInstance;case TypeTag.StringEnum:{const variantCount=bytes[offset.value++];const lookupArray=[];for(let i=0;i<variantCount;i++){const len=bytes[offset.value]|(bytes[offset.value+1]<<8)|(bytes[offset.value+2]<<16)|(bytes[offset.value+3]<<24);offset.value+=4;const strBytes=bytes.subarray(offset.value,offset.value+len);offset.value+=len;lookupArray.push(new TextDecoder().decode(strBytes));}return new StringEnumType(lookupArray);}case TypeTag.Tuple:{const elementCount=bytes[offset.value++];const elementTypes=[];for(let i=0;i<elementCount;i++){elementTypes.push(parseTypeDef(bytes,offset));}return new TupleType(elementTypes);}case TypeTag.Date:return dateTypeInstance;case TypeTag.PackedArray:{const elementTag=bytes[offset.value++];const ctor=packedArrayConstructors[elementTag];if(!ctor){throw new Error(`Unsupported packed array element: ${elementTag}`);}return new PackedArrayType(ctor);}case TypeTag.Struct:{const fieldCount=bytes[offset.value++];const fieldNames=[];const fieldTypes=[];for(let i=0;i<fieldCount;i++){const len=bytes[offset.value]|(bytes[offset.value+1]<<8)|(bytes[offset.value+2]<<16)|(bytes[offset.value+3]<<24);offset.value+=4;fieldNames.push(new TextDecoder().decode(bytes.subarray(offset.value,offset.value+len)));offset.value+=len;fieldTypes.push(parseTypeDef(bytes,offset));}return new StructType(fieldNames,fieldTypes);}case TypeTag.Record:return new RecordType(parseTypeDef(bytes,offset));case TypeTag.Either:{const leftType=parseTypeDef(bytes,offset);const rightType=parseTypeDef(bytes,offset);return new EitherType(leftType,rightType);}case TypeTag.MutSlice:return new MutSliceType(parseTypeDef(bytes,offset));default:throw new Error(`Unknown TypeTag: ${tag}`);}}const nativeRefRegistry=new FinalizationRegistry((fnId)=>{const encoder=new DataEncoder();encoder.pushU8(MessageType.Evaluate);encoder.pushU32(DROP_NATIVE_REF_FN_ID);encoder.pushU32(fnId);const response=sync_request_binary(`/__wbg__/handler`,encoder.finalize());handleBinaryResponse(response);});class RustFunction{fnId;paramTypes;returnType;constructor(fnId,paramTypes,returnType){this.fnId=fnId;this.paramTypes=paramTypes;this.returnType=returnType;nativeRefRegistry.register(this,fnId);}call(...args){window.jsHeap.pushBorrowFrame();const encoder=new DataEncoder();encoder.pushU8(MessageType.Evaluate);encoder.pushU32(0);encoder.pushU32(this.fnId);for(let i=0;i<this.paramTypes.length;i++){this.paramTypes[i].encode(encoder,args[i]);}const response=sync_request_binary(`/__wbg__/handler`,encoder.finalize());const result=handleBinaryResponse(response);window.jsHeap.popBorrowFrame();const decoded=this.returnType.decode(result);if(result&&!result.isEmpty()){throw new Error("Unprocessed data remaining after RustFunction call");}return decoded;}}const IPC_MAGIC=[0x57,0x52,0x59];const IPC_VERSION=9;const HEADER_LEN=16;class DataEncoder{u8Buf;u16Buf;u32Buf;strBuf;constructor(){this.u8Buf=[];this.u16Buf=[];this.u32Buf=[];this.strBuf=[];}pushU8(value){this.u8Buf.push(value&0xff);}pushBytes(bytes){for(let i=0;i<bytes.length;i++){this.u8Buf.push(bytes[i]);}}pushU16(value){this.u16Buf.push(value&0xffff);}pushU32(value){this.u32Buf.push(value>>>0);}pushU64(value){const low=value>>>0;const high=Math.floor(value/0x100000000)>>>0;this.pushU32(low);this.pushU32(high);}pushU128(value){const low=value>>>0;const high=Math.floor(value/0x10000000000000000)>>>0;this.pushU64(low);this.pushU64(high);}pushF32(value){const floatBuf=new Float32Array(1);floatBuf[0]=value;const intBuf=new Uint32Array(floatBuf.buffer);this.pushU32(intBuf[0]);}pushF64(value){const floatBuf=new Float64Array(1);floatBuf[0]=value;const intBuf=new Uint32Array(floatBuf.buffer);this.pushU32(intBuf[0]);this.pushU32(intBuf[1]);}pushStr(value){const encoded=new TextEncoder().encode(value);this.pushU32(encoded.length);for(let i=0;i<encoded.length;i++){this.strBuf.push(encoded[i]);}}finalize(){const u16Offset=HEADER_LEN+this.u32Buf.length*4;const u8Offset=u16Offset+this.u16Buf.length*2;const strOffset=u8Offset+this.u8Buf.length;const totalSize=strOffset+this.strBuf.length;const buffer=new ArrayBuffer(totalSize);const dataView=new DataView(buffer);for(let i=0;i<IPC_MAGIC.length;i++){dataView.setUint8(i,IPC_MAGIC[i]);}dataView.setUint8(3,IPC_VERSION);dataView.setUint32(4,u16Offset,true);dataView.setUint32(8,u8Offset,true);dataView.setUint32(12,strOffset,true);let offset=HEADER_LEN;for(const val of this.u32Buf){dataView.setUint32(offset,val,true);offset+=4;}for(const val of this.u16Buf){dataView.setUint16(offset,val,true);offset+=2;}const u8View=new Uint8Array(buffer,u8Offset,this.u8Buf.length);u8View.set(this.u8Buf);const strView=new Uint8Array(buffer,strOffset,this.strBuf.length);strView.set(this.strBuf);return buffer;}}class DataDecoder{u8Buf;u8Offset;u16Buf;u16Offset;u32Buf;u32Offset;strBuf;strOffset;constructor(data){const headerView=new DataView(data,0,HEADER_LEN);for(let i=0;i<IPC_MAGIC.length;i++){if(headerView.getUint8(i)!==IPC_MAGIC[i]){throw new Error("Invalid IPC message: missing magic bytes");}}const version=headerView.getUint8(3);if(version!==IPC_VERSION){throw new Error(`IPC protocol version mismatch: expected ${IPC_VERSION}, got ${version}`);}const u16ByteOffset=headerView.getUint32(4,true);const u8ByteOffset=headerView.getUint32(8,true);const strByteOffset=headerView.getUint32(12,true);const u32ByteLength=u16ByteOffset-HEADER_LEN;this.u32Buf=new Uint32Array(data,HEADER_LEN,u32ByteLength/4);this.u32Offset=0;const u16ByteLength=u8ByteOffset-u16ByteOffset;this.u16Buf=new Uint16Array(data,u16ByteOffset,u16ByteLength/2);this.u16Offset=0;const u8ByteLength=strByteOffset-u8ByteOffset;this.u8Buf=new Uint8Array(data,u8ByteOffset,u8ByteLength);this.u8Offset=0;const strBuf=new Uint8Array(data,strByteOffset);this.strBuf=new TextDecoder("utf-8").decode(strBuf);this.strOffset=0;}takeU8(){return this.u8Buf[this.u8Offset++];}takeBytes(count){const bytes=this.u8Buf.subarray(this.u8Offset,this.u8Offset+count);this.u8Offset+=count;return bytes;}takeU16(){return this.u16Buf[this.u16Offset++];}takeU32(){return this.u32Buf[this.u32Offset++];}hasMoreU32(){return this.u32Offset<this.u32Buf.length;}takeU64(){const low=this.takeU32();const high=this.takeU32();return low+high*0x100000000;}takeU128(){const low=this.takeU64();const high=this.takeU64();return low+high*0x10000000000000000;}takeF32(){const intVal=this.takeU32();const intBuf=new Uint32Array(1);intBuf[0]=intVal;const floatBuf=new Float32Array(intBuf.buffer);return floatBuf[0];}takeF64(){const low=this.takeU32();const high=this.takeU32();const intBuf=new Uint32Array(2);intBuf[0]=low;intBuf[1]=high;const floatBuf=new Float64Array(intBuf.buffer);return floatBuf[0];}takeStr(){const len=this.takeU32();const str=this.strBuf.substring(this.strOffset,this.strOffset+len);this.strOffset+=len;return str;}takeI8(){const unsigned=this.takeU8();return unsigned>0x7f?unsigned-0x100:unsigned;}takeI16(){const unsigned=this.takeU16();return unsigned>0x7fff?unsigned-0x10000:unsigned;}takeI32(){const unsigned=this.takeU32();return unsigned|0;}takeI64(){const low=this.takeU32();const high=this.takeU32();const signedHigh=high|0;return low+signedHigh*0x100000000;}takeI128(){const low=this.takeU64();const high=this.takeU64();const signedHigh=high|0;return low+signedHigh*0x10000000000000000;}getRemainingBytes(){return this.u8Buf.subarray(this.u8Offset);}skipBytes(count){this.u8Offset+=count;}isEmpty(){return(this.u8Offset>=this.u8Buf.length&&this.u16Offset>=this.u16Buf.length&&this.u32Offset>=this.u32Buf.length&&this.strOffset>=this.strBuf.length);}}let functionRegistry=null;const typeCache=new Map();function getFunctionRegistry(){return functionRegistry;}function setFunctionRegistry(registry){functionRegistry=registry;}function getTypeCache(){return typeCache;}const MessageType={Evaluate:0,Respond:1,};const TYPE_CACHED=0xff;const TYPE_FULL=0xfe;const DROP_NATIVE_REF_FN_ID=0xffffffff;const CALL_EXPORT_FN_ID=0xfffffffe;function sync_request_binary(endpoint,data){const xhr=new XMLHttpRequest();xhr.open("POST",endpoint,false);const bytes=new Uint8Array(data);let binary="";for(let i=0;i<bytes.length;i++){binary+=String.fromCharCode(bytes[i]);}const base64=btoa(binary);xhr.setRequestHeader("dioxus-data",base64);xhr.send();if(xhr.status===503){throw new Error("The Rust application has exited");}if(xhr.status===200&&xhr.responseText){const responseBinary=atob(xhr.responseText);const responseBytes=new Uint8Array(responseBinary.length);for(let i=0;i<responseBinary.length;i++){responseBytes[i]=responseBinary.charCodeAt(i);}return responseBytes.buffer;}return null;}let socket=null;function connectSocket(){if(typeof WebSocket==="undefined"){return;}const xhr=new XMLHttpRequest();xhr.open("GET","/__wbg__/transport",false);xhr.send();if(xhr.status!==200||!xhr.responseText){return;}const connecting=new WebSocket(xhr.responseText);connecting.binaryType="arraybuffer";connecting.onopen=()=>{socket=connecting;};connecting.onclose=()=>{if(socket===connecting){socket=null;}};connecting.onmessage=(event)=>{const remaining=handleBinaryResponse(event.data,replyToRust);if(remaining){throw new Error("Unprocessed data remaining after Evaluate handling");}};}function replyToRust(data){if(socket&&socket.readyState===WebSocket.OPEN){socket.send(data);return null;}return sync_request_binary(`/__wbg__/handler`,data);}function replyOverHttp(data){return sync_request_binary(`/__wbg__/handler`,data);}function evaluate_from_rust_binary(dataBase64){const binary=atob(dataBase64);const bytes=new Uint8Array(binary.length);for(let i=0;i<binary.length;i++){bytes[i]=binary.charCodeAt(i);}const remaining=handleBinaryResponse(bytes.buffer,replyToRust);if(remaining){throw new Error("Unprocessed data remaining after Evaluate handling");}}function parseTypeInfo(decoder){const typeCache=getTypeCache();const typeMarker=decoder.takeU8();if(typeMarker===TYPE_CACHED){const typeId=decoder.takeU32();const cached=typeCache.get(typeId);if(!cached){throw new Error(`Unknown cached type ID: ${typeId}`);}return cached;}else if(typeMarker===TYPE_FULL){const typeId=decoder.takeU32();const paramCount=decoder.takeU8();const typeBytes=decoder.getRemainingBytes();const offset={value:0};const paramTypes=[];for(let i=0;i<paramCount;i++){paramTypes.push(parseTypeDef(typeBytes,offset));}const returnType=parseTypeDef(typeBytes,offset);decoder.skipBytes(offset.value);const cached={paramTypes,returnType};typeCache.set(typeId,cached);return cached;}else{throw new Error(`Unknown type marker: ${typeMarker}`);}}function handleBinaryResponse(response,reply=replyOverHttp){if(!response||response.byteLength===0){return null;}const decoder=new DataDecoder(response);const rawMsgType=decoder.takeU8();const msgType=rawMsgType;if(msgType===MessageType.Respond){return decoder;}else if(msgType===MessageType.Evaluate){const reservedCount=decoder.takeU32();window.jsHeap.pushReservationScope(reservedCount);const encoder=new DataEncoder();encoder.pushU8(MessageType.Respond);window.jsHeap.pushBorrowFrame();while(decoder.hasMoreU32()){const fnId=decoder.takeU32();const typeInfo=parseTypeInfo(decoder);const functionRegistry=getFunctionRegistry();const jsFunction=functionRegistry[fnId];if(!jsFunction){throw new Error("Unknown function ID in response: "+fnId);}const params=typeInfo.paramTypes.map((paramType)=>paramType.decode(decoder));const mutableSlices=takeMutableSlices();const result=jsFunction(...params);if(typeInfo.returnType instanceof HeapRefType&&reservedCount>0){window.jsHeap.fillNextReserved(result);}else{typeInfo.returnType.encode(encoder,result);}for(const{sequenceType,value}of mutableSlices){sequenceType.encode(encoder,value);}}window.jsHeap.popBorrowFrame();window.jsHeap.popReservationScope();const nextResponse=reply(encoder.finalize());return handleBinaryResponse(nextResponse,reply);}if(!decoder.isEmpty()){throw new Error("Unprocessed data remaining after Evaluate handling");}return null;}const exportRegistry=new FinalizationRegistry((info)=>{const encoder=new DataEncoder();encoder.pushU8(MessageType.Evaluate);encoder.pushU32(CALL_EXPORT_FN_ID);const dropName=`${info.className}::__drop`;encoder.pushStr(dropName);encoder.pushU32(info.handle);const response=sync_request_binary(`/__wbg__/handler`,encoder.finalize());handleBinaryResponse(response);});function takeExportStatus(decoder){if(!decoder){return;}const status=decoder.takeU8();if(status===1){const id=decoder.takeU64();const error=window.jsHeap.get(id);window.jsHeap.remove(id);throw error;}else if(status===2){throw new Error(decoder.takeStr());}}function sendExportCall(exportName,args){window.jsHeap.pushBorrowFrame();const encoder=new DataEncoder();encoder.pushU8(MessageType.Evaluate);encoder.pushU32(CALL_EXPORT_FN_ID);encoder.pushStr(exportName);for(const arg of args){if(typeof arg==="number"){encoder.pushU32(arg);}else if(arg&&typeof arg.__handle==="number"){encoder.pushU32(arg.__handle);}else{throw new Error(`Unsupported argument type: ${typeof arg}`);}}const response=sync_request_binary(`/__wbg__/handler`,encoder.finalize());const decoder=handleBinaryResponse(response);window.jsHeap.popBorrowFrame();takeExportStatus(decoder);return decoder;}function callExport(exportName,...args){const decoder=sendExportCall(exportName,args);if(decoder&&decoder.hasMoreU32()){return decoder.takeI32();}return undefined;}function callAsyncExport(exportName,...args){let decoder;try{decoder=sendExportCall(exportName,args);}catch(e){return Promise.reject(e);}const id=decoder.takeU64();const promise=window.jsHeap.get(id);window.jsHeap.remove(id);return promise;}function iterNext(exportName,handle){window.jsHeap.pushBorrowFrame();const encoder=new DataEncoder();encoder.pushU8(MessageType.Evaluate);encoder.pushU32(CALL_EXPORT_FN_ID);encoder.pushStr(exportName);encoder.pushU32(handle);const response=sync_request_binary(`/__wbg__/handler`,encoder.finalize());const decoder=handleBinaryResponse(response);window.jsHeap.popBorrowFrame();takeExportStatus(decoder);if(!decoder||decoder.takeU8()===0){return{done:true,value:undefined};}const id=decoder.takeU64();const value=window.jsHeap.get(id);window.jsHeap.remove(id);return{done:false,value};}function createWrapper(handle,className){const ClassConstructor=(window)[className];if(ClassConstructor&&typeof ClassConstructor.__wrap==='function'){return ClassConstructor.__wrap(handle);}const wrapper={__handle:handle,__className:className,};const proxy=new Proxy(wrapper,{get(target,prop){if(prop==="__handle"||prop==="__className"){return target[prop];}if(typeof prop==="symbol"||prop==="then"||prop==="toJSON"){return undefined;}return(...args)=>{const exportName=`${className}::${String(prop)}`;return callExport(exportName,handle,...args);};},});exportRegistry.register(proxy,{handle,className});return proxy;}(window).__wryCallExport=callExport;(window).__wryCallAsyncExport=callAsyncExport;(window).__wryExportRegistry=exportRegistry;(window).__wryIterNext=iterNext;const rustExports={createWrapper,callExport,callAsyncExport,iterNext,};const LEVELS=["debug","log","info","warn","error"];let forwarding=false;function formatArgs(args){return args.map((arg)=>{try{return typeof arg==="object"?JSON.stringify(arg):String(arg);}catch(e){return String(arg);}}).join(" ");}function forward(level,args){const bytes=new TextEncoder().encode(formatArgs(args));let binary="";for(let i=0;i<bytes.length;i++){binary+=String.fromCharCode(bytes[i]);}const xhr=new XMLHttpRequest();xhr.open("POST",`/__wbg__/console/${level}`,false);xhr.setRequestHeader("dioxus-data",btoa(binary));xhr.send();}function enableConsoleSink(){if(forwarding){return;}forwarding=true;for(const level of LEVELS){const original=console[level];console[level]=function(...args){original.apply(console,args);try{forward(level,args);}catch(e){}};}}const openDialogs=[];function showDialog(kind,message,defaultValue){return new Promise((resolve)=>{const overlay=document.createElement("div");overlay.className="wry-dialog-overlay";overlay.tabIndex=-1;overlay.style.cssText="position:fixed;inset:0;z-index:2147483647;display:flex;"+"align-items:center;justify-content:center;background:rgba(0,0,0,0.4);";const dialog=document.createElement("div");dialog.className="wry-dialog";dialog.setAttribute("role",kind==="alert"?"alertdialog":"dialog");dialog.setAttribute("aria-modal","true");dialog.style.cssText="min-width:280px;max-width:80vw;padding:16px;border-radius:8px;"+"background:#fff;color:#000;font:14px system-ui,sans-serif;"+"box-shadow:0 8px 32px rgba(0,0,0,0.3);";const text=document.createElement("p");text.style.cssText="margin:0 0 12px;white-space:pre-wrap;";text.textContent=message;dialog.appendChild(text);let input=null;if(kind==="prompt"){input=document.createElement("input");input.className="wry-dialog-input";input.style.cssText="box-sizing:border-box;width:100%;margin-bottom:12px;";input.value=defaultValue;dialog.appendChild(input);}const buttons=document.createElement("div");buttons.style.cssText="display:flex;justify-content:flex-end;gap:8px;";dialog.appendChild(buttons);const finish=(accepted)=>{const index=openDialogs.indexOf(overlay);if(index===-1){return;}openDialogs.splice(index,1);overlay.remove();openDialogs[openDialogs.length-1]?.focus();if(kind==="confirm"){resolve(accepted);}else if(kind==="prompt"){resolve(accepted?input.value:null);}else{resolve(undefined);}};overlay.addEventListener("keydown",(event)=>{if(openDialogs[openDialogs.length-1]!==overlay){return;}if(event.key==="Escape"){event.preventDefault();event.stopPropagation();finish(kind==="alert");}else if(event.key==="Enter"&&event.target===input){event.preventDefault();event.stopPropagation();finish(true);}});const addButton=(label,action,accepted)=>{const button=document.createElement("button");button.type="button";button.textContent=label;button.dataset.action=action;button.addEventListener("click",()=>finish(accepted));buttons.appendChild(button);return button;};if(kind!=="alert"){addButton("Cancel","cancel",false);}const ok=addButton("OK","ok",true);openDialogs.push(overlay);overlay.appendChild(dialog);(document.body||document.documentElement).appendChild(overlay);(input||ok).focus();});}window.setFunctionRegistry=setFunctionRegistry;window.evaluate_from_rust_binary=evaluate_from_rust_binary;window.jsHeap=new JSHeap();window.rustExports=rustExports;window.enableConsoleSink=enableConsoleSink;window.showWryDialog=showDialog;connectSocket();
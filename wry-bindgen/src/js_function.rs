@@ -0,0 +1,49 @@
+//! A binding to JavaScript functions received at runtime
+
+use crate::js_helpers::{
+    js_function_apply, js_function_call0, js_function_call1, js_function_call2, js_is_function,
+};
+use crate::{JsArray, JsValue, wasm_bindgen};
+
+#[wasm_bindgen(crate = crate)]
+extern "C" {
+    /// A handle to a JavaScript function living in the webview.
+    ///
+    /// Unlike functions imported with `#[wasm_bindgen]`, this can wrap any function value,
+    /// such as a callback handed to Rust by a JS library. Casting checks
+    /// `typeof x === "function"`.
+    #[wasm_bindgen(js_name = Function, is_type_of = js_is_function)]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub type JsFunction;
+}
+
+impl JsFunction {
+    /// Call the function with no arguments and `this` set to `context`.
+    ///
+    /// Returns the exception as `Err` if the function throws, or if the handle does not
+    /// refer to a function, for example after an unchecked cast.
+    pub fn call0(&self, context: &JsValue) -> Result<JsValue, JsValue> {
+        js_function_call0(self, context)
+    }
+
+    /// Call the function with one argument and `this` set to `context`.
+    pub fn call1(&self, context: &JsValue, arg1: &JsValue) -> Result<JsValue, JsValue> {
+        js_function_call1(self, context, arg1)
+    }
+
+    /// Call the function with two arguments and `this` set to `context`.
+    pub fn call2(
+        &self,
+        context: &JsValue,
+        arg1: &JsValue,
+        arg2: &JsValue,
+    ) -> Result<JsValue, JsValue> {
+        js_function_call2(self, context, arg1, arg2)
+    }
+
+    /// Call the function with the elements of `args` as arguments and `this` set to
+    /// `context`, like `Function.prototype.apply`.
+    pub fn apply(&self, context: &JsValue, args: &JsArray) -> Result<JsValue, JsValue> {
+        js_function_apply(self, context, args)
+    }
+}
@@ -1,9 +1,17 @@
 //! Javascript methods defined for use in JsValue methods
 
 use alloc::string::String;
-
+use alloc::vec::Vec;
+
+use crate::ArrayLength;
+use crate::Either;
+use crate::JsArray;
+use crate::JsFunction;
+use crate::JsIterator;
+use crate::JsObject;
 use crate::JsValue;
 use crate::object_store::ObjectHandle;
+use crate::typed_array::{PackedByteBuf, PackedBytes, Uint8Array};
 use crate::wasm_bindgen;
 
 #[wasm_bindgen(crate = crate, inline_js = include_str!("./js/convert.js"))]
@@ -14,12 +22,6 @@ extern "C" {
     #[wasm_bindgen(js_name = "is_null")]
     pub(crate) fn js_is_null(x: &JsValue) -> bool;
 
-    #[wasm_bindgen(js_name = "is_true")]
-    pub(crate) fn js_is_true(x: &JsValue) -> bool;
-
-    #[wasm_bindgen(js_name = "is_false")]
-    pub(crate) fn js_is_false(x: &JsValue) -> bool;
-
     #[wasm_bindgen(js_name = "get_typeof")]
     pub(crate) fn js_typeof(x: &JsValue) -> JsValue;
 
@@ -44,6 +46,15 @@ extern "C" {
     #[wasm_bindgen(js_name = "is_bigint")]
     pub(crate) fn js_is_bigint(x: &JsValue) -> bool;
 
+    #[wasm_bindgen(js_name = "is_array_buffer")]
+    pub(crate) fn js_is_array_buffer(x: &JsValue) -> bool;
+
+    #[wasm_bindgen(js_name = "is_date")]
+    pub(crate) fn js_is_date(x: &JsValue) -> bool;
+
+    #[wasm_bindgen(js_name = "is_typed_array")]
+    pub(crate) fn js_is_typed_array(x: &JsValue) -> bool;
+
     /// Get the string value of a JsValue if it is a string, otherwise None.
     #[wasm_bindgen(js_name = "as_string")]
     pub(crate) fn js_as_string(x: &JsValue) -> Option<String>;
@@ -52,6 +63,14 @@ extern "C" {
     #[wasm_bindgen(js_name = "as_f64")]
     pub(crate) fn js_as_f64(x: &JsValue) -> Option<f64>;
 
+    /// Get the bool value of a JsValue if it is a boolean, otherwise None.
+    #[wasm_bindgen(js_name = "as_bool")]
+    pub(crate) fn js_as_bool(x: &JsValue) -> Option<bool>;
+
+    /// Get the kind of a JsValue along with its number or string payload, if any.
+    #[wasm_bindgen(js_name = "value_kind")]
+    pub(crate) fn js_value_kind(x: &JsValue) -> (u8, Either<f64, String>);
+
     /// Get a debug string representation of the JsValue.
     #[wasm_bindgen(js_name = "debug_string")]
     pub(crate) fn js_debug_string(x: &JsValue) -> String;
@@ -119,6 +138,9 @@ extern "C" {
     #[wasm_bindgen(js_name = "js_loose_eq")]
     pub(crate) fn js_loose_eq(a: &JsValue, b: &JsValue) -> bool;
 
+    #[wasm_bindgen(js_name = "js_deep_equal")]
+    pub(crate) fn js_deep_equal(a: &JsValue, b: &JsValue) -> bool;
+
     // Other operators
     #[wasm_bindgen(js_name = "js_in")]
     pub(crate) fn js_in(prop: &JsValue, obj: &JsValue) -> bool;
@@ -127,13 +149,25 @@ extern "C" {
     #[wasm_bindgen(js_name = "is_error")]
     pub(crate) fn js_is_error(x: &JsValue) -> bool;
 
+    // instanceof check against a constructor value
+    #[wasm_bindgen(js_name = "js_instanceof")]
+    pub(crate) fn js_instanceof(x: &JsValue, ctor: &JsValue) -> bool;
+
+    // instanceof check against a global constructor looked up by name
+    #[wasm_bindgen(js_name = "instanceof_global")]
+    pub(crate) fn js_instanceof_global(x: &JsValue, name: &str) -> bool;
+
+    // Reflect.construct, which throws if the value is not a constructor
+    #[wasm_bindgen(js_name = "construct", catch)]
+    pub(crate) fn js_construct(ctor: &JsValue, args: &[JsValue]) -> Result<JsValue, JsValue>;
+
     // Heap management - clone a value in the JS heap
     #[wasm_bindgen(js_name = "clone_heap_ref")]
     pub(crate) fn js_clone_heap_ref(heap_id: u64) -> JsValue;
 
     // Heap management - drop a value from the JS heap
-    #[wasm_bindgen(js_name = "drop_heap_ref")]
-    pub(crate) fn js_drop_heap_ref(heap_id: u64);
+    #[wasm_bindgen(js_name = "drop_heap_refs")]
+    pub(crate) fn js_drop_heap_refs(heap_ids: &[u64]);
 
     // Create a wrapper object for an exported Rust struct
     #[wasm_bindgen(js_name = "create_rust_object_wrapper")]
@@ -143,4 +177,114 @@ extern "C" {
     // Returns -1 if the object doesn't have a __handle property
     #[wasm_bindgen(js_name = "extract_rust_handle")]
     pub fn js_extract_rust_handle(obj: &JsValue) -> Option<ObjectHandle>;
+
+    // JSON helpers for the serde bridge
+    #[wasm_bindgen(js_name = "json_parse")]
+    pub(crate) fn js_json_parse(json: &str) -> JsValue;
+
+    #[wasm_bindgen(js_name = "json_stringify", catch)]
+    pub(crate) fn js_json_stringify(x: &JsValue) -> Result<String, JsValue>;
+
+    #[wasm_bindgen(js_name = "snapshot_json", catch)]
+    pub(crate) fn js_snapshot_json(x: &JsValue) -> Result<String, JsValue>;
+
+    #[wasm_bindgen(js_name = "structured_clone", catch)]
+    pub(crate) fn js_structured_clone(x: &JsValue) -> Result<JsValue, JsValue>;
+
+    /// Create a promise along with the functions that settle it.
+    #[wasm_bindgen(js_name = "new_deferred")]
+    pub(crate) fn js_new_deferred() -> JsValue;
+
+    #[wasm_bindgen(js_name = "deferred_promise")]
+    pub(crate) fn js_deferred_promise(deferred: &JsValue) -> JsValue;
+
+    #[wasm_bindgen(js_name = "settle_deferred")]
+    pub(crate) fn js_settle_deferred(deferred: &JsValue, ok: bool, value: &JsValue);
+
+    #[wasm_bindgen(js_name = "reject_deferred_with_error")]
+    pub(crate) fn js_reject_deferred_with_error(deferred: &JsValue, message: &str);
+
+    #[wasm_bindgen(js_name = "identity_token")]
+    pub(crate) fn js_identity_token(x: &JsValue) -> u64;
+
+    // Object helpers
+    #[wasm_bindgen(js_name = "new_object")]
+    pub(crate) fn js_new_object() -> JsObject;
+
+    // Array helpers
+    #[wasm_bindgen(js_name = "new_array")]
+    pub(crate) fn js_new_array() -> JsArray;
+
+    #[wasm_bindgen(js_name = "array_of")]
+    pub(crate) fn js_array_of(values: &[JsValue]) -> JsArray;
+
+    #[wasm_bindgen(js_name = "array_sort_by")]
+    pub(crate) fn js_array_sort_by(arr: &JsArray, compare: &mut dyn FnMut(JsValue, JsValue) -> i32);
+
+    #[wasm_bindgen(js_name = "array_to_vec")]
+    pub(crate) fn js_array_to_vec(arr: &JsArray) -> Vec<JsValue>;
+
+    #[wasm_bindgen(js_name = "is_array")]
+    pub(crate) fn js_is_array(x: &JsValue) -> bool;
+
+    #[wasm_bindgen(js_name = "array_push")]
+    pub(crate) fn js_array_push(arr: &JsArray, value: &JsValue) -> ArrayLength;
+
+    #[wasm_bindgen(js_name = "array_get")]
+    pub(crate) fn js_array_get(arr: &JsArray, index: u32) -> JsValue;
+
+    #[wasm_bindgen(js_name = "array_set")]
+    pub(crate) fn js_array_set(arr: &JsArray, index: u32, value: &JsValue);
+
+    #[wasm_bindgen(js_name = "array_length")]
+    pub(crate) fn js_array_length(arr: &JsArray) -> u32;
+
+    // Function helpers
+    #[wasm_bindgen(js_name = "function_call", catch)]
+    pub(crate) fn js_function_call0(f: &JsFunction, this: &JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = "function_call", catch)]
+    pub(crate) fn js_function_call1(
+        f: &JsFunction,
+        this: &JsValue,
+        arg1: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = "function_call", catch)]
+    pub(crate) fn js_function_call2(
+        f: &JsFunction,
+        this: &JsValue,
+        arg1: &JsValue,
+        arg2: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_name = "function_apply", catch)]
+    pub(crate) fn js_function_apply(
+        f: &JsFunction,
+        this: &JsValue,
+        args: &JsArray,
+    ) -> Result<JsValue, JsValue>;
+
+    // Iterator helpers
+    #[wasm_bindgen(js_name = "try_iter")]
+    pub(crate) fn js_try_iter(x: &JsValue) -> Option<JsIterator>;
+
+    #[wasm_bindgen(js_name = "is_iterator")]
+    pub(crate) fn js_is_iterator(x: &JsValue) -> bool;
+
+    #[wasm_bindgen(js_name = "iterator_next")]
+    pub(crate) fn js_iterator_next(iter: &JsIterator) -> (bool, JsValue);
+
+    #[wasm_bindgen(js_name = "object_entries")]
+    pub(crate) fn js_object_entries(obj: &JsObject) -> (Vec<String>, Vec<JsValue>);
+
+    // Uint8Array helpers
+    #[wasm_bindgen(js_name = "is_uint8_array")]
+    pub(crate) fn js_is_uint8_array(x: &JsValue) -> bool;
+
+    #[wasm_bindgen(js_name = "uint8_array_from_bytes")]
+    pub(crate) fn js_uint8_array_from_bytes(bytes: PackedBytes) -> Uint8Array;
+
+    #[wasm_bindgen(js_name = "uint8_array_to_bytes")]
+    pub(crate) fn js_uint8_array_to_bytes(array: &Uint8Array) -> PackedByteBuf;
 }
@@ -11,6 +11,16 @@
 //! - [`function`] - JSFunction type for calling JavaScript functions
 //! - [`mod@batch`] - Batching system for grouping multiple JS operations
 //! - [`runtime`] - Event loop and runtime management
+//! - [`console_capture`] - Forwarding the webview's console output to Rust in tests
+//! - `test_harness` - Running bindings in-process without a webview, behind the
+//!   `test-harness` feature
+//!
+//! # Logging
+//!
+//! Diagnostics go through the [`log`](https://docs.rs/log) facade, so they are only
+//! formatted when the application installs a logger that enables them. The IPC path
+//! logs every batch and every call from JS at trace level, and problems such as
+//! malformed messages at warn level.
 
 #![no_std]
 
@@ -18,20 +28,36 @@ pub extern crate alloc;
 #[macro_use]
 extern crate std;
 
+mod array;
 pub mod batch;
 mod cast;
+#[cfg(feature = "chrono")]
+mod chrono_support;
+pub mod console_capture;
 pub mod convert;
+mod either;
 pub mod encode;
 pub mod function;
 mod function_registry;
 mod intern;
 pub(crate) mod ipc;
+mod iterator;
+mod js_function;
 mod js_helpers;
 mod lazy;
+mod object;
 #[doc(hidden)]
 pub mod object_store;
 pub mod runtime;
+#[cfg(feature = "serde-serialize")]
+mod serde_support;
+mod snapshot;
+#[cfg(feature = "test-harness")]
+pub mod test_harness;
+pub mod typed_array;
 mod value;
+mod visit;
+mod websocket;
 pub mod wry;
 
 pub use intern::*;
@@ -66,6 +92,16 @@ pub mod __rt {
         func.call(value)
     }
 
+    /// Check whether `value` is an instance of the global JS class named `name`.
+    ///
+    /// This backs the `JsCast::instanceof` impls generated for imported types. It returns
+    /// false when no global class with that name exists, and matches values from other
+    /// realms, such as iframes, by constructor name.
+    #[inline]
+    pub fn instanceof_global(value: &JsValue, name: &str) -> bool {
+        crate::js_helpers::js_instanceof_global(value, name)
+    }
+
     /// Convert a panic value into a JsValue error.
     ///
     /// This is used by wasm-bindgen-futures to convert Rust panics into JS errors.
@@ -111,7 +147,7 @@ impl TryFrom<JsValue> for u64 {
     type Error = JsValue;
 
     fn try_from(value: JsValue) -> Result<Self, Self::Error> {
-        eprintln!("TryFrom<JsValue> for u64 is likely wrong");
+        log::warn!("TryFrom<JsValue> for u64 is likely wrong");
         #[wasm_bindgen(crate = crate, inline_js = "export function BigIntAsU64(val) {
             if (typeof val !== 'bigint') {
                 throw new Error('Value is not a BigInt');
@@ -131,7 +167,7 @@ impl TryFrom<JsValue> for i64 {
     type Error = JsValue;
 
     fn try_from(value: JsValue) -> Result<Self, Self::Error> {
-        eprintln!("TryFrom<JsValue> for u64 is likely wrong");
+        log::warn!("TryFrom<JsValue> for i64 is likely wrong");
         #[wasm_bindgen(crate = crate, inline_js = "export function BigIntAsU64(val) {
             if (typeof val !== 'bigint') {
                 throw new Error('Value is not a BigInt');
@@ -212,6 +248,30 @@ impl TryFrom<JsValue> for String {
     }
 }
 
+impl TryFrom<&JsValue> for String {
+    type Error = JsValue;
+
+    fn try_from(value: &JsValue) -> Result<Self, Self::Error> {
+        value.as_string().ok_or_else(|| value.clone())
+    }
+}
+
+impl TryFrom<JsValue> for bool {
+    type Error = JsValue;
+
+    fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+        value.as_bool().ok_or(value)
+    }
+}
+
+impl TryFrom<&JsValue> for bool {
+    type Error = JsValue;
+
+    fn try_from(value: &JsValue) -> Result<Self, Self::Error> {
+        value.as_bool().ok_or_else(|| value.clone())
+    }
+}
+
 to_js_value!(i8);
 from_js_value!(i8);
 to_js_value!(i16);
@@ -281,7 +341,7 @@ impl<T: ?Sized> Closure<T> {
 
     /// Wrap a raw closure. Only for use by generated code.
     pub(crate) fn wrap_encode_decode<FnPtr>(
-        encode_decode: impl Fn(&mut DecodedData, &mut EncodedData) + 'static,
+        encode_decode: impl Fn(&mut DecodedData, &mut EncodedData) -> Result<(), DecodeError> + 'static,
     ) -> Self
     where
         CallbackKey<FnPtr>: BinaryEncode + EncodeTypeDef,
@@ -294,7 +354,8 @@ impl<T: ?Sized> Closure<T> {
 
     /// Wrap a raw closure. Only for use by generated code.
     pub(crate) fn wrap_encode_decode_mut<FnPtr>(
-        encode_decode: impl FnMut(&mut DecodedData, &mut EncodedData) + 'static,
+        encode_decode: impl FnMut(&mut DecodedData, &mut EncodedData) -> Result<(), DecodeError>
+        + 'static,
     ) -> Self
     where
         CallbackKey<FnPtr>: BinaryEncode + EncodeTypeDef,
@@ -368,9 +429,18 @@ use alloc::boxed::Box;
 use alloc::string::{String, ToString};
 use core::ops::{Deref, DerefMut};
 // Re-export core types
+pub use array::{ArrayLength, JsArray};
 pub use cast::JsCast;
+pub use either::Either;
+pub use iterator::JsIterator;
+pub use js_function::JsFunction;
 pub use lazy::JsThreadLocal;
+pub use object::JsObject;
+pub use object_store::WryIterable;
+pub use snapshot::OwnedJsSnapshot;
 pub use value::JsValue;
+pub use visit::JsValueVisitor;
+pub use wry::initialization_script;
 
 /// A wrapper type around slices and vectors for binding the `Uint8ClampedArray` in JS.
 ///
@@ -467,9 +537,11 @@ impl JsCast for JsError {
 pub use batch::batch;
 pub use encode::{BatchableResult, BinaryDecode, BinaryEncode, EncodeTypeDef};
 pub use function::JSFunction;
-pub use ipc::{DecodeError, DecodedData, EncodedData};
+pub use ipc::{DecodeError, DecodedData, EncodedData, IPC_VERSION, IPCMessage, MessageType};
+pub use runtime::{block_on, spawn_local};
 
 // Re-export the macros
+pub use wry_bindgen_macro::BinaryCodec;
 pub use wry_bindgen_macro::link_to;
 pub use wry_bindgen_macro::wasm_bindgen;
 
@@ -482,8 +554,8 @@ use crate::object_store::insert_object;
 
 // Re-export function registry types
 pub use function_registry::{
-    InlineJsModule, JsClassMemberKind, JsClassMemberSpec, JsExportSpec, JsFunctionSpec,
-    LazyJsFunction,
+    ExportError, InlineJsModule, JsClassMemberKind, JsClassMemberSpec, JsExportSpec,
+    JsFunctionSpec, LazyJsFunction,
 };
 
 /// Macro to register and call a JavaScript function.
@@ -497,15 +569,20 @@ pub use function_registry::{
 /// # Usage
 /// ```ignore
 /// __wry_call_js_function!("(a, b) => a + b", fn(i32, i32) -> i32, (x, y))
+/// __wry_call_js_function!(name = "add", "(a0, a1) => add(a0, a1)", fn(i32, i32) -> i32, (x, y))
 /// ```
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __wry_call_js_function {
-    ($js_code:expr, $fn_type:ty, ($($args:expr),*)) => {{
-        static __FUNC: $crate::LazyJsFunction<$fn_type> = $crate::__wry_submit_js_function!($js_code);
+    (name = $name:expr, $js_code:expr, $fn_type:ty, ($($args:expr),*)) => {{
+        static __FUNC: $crate::LazyJsFunction<$fn_type> =
+            $crate::__wry_submit_js_function!(name = $name, $js_code);
 
         __FUNC.call($($args),*)
     }};
+    ($js_code:expr, $fn_type:ty, ($($args:expr),*)) => {
+        $crate::__wry_call_js_function!(name = "", $js_code, $fn_type, ($($args),*))
+    };
 }
 
 /// Macro to register and call a JavaScript function.
@@ -518,13 +595,14 @@ macro_rules! __wry_call_js_function {
 /// # Usage
 /// ```ignore
 /// __wry_submit_js_function!("(a, b) => a + b")
+/// __wry_submit_js_function!(name = "add", "(a0, a1) => add(a0, a1)")
 /// ```
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __wry_submit_js_function {
-    ($js_code:expr) => {{
+    (name = $name:expr, $js_code:expr) => {{
         static __SPEC: $crate::JsFunctionSpec =
-            $crate::JsFunctionSpec::new(|| $crate::alloc::format!($js_code));
+            $crate::JsFunctionSpec::named($name, || $crate::alloc::format!($js_code));
 
         $crate::inventory::submit! {
             __SPEC
@@ -532,6 +610,9 @@ macro_rules! __wry_submit_js_function {
 
         __SPEC.resolve_as()
     }};
+    ($js_code:expr) => {
+        $crate::__wry_submit_js_function!(name = "", $js_code)
+    };
 }
 
 /// Extension trait for Option to unwrap or throw a JS error.
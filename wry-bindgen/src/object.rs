@@ -0,0 +1,37 @@
+//! A binding to the JavaScript `Object` type
+
+use alloc::string::String;
+use std::collections::HashMap;
+
+use crate::js_helpers::{js_new_object, js_object_entries};
+use crate::{JsValue, wasm_bindgen};
+
+#[wasm_bindgen(crate = crate)]
+extern "C" {
+    /// A handle to a plain JavaScript `Object` living in the webview.
+    #[wasm_bindgen(js_name = Object)]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub type JsObject;
+}
+
+impl JsObject {
+    /// Create a new, empty JS object.
+    pub fn new() -> JsObject {
+        js_new_object()
+    }
+
+    /// Copy the object's own enumerable properties into a Rust [`HashMap`].
+    ///
+    /// The keys and values are transferred together in a single IPC round-trip
+    /// instead of reading each property separately.
+    pub fn to_hashmap(&self) -> HashMap<String, JsValue> {
+        let (keys, values) = js_object_entries(self);
+        keys.into_iter().zip(values).collect()
+    }
+}
+
+impl Default for JsObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -5,12 +5,27 @@
 //! can be retrieved, borrowed, and dropped. It also stores callback functions
 //! that can be called from JavaScript.
 
-use crate::batch::with_runtime;
-use crate::{BatchableResult, BinaryDecode, BinaryEncode, EncodeTypeDef};
+use alloc::rc::Rc;
+use core::any::Any;
+
+use crate::batch::{QueuedMutation, force_flush, with_runtime};
+use crate::{
+    BatchableResult, BinaryDecode, BinaryEncode, EncodeTypeDef, EncodedData, ExportError, JsValue,
+};
 
 /// Handle to an exported object in the store.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct ObjectHandle(u32);
+pub struct ObjectHandle(pub(crate) u32);
+
+/// Implemented by `#[wasm_bindgen]` structs exported to JS.
+///
+/// Exported methods can only take references to exported classes. Each such argument
+/// is borrowed from the object store for the duration of the call.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not an exported `#[wasm_bindgen]` struct",
+    label = "exported methods can only take references to exported structs"
+)]
+pub trait ExportedClass: 'static {}
 
 impl BinaryDecode for ObjectHandle {
     fn decode(decoder: &mut crate::DecodedData) -> Result<Self, crate::DecodeError> {
@@ -47,6 +62,34 @@ pub fn with_object_mut<T: 'static, R>(handle: ObjectHandle, f: impl FnOnce(&mut
     })
 }
 
+/// Like [`with_object`], but returns an error instead of panicking when the handle does
+/// not refer to a live object, for example because JS freed the wrapper.
+pub fn try_with_object<T: 'static, R>(
+    handle: ObjectHandle,
+    f: impl FnOnce(&T) -> R,
+) -> Result<R, InvalidHandleError> {
+    with_runtime(|state| {
+        let obj = state
+            .try_get_object::<T>(handle.0)
+            .ok_or(InvalidHandleError(handle))?;
+        Ok(f(&*obj))
+    })
+}
+
+/// Like [`with_object_mut`], but returns an error instead of panicking when the handle
+/// does not refer to a live object.
+pub fn try_with_object_mut<T: 'static, R>(
+    handle: ObjectHandle,
+    f: impl FnOnce(&mut T) -> R,
+) -> Result<R, InvalidHandleError> {
+    with_runtime(|state| {
+        let mut obj = state
+            .try_get_object_mut::<T>(handle.0)
+            .ok_or(InvalidHandleError(handle))?;
+        Ok(f(&mut *obj))
+    })
+}
+
 pub fn insert_object<T: 'static>(obj: T) -> ObjectHandle {
     with_runtime(|state| ObjectHandle(state.insert_object(obj)))
 }
@@ -55,8 +98,257 @@ pub fn remove_object<T: 'static>(handle: ObjectHandle) -> T {
     with_runtime(|state| state.remove_object(handle.0))
 }
 
+/// Like [`remove_object`], but returns an error instead of panicking when the object
+/// cannot be removed. The store is left unchanged on error.
+pub fn try_remove_object<T: ExportedClass>(handle: ObjectHandle) -> Result<T, BorrowObjectError> {
+    with_runtime(|state| state.try_remove_object(handle.0))
+}
+
+/// Borrow an exported object for the duration of an exported call.
+///
+/// The object is taken out of the store while borrowed so it can be used while
+/// another object from the store is borrowed, or while the object calls into JS.
+/// It is put back under the same handle when the returned [`BorrowedObject`] is dropped.
+pub fn borrow_object<T: 'static>(handle: ObjectHandle) -> BorrowedObject<T> {
+    let value =
+        with_runtime(|state| state.lend_object(handle.0)).unwrap_or_else(|err| panic!("{err}"));
+    BorrowedObject {
+        handle,
+        value: Some(value),
+    }
+}
+
+/// Like [`borrow_object`], but returns an error instead of panicking when the object
+/// cannot be borrowed.
+///
+/// The store is left unchanged on error, so an object of the wrong type or one that is
+/// already borrowed, for example because it was passed twice to the same call, is not
+/// lost.
+pub fn try_borrow_object<T: ExportedClass>(
+    handle: ObjectHandle,
+) -> Result<BorrowedObject<T>, BorrowObjectError> {
+    let value = with_runtime(|state| state.lend_object(handle.0))?;
+    Ok(BorrowedObject {
+        handle,
+        value: Some(value),
+    })
+}
+
+/// Mutate an exported object from a callback, even if the object is already borrowed.
+///
+/// Event handlers can run while the object they want to change is in use, for example
+/// when a method on the object dispatches an event synchronously. If the object is
+/// available, `f` runs immediately. If it is currently borrowed, `f` is queued and runs
+/// as soon as the outstanding borrow ends, so the mutation is never lost and the call
+/// never panics on the borrow.
+///
+/// Returns an error if the handle does not refer to a live exported object.
+///
+/// ```ignore
+/// let handle = extract_rust_handle(&counter_js).unwrap();
+/// let on_click = Closure::new(move || {
+///     with_exported_mut_in_callback::<Counter>(handle, |counter| counter.increment()).unwrap();
+/// });
+/// ```
+pub fn with_exported_mut_in_callback<T: 'static>(
+    handle: ObjectHandle,
+    f: impl FnOnce(&mut T) + 'static,
+) -> Result<(), InvalidHandleError> {
+    let mutation: QueuedMutation =
+        Box::new(move |obj: &mut dyn Any| f(obj.downcast_mut::<T>().expect("type mismatch")));
+    let lent = with_runtime(
+        |state| match state.queue_object_mutation(handle.0, mutation) {
+            Ok(()) => Ok(None),
+            Err(mutation) => state
+                .lend_object::<T>(handle.0)
+                .map(|value| Some((value, mutation)))
+                .map_err(|_| InvalidHandleError(handle)),
+        },
+    )?;
+    if let Some((value, mutation)) = lent {
+        // Lend the object out while `f` runs so callbacks it triggers are queued
+        let mut borrowed = BorrowedObject {
+            handle,
+            value: Some(value),
+        };
+        mutation(&mut *borrowed);
+    }
+    Ok(())
+}
+
+/// Error returned when a handle does not refer to a live exported object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidHandleError(ObjectHandle);
+
+impl InvalidHandleError {
+    /// The handle that did not refer to a live object.
+    pub fn handle(&self) -> ObjectHandle {
+        self.0
+    }
+}
+
+impl core::fmt::Display for InvalidHandleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "no exported object with handle {}; it was freed or moved out",
+            self.0.0
+        )
+    }
+}
+
+impl core::error::Error for InvalidHandleError {}
+
+/// Error returned when an exported object cannot be borrowed from the store.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BorrowObjectError {
+    /// The handle does not refer to a live exported object.
+    InvalidHandle(ObjectHandle),
+    /// The object is already borrowed by this call or a pending one.
+    AlreadyBorrowed(ObjectHandle),
+    /// The handle refers to an exported object of another type.
+    WrongType {
+        handle: ObjectHandle,
+        expected: &'static str,
+    },
+}
+
+impl core::fmt::Display for BorrowObjectError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BorrowObjectError::InvalidHandle(handle) => InvalidHandleError(*handle).fmt(f),
+            BorrowObjectError::AlreadyBorrowed(handle) => write!(
+                f,
+                "exported object with handle {} is already borrowed",
+                handle.0
+            ),
+            BorrowObjectError::WrongType { handle, expected } => write!(
+                f,
+                "exported object with handle {} is not a {expected}",
+                handle.0
+            ),
+        }
+    }
+}
+
+impl core::error::Error for BorrowObjectError {}
+
+/// An exported object lent out of the store by [`borrow_object`].
+pub struct BorrowedObject<T: 'static> {
+    handle: ObjectHandle,
+    value: Option<T>,
+}
+
+impl<T: 'static> core::ops::Deref for BorrowedObject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+            .as_ref()
+            .expect("borrowed object already released")
+    }
+}
+
+impl<T: 'static> core::ops::DerefMut for BorrowedObject<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+            .as_mut()
+            .expect("borrowed object already released")
+    }
+}
+
+impl<T: 'static> Drop for BorrowedObject<T> {
+    fn drop(&mut self) {
+        if let Some(mut value) = self.value.take() {
+            // Apply mutations callbacks queued while the object was lent out. These can
+            // queue more mutations, so keep going until the queue is empty.
+            loop {
+                let queued = with_runtime(|state| state.take_object_mutations(self.handle.0));
+                if queued.is_empty() {
+                    break;
+                }
+                for mutation in queued {
+                    mutation(&mut value);
+                }
+            }
+            // An object JS freed while it was lent out is dropped here instead of restored
+            let freed = with_runtime(|state| state.restore_object(self.handle.0, value));
+            if freed.is_some() {
+                drop(freed);
+                crate::batch::flush_pending_drops_unless_batching();
+            }
+        }
+    }
+}
+
+/// Share an exported object between calls that only read it.
+///
+/// Like [`try_borrow_object`], the object is taken out of the store, but any number of
+/// shares of the same handle can be held at once, so overlapping `&self` calls can all
+/// use it. It is put back into the store when the last [`SharedObject`] is dropped.
+/// Fails if the object is borrowed exclusively.
+pub fn try_share_object<T: ExportedClass>(
+    handle: ObjectHandle,
+) -> Result<SharedObject<T>, BorrowObjectError> {
+    let value = with_runtime(|state| state.share_object(handle.0))?;
+    Ok(SharedObject {
+        handle,
+        value: Some(value),
+    })
+}
+
+/// An exported object shared out of the store by [`try_share_object`].
+pub struct SharedObject<T: 'static> {
+    handle: ObjectHandle,
+    value: Option<Rc<T>>,
+}
+
+impl<T: 'static> core::ops::Deref for SharedObject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().expect("shared object already released")
+    }
+}
+
+impl<T: 'static> Drop for SharedObject<T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            let last = with_runtime(|state| state.unshare_object(self.handle.0, value));
+            // The last share restores the object like an exclusive borrow would
+            drop(last.map(|value| BorrowedObject {
+                handle: self.handle,
+                value: Some(value),
+            }));
+        }
+    }
+}
+
+/// Drop an exported object and release the JS values it holds.
+///
+/// The object is dropped outside the runtime borrow, and any `JsValue`s it owned are
+/// freed in JS before this returns. Once JS sees the wrapper's drop complete, no heap
+/// slot still refers to values owned by the dropped object.
+///
+/// An object lent out to a call in progress is dropped when the call gives it back.
 pub fn drop_object(handle: ObjectHandle) -> bool {
-    with_runtime(|state| state.remove_object_untyped(handle.0)).is_some()
+    let (removed, lent) = with_runtime(|state| {
+        let removed = state.remove_object_untyped(handle.0);
+        let lent = removed.is_none() && state.free_lent_object(handle.0);
+        (removed, lent)
+    });
+    let existed = removed.is_some() || lent;
+    drop(removed);
+    crate::batch::flush_pending_drops_unless_batching();
+    existed
+}
+
+/// Drop every callback and exported object an attached app registered with JS, once the
+/// app has finished. JS calls to them afterwards fail like calls to a dropped object.
+pub(crate) fn release_app_scope(scope: u32) {
+    let released = with_runtime(|state| state.release_app_scope(scope));
+    drop(released);
+    crate::batch::flush_pending_drops_unless_batching();
 }
 
 /// Create a JavaScript wrapper object for an exported Rust struct.
@@ -66,3 +358,59 @@ pub fn create_js_wrapper<T: 'static>(handle: ObjectHandle, class_name: &str) ->
     // The JS side will create an object with the appropriate methods
     crate::js_helpers::create_rust_object_wrapper(handle.0, class_name)
 }
+
+/// An exported Rust type that JavaScript can iterate over with `for...of`.
+///
+/// Mark the struct with `#[wasm_bindgen(iterable)]` and implement this trait to give
+/// its JS wrapper a `[Symbol.iterator]` method. Each step of the iterator calls
+/// [`WryIterable::next`] on the Rust value, so elements are produced on demand.
+///
+/// # Example
+///
+/// ```ignore
+/// #[wasm_bindgen(iterable)]
+/// pub struct Range {
+///     current: u32,
+///     end: u32,
+/// }
+///
+/// impl WryIterable for Range {
+///     fn next(&mut self) -> Option<JsValue> {
+///         (self.current < self.end).then(|| {
+///             self.current += 1;
+///             JsValue::from_f64((self.current - 1) as f64)
+///         })
+///     }
+/// }
+/// ```
+pub trait WryIterable: 'static {
+    /// Produce the next element, or `None` when the iterator is exhausted.
+    fn next(&mut self) -> Option<JsValue>;
+}
+
+/// Advance an exported iterable and encode the result for the JS iterator.
+///
+/// The yielded value is handed over to JS, which removes it from the heap after
+/// reading it, so it is not dropped on the Rust side. An iterator that was freed or
+/// is already borrowed is reported to JS as an error instead of panicking.
+#[doc(hidden)]
+pub fn iterable_next<T: WryIterable + ExportedClass>(
+    handle: ObjectHandle,
+) -> Result<EncodedData, ExportError> {
+    let next = {
+        let mut obj = try_borrow_object::<T>(handle)?;
+        obj.next()
+    };
+    // Make sure any batched calls that created the value have run before JS reads it
+    force_flush();
+
+    let mut encoder = EncodedData::new();
+    match next {
+        Some(value) => {
+            encoder.push_u8(1);
+            encoder.push_u64(value.into_abi() as u64);
+        }
+        None => encoder.push_u8(0),
+    }
+    Ok(encoder)
+}
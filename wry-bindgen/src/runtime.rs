@@ -3,21 +3,26 @@
 //! This module handles the connection between the Rust runtime and the
 //! JavaScript environment via winit's event loop.
 
+use core::future::Future;
 use core::pin::Pin;
-use std::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::task::{Context, Poll, Waker};
+use std::sync::{Arc, Mutex};
 
 use alloc::boxed::Box;
+use alloc::vec::Vec;
 use async_channel::{Receiver, Sender};
 use futures_util::{FutureExt, StreamExt};
 use spin::RwLock;
 
 use crate::BinaryDecode;
+use crate::ExportError;
 use crate::batch::with_runtime;
 use crate::function::{CALL_EXPORT_FN_ID, DROP_NATIVE_REF_FN_ID, RustCallback};
 use crate::ipc::MessageType;
-use crate::ipc::{DecodedData, DecodedVariant, IPCMessage};
+use crate::ipc::{DecodeError, DecodedData, DecodedVariant, IPCMessage};
 use crate::object_store::ObjectHandle;
-use crate::object_store::remove_object;
+use crate::websocket::SocketWriter;
 
 /// Application-level events that can be sent through the event loop.
 ///
@@ -51,6 +56,56 @@ impl WryBindgenEvent {
         }
     }
 
+    /// Create a new webview reloaded event.
+    pub(crate) fn webview_reloaded(id: u64) -> Self {
+        Self {
+            id,
+            event: AppEventVariant::WebviewReloaded,
+        }
+    }
+
+    /// Check if this event reports that an already loaded webview navigated or reloaded.
+    ///
+    /// The page's JS heap was wiped, so every `JsValue` the app held is now stale.
+    /// Hosts can watch for this event, and apps can await
+    /// [`webview_reloaded`](crate::batch::webview_reloaded), to set up the JS-side state
+    /// again. Calls the old page never answered fail.
+    pub fn is_webview_reload(&self) -> bool {
+        matches!(self.event, AppEventVariant::WebviewReloaded)
+    }
+
+    /// Create a new app finished event.
+    pub(crate) fn app_finished(id: u64) -> Self {
+        Self {
+            id,
+            event: AppEventVariant::AppFinished,
+        }
+    }
+
+    /// Create an event for a webview that opened a WebSocket connection.
+    pub(crate) fn socket_connected(id: u64, writer: SocketWriter) -> Self {
+        Self {
+            id,
+            event: AppEventVariant::SocketConnected(writer),
+        }
+    }
+
+    /// Create an event for a message JS sent over its WebSocket connection.
+    pub(crate) fn socket_message(id: u64, msg: IPCMessage) -> Self {
+        Self {
+            id,
+            event: AppEventVariant::SocketMessage(msg),
+        }
+    }
+
+    /// Create an event for a WebSocket connection that closed.
+    pub(crate) fn socket_closed(id: u64, connection: u64) -> Self {
+        Self {
+            id,
+            event: AppEventVariant::SocketClosed { connection },
+        }
+    }
+
     /// Consume the event and return the inner variant.
     pub(crate) fn into_variant(self) -> AppEventVariant {
         self.event
@@ -63,29 +118,82 @@ pub(crate) enum AppEventVariant {
     Ipc(IPCMessage),
     /// The webview has finished loading
     WebviewLoaded,
+    /// A webview that had already loaded finished loading a new page
+    WebviewReloaded,
+    /// The app future has completed and will not answer any more requests
+    AppFinished,
+    /// The webview opened a WebSocket connection to send and receive messages over
+    SocketConnected(SocketWriter),
+    /// A message from JavaScript sent over the WebSocket connection
+    SocketMessage(IPCMessage),
+    /// The WebSocket connection with the given number closed
+    SocketClosed { connection: u64 },
 }
 
 #[derive(Clone)]
 pub(crate) struct IPCSenders {
     eval_sender: Sender<IPCMessage>,
     respond_sender: futures_channel::mpsc::UnboundedSender<IPCMessage>,
+    /// Incremented every time the webview is recreated, shared with [`WryIPC`]
+    generation: Arc<WebviewGeneration>,
 }
 
 impl IPCSenders {
-    pub(crate) fn start_send(&self, msg: IPCMessage) {
-        match msg.ty().unwrap() {
+    /// Forward a message from JS to the app thread.
+    ///
+    /// Both channels are unbounded, so sending only fails once the app has finished and
+    /// dropped its receivers. The message is discarded in that case: the app finished
+    /// event answers whatever request JS is waiting on. `ty` is the message type the
+    /// protocol handler already decoded from `msg`.
+    pub(crate) fn start_send(&self, ty: MessageType, msg: IPCMessage) {
+        match ty {
             MessageType::Evaluate => {
-                self.eval_sender
-                    .try_send(msg)
-                    .expect("Failed to send evaluate message");
+                let _ = self.eval_sender.try_send(msg);
             }
             MessageType::Respond => {
-                self.respond_sender
-                    .unbounded_send(msg)
-                    .expect("Failed to send respond message");
+                let _ = self.respond_sender.unbounded_send(msg);
             }
         }
     }
+
+    /// Tell the app thread that the webview was replaced and its JS state is gone.
+    pub(crate) fn webview_recreated(&self) {
+        self.generation.bump();
+    }
+}
+
+/// How many times a webview has been recreated, with the tasks waiting for the next time
+#[derive(Default)]
+struct WebviewGeneration {
+    count: AtomicU64,
+    waiters: Mutex<Vec<Waker>>,
+}
+
+impl WebviewGeneration {
+    fn get(&self) -> u64 {
+        self.count.load(Ordering::Acquire)
+    }
+
+    fn bump(&self) {
+        self.count.fetch_add(1, Ordering::Release);
+        let waiters = core::mem::take(&mut *self.waiters.lock().unwrap());
+        for waker in waiters {
+            waker.wake();
+        }
+    }
+
+    fn poll_change(&self, seen: u64, cx: &mut Context<'_>) -> Poll<u64> {
+        // Check under the lock so a bump in between cannot miss the waker
+        let mut waiters = self.waiters.lock().unwrap();
+        let current = self.get();
+        if current != seen {
+            return Poll::Ready(current);
+        }
+        if !waiters.iter().any(|waker| waker.will_wake(cx.waker())) {
+            waiters.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
 }
 
 struct IPCReceivers {
@@ -121,6 +229,8 @@ impl IPCReceivers {
 pub(crate) struct WryIPC {
     pub(crate) proxy: Arc<dyn Fn(WryBindgenEvent) + Send + Sync>,
     receivers: RwLock<IPCReceivers>,
+    /// Incremented every time the webview is recreated, shared with [`IPCSenders`]
+    generation: Arc<WebviewGeneration>,
 }
 
 impl WryIPC {
@@ -128,18 +238,34 @@ impl WryIPC {
     pub(crate) fn new(proxy: Arc<dyn Fn(WryBindgenEvent) + Send + Sync>) -> (Self, IPCSenders) {
         let (eval_sender, eval_receiver) = async_channel::unbounded();
         let (respond_sender, respond_receiver) = futures_channel::mpsc::unbounded();
+        let generation = Arc::new(WebviewGeneration::default());
         let senders = IPCSenders {
             eval_sender,
             respond_sender,
+            generation: generation.clone(),
         };
         let receivers = RwLock::new(IPCReceivers {
             eval_receiver: Box::pin(eval_receiver),
             respond_receiver,
         });
-        let ipc = Self { proxy, receivers };
+        let ipc = Self {
+            proxy,
+            receivers,
+            generation,
+        };
         (ipc, senders)
     }
 
+    /// How many times the webview has been recreated.
+    pub(crate) fn webview_generation(&self) -> u64 {
+        self.generation.get()
+    }
+
+    /// Resolve with the current webview generation once it is no longer `seen`.
+    pub(crate) fn poll_webview_generation(&self, seen: u64, cx: &mut Context<'_>) -> Poll<u64> {
+        self.generation.poll_change(seen, cx)
+    }
+
     /// Send a response back to JavaScript.
     pub(crate) fn js_response(&self, id: u64, responder: IPCMessage) {
         (self.proxy)(WryBindgenEvent::ipc(id, responder));
@@ -155,7 +281,7 @@ pub(crate) fn progress_js_with<O>(
     match decoder {
         DecodedVariant::Respond { data } => Some(with_respond(data)),
         DecodedVariant::Evaluate { mut data } => {
-            handle_rust_callback(&mut data);
+            dispatch_rust_callback(&mut data);
             None
         }
     }
@@ -169,47 +295,224 @@ pub async fn handle_callbacks() {
         match decoder {
             DecodedVariant::Respond { .. } => unreachable!(),
             DecodedVariant::Evaluate { mut data } => {
-                handle_rust_callback(&mut data);
+                dispatch_rust_callback(&mut data);
             }
         }
     }
 }
 
+/// A future started with [`spawn_local`]
+pub(crate) type LocalTask = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Start a future on the app thread without waiting for it.
+///
+/// The future is polled alongside the app future, in the runtime of the webview it was
+/// spawned from, until it completes or the app finishes. Futures spawned inside a
+/// callback from JS are first polled before the callback returns to JS, so they can
+/// start their JS calls without waiting for another message.
+///
+/// This must be called on the app thread.
+pub fn spawn_local<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    let waker = with_runtime(|runtime| runtime.spawn_task(Box::pin(future)));
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+/// Run the future of an async export and return the promise JS receives for it.
+///
+/// The future is spawned with [`spawn_local`]. The promise resolves with its `Ok`
+/// value and rejects with its `Err` value, or with an `Error` if the future panics.
+/// Used by the `#[wasm_bindgen]` macro for `async fn` exports.
+#[doc(hidden)]
+pub fn spawn_export<F>(
+    export_name: &'static str,
+    future: F,
+) -> Result<crate::EncodedData, ExportError>
+where
+    F: Future<Output = Result<crate::JsValue, crate::JsValue>> + 'static,
+{
+    let deferred = crate::js_helpers::js_new_deferred();
+    let promise = crate::js_helpers::js_deferred_promise(&deferred);
+    spawn_local(async move {
+        let future = CatchUnwind {
+            future: Box::pin(future),
+        };
+        match future.await {
+            Ok(Ok(value)) => crate::js_helpers::js_settle_deferred(&deferred, true, &value),
+            Ok(Err(err)) => crate::js_helpers::js_settle_deferred(&deferred, false, &err),
+            Err(message) => crate::js_helpers::js_reject_deferred_with_error(
+                &deferred,
+                &format!("Export {export_name} panicked: {message}"),
+            ),
+        }
+        // JS may be waiting on nothing but this promise, so don't leave it in the batch
+        crate::batch::force_flush();
+    });
+
+    // The promise must exist in JS before its id is sent back
+    crate::batch::force_flush();
+    let mut encoder = crate::EncodedData::new();
+    // Ownership of the promise moves to JS
+    encoder.push_u64(promise.into_abi() as u64);
+    Ok(encoder)
+}
+
+/// Resolves to the panic message instead of unwinding if polling the inner future panics.
+struct CatchUnwind<F: Future> {
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, alloc::string::String>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = self.future.as_mut();
+        match std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| alloc::string::String::from(*s))
+                    .or_else(|| payload.downcast_ref::<alloc::string::String>().cloned())
+                    .unwrap_or_else(|| "Rust panic".into());
+                Poll::Ready(Err(message))
+            }
+        }
+    }
+}
+
+/// Poll the futures spawned with [`spawn_local`] that are ready to make progress.
+fn poll_spawned_tasks(cx: &mut Context<'_>) {
+    loop {
+        // The tasks are already being polled further up the stack
+        let Some(mut tasks) = with_runtime(|runtime| runtime.take_tasks(cx.waker())) else {
+            return;
+        };
+        while let Poll::Ready(Some(())) = tasks.poll_next_unpin(cx) {}
+        // Poll futures spawned by the tasks before returning
+        if !with_runtime(|runtime| runtime.restore_tasks(tasks)) {
+            return;
+        }
+    }
+}
+
+/// Drive the futures spawned with [`spawn_local`]. This never completes.
+pub(crate) async fn run_spawned_tasks() {
+    core::future::poll_fn(|cx| {
+        poll_spawned_tasks(cx);
+        Poll::<()>::Pending
+    })
+    .await
+}
+
+/// Run a future to completion from synchronous code on the app thread.
+///
+/// `pollster::block_on` deadlocks on futures that wait for JS, such as a `JsFuture`:
+/// the promise resolves by calling back into Rust, and those callbacks are only
+/// handled while the app future is being polled. This `block_on` handles callbacks
+/// from JS while it waits, so the future can make progress.
+///
+/// Prefer `.await` inside async code. Use this only where you cannot await, for
+/// example inside a synchronous trait method or FFI callback. It must be called on the
+/// app thread, and it cannot wait for JS from inside a callback that JS is blocked on,
+/// because JS cannot run its event loop until that callback returns.
+pub fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    use futures_util::future::{Either, select};
+
+    let receiver = with_runtime(|runtime| runtime.ipc().receivers.read().eval_receiver.clone());
+    let mut future = core::pin::pin!(future);
+    loop {
+        // Send any batched calls the future is waiting on before blocking
+        crate::batch::force_flush();
+        let next_callback = core::pin::pin!(receiver.recv());
+        match pollster::block_on(select(future.as_mut(), next_callback)) {
+            Either::Left((output, _)) => return output,
+            Either::Right((Ok(message), _)) => {
+                let decoder = message.decoded().expect("Failed to decode response");
+                match decoder {
+                    DecodedVariant::Respond { .. } => unreachable!(),
+                    DecodedVariant::Evaluate { mut data } => {
+                        dispatch_rust_callback(&mut data);
+                    }
+                }
+            }
+            Either::Right((Err(_), _)) => panic!("The webview closed while blocking on a future"),
+        }
+    }
+}
+
+/// Handle a Rust callback invocation from JavaScript, dropping it if it is malformed.
+///
+/// A frame that cannot be decoded is logged and answered with an empty response instead
+/// of panicking, so a buggy or compromised webview cannot crash the app. JS is blocked
+/// until it gets a response, and then throws while decoding the missing return value.
+fn dispatch_rust_callback(data: &mut DecodedData) {
+    // A callback from a reloaded page refers to IDs in its new heap
+    with_runtime(|state| state.sync_webview_generation());
+    if let Err(err) = handle_rust_callback(data) {
+        log::warn!("Dropping a malformed callback from JS: {err}");
+        let response = IPCMessage::new_respond(|_| {});
+        with_runtime(|runtime| runtime.ipc().js_response(runtime.webview_id(), response));
+    }
+}
+
 /// Handle a Rust callback invocation from JavaScript.
-fn handle_rust_callback(data: &mut DecodedData) {
-    let fn_id = data.take_u32().expect("Failed to read fn_id");
+///
+/// Returns an error without responding to JS if the frame cannot be decoded. Arguments
+/// are decoded by the callback itself once the frame has been dispatched.
+fn handle_rust_callback(data: &mut DecodedData) -> Result<(), DecodeError> {
+    let generation = with_runtime(|runtime| runtime.ipc().webview_generation());
+    let fn_id = data.take_u32()?;
+    log::trace!("Handling call {fn_id} from JS");
     let response = match fn_id {
         // Call a registered Rust callback
         0 => {
-            let key = data.take_u32().unwrap();
+            let key = data.take_u32()?;
 
             // Clone the Rc while briefly borrowing the batch state, then release the borrow.
             // This allows nested callbacks to access the object store during our callback execution.
-            let callback = with_runtime(|state| {
-                let rust_callback = state.get_object::<RustCallback>(key);
-
-                rust_callback.clone_rc()
+            let (callback, scope) = with_runtime(|state| {
+                let rust_callback = state.try_get_object::<RustCallback>(key)?;
+
+                Some((rust_callback.clone_rc(), state.object_app_scope(key)))
+            })
+            .ok_or_else(|| DecodeError::Custom(format!("unknown callback {key}")))?;
+
+            // Push a borrow frame before calling the callback - nested calls won't clear our borrowed refs.
+            // The callback runs as part of the app that registered it.
+            let outer_scope = with_runtime(|state| {
+                state.push_borrow_frame();
+                state.enter_app_scope(scope)
             });
 
-            // Push a borrow frame before calling the callback - nested calls won't clear our borrowed refs
-            with_runtime(|state| state.push_borrow_frame());
-
             // Call through the cloned Rc (uniform Fn interface)
+            let mut result = Ok(());
             let response = IPCMessage::new_respond(|encoder| {
-                (callback)(data, encoder);
+                result = (callback)(data, encoder);
             });
 
             // Pop the borrow frame after the callback completes
-            with_runtime(|state| state.pop_borrow_frame());
+            with_runtime(|state| {
+                state.enter_app_scope(outer_scope);
+                state.pop_borrow_frame();
+            });
 
+            // Arguments the callback could not decode leave JS without a return value
+            result?;
             response
         }
         // Drop a native Rust object when JS GC'd the wrapper
         DROP_NATIVE_REF_FN_ID => {
-            let key = ObjectHandle::decode(data).expect("Failed to decode object handle");
+            let key = ObjectHandle::decode(data)?;
 
-            // Remove the object from the thread-local encoder
-            remove_object::<RustCallback>(key);
+            // Drop the callback. The JS values it captured are freed before responding.
+            // It is already gone if the attached app that registered it has finished.
+            drop(with_runtime(|state| state.remove_object_untyped(key.0)));
 
             // Send empty response
             IPCMessage::new_respond(|_| {})
@@ -217,30 +520,61 @@ fn handle_rust_callback(data: &mut DecodedData) {
         // Call an exported Rust struct method
         CALL_EXPORT_FN_ID => {
             // Read the export name
-            let export_name: alloc::string::String =
-                crate::encode::BinaryDecode::decode(data).expect("Failed to decode export name");
+            let export_name: alloc::string::String = crate::encode::BinaryDecode::decode(data)?;
 
             // Find the export handler
             let export = crate::inventory::iter::<crate::JsExportSpec>()
                 .find(|e| e.name == export_name)
-                .unwrap_or_else(|| panic!("Unknown export: {export_name}"));
+                .ok_or_else(|| DecodeError::Custom(format!("unknown export {export_name}")))?;
 
             // Call the handler
             let result = (export.handler)(data);
 
-            assert!(data.is_empty(), "Extra data remaining after export call");
+            if !data.is_empty() {
+                return Err(DecodeError::Custom(format!(
+                    "extra data remaining after export call {export_name}"
+                )));
+            }
 
-            // Send response
+            // Send response, prefixed with a status byte: 0 = returned, 1 = throw the
+            // JsValue with this heap id, 2 = throw an Error with this message
             match result {
                 Ok(encoded) => IPCMessage::new_respond(|encoder| {
+                    encoder.push_u8(0);
                     encoder.extend(&encoded);
                 }),
-                Err(err) => {
-                    panic!("Export call failed: {err}");
+                Err(ExportError::Throw(err)) => {
+                    // Make sure any batched calls that created the error have run before JS reads it
+                    crate::batch::force_flush();
+                    IPCMessage::new_respond(|encoder| {
+                        encoder.push_u8(1);
+                        // Ownership of the error moves to JS
+                        encoder.push_u64(err.into_abi() as u64);
+                    })
                 }
+                Err(ExportError::Decode(message)) => IPCMessage::new_respond(|encoder| {
+                    encoder.push_u8(2);
+                    encoder.push_str(&format!("Export call {export_name} failed: {message}"));
+                }),
             }
         }
-        _ => todo!(),
+        _ => return Err(DecodeError::Custom(format!("unknown function id {fn_id}"))),
     };
-    with_runtime(|runtime| runtime.ipc().js_response(runtime.webview_id(), response));
+
+    // Start any futures the call spawned before JS continues
+    let waker =
+        with_runtime(|runtime| runtime.task_waker()).unwrap_or_else(futures_util::task::noop_waker);
+    poll_spawned_tasks(&mut Context::from_waker(&waker));
+
+    // Free the values the call dropped now. JS may not call into Rust again for a while,
+    // so waiting for the next call would keep them alive until then.
+    crate::batch::flush_pending_drops_unless_batching();
+
+    // The page that made the call is gone if the webview was recreated meanwhile
+    with_runtime(|runtime| {
+        if runtime.ipc().webview_generation() == generation {
+            runtime.ipc().js_response(runtime.webview_id(), response);
+        }
+    });
+    Ok(())
 }
@@ -0,0 +1,38 @@
+//! Converting between `serde` types and JS values through JSON.
+//!
+//! This mirrors the `serde-serialize` feature of wasm-bindgen. Values cross the IPC
+//! boundary as a JSON string and are parsed or stringified on the JS side.
+
+use alloc::format;
+
+use serde::Serialize;
+use serde::de::{DeserializeOwned, Error as _};
+
+use crate::JsValue;
+use crate::js_helpers::{js_json_parse, js_json_stringify};
+
+impl JsValue {
+    /// Create a JS value by serializing `value` to JSON and parsing it with `JSON.parse`.
+    ///
+    /// Like `JSON.stringify`, `NaN` and infinite floats become `null`. `None` also becomes
+    /// `null`, so an `Option` field is always present on the JS object.
+    pub fn from_serde<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<JsValue> {
+        let json = serde_json::to_string(value)?;
+        Ok(js_json_parse(&json))
+    }
+
+    /// Deserialize this value from the JSON produced by `JSON.stringify`.
+    ///
+    /// `JSON.stringify` drops object fields that are `undefined` and turns `undefined`
+    /// array elements into `null`, so a missing or `undefined` field deserializes like an
+    /// absent one: `Option` fields become `None` and other fields need `#[serde(default)]`.
+    /// `null` fields, `NaN` and infinite numbers also deserialize as `None`. A bare
+    /// `undefined` is treated as `null`. Values `JSON.stringify` rejects, such as `BigInt`
+    /// or cyclic objects, return an error.
+    pub fn into_serde<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        let json = js_json_stringify(self).map_err(|err| {
+            serde_json::Error::custom(format!("JSON.stringify failed: {}", err.as_debug_string()))
+        })?;
+        serde_json::from_str(&json)
+    }
+}
@@ -0,0 +1,53 @@
+//! Detached and deep copies of JS values.
+
+use alloc::string::String;
+
+use crate::JsValue;
+use crate::js_helpers::{js_json_parse, js_snapshot_json, js_structured_clone};
+
+/// A deep copy of a JSON-compatible JS value, held in Rust instead of the JS heap.
+///
+/// Created with [`JsValue::snapshot`]. Unlike cloning a [`JsValue`], which references
+/// the same live object, a snapshot is unaffected by later mutations of the original and
+/// keeps its contents after the original is garbage collected.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OwnedJsSnapshot {
+    json: String,
+}
+
+impl OwnedJsSnapshot {
+    /// Create a fresh JS value from the snapshot.
+    ///
+    /// Each call parses the snapshot again, so the returned values are independent.
+    pub fn rehydrate(&self) -> JsValue {
+        js_json_parse(&self.json)
+    }
+
+    /// The snapshot as JSON text.
+    pub fn as_json(&self) -> &str {
+        &self.json
+    }
+}
+
+impl JsValue {
+    /// Deep-copy this value out of the JS heap with `JSON.stringify`.
+    ///
+    /// The snapshot follows JSON rules: object fields that are `undefined` or functions
+    /// are dropped, `NaN` becomes `null` and `Date`s become strings. Values with no JSON
+    /// form at all, such as `undefined`, functions, `BigInt`s or cyclic objects, return
+    /// the exception thrown in JS.
+    pub fn snapshot(&self) -> Result<OwnedJsSnapshot, JsValue> {
+        let json = js_snapshot_json(self)?;
+        Ok(OwnedJsSnapshot { json })
+    }
+
+    /// Deep-copy this value inside JS with `structuredClone`.
+    ///
+    /// Unlike [`JsValue::snapshot`], the copy stays in the JS heap and keeps the types JSON
+    /// loses, such as `Date`, `Map`, `Set`, typed arrays, `BigInt` and cyclic references.
+    /// Values the structured clone algorithm rejects, such as functions or DOM nodes,
+    /// return the `DataCloneError` thrown in JS.
+    pub fn structured_clone(&self) -> Result<JsValue, JsValue> {
+        js_structured_clone(self)
+    }
+}
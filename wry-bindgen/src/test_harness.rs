@@ -0,0 +1,944 @@
+//! An in-process stand-in for the webview, for testing bindings without a display.
+//!
+//! [`TestHarness`] plays the JS side of the IPC protocol in Rust. Calls into JS are
+//! encoded into the same [`IPCMessage`]s that are sent to a real webview, decoded with
+//! the type definitions sent alongside them, and answered with a `Respond` message by
+//! Rust closures registered with [`TestHarness::define`]. Heap references, borrowed
+//! references and batching follow the same rules as the JS heap, so bindings run
+//! unchanged.
+//!
+//! JS values are modeled by [`Value`]. Numbers are `f64` like in JS, tuples and typed
+//! arrays are arrays, and structs, maps and `Result`s are plain objects. Rust callbacks
+//! passed to JS are received as [`Value::Callback`], but cannot be called from a handler.
+//!
+//! This module is only available with the `test-harness` feature.
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[wasm_bindgen(inline_js = "export function add(a, b) { return a + b; }")]
+//! extern "C" {
+//!     fn add(a: f64, b: f64) -> f64;
+//! }
+//!
+//! let mut harness = TestHarness::new();
+//! harness.define("add", |args| {
+//!     Value::Number(args[0].as_f64().unwrap() + args[1].as_f64().unwrap())
+//! });
+//! assert_eq!(harness.run(|| add(1.0, 2.0)), 3.0);
+//! ```
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use std::sync::{Arc, Mutex};
+
+use crate::batch::{Runtime, in_runtime};
+use crate::encode::{TYPE_CACHED, TYPE_FULL, TypeTag};
+use crate::function_registry::FUNCTION_REGISTRY;
+use crate::ipc::{DecodeError, DecodedData, DecodedVariant, EncodedData, IPCMessage, MessageType};
+use crate::runtime::{AppEventVariant, IPCSenders, WryBindgenEvent, WryIPC};
+use crate::value::{
+    JSIDX_FALSE, JSIDX_NULL, JSIDX_OFFSET, JSIDX_RESERVED, JSIDX_TRUE, JSIDX_UNDEFINED,
+};
+
+/// The JS code of casts between types, which returns its argument
+const IDENTITY: &str = "(a0) => a0";
+
+/// The name of the helper that frees dropped heap references
+const DROP_HEAP_REFS: &str = "drop_heap_refs";
+
+/// A JS value as seen by the [`TestHarness`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// `undefined`
+    Undefined,
+    /// `null`
+    Null,
+    /// A boolean
+    Bool(bool),
+    /// A number. Every numeric type, including dates in milliseconds, is an `f64` in JS.
+    Number(f64),
+    /// A string
+    String(String),
+    /// An array, also used for tuples and typed arrays
+    Array(Vec<Value>),
+    /// A plain object, also used for structs, maps and `Result`s as `{ ok }` or `{ err }`
+    Object(BTreeMap<String, Value>),
+    /// A Rust callback passed to JS, identified by its key in the object store
+    Callback(u32),
+}
+
+impl Value {
+    /// Get the number, if this is a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the boolean, if this is a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the string, if this is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Check if this is `null` or `undefined`.
+    pub fn is_nullish(&self) -> bool {
+        matches!(self, Value::Undefined | Value::Null)
+    }
+
+    /// Convert to a boolean like JS does in a condition.
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Undefined | Value::Null => false,
+            Value::Bool(value) => *value,
+            Value::Number(value) => *value != 0.0 && !value.is_nan(),
+            Value::String(value) => !value.is_empty(),
+            Value::Array(_) | Value::Object(_) | Value::Callback(_) => true,
+        }
+    }
+
+    /// Convert to a number like JS does with `Number(value)`.
+    fn to_number(&self) -> f64 {
+        match self {
+            Value::Null => 0.0,
+            Value::Bool(value) => *value as u8 as f64,
+            Value::Number(value) => *value,
+            Value::String(value) if value.trim().is_empty() => 0.0,
+            Value::String(value) => value.trim().parse().unwrap_or(f64::NAN),
+            _ => f64::NAN,
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Number(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(value: Vec<Value>) -> Self {
+        Value::Array(value)
+    }
+}
+
+type Handler = Box<dyn FnMut(Vec<Value>) -> Value + Send>;
+
+/// Runs bindings against JS functions implemented in Rust, without a webview.
+///
+/// Each harness has its own runtime and JS heap. Calls into JS made inside
+/// [`TestHarness::run`] are answered by the handlers registered with
+/// [`TestHarness::define`].
+pub struct TestHarness {
+    runtime: Option<Runtime>,
+    js: Arc<Mutex<JsSide>>,
+}
+
+impl TestHarness {
+    /// Create a harness with an empty JS heap and no handlers.
+    pub fn new() -> Self {
+        let js = Arc::new(Mutex::new(JsSide::new()));
+        let proxy = {
+            let js = js.clone();
+            move |event: WryBindgenEvent| {
+                if let AppEventVariant::Ipc(message) = event.into_variant() {
+                    js.lock().unwrap().evaluate(&message);
+                }
+            }
+        };
+        let (ipc, senders) = WryIPC::new(Arc::new(proxy));
+        js.lock().unwrap().senders = Some(senders);
+        let runtime = Runtime::new(ipc, crate::wry::unique_id());
+        Self {
+            runtime: Some(runtime),
+            js,
+        }
+    }
+
+    /// Answer calls to the imported JS function with the given name using `handler`.
+    ///
+    /// The name is the one the import has in JS, including its namespace or class:
+    /// `add` for a function, `console.log` for a function in a namespace, `Foo.bar` for
+    /// a static method, `new Foo` for a constructor, `Foo.prototype.bar` for a method
+    /// and `get Foo.prototype.bar` or `set Foo.prototype.bar` for property accessors.
+    /// Imports are matched by name only, so imports of the same name from different
+    /// modules share a handler. Calls to a function without a handler panic with its
+    /// name.
+    ///
+    /// The handler receives the decoded arguments and returns the value JS would have
+    /// returned. It runs while the harness is answering a message, so it must not call
+    /// into JS itself.
+    pub fn define(&self, name: &str, handler: impl FnMut(Vec<Value>) -> Value + Send + 'static) {
+        self.js
+            .lock()
+            .unwrap()
+            .handlers
+            .insert(name.to_string(), Box::new(handler));
+    }
+
+    /// Run `f` with this harness standing in for the webview.
+    ///
+    /// JS values created inside `f` belong to this harness, so they must be dropped
+    /// before it returns. Values dropped inside `f` are freed from the JS heap before
+    /// `run` returns.
+    pub fn run<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        let runtime = self
+            .runtime
+            .take()
+            .expect("TestHarness::run can not be called from inside itself");
+        let (runtime, out) = in_runtime(runtime, || {
+            let out = f();
+            crate::batch::flush_drops();
+            out
+        });
+        self.runtime = Some(runtime);
+        out
+    }
+
+    /// Get the number of values in the JS heap, not counting reserved constants.
+    pub fn heap_objects_alive(&self) -> usize {
+        self.js.lock().unwrap().heap.alive()
+    }
+}
+
+impl Default for TestHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The types of a JS function's parameters and return value
+struct FunctionType {
+    params: Vec<TypeDef>,
+    ret: TypeDef,
+}
+
+/// A parsed type definition, mirroring the type classes of the JS side
+#[derive(Clone)]
+enum TypeDef {
+    Void,
+    Bool,
+    Number(TypeTag),
+    String,
+    HeapRef,
+    BorrowedRef,
+    Callback,
+    Option(Box<TypeDef>),
+    Result(Box<TypeDef>, Box<TypeDef>),
+    Array(Box<TypeDef>),
+    U8Clamped,
+    StringEnum(Vec<String>),
+    Tuple(Vec<TypeDef>),
+    Date,
+    PackedArray(TypeTag),
+    Struct(Vec<(String, TypeDef)>),
+    Record(Box<TypeDef>),
+    Either(Box<TypeDef>, Box<TypeDef>),
+    MutSlice(Box<TypeDef>),
+}
+
+impl TypeDef {
+    fn parse(data: &mut DecodedData) -> Result<Self, DecodeError> {
+        let tag = TypeTag::try_from(data.take_u8()?)?;
+        Ok(match tag {
+            TypeTag::Void => TypeDef::Void,
+            TypeTag::Bool => TypeDef::Bool,
+            TypeTag::U8
+            | TypeTag::U16
+            | TypeTag::U32
+            | TypeTag::U64
+            | TypeTag::U128
+            | TypeTag::I8
+            | TypeTag::I16
+            | TypeTag::I32
+            | TypeTag::I64
+            | TypeTag::I128
+            | TypeTag::F32
+            | TypeTag::F64
+            | TypeTag::Usize
+            | TypeTag::Isize => TypeDef::Number(tag),
+            TypeTag::String => TypeDef::String,
+            TypeTag::HeapRef => TypeDef::HeapRef,
+            TypeTag::BorrowedRef => TypeDef::BorrowedRef,
+            TypeTag::Callback => {
+                // The harness never calls callbacks, but their types still have to be consumed
+                let param_count = data.take_u8()?;
+                for _ in 0..param_count {
+                    TypeDef::parse(data)?;
+                }
+                TypeDef::parse(data)?;
+                TypeDef::Callback
+            }
+            TypeTag::Option => TypeDef::Option(Box::new(TypeDef::parse(data)?)),
+            TypeTag::Result => TypeDef::Result(
+                Box::new(TypeDef::parse(data)?),
+                Box::new(TypeDef::parse(data)?),
+            ),
+            TypeTag::Array => TypeDef::Array(Box::new(TypeDef::parse(data)?)),
+            TypeTag::U8Clamped => TypeDef::U8Clamped,
+            TypeTag::StringEnum => {
+                let variant_count = data.take_u8()?;
+                let variants = (0..variant_count)
+                    .map(|_| take_type_def_str(data))
+                    .collect::<Result<_, _>>()?;
+                TypeDef::StringEnum(variants)
+            }
+            TypeTag::Tuple => {
+                let element_count = data.take_u8()?;
+                let elements = (0..element_count)
+                    .map(|_| TypeDef::parse(data))
+                    .collect::<Result<_, _>>()?;
+                TypeDef::Tuple(elements)
+            }
+            TypeTag::Date => TypeDef::Date,
+            TypeTag::PackedArray => {
+                let element = TypeTag::try_from(data.take_u8()?)?;
+                if packed_element_size(element).is_none() {
+                    return Err(DecodeError::Custom(format!(
+                        "unsupported packed array element: {element:?}"
+                    )));
+                }
+                TypeDef::PackedArray(element)
+            }
+            TypeTag::Struct => {
+                let field_count = data.take_u8()?;
+                let fields = (0..field_count)
+                    .map(|_| Ok((take_type_def_str(data)?, TypeDef::parse(data)?)))
+                    .collect::<Result<_, DecodeError>>()?;
+                TypeDef::Struct(fields)
+            }
+            TypeTag::Record => TypeDef::Record(Box::new(TypeDef::parse(data)?)),
+            TypeTag::MutSlice => TypeDef::MutSlice(Box::new(TypeDef::parse(data)?)),
+            TypeTag::Either => TypeDef::Either(
+                Box::new(TypeDef::parse(data)?),
+                Box::new(TypeDef::parse(data)?),
+            ),
+        })
+    }
+
+    /// Check whether a value has the shape this type encodes, used to pick the branch of an Either
+    fn accepts(&self, value: &Value) -> bool {
+        match self {
+            TypeDef::Bool => matches!(value, Value::Bool(_)),
+            TypeDef::Number(_) | TypeDef::Date => matches!(value, Value::Number(_)),
+            TypeDef::String | TypeDef::StringEnum(_) => matches!(value, Value::String(_)),
+            TypeDef::Void => value.is_nullish(),
+            TypeDef::Option(inner) => value.is_nullish() || inner.accepts(value),
+            TypeDef::Array(_)
+            | TypeDef::Tuple(_)
+            | TypeDef::PackedArray(_)
+            | TypeDef::U8Clamped
+            | TypeDef::MutSlice(_) => {
+                matches!(value, Value::Array(_))
+            }
+            TypeDef::Struct(_) | TypeDef::Record(_) => matches!(value, Value::Object(_)),
+            _ => true,
+        }
+    }
+}
+
+/// Take a string stored in a type definition as a little-endian u32 length and UTF-8 bytes
+fn take_type_def_str(data: &mut DecodedData) -> Result<String, DecodeError> {
+    let len = data.take_bytes_ref(4)?;
+    let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize;
+    let bytes = data.take_bytes_ref(len)?;
+    core::str::from_utf8(bytes)
+        .map(ToString::to_string)
+        .map_err(|e| DecodeError::InvalidUtf8 {
+            position: e.valid_up_to(),
+        })
+}
+
+/// The size of an element of a packed array, or `None` if the type is not packed
+fn packed_element_size(tag: TypeTag) -> Option<usize> {
+    match tag {
+        TypeTag::I8 => Some(1),
+        TypeTag::U16 | TypeTag::I16 => Some(2),
+        TypeTag::U32 | TypeTag::I32 | TypeTag::F32 => Some(4),
+        TypeTag::F64 => Some(8),
+        _ => None,
+    }
+}
+
+/// Convert a number to a u32 like JS does with `value >>> 0`
+fn to_uint32(value: f64) -> u32 {
+    if !value.is_finite() {
+        return 0;
+    }
+    value.trunc().rem_euclid(4294967296.0) as u32
+}
+
+/// Push a number as the two u32 halves JS sends for 64 bit integers
+fn push_js_u64(encoder: &mut EncodedData, value: f64) {
+    encoder.push_u32(to_uint32(value));
+    encoder.push_u32(to_uint32((value / 4294967296.0).floor()));
+}
+
+/// The JS half of the IPC protocol: the function handlers, type cache and heap
+struct JsSide {
+    handlers: BTreeMap<String, Handler>,
+    types: BTreeMap<u32, Arc<FunctionType>>,
+    heap: Heap,
+    /// Mutable slices decoded for the operation being run, sent back after its result
+    mutable_slices: Vec<(TypeDef, Value)>,
+    senders: Option<IPCSenders>,
+}
+
+impl JsSide {
+    fn new() -> Self {
+        Self {
+            handlers: BTreeMap::new(),
+            types: BTreeMap::new(),
+            heap: Heap::new(),
+            mutable_slices: Vec::new(),
+            senders: None,
+        }
+    }
+
+    /// Run every operation in an Evaluate message from Rust and send back the results.
+    fn evaluate(&mut self, message: &IPCMessage) {
+        let response = self
+            .run_operations(message)
+            .unwrap_or_else(|err| panic!("The test harness failed to decode a message: {err}"));
+        self.senders
+            .as_ref()
+            .expect("The harness senders are set when it is created")
+            .start_send(MessageType::Respond, response);
+    }
+
+    fn run_operations(&mut self, message: &IPCMessage) -> Result<IPCMessage, DecodeError> {
+        let DecodedVariant::Evaluate { mut data } = message.decoded()? else {
+            return Err(DecodeError::Custom(
+                "JS never waits for a response from Rust in the test harness".to_string(),
+            ));
+        };
+
+        let reserved_count = data.take_u32()?;
+        self.heap.push_reservation_scope(reserved_count);
+        self.heap.push_borrow_frame();
+
+        let mut encoder = EncodedData::new();
+        encoder.push_u8(MessageType::Respond as u8);
+        while data.has_more_u32() {
+            let fn_id = data.take_u32()?;
+            let ty = self.take_function_type(&mut data)?;
+            let args = ty
+                .params
+                .iter()
+                .map(|param| self.decode(param, &mut data))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mutable_slices = core::mem::take(&mut self.mutable_slices);
+            let result = self.call(fn_id, args);
+            if matches!(ty.ret, TypeDef::HeapRef) && reserved_count > 0 {
+                self.heap.fill_next_reserved(result);
+            } else {
+                self.encode(&ty.ret, result, &mut encoder);
+            }
+            // Handlers can not change their arguments, so slices are sent back unchanged
+            for (sequence, value) in mutable_slices {
+                self.encode(&sequence, value, &mut encoder);
+            }
+        }
+
+        self.heap.pop_borrow_frame();
+        self.heap.pop_reservation_scope();
+        Ok(IPCMessage::new(encoder.to_bytes()))
+    }
+
+    /// Read a full or cached type definition of a function call.
+    fn take_function_type(
+        &mut self,
+        data: &mut DecodedData,
+    ) -> Result<Arc<FunctionType>, DecodeError> {
+        let marker = data.take_u8()?;
+        let type_id = data.take_u32()?;
+        match marker {
+            TYPE_CACHED => self
+                .types
+                .get(&type_id)
+                .cloned()
+                .ok_or_else(|| DecodeError::Custom(format!("unknown cached type id {type_id}"))),
+            TYPE_FULL => {
+                let param_count = data.take_u8()?;
+                let params = (0..param_count)
+                    .map(|_| TypeDef::parse(data))
+                    .collect::<Result<_, _>>()?;
+                let ret = TypeDef::parse(data)?;
+                let ty = Arc::new(FunctionType { params, ret });
+                self.types.insert(type_id, ty.clone());
+                Ok(ty)
+            }
+            marker => Err(DecodeError::Custom(format!("unknown type marker {marker}"))),
+        }
+    }
+
+    /// Call the JS function with the given id.
+    fn call(&mut self, fn_id: u32, args: Vec<Value>) -> Value {
+        let spec = FUNCTION_REGISTRY
+            .function_spec(fn_id)
+            .unwrap_or_else(|| panic!("Unknown function ID in call from Rust: {fn_id}"));
+        // Functions that are not imported with `#[wasm_bindgen]` are only known by their code
+        let name = match spec.name() {
+            "" => (spec.js_code())(),
+            name => name.to_string(),
+        };
+        match name.as_str() {
+            IDENTITY => args.into_iter().next().unwrap_or(Value::Undefined),
+            DROP_HEAP_REFS => {
+                if let Some(Value::Array(ids)) = args.first() {
+                    for id in ids {
+                        self.heap.remove(id.to_number() as u64);
+                    }
+                }
+                Value::Undefined
+            }
+            _ => {
+                let handler = self.handlers.get_mut(&name).unwrap_or_else(|| {
+                    panic!("The test harness has no handler for the JS function `{name}`")
+                });
+                handler(args)
+            }
+        }
+    }
+
+    fn decode(&mut self, ty: &TypeDef, data: &mut DecodedData) -> Result<Value, DecodeError> {
+        Ok(match ty {
+            TypeDef::Void => Value::Undefined,
+            TypeDef::Bool => Value::Bool(data.take_u8()? != 0),
+            TypeDef::Number(tag) => Value::Number(decode_number(*tag, data)?),
+            TypeDef::String => Value::String(data.take_str()?.to_string()),
+            TypeDef::HeapRef | TypeDef::BorrowedRef => self.heap.get(data.take_u64()?),
+            TypeDef::Callback => Value::Callback(data.take_u32()?),
+            TypeDef::Option(inner) => match data.take_u8()? {
+                0 => Value::Null,
+                _ => self.decode(inner, data)?,
+            },
+            TypeDef::Result(ok, err) => {
+                let (key, value) = match data.take_u8()? {
+                    1 => ("ok", self.decode(ok, data)?),
+                    _ => ("err", self.decode(err, data)?),
+                };
+                Value::Object(BTreeMap::from([(key.to_string(), value)]))
+            }
+            TypeDef::Array(element) => {
+                let len = data.take_u32()?;
+                let elements = (0..len)
+                    .map(|_| self.decode(element, data))
+                    .collect::<Result<_, _>>()?;
+                Value::Array(elements)
+            }
+            TypeDef::U8Clamped => {
+                let len = data.take_u32()?;
+                let elements = (0..len)
+                    .map(|_| Ok(Value::Number(data.take_u8()? as f64)))
+                    .collect::<Result<_, DecodeError>>()?;
+                Value::Array(elements)
+            }
+            TypeDef::StringEnum(variants) => {
+                let index = data.take_u32()? as usize;
+                variants
+                    .get(index)
+                    .map_or(Value::Undefined, |variant| Value::String(variant.clone()))
+            }
+            TypeDef::Tuple(elements) => Value::Array(
+                elements
+                    .iter()
+                    .map(|element| self.decode(element, data))
+                    .collect::<Result<_, _>>()?,
+            ),
+            TypeDef::Date => Value::Number(f64::from_bits(data.take_u64()?)),
+            TypeDef::PackedArray(tag) => {
+                if narrow_range(*tag).is_some() {
+                    // Skip the range flag, values from Rust always fit their type
+                    data.take_u8()?;
+                }
+                let len = data.take_u32()? as usize;
+                let size = packed_element_size(*tag).expect("checked when parsing the type");
+                let byte_len = len.checked_mul(size).ok_or_else(|| {
+                    DecodeError::Custom(format!("packed array of {len} elements is too large"))
+                })?;
+                let bytes = data.take_bytes_ref(byte_len)?;
+                Value::Array(
+                    bytes
+                        .chunks_exact(size)
+                        .map(|bytes| Value::Number(decode_packed(*tag, bytes)))
+                        .collect(),
+                )
+            }
+            TypeDef::Struct(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(name, field)| Ok((name.clone(), self.decode(field, data)?)))
+                    .collect::<Result<_, DecodeError>>()?,
+            ),
+            TypeDef::Record(value) => {
+                let len = data.take_u32()?;
+                let entries = (0..len)
+                    .map(|_| {
+                        let key = data.take_str()?.to_string();
+                        Ok((key, self.decode(value, data)?))
+                    })
+                    .collect::<Result<_, DecodeError>>()?;
+                Value::Object(entries)
+            }
+            TypeDef::Either(left, right) => match data.take_u8()? {
+                0 => self.decode(left, data)?,
+                _ => self.decode(right, data)?,
+            },
+            TypeDef::MutSlice(sequence) => {
+                let value = self.decode(sequence, data)?;
+                self.mutable_slices
+                    .push((sequence.as_ref().clone(), value.clone()));
+                value
+            }
+        })
+    }
+
+    fn encode(&mut self, ty: &TypeDef, value: Value, encoder: &mut EncodedData) {
+        match ty {
+            TypeDef::Void => {}
+            TypeDef::Bool => encoder.push_u8(value.is_truthy() as u8),
+            TypeDef::Number(tag) => encode_number(*tag, value.to_number(), encoder),
+            TypeDef::String => match value {
+                Value::String(value) => encoder.push_str(&value),
+                value => panic!("Expected a string to return from JS, got {value:?}"),
+            },
+            TypeDef::HeapRef => {
+                self.heap.insert(value);
+            }
+            TypeDef::BorrowedRef => self.heap.add_borrowed_ref(value),
+            TypeDef::Callback => match value {
+                Value::Callback(key) => encoder.push_u32(key),
+                value => encoder.push_u32(to_uint32(value.to_number())),
+            },
+            TypeDef::Option(inner) => {
+                if value.is_nullish() {
+                    encoder.push_u8(0);
+                } else {
+                    encoder.push_u8(1);
+                    self.encode(inner, value, encoder);
+                }
+            }
+            TypeDef::Result(ok, err) => {
+                let mut fields = expect_object(value);
+                if let Some(value) = fields.remove("ok") {
+                    encoder.push_u8(1);
+                    self.encode(ok, value, encoder);
+                } else if let Some(value) = fields.remove("err") {
+                    encoder.push_u8(0);
+                    self.encode(err, value, encoder);
+                } else {
+                    panic!("Expected a result object with an `ok` or `err` field");
+                }
+            }
+            TypeDef::Array(element) => {
+                let elements = expect_array(value);
+                encoder.push_u32(elements.len() as u32);
+                for value in elements {
+                    self.encode(element, value, encoder);
+                }
+            }
+            TypeDef::U8Clamped => {
+                let elements = expect_array(value);
+                encoder.push_u32(elements.len() as u32);
+                for value in elements {
+                    encoder.push_u8(to_uint32(value.to_number()) as u8);
+                }
+            }
+            TypeDef::StringEnum(variants) => {
+                let index = variants
+                    .iter()
+                    .position(|variant| value.as_str() == Some(variant.as_str()))
+                    .unwrap_or(variants.len());
+                encoder.push_u32(index as u32);
+            }
+            TypeDef::Tuple(elements) => {
+                let mut values = expect_array(value).into_iter();
+                for element in elements {
+                    let value = values.next().unwrap_or(Value::Undefined);
+                    self.encode(element, value, encoder);
+                }
+            }
+            TypeDef::Date => encoder.push_u64(value.to_number().to_bits()),
+            TypeDef::PackedArray(tag) => {
+                let elements = expect_array(value);
+                if let Some(range) = narrow_range(*tag) {
+                    // One flag covers the whole array
+                    let in_range = elements
+                        .iter()
+                        .all(|value| in_narrow_range(value.to_number(), range));
+                    encoder.push_u8(in_range as u8);
+                }
+                encoder.push_u32(elements.len() as u32);
+                for value in elements {
+                    encode_packed(*tag, value.to_number(), encoder);
+                }
+            }
+            TypeDef::Struct(fields) => {
+                let mut values = expect_object(value);
+                for (name, field) in fields {
+                    let value = values.remove(name).unwrap_or(Value::Undefined);
+                    self.encode(field, value, encoder);
+                }
+            }
+            TypeDef::Record(value_ty) => {
+                let entries = expect_object(value);
+                encoder.push_u32(entries.len() as u32);
+                for (key, value) in entries {
+                    encoder.push_str(&key);
+                    self.encode(value_ty, value, encoder);
+                }
+            }
+            TypeDef::Either(left, right) => {
+                if left.accepts(&value) {
+                    encoder.push_u8(0);
+                    self.encode(left, value, encoder);
+                } else {
+                    encoder.push_u8(1);
+                    self.encode(right, value, encoder);
+                }
+            }
+            TypeDef::MutSlice(sequence) => self.encode(sequence, value, encoder),
+        }
+    }
+}
+
+fn expect_array(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(elements) => elements,
+        value => panic!("Expected an array to return from JS, got {value:?}"),
+    }
+}
+
+fn expect_object(value: Value) -> BTreeMap<String, Value> {
+    match value {
+        Value::Object(fields) => fields,
+        value => panic!("Expected an object to return from JS, got {value:?}"),
+    }
+}
+
+fn decode_number(tag: TypeTag, data: &mut DecodedData) -> Result<f64, DecodeError> {
+    if matches!(tag, TypeTag::U8 | TypeTag::U16 | TypeTag::I8 | TypeTag::I16) {
+        // Skip the range flag, values from Rust always fit their type
+        data.take_u8()?;
+    }
+    Ok(match tag {
+        TypeTag::U8 => data.take_u8()? as f64,
+        TypeTag::U16 => data.take_u16()? as f64,
+        TypeTag::U32 => data.take_u32()? as f64,
+        TypeTag::U64 | TypeTag::Usize => data.take_u64()? as f64,
+        TypeTag::U128 => data.take_u128()? as f64,
+        TypeTag::I8 => data.take_u8()? as i8 as f64,
+        TypeTag::I16 => data.take_u16()? as i16 as f64,
+        TypeTag::I32 => data.take_u32()? as i32 as f64,
+        TypeTag::I64 | TypeTag::Isize => data.take_u64()? as i64 as f64,
+        TypeTag::I128 => data.take_u128()? as i128 as f64,
+        TypeTag::F32 => f32::from_bits(data.take_u32()?) as f64,
+        TypeTag::F64 => f64::from_bits(data.take_u64()?),
+        tag => unreachable!("{tag:?} is not a number"),
+    })
+}
+
+/// The range of a narrow integer type, whose values are sent after a range flag
+fn narrow_range(tag: TypeTag) -> Option<(f64, f64)> {
+    match tag {
+        TypeTag::U8 => Some((0.0, 255.0)),
+        TypeTag::U16 => Some((0.0, 65535.0)),
+        TypeTag::I8 => Some((-128.0, 127.0)),
+        TypeTag::I16 => Some((-32768.0, 32767.0)),
+        _ => None,
+    }
+}
+
+fn in_narrow_range(value: f64, (min, max): (f64, f64)) -> bool {
+    value.fract() == 0.0 && value >= min && value <= max
+}
+
+fn encode_number(tag: TypeTag, value: f64, encoder: &mut EncodedData) {
+    match tag {
+        TypeTag::U8 | TypeTag::U16 | TypeTag::I8 | TypeTag::I16 => {
+            // Narrow integers are sent as a range flag followed by the wrapped value
+            let range = narrow_range(tag).expect("narrow integer tag");
+            encoder.push_u8(in_narrow_range(value, range) as u8);
+            if matches!(tag, TypeTag::U8 | TypeTag::I8) {
+                encoder.push_u8(to_uint32(value) as u8);
+            } else {
+                encoder.push_u16(to_uint32(value) as u16);
+            }
+        }
+        TypeTag::U32 | TypeTag::I32 => encoder.push_u32(to_uint32(value)),
+        TypeTag::U64 | TypeTag::I64 | TypeTag::Usize | TypeTag::Isize => {
+            push_js_u64(encoder, value)
+        }
+        TypeTag::U128 | TypeTag::I128 => {
+            push_js_u64(encoder, to_uint32(value) as f64);
+            push_js_u64(
+                encoder,
+                to_uint32((value / 18446744073709551616.0).floor()) as f64,
+            );
+        }
+        TypeTag::F32 => encoder.push_u32((value as f32).to_bits()),
+        TypeTag::F64 => encoder.push_u64(value.to_bits()),
+        tag => unreachable!("{tag:?} is not a number"),
+    }
+}
+
+fn decode_packed(tag: TypeTag, bytes: &[u8]) -> f64 {
+    match tag {
+        TypeTag::I8 => bytes[0] as i8 as f64,
+        TypeTag::U16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+        TypeTag::I16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+        TypeTag::U32 => u32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+        TypeTag::I32 => i32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+        TypeTag::F32 => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+        TypeTag::F64 => f64::from_le_bytes(bytes.try_into().unwrap()),
+        tag => unreachable!("{tag:?} is not a packed array element"),
+    }
+}
+
+fn encode_packed(tag: TypeTag, value: f64, encoder: &mut EncodedData) {
+    // Typed arrays wrap integers like `value >>> 0` and round floats to the element type
+    match tag {
+        TypeTag::I8 => encoder.push_bytes(&[to_uint32(value) as u8]),
+        TypeTag::U16 | TypeTag::I16 => encoder.push_bytes(&(to_uint32(value) as u16).to_le_bytes()),
+        TypeTag::U32 | TypeTag::I32 => encoder.push_bytes(&to_uint32(value).to_le_bytes()),
+        TypeTag::F32 => encoder.push_bytes(&(value as f32).to_le_bytes()),
+        TypeTag::F64 => encoder.push_bytes(&value.to_le_bytes()),
+        tag => unreachable!("{tag:?} is not a packed array element"),
+    }
+}
+
+/// A reservation of heap ids for the return values of a batch, see `JSHeap` in heap.ts
+struct Reservation {
+    start: u64,
+    count: u64,
+    next: u64,
+}
+
+/// The JS heap, kept in sync with the runtime's heap ids like `JSHeap` in heap.ts
+struct Heap {
+    slots: BTreeMap<u64, Value>,
+    max_id: u64,
+    borrow_stack_pointer: u64,
+    borrow_frames: Vec<u64>,
+    reservations: Vec<Reservation>,
+}
+
+impl Heap {
+    fn new() -> Self {
+        let slots = BTreeMap::from([
+            (JSIDX_UNDEFINED, Value::Undefined),
+            (JSIDX_NULL, Value::Null),
+            (JSIDX_TRUE, Value::Bool(true)),
+            (JSIDX_FALSE, Value::Bool(false)),
+        ]);
+        Self {
+            slots,
+            max_id: JSIDX_RESERVED,
+            borrow_stack_pointer: JSIDX_OFFSET,
+            borrow_frames: Vec::new(),
+            reservations: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, value: Value) -> u64 {
+        let id = self.max_id;
+        self.max_id += 1;
+        self.slots.insert(id, value);
+        id
+    }
+
+    fn get(&self, id: u64) -> Value {
+        self.slots.get(&id).cloned().unwrap_or(Value::Undefined)
+    }
+
+    fn remove(&mut self, id: u64) {
+        // Never remove reserved slots
+        if id >= JSIDX_RESERVED {
+            self.slots.remove(&id);
+        }
+    }
+
+    fn alive(&self) -> usize {
+        self.slots.range(JSIDX_RESERVED..).count()
+    }
+
+    fn push_reservation_scope(&mut self, count: u32) {
+        let count = count as u64;
+        self.reservations.push(Reservation {
+            start: self.max_id,
+            count,
+            next: 0,
+        });
+        self.max_id += count;
+    }
+
+    fn pop_reservation_scope(&mut self) {
+        self.reservations.pop();
+    }
+
+    fn fill_next_reserved(&mut self, value: Value) {
+        let scope = self
+            .reservations
+            .last_mut()
+            .filter(|scope| scope.next < scope.count)
+            .expect("No reserved slots available");
+        let id = scope.start + scope.next;
+        scope.next += 1;
+        self.slots.insert(id, value);
+    }
+
+    fn add_borrowed_ref(&mut self, value: Value) {
+        assert!(
+            self.borrow_stack_pointer > 1,
+            "Borrow stack overflow: too many borrowed references in a single operation"
+        );
+        self.borrow_stack_pointer -= 1;
+        self.slots.insert(self.borrow_stack_pointer, value);
+    }
+
+    fn push_borrow_frame(&mut self) {
+        self.borrow_frames.push(self.borrow_stack_pointer);
+    }
+
+    fn pop_borrow_frame(&mut self) {
+        if let Some(saved_pointer) = self.borrow_frames.pop() {
+            for id in self.borrow_stack_pointer..saved_pointer {
+                self.slots.remove(&id);
+            }
+            self.borrow_stack_pointer = saved_pointer;
+        }
+    }
+}
@@ -0,0 +1,54 @@
+/**
+ * Console forwarding
+ *
+ * Console sinks are only available to tests. The console methods are left alone
+ * until Rust installs a sink and calls `enableConsoleSink`, so an app without a sink
+ * never has its console wrapped.
+ */
+
+const LEVELS = ["debug", "log", "info", "warn", "error"] as const;
+
+let forwarding = false;
+
+function formatArgs(args: unknown[]): string {
+  return args
+    .map((arg) => {
+      try {
+        return typeof arg === "object" ? JSON.stringify(arg) : String(arg);
+      } catch (e) {
+        return String(arg);
+      }
+    })
+    .join(" ");
+}
+
+// Send the message with a sync request so the sink has it before the logging call returns
+function forward(level: string, args: unknown[]) {
+  const bytes = new TextEncoder().encode(formatArgs(args));
+  let binary = "";
+  for (let i = 0; i < bytes.length; i++) {
+    binary += String.fromCharCode(bytes[i]);
+  }
+  const xhr = new XMLHttpRequest();
+  xhr.open("POST", `/__wbg__/console/${level}`, false);
+  xhr.setRequestHeader("dioxus-data", btoa(binary));
+  xhr.send();
+}
+
+export function enableConsoleSink() {
+  if (forwarding) {
+    return;
+  }
+  forwarding = true;
+  for (const level of LEVELS) {
+    const original = console[level];
+    console[level] = function (...args: unknown[]) {
+      original.apply(console, args);
+      try {
+        forward(level, args);
+      } catch (e) {
+        // Logging must never throw, even if the app has exited
+      }
+    };
+  }
+}
@@ -4,12 +4,6 @@ export function is_undefined(x: any): boolean {
 export function is_null(x: any): boolean {
   return x === null;
 }
-export function is_true(x: any): boolean {
-  return x === true;
-}
-export function is_false(x: any): boolean {
-  return x === false;
-}
 export function get_typeof(x: any): string {
   return typeof x;
 }
@@ -34,12 +28,24 @@ export function is_symbol(x: any): boolean {
 export function is_bigint(x: any): boolean {
   return typeof x === "bigint";
 }
+export function is_array_buffer(x: any): boolean {
+  return x instanceof ArrayBuffer;
+}
+export function is_date(x: any): boolean {
+  return x instanceof Date;
+}
+export function is_typed_array(x: any): boolean {
+  return ArrayBuffer.isView(x) && !(x instanceof DataView);
+}
 export function as_string(x: any): string | null {
   return typeof x === "string" ? x : null;
 }
 export function as_f64(x: any): number | null {
   return typeof x === "number" ? x : null;
 }
+export function as_bool(x: any): boolean | null {
+  return typeof x === "boolean" ? x : null;
+}
 export function debug_string(x: any): string {
   try {
     return x.toString();
@@ -118,6 +124,77 @@ export function js_loose_eq(a: any, b: any): boolean {
   return a == b;
 }
 
+// Structural equality over own enumerable properties and array elements
+export function js_deep_equal(a: any, b: any): boolean {
+  return deepEqual(a, b, new Set(), new Map());
+}
+
+// `ancestors` holds the objects being compared further up, so reaching one again means
+// a cycle. `equal` remembers pairs already found equal so shared children are
+// compared once.
+function deepEqual(
+  a: any,
+  b: any,
+  ancestors: Set<object>,
+  equal: Map<object, Set<object>>
+): boolean {
+  // NaN equals itself here, unlike with ===
+  if (a === b || (a !== a && b !== b)) {
+    return true;
+  }
+  if (typeof a !== "object" || typeof b !== "object" || a === null || b === null) {
+    return false;
+  }
+  if (equal.get(a)?.has(b)) {
+    return true;
+  }
+  // A cycle has no finite structure to compare, so treat it as unequal
+  if (ancestors.has(a) || ancestors.has(b)) {
+    return false;
+  }
+  if (Object.getPrototypeOf(a) !== Object.getPrototypeOf(b)) {
+    return false;
+  }
+  if (a instanceof Date) {
+    return a.getTime() === b.getTime();
+  }
+  // Sets and map keys are matched by identity, as `has` does. Map values are compared
+  // deeply.
+  if (a instanceof Set) {
+    return a.size === b.size && [...a].every((item) => b.has(item));
+  }
+  let result: boolean;
+  ancestors.add(a).add(b);
+  if (a instanceof Map) {
+    result =
+      a.size === b.size &&
+      [...a].every(
+        ([key, value]) => b.has(key) && deepEqual(value, b.get(key), ancestors, equal)
+      );
+  } else {
+    const keysA = Object.keys(a);
+    const keysB = Object.keys(b);
+    result =
+      keysA.length === keysB.length &&
+      keysA.every(
+        (key) =>
+          Object.prototype.hasOwnProperty.call(b, key) &&
+          deepEqual(a[key], b[key], ancestors, equal)
+      );
+  }
+  ancestors.delete(a);
+  ancestors.delete(b);
+  if (result) {
+    let seen = equal.get(a);
+    if (!seen) {
+      seen = new Set();
+      equal.set(a, seen);
+    }
+    seen.add(b);
+  }
+  return result;
+}
+
 // Other operators
 export function js_in(prop: any, obj: any): boolean {
   return prop in obj;
@@ -128,15 +205,56 @@ export function is_error(x: any): boolean {
   return x instanceof Error;
 }
 
+// instanceof check against a constructor value, false if ctor is not callable
+export function js_instanceof(x: any, ctor: any): boolean {
+  try {
+    return x instanceof ctor;
+  } catch {
+    return false;
+  }
+}
+
+// instanceof check against the global constructor with this name, for imported classes.
+// Returns false instead of throwing when no such global exists or it is not callable.
+// Objects from another realm, such as an iframe, never pass instanceof against this
+// realm's constructor, so for those the prototype chain is matched by constructor name.
+export function instanceof_global(x: any, name: string): boolean {
+  const ctor = (globalThis as any)[name];
+  if (typeof ctor !== "function") {
+    return false;
+  }
+  try {
+    if (x instanceof ctor) {
+      return true;
+    }
+  } catch {
+    return false;
+  }
+  if ((typeof x !== "object" && typeof x !== "function") || x === null) {
+    return false;
+  }
+  const chain: any[] = [];
+  for (let proto = Object.getPrototypeOf(x); proto !== null; proto = Object.getPrototypeOf(proto)) {
+    chain.push(proto);
+  }
+  // Same-realm objects end at this realm's Object.prototype and already failed above
+  if (chain.length === 0 || chain[chain.length - 1] === Object.prototype) {
+    return false;
+  }
+  return chain.some((proto) => Object.prototype.hasOwnProperty.call(proto, "constructor") && proto.constructor.name === name);
+}
+
 // Heap management - clone a value in the JS heap
 // Returns the value itself (not the ID) - HeapRefType.encode handles inserting it
 export function clone_heap_ref(heapId: number): unknown {
   return window.jsHeap.get(heapId);
 }
 
-// Heap management - drop a value from the JS heap
-export function drop_heap_ref(heapId: number): void {
-  window.jsHeap.remove(heapId);
+// Heap management - drop values from the JS heap, coalesced into one call by Rust
+export function drop_heap_refs(heapIds: number[]): void {
+  for (const heapId of heapIds) {
+    window.jsHeap.remove(heapId);
+  }
 }
 
 // Create a wrapper object for an exported Rust struct
@@ -149,3 +267,201 @@ export function create_rust_object_wrapper(handle: number, className: string): u
 export function extract_rust_handle(obj: any): number | null {
   return (obj && typeof obj.__handle === 'number') ? obj.__handle : null;
 }
+
+export function json_parse(json: string): unknown {
+  return JSON.parse(json);
+}
+
+// A bare undefined has no JSON form, so it is sent as null like wasm-bindgen does
+export function json_stringify(x: unknown): string {
+  return JSON.stringify(x === undefined ? null : x);
+}
+
+// Unlike json_stringify, values without a JSON form (undefined, functions, symbols) are rejected
+export function snapshot_json(x: unknown): string {
+  const json = JSON.stringify(x);
+  if (json === undefined) {
+    throw new TypeError(`${typeof x} values cannot be snapshotted`);
+  }
+  return json;
+}
+
+// Throws a DataCloneError for values the structured clone algorithm rejects, such as functions
+export function structured_clone(x: unknown): unknown {
+  return structuredClone(x);
+}
+
+// Exceptions, including calling a value that is not a function, are caught by Rust
+export function function_apply(f: any, thisArg: unknown, args: unknown[]): unknown {
+  return Reflect.apply(f, thisArg, args);
+}
+
+// Bound with a fixed number of arguments for each arity in Rust
+export function function_call(f: any, thisArg: unknown, ...args: unknown[]): unknown {
+  return Reflect.apply(f, thisArg, args);
+}
+
+export function construct(ctor: any, args: unknown[]): unknown {
+  return Reflect.construct(ctor, args);
+}
+
+// Identity tokens. Objects are held weakly so tokens never keep them alive and get even
+// tokens from a counter. Primitives cannot be weak keys, so their odd tokens are a hash
+// of the type and value instead of being stored. Those are not unique: hashes can
+// collide, and values that print the same (like `Symbol("a")` twice) share a token.
+const objectTokens = new WeakMap<object, number>();
+let nextObjectToken = 2;
+
+// A 52-bit FNV-1a hash of the text, built from two 32-bit hashes with different seeds
+function hashText(text: string): number {
+  let low = 0x811c9dc5;
+  let high = 0x050c5d1f;
+  for (let i = 0; i < text.length; i++) {
+    const code = text.charCodeAt(i);
+    low = Math.imul(low ^ code, 0x01000193);
+    high = Math.imul(high ^ code, 0x01000193);
+  }
+  return (high >>> 12) * 0x100000000 + (low >>> 0);
+}
+
+export function identity_token(x: any): number {
+  const isObject = (typeof x === "object" && x !== null) || typeof x === "function";
+  if (!isObject) {
+    // Odd tokens stay below 2^53 so they survive the trip through an f64
+    return hashText(typeof x + ":" + String(x)) * 2 + 1;
+  }
+  let token = objectTokens.get(x);
+  if (token === undefined) {
+    token = nextObjectToken;
+    nextObjectToken += 2;
+    objectTokens.set(x, token);
+  }
+  return token;
+}
+
+// Object helpers
+export function new_object(): Record<string, unknown> {
+  return {};
+}
+
+// Array helpers
+export function new_array(): unknown[] {
+  return [];
+}
+
+// The elements are decoded into a fresh array, so it can be returned as is
+export function array_of(values: unknown[]): unknown[] {
+  return values;
+}
+
+// Sort an array in place using a comparator that calls back into Rust
+export function array_sort_by(arr: unknown[], compare: (a: unknown, b: unknown) => number): void {
+  arr.sort((a, b) => compare(a, b));
+}
+
+// Return the array itself so every element is transferred in a single call
+export function array_to_vec(arr: unknown[]): unknown[] {
+  return arr;
+}
+
+export function is_array(x: unknown): boolean {
+  return Array.isArray(x);
+}
+
+export function array_push(arr: unknown[], value: unknown): number {
+  return arr.push(value);
+}
+
+export function array_get(arr: unknown[], index: number): unknown {
+  return arr[index];
+}
+
+export function array_set(arr: unknown[], index: number, value: unknown): void {
+  arr[index] = value;
+}
+
+export function array_length(arr: unknown[]): number {
+  return arr.length;
+}
+
+// Iterator helpers
+export function try_iter(x: any): Iterator<unknown> | null {
+  if (x === null || x === undefined || typeof x[Symbol.iterator] !== "function") {
+    return null;
+  }
+  return x[Symbol.iterator]();
+}
+
+export function is_iterator(x: any): boolean {
+  return typeof x === "object" && x !== null && typeof x.next === "function";
+}
+
+// Return `done` alongside the value so a yielded `undefined` does not end iteration
+export function iterator_next(iter: Iterator<unknown>): [boolean, unknown] {
+  const result = iter.next();
+  return [!!result.done, result.value];
+}
+
+// Split an object's own enumerable properties into parallel key and value arrays
+export function object_entries(obj: Record<string, unknown>): [string[], unknown[]] {
+  const keys = Object.keys(obj);
+  return [keys, keys.map((key) => obj[key])];
+}
+
+export function is_uint8_array(x: unknown): boolean {
+  return x instanceof Uint8Array;
+}
+
+// Rust sends the bytes as one packed block, which already decodes to a Uint8Array
+export function uint8_array_from_bytes(bytes: Uint8Array): Uint8Array {
+  return bytes;
+}
+
+export function uint8_array_to_bytes(array: Uint8Array): Uint8Array {
+  return array;
+}
+
+// Classify a value for JsValue::visit, returning its kind with any primitive payload so
+// Rust needs only one call. Kinds must match the KIND_* constants in visit.rs
+export function value_kind(x: unknown): [number, number | string] {
+  switch (typeof x) {
+    case "undefined":
+      return [0, 0];
+    case "boolean":
+      return [2, x ? 1 : 0];
+    case "number":
+      return [3, x];
+    case "string":
+      return [4, x];
+    case "function":
+      return [6, 0];
+    case "object":
+      if (x === null) {
+        return [1, 0];
+      }
+      return [Array.isArray(x) ? 5 : 7, 0];
+    default:
+      return [8, 0];
+  }
+}
+export function new_deferred(): any {
+  const deferred: any = {};
+  deferred.promise = new Promise((resolve, reject) => {
+    deferred.resolve = resolve;
+    deferred.reject = reject;
+  });
+  return deferred;
+}
+export function deferred_promise(deferred: any): Promise<unknown> {
+  return deferred.promise;
+}
+export function settle_deferred(deferred: any, ok: boolean, value: any): void {
+  if (ok) {
+    deferred.resolve(value);
+  } else {
+    deferred.reject(value);
+  }
+}
+export function reject_deferred_with_error(deferred: any, message: string): void {
+  deferred.reject(new Error(message));
+}
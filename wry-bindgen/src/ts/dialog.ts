@@ -0,0 +1,109 @@
+/**
+ * Modal dialogs drawn as an HTML overlay
+ *
+ * Rust shows these instead of the native `alert`, `confirm` and `prompt`, which block
+ * the page until they are closed. Several dialogs can be open at once, and keyboard
+ * input only ever goes to the topmost one.
+ */
+
+type DialogKind = "alert" | "confirm" | "prompt";
+
+// Overlays of the open dialogs, with the topmost last
+const openDialogs: HTMLElement[] = [];
+
+export function showDialog(
+  kind: DialogKind,
+  message: string,
+  defaultValue: string
+): Promise<boolean | string | null | undefined> {
+  return new Promise((resolve) => {
+    const overlay = document.createElement("div");
+    overlay.className = "wry-dialog-overlay";
+    // Focusable so keys still reach the dialog after the backdrop is clicked
+    overlay.tabIndex = -1;
+    overlay.style.cssText =
+      "position:fixed;inset:0;z-index:2147483647;display:flex;" +
+      "align-items:center;justify-content:center;background:rgba(0,0,0,0.4);";
+
+    const dialog = document.createElement("div");
+    dialog.className = "wry-dialog";
+    dialog.setAttribute("role", kind === "alert" ? "alertdialog" : "dialog");
+    dialog.setAttribute("aria-modal", "true");
+    dialog.style.cssText =
+      "min-width:280px;max-width:80vw;padding:16px;border-radius:8px;" +
+      "background:#fff;color:#000;font:14px system-ui,sans-serif;" +
+      "box-shadow:0 8px 32px rgba(0,0,0,0.3);";
+
+    const text = document.createElement("p");
+    text.style.cssText = "margin:0 0 12px;white-space:pre-wrap;";
+    text.textContent = message;
+    dialog.appendChild(text);
+
+    let input: HTMLInputElement | null = null;
+    if (kind === "prompt") {
+      input = document.createElement("input");
+      input.className = "wry-dialog-input";
+      input.style.cssText = "box-sizing:border-box;width:100%;margin-bottom:12px;";
+      input.value = defaultValue;
+      dialog.appendChild(input);
+    }
+
+    const buttons = document.createElement("div");
+    buttons.style.cssText = "display:flex;justify-content:flex-end;gap:8px;";
+    dialog.appendChild(buttons);
+
+    const finish = (accepted: boolean) => {
+      const index = openDialogs.indexOf(overlay);
+      if (index === -1) {
+        return;
+      }
+      openDialogs.splice(index, 1);
+      overlay.remove();
+      // Hand the keyboard back to the dialog underneath, if any
+      openDialogs[openDialogs.length - 1]?.focus();
+      if (kind === "confirm") {
+        resolve(accepted);
+      } else if (kind === "prompt") {
+        resolve(accepted ? input!.value : null);
+      } else {
+        resolve(undefined);
+      }
+    };
+
+    // Listening on the overlay keeps key presses elsewhere on the page untouched
+    overlay.addEventListener("keydown", (event) => {
+      if (openDialogs[openDialogs.length - 1] !== overlay) {
+        return;
+      }
+      if (event.key === "Escape") {
+        event.preventDefault();
+        event.stopPropagation();
+        finish(kind === "alert");
+      } else if (event.key === "Enter" && event.target === input) {
+        // Focused buttons already activate on Enter, only the input needs help
+        event.preventDefault();
+        event.stopPropagation();
+        finish(true);
+      }
+    });
+
+    const addButton = (label: string, action: string, accepted: boolean) => {
+      const button = document.createElement("button");
+      button.type = "button";
+      button.textContent = label;
+      button.dataset.action = action;
+      button.addEventListener("click", () => finish(accepted));
+      buttons.appendChild(button);
+      return button;
+    };
+    if (kind !== "alert") {
+      addButton("Cancel", "cancel", false);
+    }
+    const ok = addButton("OK", "ok", true);
+
+    openDialogs.push(overlay);
+    overlay.appendChild(dialog);
+    (document.body || document.documentElement).appendChild(overlay);
+    (input || ok).focus();
+  });
+}
@@ -1,5 +1,12 @@
 import { RustFunction } from "./rust_function";
 
+// Magic bytes ("WRY") and wire format version - must match Rust's IPC_MAGIC and IPC_VERSION
+const IPC_MAGIC = [0x57, 0x52, 0x59];
+const IPC_VERSION = 9;
+
+// Length of the buffer header: magic, version and three u32 offsets
+const HEADER_LEN = 16;
+
 /**
  * Encoder for building binary messages to send to Rust.
  */
@@ -20,6 +27,12 @@ class DataEncoder {
     this.u8Buf.push(value & 0xff);
   }
 
+  pushBytes(bytes: Uint8Array) {
+    for (let i = 0; i < bytes.length; i++) {
+      this.u8Buf.push(bytes[i]);
+    }
+  }
+
   pushU16(value: number) {
     this.u16Buf.push(value & 0xffff);
   }
@@ -66,7 +79,7 @@ class DataEncoder {
   }
 
   finalize(): ArrayBuffer {
-    const u16Offset = 12 + this.u32Buf.length * 4;
+    const u16Offset = HEADER_LEN + this.u32Buf.length * 4;
     const u8Offset = u16Offset + this.u16Buf.length * 2;
     const strOffset = u8Offset + this.u8Buf.length;
     const totalSize = strOffset + this.strBuf.length;
@@ -74,13 +87,19 @@ class DataEncoder {
     const buffer = new ArrayBuffer(totalSize);
     const dataView = new DataView(buffer);
 
+    // Write magic and protocol version
+    for (let i = 0; i < IPC_MAGIC.length; i++) {
+      dataView.setUint8(i, IPC_MAGIC[i]);
+    }
+    dataView.setUint8(3, IPC_VERSION);
+
     // Write header offsets (little-endian)
-    dataView.setUint32(0, u16Offset, true);
-    dataView.setUint32(4, u8Offset, true);
-    dataView.setUint32(8, strOffset, true);
+    dataView.setUint32(4, u16Offset, true);
+    dataView.setUint32(8, u8Offset, true);
+    dataView.setUint32(12, strOffset, true);
 
     // Write u32 buffer
-    let offset = 12;
+    let offset = HEADER_LEN;
     for (const val of this.u32Buf) {
       dataView.setUint32(offset, val, true);
       offset += 4;
@@ -121,14 +140,23 @@ class DataDecoder {
   private strOffset: number;
 
   constructor(data: ArrayBuffer) {
-    const headerView = new DataView(data, 0, 12);
-    const u16ByteOffset = headerView.getUint32(0, true);
-    const u8ByteOffset = headerView.getUint32(4, true);
-    const strByteOffset = headerView.getUint32(8, true);
-
-    // u32 buffer starts at byte 12, ends at u16ByteOffset
-    const u32ByteLength = u16ByteOffset - 12;
-    this.u32Buf = new Uint32Array(data, 12, u32ByteLength / 4);
+    const headerView = new DataView(data, 0, HEADER_LEN);
+    for (let i = 0; i < IPC_MAGIC.length; i++) {
+      if (headerView.getUint8(i) !== IPC_MAGIC[i]) {
+        throw new Error("Invalid IPC message: missing magic bytes");
+      }
+    }
+    const version = headerView.getUint8(3);
+    if (version !== IPC_VERSION) {
+      throw new Error(`IPC protocol version mismatch: expected ${IPC_VERSION}, got ${version}`);
+    }
+    const u16ByteOffset = headerView.getUint32(4, true);
+    const u8ByteOffset = headerView.getUint32(8, true);
+    const strByteOffset = headerView.getUint32(12, true);
+
+    // u32 buffer starts after the header, ends at u16ByteOffset
+    const u32ByteLength = u16ByteOffset - HEADER_LEN;
+    this.u32Buf = new Uint32Array(data, HEADER_LEN, u32ByteLength / 4);
     this.u32Offset = 0;
 
     // u16 buffer
@@ -151,6 +179,15 @@ class DataDecoder {
     return this.u8Buf[this.u8Offset++];
   }
 
+  /**
+   * Take a number of bytes from the u8 buffer without copying them.
+   */
+  takeBytes(count: number): Uint8Array {
+    const bytes = this.u8Buf.subarray(this.u8Offset, this.u8Offset + count);
+    this.u8Offset += count;
+    return bytes;
+  }
+
   takeU16(): number {
     return this.u16Buf[this.u16Offset++];
   }
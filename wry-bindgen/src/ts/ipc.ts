@@ -2,8 +2,9 @@
  * Binary Protocol Encoder/Decoder
  *
  * The binary format uses aligned buffers for efficient memory access:
- * - First 12 bytes: three u32 offsets (u16_offset, u8_offset, str_offset)
- * - u32 buffer: from byte 12 to u16_offset
+ * - First 4 bytes: the magic bytes "WRY" followed by the protocol version
+ * - Next 12 bytes: three u32 offsets (u16_offset, u8_offset, str_offset)
+ * - u32 buffer: from byte 16 to u16_offset
  * - u16 buffer: from u16_offset to u8_offset
  * - u8 buffer: from u8_offset to str_offset
  * - string buffer: from str_offset to end
@@ -15,7 +16,7 @@
 
 import { DataDecoder, DataEncoder } from "./encoding";
 import { getFunctionRegistry, getTypeCache, CachedTypeInfo } from "./function_registry";
-import { parseTypeDef, TypeClass, HeapRefType } from "./types";
+import { parseTypeDef, TypeClass, HeapRefType, takeMutableSlices } from "./types";
 
 enum MessageType {
   Evaluate = 0,
@@ -53,6 +54,9 @@ function sync_request_binary(
   xhr.setRequestHeader("dioxus-data", base64);
   xhr.send();
 
+  if (xhr.status === 503) {
+    throw new Error("The Rust application has exited");
+  }
   if (xhr.status === 200 && xhr.responseText) {
     // Decode base64 response to ArrayBuffer
     const responseBinary = atob(xhr.responseText);
@@ -65,6 +69,68 @@ function sync_request_binary(
   return null;
 }
 
+/**
+ * The WebSocket Rust sends calls over, once it is open.
+ * Only set when the app uses the WebSocket transport.
+ */
+let socket: WebSocket | null = null;
+
+/**
+ * Connect to the WebSocket server if the app uses the WebSocket transport.
+ *
+ * Calls from Rust then arrive over the socket and are answered over it, without
+ * going through `evaluate_script`. Calls from JS still use sync requests to the
+ * handler, since JS has to wait for their results.
+ */
+function connectSocket() {
+  if (typeof WebSocket === "undefined") {
+    return;
+  }
+  const xhr = new XMLHttpRequest();
+  xhr.open("GET", "/__wbg__/transport", false);
+  xhr.send();
+  if (xhr.status !== 200 || !xhr.responseText) {
+    return;
+  }
+  const connecting = new WebSocket(xhr.responseText);
+  connecting.binaryType = "arraybuffer";
+  connecting.onopen = () => {
+    socket = connecting;
+  };
+  connecting.onclose = () => {
+    if (socket === connecting) {
+      socket = null;
+    }
+  };
+  connecting.onmessage = (event: MessageEvent<ArrayBuffer>) => {
+    const remaining = handleBinaryResponse(event.data, replyToRust);
+    if (remaining) {
+      throw new Error("Unprocessed data remaining after Evaluate handling");
+    }
+  };
+}
+
+/**
+ * Answer a call that Rust started.
+ *
+ * Answers go over the WebSocket when it is open. Rust then sends its next call
+ * over the socket too, so there is no response to process here.
+ */
+function replyToRust(data: ArrayBuffer): ArrayBuffer | null {
+  if (socket && socket.readyState === WebSocket.OPEN) {
+    socket.send(data);
+    return null;
+  }
+  return sync_request_binary(`/__wbg__/handler`, data);
+}
+
+/**
+ * Answer a call made while JS is waiting on Rust, which always goes through the handler.
+ */
+function replyOverHttp(data: ArrayBuffer): ArrayBuffer | null {
+  return sync_request_binary(`/__wbg__/handler`, data);
+}
+
 /**
  * Entry point for Rust to call JS functions using binary protocol.
  * Handles batched operations - reads and executes operations until buffer is exhausted.
@@ -78,7 +144,7 @@ function evaluate_from_rust_binary(dataBase64: string) {
   for (let i = 0; i < binary.length; i++) {
     bytes[i] = binary.charCodeAt(i);
   }
-  const remaining = handleBinaryResponse(bytes.buffer);
+  const remaining = handleBinaryResponse(bytes.buffer, replyToRust);
   if (remaining) {
     throw new Error("Unprocessed data remaining after Evaluate handling");
   }
@@ -129,9 +195,12 @@ function parseTypeInfo(decoder: DataDecoder): CachedTypeInfo {
 /**
  * Handle binary response from Rust.
  * May contain nested Evaluate calls (for callbacks).
+ *
+ * `reply` sends the results of an Evaluate back to Rust and returns the next message.
  */
 function handleBinaryResponse(
-  response: ArrayBuffer | null
+  response: ArrayBuffer | null,
+  reply: (data: ArrayBuffer) => ArrayBuffer | null = replyOverHttp
 ): DataDecoder | null {
   if (!response || response.byteLength === 0) {
     return null;
@@ -174,6 +243,7 @@ function handleBinaryResponse(
 
       // Decode parameters using their respective types
       const params = typeInfo.paramTypes.map((paramType) => paramType.decode(decoder));
+      const mutableSlices = takeMutableSlices();
 
       // Call the original JS function with decoded parameters
       const result = jsFunction(...params);
@@ -187,6 +257,11 @@ function handleBinaryResponse(
         // Encode the result using the return type
         typeInfo.returnType.encode(encoder, result);
       }
+
+      // Send the contents of mutable slices back so Rust can copy them into the slices
+      for (const { sequenceType, value } of mutableSlices) {
+        sequenceType.encode(encoder, value);
+      }
     }
 
     // Pop the borrow frame after all operations complete
@@ -195,11 +270,8 @@ function handleBinaryResponse(
     // Pop the reservation scope
     window.jsHeap.popReservationScope();
 
-    const nextResponse = sync_request_binary(
-      `/__wbg__/handler`,
-      encoder.finalize()
-    );
-    return handleBinaryResponse(nextResponse);
+    const nextResponse = reply(encoder.finalize());
+    return handleBinaryResponse(nextResponse, reply);
   }
 
   if (!decoder.isEmpty()) {
@@ -210,6 +282,7 @@ function handleBinaryResponse(
 }
 
 export {
+  connectSocket,
   evaluate_from_rust_binary,
   handleBinaryResponse,
   sync_request_binary,
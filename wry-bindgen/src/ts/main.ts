@@ -1,13 +1,18 @@
 import { JSHeap } from "./heap.ts";
 import "./ipc.ts";
-import { evaluate_from_rust_binary } from "./ipc.ts";
+import { connectSocket, evaluate_from_rust_binary } from "./ipc.ts";
 import { RawJsFunction, setFunctionRegistry } from "./function_registry.ts";
 import { rustExports } from "./rust_exports.ts";
+import { enableConsoleSink } from "./console.ts";
+import { showDialog } from "./dialog.ts";
 
 window.setFunctionRegistry = setFunctionRegistry;
 window.evaluate_from_rust_binary = evaluate_from_rust_binary;
 window.jsHeap = new JSHeap();
 window.rustExports = rustExports;
+window.enableConsoleSink = enableConsoleSink;
+window.showWryDialog = showDialog;
+connectSocket();
 
 declare global {
   interface Window {
@@ -15,5 +20,7 @@ declare global {
     evaluate_from_rust_binary: (dataBase64: string) => unknown;
     jsHeap: JSHeap;
     rustExports: typeof rustExports;
+    enableConsoleSink: () => void;
+    showWryDialog: typeof showDialog;
   }
 }
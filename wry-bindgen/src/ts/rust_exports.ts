@@ -1,4 +1,4 @@
-import { DataEncoder } from "./encoding";
+import { DataDecoder, DataEncoder } from "./encoding";
 import { handleBinaryResponse, MessageType, sync_request_binary, CALL_EXPORT_FN_ID } from "./ipc";
 
 /**
@@ -21,10 +21,30 @@ const exportRegistry = new FinalizationRegistry<{ handle: number; className: str
 });
 
 /**
- * Call an exported Rust method by name.
- * This is exposed as window.__wryCallExport for generated class methods to use.
+ * Read the status byte Rust prefixes to every export response and rethrow errors.
+ * 0 = the export returned, 1 = it returned Err with a JS value, 2 = the call failed in Rust.
  */
-function callExport(exportName: string, ...args: any[]): any {
+function takeExportStatus(decoder: DataDecoder | null): void {
+  if (!decoder) {
+    return;
+  }
+  const status = decoder.takeU8();
+  if (status === 1) {
+    // Rust hands ownership of the error to JS, so take it out of the heap
+    const id = decoder.takeU64();
+    const error = window.jsHeap.get(id);
+    window.jsHeap.remove(id);
+    throw error;
+  } else if (status === 2) {
+    throw new Error(decoder.takeStr());
+  }
+}
+
+/**
+ * Send a call to an exported Rust method and return the decoder for its result.
+ * Throws if the export returned an error.
+ */
+function sendExportCall(exportName: string, args: any[]): DataDecoder | null {
   window.jsHeap.pushBorrowFrame();
 
   const encoder = new DataEncoder();
@@ -36,6 +56,9 @@ function callExport(exportName: string, ...args: any[]): any {
   for (const arg of args) {
     if (typeof arg === "number") {
       encoder.pushU32(arg);
+    } else if (arg && typeof arg.__handle === "number") {
+      // Exported Rust objects are passed by their object store handle
+      encoder.pushU32(arg.__handle);
     } else {
       throw new Error(`Unsupported argument type: ${typeof arg}`);
     }
@@ -45,6 +68,16 @@ function callExport(exportName: string, ...args: any[]): any {
   const decoder = handleBinaryResponse(response);
 
   window.jsHeap.popBorrowFrame();
+  takeExportStatus(decoder);
+  return decoder;
+}
+
+/**
+ * Call an exported Rust method by name.
+ * This is exposed as window.__wryCallExport for generated class methods to use.
+ */
+function callExport(exportName: string, ...args: any[]): any {
+  const decoder = sendExportCall(exportName, args);
 
   // If we have response data, try to decode it
   // For now, try to decode as i32 if there's u32 data available
@@ -55,6 +88,56 @@ function callExport(exportName: string, ...args: any[]): any {
   return undefined;
 }
 
+/**
+ * Call an exported async Rust method by name.
+ * Rust spawns the method's future and returns a promise that settles when it completes.
+ * This is exposed as window.__wryCallAsyncExport for generated class methods to use.
+ */
+function callAsyncExport(exportName: string, ...args: any[]): Promise<unknown> {
+  let decoder: DataDecoder | null;
+  try {
+    decoder = sendExportCall(exportName, args);
+  } catch (e) {
+    // Async functions report every failure through the promise
+    return Promise.reject(e);
+  }
+  // Rust hands ownership of the promise to JS, so take it out of the heap
+  const id = decoder!.takeU64();
+  const promise = window.jsHeap.get(id) as Promise<unknown>;
+  window.jsHeap.remove(id);
+  return promise;
+}
+
+/**
+ * Advance an exported Rust iterable by calling its `__next` export.
+ * This is exposed as window.__wryIterNext for the generated `[Symbol.iterator]` method.
+ * Returns an iterator result object for use by the JS iteration protocol.
+ */
+function iterNext(exportName: string, handle: number): IteratorResult<unknown> {
+  window.jsHeap.pushBorrowFrame();
+
+  const encoder = new DataEncoder();
+  encoder.pushU8(MessageType.Evaluate);
+  encoder.pushU32(CALL_EXPORT_FN_ID);
+  encoder.pushStr(exportName);
+  encoder.pushU32(handle);
+
+  const response = sync_request_binary(`/__wbg__/handler`, encoder.finalize());
+  const decoder = handleBinaryResponse(response);
+
+  window.jsHeap.popBorrowFrame();
+  takeExportStatus(decoder);
+
+  if (!decoder || decoder.takeU8() === 0) {
+    return { done: true, value: undefined };
+  }
+  // Rust hands ownership of the yielded value to JS, so take it out of the heap
+  const id = decoder.takeU64();
+  const value = window.jsHeap.get(id);
+  window.jsHeap.remove(id);
+  return { done: false, value };
+}
+
 /**
  * Create a JavaScript wrapper object for a Rust exported struct.
  * Uses the generated class from JsClassSpec if available, otherwise falls back to Proxy.
@@ -100,7 +183,9 @@ function createWrapper(handle: number, className: string): object {
 
 // Expose callExport and exportRegistry as window globals for generated classes to use
 (window as any).__wryCallExport = callExport;
+(window as any).__wryCallAsyncExport = callAsyncExport;
 (window as any).__wryExportRegistry = exportRegistry;
+(window as any).__wryIterNext = iterNext;
 
 /**
  * RustExports manager - provides wrapper creation for exported structs.
@@ -108,6 +193,8 @@ function createWrapper(handle: number, className: string): object {
 const rustExports = {
   createWrapper,
   callExport,
+  callAsyncExport,
+  iterNext,
 };
 
-export { rustExports, createWrapper, callExport };
+export { rustExports, createWrapper, callExport, callAsyncExport, iterNext };
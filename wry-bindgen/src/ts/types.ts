@@ -6,7 +6,7 @@ import { RustFunction } from "./rust_function";
  * Must match the Rust TypeTag enum exactly.
  */
 enum TypeTag {
-  Null = 0,
+  Void = 0,
   Bool = 1,
   U8 = 2,
   U16 = 3,
@@ -31,6 +31,13 @@ enum TypeTag {
   BorrowedRef = 22,
   U8Clamped = 23,
   StringEnum = 24,
+  Tuple = 25,
+  Date = 26,
+  PackedArray = 27,
+  Struct = 28,
+  Record = 29,
+  Either = 30,
+  MutSlice = 31,
 }
 
 /**
@@ -148,18 +155,30 @@ class CallbackType implements TypeClass {
 }
 
 /**
- * Type class for null values with encoding/decoding methods
+ * Type class for the Rust unit type with encoding/decoding methods.
+ * Unit carries no data in either direction, so it is never confused with `null`.
  */
-class NullType implements TypeClass {
-  encode(encoder: DataEncoder, value: null): void {
-    // Null doesn't need to encode anything
+class VoidType implements TypeClass {
+  encode(encoder: DataEncoder, value: unknown): void {
+    // Unit doesn't encode anything, whatever value the JS function returned
   }
 
-  decode(decoder: DataDecoder): null {
-    return null;
+  decode(decoder: DataDecoder): undefined {
+    return undefined;
   }
 }
 
+// Narrow integers are sent as a range flag followed by the value wrapped to its width.
+// The flag is cleared for values that are not integers in the range of the type, so
+// Rust can reject them instead of silently truncating.
+const NARROW_IN_RANGE = 1;
+const NARROW_OUT_OF_RANGE = 0;
+
+function pushNarrowRangeFlag(encoder: DataEncoder, value: number, min: number, max: number): void {
+  const inRange = Number.isInteger(value) && value >= min && value <= max;
+  encoder.pushU8(inRange ? NARROW_IN_RANGE : NARROW_OUT_OF_RANGE);
+}
+
 type NumberType = "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" | "usize" | "isize" | "f32" | "f64";
 
 /**
@@ -175,9 +194,19 @@ class NumericType implements TypeClass {
   encode(encoder: DataEncoder, value: number): void {
     switch (this.size) {
       case "u8":
+        pushNarrowRangeFlag(encoder, value, 0, 0xff);
         encoder.pushU8(value);
         break;
       case "u16":
+        pushNarrowRangeFlag(encoder, value, 0, 0xffff);
+        encoder.pushU16(value);
+        break;
+      case "i8":
+        pushNarrowRangeFlag(encoder, value, -0x80, 0x7f);
+        encoder.pushU8(value);
+        break;
+      case "i16":
+        pushNarrowRangeFlag(encoder, value, -0x8000, 0x7fff);
         encoder.pushU16(value);
         break;
       case "u32":
@@ -189,14 +218,6 @@ class NumericType implements TypeClass {
       case "u128":
         encoder.pushU128(value);
         break;
-      case "i8":
-        // Signed integers encode as unsigned (Rust: self as u8)
-        encoder.pushU8(value & 0xff);
-        break;
-      case "i16":
-        // Signed integers encode as unsigned (Rust: self as u16)
-        encoder.pushU16(value & 0xffff);
-        break;
       case "i32":
         // Signed integers encode as unsigned (Rust: self as u32)
         encoder.pushU32(value >>> 0);
@@ -227,6 +248,10 @@ class NumericType implements TypeClass {
   }
 
   decode(decoder: DataDecoder): number {
+    if (this.size === "u8" || this.size === "u16" || this.size === "i8" || this.size === "i16") {
+      // Skip the range flag, values from Rust always fit their type
+      decoder.takeU8();
+    }
     switch (this.size) {
       case "u8":
         return decoder.takeU8();
@@ -263,7 +288,7 @@ class NumericType implements TypeClass {
 }
 
 class OptionType implements TypeClass {
-  private wrappedType: TypeClass;
+  readonly wrappedType: TypeClass;
 
   constructor(wrappedType: TypeClass) {
     this.wrappedType = wrappedType;
@@ -325,6 +350,74 @@ class ResultType implements TypeClass {
   }
 }
 
+/**
+ * Check whether a JS value has the shape a type encodes, used to pick the branch of an Either.
+ * Types that can hold any JS value, like heap references, accept everything.
+ */
+function acceptsValue(type: TypeClass, value: any): boolean {
+  if (type instanceof BoolType) {
+    return typeof value === "boolean";
+  }
+  if (type instanceof NumericType) {
+    return typeof value === "number" || typeof value === "bigint";
+  }
+  if (type instanceof StringType || type instanceof StringEnumType) {
+    return typeof value === "string";
+  }
+  if (type instanceof VoidType) {
+    return value === null || value === undefined;
+  }
+  if (type instanceof OptionType) {
+    return value === null || value === undefined || acceptsValue(type.wrappedType, value);
+  }
+  if (type instanceof DateType) {
+    return value instanceof Date;
+  }
+  if (type instanceof ArrayType || type instanceof TupleType) {
+    return Array.isArray(value);
+  }
+  if (type instanceof PackedArrayType || type instanceof U8ClampedType) {
+    return ArrayBuffer.isView(value);
+  }
+  if (type instanceof StructType || type instanceof RecordType) {
+    return typeof value === "object" && value !== null && !Array.isArray(value);
+  }
+  return true;
+}
+
+/**
+ * Type class for Either values. JS sees the bare value; when encoding, the left type
+ * is used if the value fits it and the right type otherwise.
+ */
+class EitherType implements TypeClass {
+  private leftType: TypeClass;
+  private rightType: TypeClass;
+
+  constructor(leftType: TypeClass, rightType: TypeClass) {
+    this.leftType = leftType;
+    this.rightType = rightType;
+  }
+
+  encode(encoder: DataEncoder, value: any): void {
+    if (acceptsValue(this.leftType, value)) {
+      encoder.pushU8(0); // Indicate Left
+      this.leftType.encode(encoder, value);
+    } else {
+      encoder.pushU8(1); // Indicate Right
+      this.rightType.encode(encoder, value);
+    }
+  }
+
+  decode(decoder: DataDecoder): any {
+    const isRight = decoder.takeU8();
+    if (isRight === 0) {
+      return this.leftType.decode(decoder);
+    } else {
+      return this.rightType.decode(decoder);
+    }
+  }
+}
+
 /**
  * Type class for array/Vec values with encoding/decoding methods
  */
@@ -352,6 +445,218 @@ class ArrayType implements TypeClass {
   }
 }
 
+type TypedArrayConstructor =
+  | Int8ArrayConstructor
+  | Uint16ArrayConstructor
+  | Int16ArrayConstructor
+  | Uint32ArrayConstructor
+  | Int32ArrayConstructor
+  | Float32ArrayConstructor
+  | Float64ArrayConstructor;
+
+// Typed array for each element TypeTag that Rust packs - must match PackedPrimitive in encode.rs
+const packedArrayConstructors: Partial<Record<TypeTag, TypedArrayConstructor>> = {
+  [TypeTag.I8]: Int8Array,
+  [TypeTag.U16]: Uint16Array,
+  [TypeTag.I16]: Int16Array,
+  [TypeTag.U32]: Uint32Array,
+  [TypeTag.I32]: Int32Array,
+  [TypeTag.F32]: Float32Array,
+  [TypeTag.F64]: Float64Array,
+};
+
+// Element ranges of the narrow packed arrays, which start with one range flag for the
+// whole array - must match RANGE_CHECKED in encode.rs
+const packedArrayRanges = new Map<TypedArrayConstructor, [number, number]>([
+  [Int8Array, [-0x80, 0x7f]],
+  [Uint16Array, [0, 0xffff]],
+  [Int16Array, [-0x8000, 0x7fff]],
+]);
+
+/**
+ * Type class for packed numeric arrays, sent as a u32 length followed by the
+ * little-endian element bytes. Decodes to a typed array and encodes any array-like.
+ */
+class PackedArrayType implements TypeClass {
+  private ctor: TypedArrayConstructor;
+  private range: [number, number] | undefined;
+
+  constructor(ctor: TypedArrayConstructor) {
+    this.ctor = ctor;
+    this.range = packedArrayRanges.get(ctor);
+  }
+
+  encode(encoder: DataEncoder, value: ArrayLike<number>): void {
+    if (this.range) {
+      // Converting to the typed array wraps values silently, so flag any that do not fit
+      // for Rust to reject. Arrays of the element type always fit.
+      const [min, max] = this.range;
+      let inRange = true;
+      if (!(value instanceof this.ctor)) {
+        for (let i = 0; i < value.length && inRange; i++) {
+          const element = value[i];
+          inRange = Number.isInteger(element) && element >= min && element <= max;
+        }
+      }
+      encoder.pushU8(inRange ? NARROW_IN_RANGE : NARROW_OUT_OF_RANGE);
+    }
+    const typed = value instanceof this.ctor ? value : this.ctor.from(value);
+    encoder.pushU32(typed.length);
+    encoder.pushBytes(new Uint8Array(typed.buffer, typed.byteOffset, typed.byteLength));
+  }
+
+  decode(decoder: DataDecoder): ArrayLike<number> {
+    if (this.range) {
+      // Skip the range flag, values from Rust always fit their type
+      decoder.takeU8();
+    }
+    const length = decoder.takeU32();
+    const bytes = decoder.takeBytes(length * this.ctor.BYTES_PER_ELEMENT);
+    // Copy into a fresh buffer, since the message buffer is not aligned for the element type.
+    // Typed arrays use the platform byte order, which is little-endian wherever webviews run.
+    const copy = new Uint8Array(bytes);
+    return new this.ctor(copy.buffer, 0, length);
+  }
+}
+
+// Mutable slices decoded for the operation being run, in argument order
+let mutableSlices: { sequenceType: TypeClass; value: any }[] = [];
+
+/**
+ * Take the mutable slices decoded since the last call. Their contents are sent back to
+ * Rust after the operation's return value.
+ */
+function takeMutableSlices(): { sequenceType: TypeClass; value: any }[] {
+  const slices = mutableSlices;
+  mutableSlices = [];
+  return slices;
+}
+
+/**
+ * Type class for mutable Rust slices. Decodes like the sequence and remembers the
+ * array, so changes JS makes to it can be copied back into the slice.
+ */
+class MutSliceType implements TypeClass {
+  private sequenceType: TypeClass;
+
+  constructor(sequenceType: TypeClass) {
+    this.sequenceType = sequenceType;
+  }
+
+  encode(encoder: DataEncoder, value: any): void {
+    this.sequenceType.encode(encoder, value);
+  }
+
+  decode(decoder: DataDecoder): any {
+    const value = this.sequenceType.decode(decoder);
+    mutableSlices.push({ sequenceType: this.sequenceType, value });
+    return value;
+  }
+}
+
+/**
+ * Type class for fixed-length tuples, represented as arrays in JS
+ */
+class TupleType implements TypeClass {
+  private elementTypes: TypeClass[];
+
+  constructor(elementTypes: TypeClass[]) {
+    this.elementTypes = elementTypes;
+  }
+
+  encode(encoder: DataEncoder, value: any[]): void {
+    for (let i = 0; i < this.elementTypes.length; i++) {
+      this.elementTypes[i].encode(encoder, value[i]);
+    }
+  }
+
+  decode(decoder: DataDecoder): any[] {
+    const result: any[] = [];
+    for (const elementType of this.elementTypes) {
+      result.push(elementType.decode(decoder));
+    }
+    return result;
+  }
+}
+
+/**
+ * Type class for structs with named fields, represented as plain objects
+ */
+class StructType implements TypeClass {
+  private fieldNames: string[];
+  private fieldTypes: TypeClass[];
+
+  constructor(fieldNames: string[], fieldTypes: TypeClass[]) {
+    this.fieldNames = fieldNames;
+    this.fieldTypes = fieldTypes;
+  }
+
+  encode(encoder: DataEncoder, value: Record<string, any>): void {
+    for (let i = 0; i < this.fieldTypes.length; i++) {
+      this.fieldTypes[i].encode(encoder, value[this.fieldNames[i]]);
+    }
+  }
+
+  decode(decoder: DataDecoder): Record<string, any> {
+    const result: Record<string, any> = {};
+    for (let i = 0; i < this.fieldTypes.length; i++) {
+      result[this.fieldNames[i]] = this.fieldTypes[i].decode(decoder);
+    }
+    return result;
+  }
+}
+
+/**
+ * Type class for string-keyed Rust maps, represented as plain objects.
+ * Sent as a u32 length followed by each key string and its value.
+ */
+class RecordType implements TypeClass {
+  private valueType: TypeClass;
+
+  constructor(valueType: TypeClass) {
+    this.valueType = valueType;
+  }
+
+  encode(encoder: DataEncoder, value: Record<string, any>): void {
+    const keys = Object.keys(value);
+    encoder.pushU32(keys.length);
+    for (const key of keys) {
+      encoder.pushStr(key);
+      this.valueType.encode(encoder, value[key]);
+    }
+  }
+
+  decode(decoder: DataDecoder): Record<string, any> {
+    const length = decoder.takeU32();
+    const result: Record<string, any> = {};
+    for (let i = 0; i < length; i++) {
+      const key = decoder.takeStr();
+      // Define the key rather than assigning it, so "__proto__" becomes an own key
+      // instead of replacing the object's prototype
+      Object.defineProperty(result, key, {
+        value: this.valueType.decode(decoder),
+        enumerable: true,
+        writable: true,
+        configurable: true,
+      });
+    }
+    return result;
+  }
+}
+
+/**
+ * Type class for dates, sent as f64 milliseconds since the Unix epoch
+ */
+class DateType implements TypeClass {
+  encode(encoder: DataEncoder, value: Date): void {
+    encoder.pushF64(value.getTime());
+  }
+
+  decode(decoder: DataDecoder): Date {
+    return new Date(decoder.takeF64());
+  }
+}
+
 /**
  * Type class for clamped u8 array values (Uint8ClampedArray).
  * Used for canvas ImageData and similar APIs.
@@ -397,10 +702,11 @@ export const strType = new StringType();
 
 // Pre-instantiated singleton types
 const boolTypeInstance = new BoolType();
-const nullTypeInstance = new NullType();
+const voidTypeInstance = new VoidType();
 const heapRefTypeInstance = new HeapRefType();
 const borrowedRefTypeInstance = new BorrowedRefType();
 const stringTypeInstance = new StringType();
+const dateTypeInstance = new DateType();
 
 /**
  * Parse a TypeDef from a byte array and return a TypeClass.
@@ -410,8 +716,8 @@ function parseTypeDef(bytes: Uint8Array, offset: { value: number }): TypeClass {
   const tag = bytes[offset.value++];
 
   switch (tag) {
-    case TypeTag.Null:
-      return nullTypeInstance;
+    case TypeTag.Void:
+      return voidTypeInstance;
     case TypeTag.Bool:
       return boolTypeInstance;
     case TypeTag.U8:
@@ -495,6 +801,51 @@ function parseTypeDef(bytes: Uint8Array, offset: { value: number }): TypeClass {
 
       return new StringEnumType(lookupArray);
     }
+    case TypeTag.Tuple: {
+      const elementCount = bytes[offset.value++];
+      const elementTypes: TypeClass[] = [];
+      for (let i = 0; i < elementCount; i++) {
+        elementTypes.push(parseTypeDef(bytes, offset));
+      }
+      return new TupleType(elementTypes);
+    }
+    case TypeTag.Date:
+      return dateTypeInstance;
+    case TypeTag.PackedArray: {
+      const elementTag = bytes[offset.value++] as TypeTag;
+      const ctor = packedArrayConstructors[elementTag];
+      if (!ctor) {
+        throw new Error(`Unsupported packed array element: ${elementTag}`);
+      }
+      return new PackedArrayType(ctor);
+    }
+    case TypeTag.Struct: {
+      const fieldCount = bytes[offset.value++];
+      const fieldNames: string[] = [];
+      const fieldTypes: TypeClass[] = [];
+      for (let i = 0; i < fieldCount; i++) {
+        // Read the field name length (u32 little-endian) and UTF-8 bytes
+        const len =
+          bytes[offset.value] |
+          (bytes[offset.value + 1] << 8) |
+          (bytes[offset.value + 2] << 16) |
+          (bytes[offset.value + 3] << 24);
+        offset.value += 4;
+        fieldNames.push(new TextDecoder().decode(bytes.subarray(offset.value, offset.value + len)));
+        offset.value += len;
+        fieldTypes.push(parseTypeDef(bytes, offset));
+      }
+      return new StructType(fieldNames, fieldTypes);
+    }
+    case TypeTag.Record:
+      return new RecordType(parseTypeDef(bytes, offset));
+    case TypeTag.Either: {
+      const leftType = parseTypeDef(bytes, offset);
+      const rightType = parseTypeDef(bytes, offset);
+      return new EitherType(leftType, rightType);
+    }
+    case TypeTag.MutSlice:
+      return new MutSliceType(parseTypeDef(bytes, offset));
     default:
       throw new Error(`Unknown TypeTag: ${tag}`);
   }
@@ -506,14 +857,21 @@ export {
   ArrayType,
   BoolType,
   BorrowedRefType,
+  DateType,
+  EitherType,
   HeapRefType,
   CallbackType,
-  NullType,
+  MutSliceType,
   NumericType,
   OptionType,
   StringType,
   StringEnumType,
+  StructType,
+  RecordType,
   ResultType,
+  TupleType,
   U8ClampedType,
+  VoidType,
   parseTypeDef,
+  takeMutableSlices,
 };
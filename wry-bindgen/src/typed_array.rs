@@ -0,0 +1,96 @@
+//! Typed arrays for moving binary data between Rust and JS.
+//!
+//! [`Uint8Array`] sends bytes as one length-prefixed block in the binary IPC buffer,
+//! and JS views that block as a real `Uint8Array` after a single copy. A `Vec<u8>`
+//! instead becomes a plain JS array of numbers, built one element at a time.
+//!
+//! # Overhead
+//!
+//! Every IPC message is base64 encoded at the protocol layer, in both directions, so a
+//! buffer of `n` bytes costs `4 * ceil(n / 3)` bytes on the wire plus a fixed header of
+//! a few dozen bytes for the message header, the type definition and the length. A
+//! 64 KiB buffer is sent as 87,384 base64 characters, about 33% more than the raw
+//! bytes. That is still far cheaper than JSON, which spends up to four characters per
+//! byte on the digits and separators of a number array.
+
+use alloc::vec::Vec;
+
+use crate::encode::{BatchableResult, BinaryDecode, BinaryEncode, EncodeTypeDef, TypeTag};
+use crate::ipc::{DecodeError, DecodedData, EncodedData};
+use crate::js_helpers::{js_is_uint8_array, js_uint8_array_from_bytes, js_uint8_array_to_bytes};
+use crate::wasm_bindgen;
+
+#[wasm_bindgen(crate = crate)]
+extern "C" {
+    /// A JS `Uint8Array` living in the webview.
+    ///
+    /// Casting checks `x instanceof Uint8Array`.
+    #[wasm_bindgen(js_name = Uint8Array, is_type_of = js_is_uint8_array)]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub type Uint8Array;
+
+    /// The number of bytes in the array.
+    #[wasm_bindgen(method, getter)]
+    pub fn length(this: &Uint8Array) -> u32;
+}
+
+impl Uint8Array {
+    /// Create a new JS `Uint8Array` holding a copy of `bytes`.
+    pub fn from_slice(bytes: &[u8]) -> Uint8Array {
+        js_uint8_array_from_bytes(PackedBytes(bytes))
+    }
+
+    /// Copy the contents of the array into a new `Vec`.
+    pub fn to_vec(&self) -> Vec<u8> {
+        js_uint8_array_to_bytes(self).0
+    }
+
+    /// Copy the contents of the array into `dst`.
+    ///
+    /// # Panics
+    /// Panics if `dst` is not the same length as the array.
+    pub fn copy_to(&self, dst: &mut [u8]) {
+        let bytes = self.to_vec();
+        assert_eq!(
+            bytes.len(),
+            dst.len(),
+            "destination length does not match the Uint8Array length"
+        );
+        dst.copy_from_slice(&bytes);
+    }
+}
+
+/// Bytes sent to JS as one packed block, which JS decodes as a `Uint8Array`.
+pub(crate) struct PackedBytes<'a>(pub(crate) &'a [u8]);
+
+impl EncodeTypeDef for PackedBytes<'_> {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        buf.push(TypeTag::PackedArray as u8);
+        buf.push(TypeTag::U8 as u8);
+    }
+}
+
+impl BinaryEncode for PackedBytes<'_> {
+    fn encode(self, encoder: &mut EncodedData) {
+        encoder.push_u32(self.0.len() as u32);
+        encoder.push_bytes(self.0);
+    }
+}
+
+/// Bytes received from a JS `Uint8Array` as one packed block.
+pub(crate) struct PackedByteBuf(pub(crate) Vec<u8>);
+
+impl EncodeTypeDef for PackedByteBuf {
+    fn encode_type_def(buf: &mut Vec<u8>) {
+        PackedBytes::encode_type_def(buf);
+    }
+}
+
+impl BinaryDecode for PackedByteBuf {
+    fn decode(decoder: &mut DecodedData) -> Result<Self, DecodeError> {
+        let len = decoder.take_u32()? as usize;
+        Ok(PackedByteBuf(decoder.take_bytes_ref(len)?.to_vec()))
+    }
+}
+
+impl BatchableResult for PackedByteBuf {}
@@ -25,6 +25,16 @@ pub(crate) const JSIDX_FALSE: u64 = JSIDX_OFFSET + 3;
 /// First usable heap ID. IDs below this are reserved for special values.
 pub(crate) const JSIDX_RESERVED: u64 = JSIDX_OFFSET + 4;
 
+/// Heap values carry the stamp of the heap they were created in above this bit. Every
+/// window has its own stamp, which changes when its webview reloads.
+///
+/// Heap IDs live below this bit. IDs are never reused, so one page can create at most
+/// 2^32 JS values (minus the reserved ones) before it reloads.
+const STAMP_SHIFT: u32 = 32;
+
+/// Mask selecting the heap ID from a stamped index.
+const HEAP_ID_MASK: u64 = (1 << STAMP_SHIFT) - 1;
+
 /// An opaque reference to a JavaScript heap object.
 ///
 /// This type is the wry-bindgen equivalent of wasm-bindgen's `JsValue`.
@@ -36,6 +46,33 @@ pub(crate) const JSIDX_RESERVED: u64 = JSIDX_OFFSET + 4;
 ///
 /// Unlike wasm-bindgen which runs in a single-threaded Wasm environment,
 /// this implementation uses the IPC protocol to communicate with JS.
+///
+/// # Numbers
+///
+/// Every Rust numeric type converts into a JS number, which is an `f64`. Integers
+/// with a magnitude up to 2^53 are represented exactly. Larger `i64`, `u64`,
+/// `i128` and `u128` values are rounded to the nearest representable `f64`, so
+/// `JsValue::from(2u64.pow(53) + 1)` holds the same number as `2u64.pow(53)`.
+///
+/// # Reloads
+///
+/// A value refers to the JS heap of the page it was created in. If the webview reloads
+/// or is recreated, that heap is gone and the value becomes stale: it passes to JS as
+/// `undefined`, clones to `undefined`, and dropping it does nothing. Check for this with
+/// [`JsValue::is_stale`].
+///
+/// # Windows
+///
+/// Each window has its own JS heap. A value used while another window's runtime is
+/// current, for example inside [`WindowHandle::run`], is stale there as well and acts as
+/// `undefined` instead of resolving to an unrelated object. Dropping it there frees it
+/// in its own window the next time that window runs.
+///
+/// The window is stored next to the heap ID, which leaves 32 bits for the ID. Heap IDs
+/// are never reused, so a page that creates more than 2^32 JS values without reloading
+/// panics.
+///
+/// [`WindowHandle::run`]: crate::wry::WindowHandle::run
 pub struct JsValue {
     #[doc(hidden)]
     pub idx: u64,
@@ -62,18 +99,63 @@ impl JsValue {
 
     /// Create a new JsValue from a heap ID.
     ///
-    /// This is called internally when decoding a value from JS.
+    /// This is called internally when decoding a value from JS. Heap values are stamped
+    /// with the heap of the current window and webview generation.
+    ///
+    /// # Panics
+    /// Panics if `id` does not fit below the stamp, which happens once a page has created
+    /// 2^32 JS values.
     #[inline]
     pub(crate) fn from_id(id: u64) -> Self {
-        Self { idx: id }
+        if id < JSIDX_RESERVED {
+            return Self { idx: id };
+        }
+        assert!(
+            id <= HEAP_ID_MASK,
+            "heap ID {id} is out of range: a page can create at most 2^32 JS values"
+        );
+        Self {
+            idx: (crate::batch::heap_stamp() << STAMP_SHIFT) | id,
+        }
     }
 
     /// Get the heap ID for this value.
     ///
-    /// This is used internally for encoding values to send to JS.
+    /// This is used internally for encoding values to send to JS. Stale values
+    /// resolve to `undefined`.
     #[inline]
     pub fn id(&self) -> u64 {
-        self.idx
+        if self.is_stale() {
+            return JSIDX_UNDEFINED;
+        }
+        self.idx & HEAP_ID_MASK
+    }
+
+    /// Check if this value belongs to a webview page that has since reloaded or been
+    /// recreated, or to a window other than the current one.
+    ///
+    /// Stale values do not refer to any JS object in the current heap. They act as
+    /// `undefined` and should be replaced by values from the new page, or only be used
+    /// inside the window they came from.
+    #[inline]
+    pub fn is_stale(&self) -> bool {
+        self.idx >= JSIDX_RESERVED && self.idx >> STAMP_SHIFT != crate::batch::heap_stamp()
+    }
+
+    /// Panic if this value refers to a JS heap slot that has already been dropped.
+    ///
+    /// This catches use-after-drop bugs where the raw heap index was copied out of a
+    /// `JsValue` and used after the original value was dropped. The check only runs
+    /// in debug builds and compiles to nothing in release builds.
+    #[inline]
+    pub fn debug_assert_alive(&self) {
+        #[cfg(debug_assertions)]
+        if self.idx >= JSIDX_RESERVED
+            && !self.is_stale()
+            && crate::batch::is_heap_id_released(self.id())
+        {
+            panic!("use of dropped JsValue #{}", self.idx);
+        }
     }
 
     /// Returns the value as f64 without type checking.
@@ -94,7 +176,7 @@ impl JsValue {
     /// Returns u32 for wasm-bindgen compatibility.
     #[inline]
     pub fn into_abi(self) -> u32 {
-        let id = self.idx;
+        let id = self.id();
         core::mem::forget(self);
         id as u32
     }
@@ -127,6 +209,24 @@ impl JsValue {
     pub fn from_f64(n: f64) -> JsValue {
         n.into()
     }
+
+    /// Creates a new, empty JS object (`{}`).
+    pub fn new_object() -> JsValue {
+        crate::JsObject::new().into()
+    }
+
+    /// Creates a new, empty JS array (`[]`).
+    pub fn new_array() -> JsValue {
+        crate::JsArray::new().into()
+    }
+
+    /// Creates a JS function that calls a Rust closure.
+    ///
+    /// This is shorthand for `Closure::new(f).into_js_value()`. The closure is never
+    /// freed, so it stays callable from JS for the lifetime of the runtime.
+    pub fn from_closure<M>(f: impl crate::encode::IntoJsFunction<M>) -> JsValue {
+        f.into_js_function()
+    }
 }
 
 impl Clone for JsValue {
@@ -137,8 +237,15 @@ impl Clone for JsValue {
             return JsValue { idx: self.idx };
         }
 
+        // The JS object is gone, so there is nothing to clone
+        if self.is_stale() {
+            return JsValue::UNDEFINED;
+        }
+
+        self.debug_assert_alive();
+
         // Clone the value on the JS heap
-        crate::js_helpers::js_clone_heap_ref(self.idx)
+        crate::js_helpers::js_clone_heap_ref(self.id())
     }
 }
 
@@ -150,8 +257,14 @@ impl Drop for JsValue {
             return;
         }
 
+        // The value lives in another window's heap, or in one wiped by a reload
+        if self.is_stale() {
+            crate::batch::queue_foreign_drop(self.idx >> STAMP_SHIFT, self.idx & HEAP_ID_MASK);
+            return;
+        }
+
         // Drop the value on the JS heap
-        crate::batch::queue_js_drop(self.idx);
+        crate::batch::queue_js_drop(self.id());
     }
 }
 
@@ -429,6 +542,20 @@ impl JsValue {
         crate::js_helpers::js_loose_eq(self, rhs)
     }
 
+    /// Structural deep equality, compared in a single call to JS.
+    ///
+    /// Objects and arrays are equal if they have the same prototype and their own
+    /// enumerable properties are deeply equal. Dates compare by time, `NaN` equals
+    /// itself and other values compare with `===`. Maps are equal if they hold the
+    /// same keys with deeply equal values, and sets if they hold the same items. Map
+    /// keys and set items are matched by identity, like `Map.prototype.has`.
+    ///
+    /// A value that contains a cycle is only deeply equal to itself. Comparing it with
+    /// any other value returns `false`, so the comparison always terminates.
+    pub fn deep_eq(&self, rhs: &JsValue) -> bool {
+        crate::js_helpers::js_deep_equal(self, rhs)
+    }
+
     /// Check if this value is a falsy value in JavaScript.
     pub fn is_falsy(&self) -> bool {
         crate::js_helpers::js_is_falsy(self)
@@ -464,6 +591,27 @@ impl JsValue {
         crate::js_helpers::js_is_bigint(self)
     }
 
+    /// Check if this value is an array, using `Array.isArray`.
+    pub fn is_array(&self) -> bool {
+        crate::js_helpers::js_is_array(self)
+    }
+
+    /// Check if this value is an `ArrayBuffer`.
+    pub fn is_array_buffer(&self) -> bool {
+        crate::js_helpers::js_is_array_buffer(self)
+    }
+
+    /// Check if this value is a `Date`.
+    pub fn is_date(&self) -> bool {
+        crate::js_helpers::js_is_date(self)
+    }
+
+    /// Check if this value is a typed array such as `Uint8Array`. A `DataView` is not
+    /// a typed array.
+    pub fn is_typed_array(&self) -> bool {
+        crate::js_helpers::js_is_typed_array(self)
+    }
+
     /// Check if this value is undefined.
     pub fn is_undefined(&self) -> bool {
         if self.idx == JSIDX_UNDEFINED {
@@ -490,39 +638,93 @@ impl JsValue {
         crate::js_helpers::js_in(self, obj)
     }
 
-    /// Get the value as a bool.
+    /// Check if this value is an instance of the constructor `ctor` (`self instanceof ctor`).
+    ///
+    /// Returns false if `ctor` is not a constructor.
+    pub fn instanceof_value(&self, ctor: &JsValue) -> bool {
+        crate::js_helpers::js_instanceof(self, ctor)
+    }
+
+    /// Get the value as a bool, or `None` if it is not a JS boolean.
+    ///
+    /// Like wasm-bindgen this does not coerce, so truthy values such as `1` return `None`.
     pub fn as_bool(&self) -> Option<bool> {
         match self.idx {
             JSIDX_TRUE => Some(true),
             JSIDX_FALSE => Some(false),
             idx if idx < JSIDX_RESERVED => None,
-            _ => {
-                // For heap values, check via JS
-                if crate::js_helpers::js_is_true(self) {
-                    Some(true)
-                } else if crate::js_helpers::js_is_false(self) {
-                    Some(false)
-                } else {
-                    None
-                }
-            }
+            // For heap values, check via JS
+            _ => crate::js_helpers::js_as_bool(self),
         }
     }
 
-    /// Get the value as an f64.
+    /// Get the value as an f64, or `None` if it is not a JS number.
+    ///
+    /// Numeric strings such as `"5"` return `None` instead of being parsed.
     pub fn as_f64(&self) -> Option<f64> {
         crate::js_helpers::js_as_f64(self)
     }
 
-    /// Get the value as a string.
+    /// Get the value as a string, or `None` if it is not a JS string.
+    ///
+    /// Other values are not converted with `String()`; use
+    /// [`as_debug_string`](JsValue::as_debug_string) for a printable form of any value.
     pub fn as_string(&self) -> Option<String> {
         crate::js_helpers::js_as_string(self)
     }
 
+    /// Borrow the value as a [`JsArray`](crate::JsArray) if it is an array.
+    ///
+    /// This is shorthand for `dyn_ref::<JsArray>()`, so it costs one `instanceof` check.
+    pub fn as_array(&self) -> Option<&crate::JsArray> {
+        crate::JsCast::dyn_ref(self)
+    }
+
+    /// Borrow the value as a [`JsObject`](crate::JsObject) if it is an object.
+    ///
+    /// This is shorthand for `dyn_ref::<JsObject>()`. Arrays and functions are objects
+    /// too, so they also succeed.
+    pub fn as_object(&self) -> Option<&crate::JsObject> {
+        crate::JsCast::dyn_ref(self)
+    }
+
     /// Get a debug string representation of the value.
     pub fn as_debug_string(&self) -> String {
         crate::js_helpers::js_debug_string(self)
     }
+
+    /// Get a token that identifies the underlying JS value.
+    ///
+    /// Every handle to the same JS object returns the same token, even though each
+    /// clone has its own heap id, so the token can key Rust-side identity maps. The
+    /// token is assigned lazily in JS and held weakly, so it does not keep the object
+    /// alive. Primitives are identified by value: their token is a hash of the value
+    /// computed on every call, so equal strings share a token and nothing is stored.
+    ///
+    /// Tokens are only unique for objects. Two different primitives can share a token,
+    /// either because their hashes collide or because they print the same, like two
+    /// symbols with the same description. Compare the values themselves when a
+    /// primitive token matches.
+    pub fn identity_token(&self) -> u64 {
+        crate::js_helpers::js_identity_token(self)
+    }
+
+    /// Call this value as a constructor, like `new Ctor(...args)` in JS.
+    ///
+    /// This performs `Reflect.construct(self, args)`. Returns the error thrown in JS if
+    /// the value is not a constructor or the constructor throws.
+    pub fn construct(&self, args: &[JsValue]) -> Result<JsValue, JsValue> {
+        crate::js_helpers::js_construct(self, args)
+    }
+
+    /// Get an iterator over this value if it is iterable.
+    ///
+    /// Returns `None` instead of throwing when the value has no `Symbol.iterator`
+    /// method, so code can branch on iterability. Strings, arrays, maps and sets
+    /// are all iterable.
+    pub fn try_iter(&self) -> Option<crate::JsIterator> {
+        crate::js_helpers::js_try_iter(self)
+    }
 }
 
 // Operator trait implementations for JsValue references
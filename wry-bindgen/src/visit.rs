@@ -0,0 +1,84 @@
+//! Dispatch on the kind of an arbitrary JS value.
+
+use alloc::string::String;
+
+use crate::value::{JSIDX_FALSE, JSIDX_NULL, JSIDX_TRUE, JSIDX_UNDEFINED};
+use crate::{Either, JsArray, JsCast, JsFunction, JsObject, JsValue};
+
+/// A visitor over the kinds of value a [`JsValue`] can hold.
+///
+/// Pass an implementation to [`JsValue::visit`] to handle values of unknown shape, for
+/// example in a serializer or formatter. Exactly one method is called per visit.
+pub trait JsValueVisitor {
+    /// The result of visiting a value
+    type Output;
+
+    /// Called for `undefined`.
+    fn visit_undefined(self) -> Self::Output;
+
+    /// Called for `null`.
+    fn visit_null(self) -> Self::Output;
+
+    /// Called for booleans.
+    fn visit_bool(self, value: bool) -> Self::Output;
+
+    /// Called for numbers.
+    fn visit_number(self, value: f64) -> Self::Output;
+
+    /// Called for strings.
+    fn visit_string(self, value: String) -> Self::Output;
+
+    /// Called for arrays, as checked by `Array.isArray`.
+    fn visit_array(self, value: &JsArray) -> Self::Output;
+
+    /// Called for functions, including classes.
+    fn visit_function(self, value: &JsFunction) -> Self::Output;
+
+    /// Called for any other object.
+    fn visit_object(self, value: &JsObject) -> Self::Output;
+
+    /// Called for symbols and bigints.
+    fn visit_other(self, value: &JsValue) -> Self::Output;
+}
+
+// Kinds reported by the `value_kind` JS helper
+const KIND_UNDEFINED: u8 = 0;
+const KIND_NULL: u8 = 1;
+const KIND_BOOL: u8 = 2;
+const KIND_NUMBER: u8 = 3;
+const KIND_STRING: u8 = 4;
+const KIND_ARRAY: u8 = 5;
+const KIND_FUNCTION: u8 = 6;
+const KIND_OBJECT: u8 = 7;
+const KIND_OTHER: u8 = 8;
+
+impl JsValue {
+    /// Call the visitor method matching the kind of this value.
+    ///
+    /// The kind and any primitive payload are resolved in a single IPC call, so a
+    /// string or number does not need a second round trip to read. Constants such as
+    /// `undefined` and `true` are resolved without any IPC at all.
+    pub fn visit<V: JsValueVisitor>(&self, visitor: V) -> V::Output {
+        match self.idx {
+            JSIDX_UNDEFINED => return visitor.visit_undefined(),
+            JSIDX_NULL => return visitor.visit_null(),
+            JSIDX_TRUE => return visitor.visit_bool(true),
+            JSIDX_FALSE => return visitor.visit_bool(false),
+            _ => {}
+        }
+
+        let (kind, payload) = crate::js_helpers::js_value_kind(self);
+        match (kind, payload) {
+            (KIND_UNDEFINED, _) => visitor.visit_undefined(),
+            (KIND_NULL, _) => visitor.visit_null(),
+            (KIND_BOOL, Either::Left(value)) => visitor.visit_bool(value != 0.0),
+            (KIND_NUMBER, Either::Left(value)) => visitor.visit_number(value),
+            (KIND_STRING, Either::Right(value)) => visitor.visit_string(value),
+            (KIND_ARRAY, _) => visitor.visit_array(self.unchecked_ref()),
+            (KIND_FUNCTION, _) => visitor.visit_function(self.unchecked_ref()),
+            (KIND_OBJECT, _) => visitor.visit_object(self.unchecked_ref()),
+            (KIND_OTHER, _) => visitor.visit_other(self),
+            (kind, _) => panic!("JS reported an unexpected value kind {kind}"),
+        }
+    }
+}
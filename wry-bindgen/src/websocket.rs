@@ -0,0 +1,385 @@
+//! A minimal WebSocket server for [`Transport::WebSocket`](crate::wry::Transport::WebSocket).
+//!
+//! Only what the transport needs is implemented: the opening handshake, binary messages
+//! split over any number of frames, ping and close. Messages carry exactly the bytes of
+//! an [`IPCMessage`], the same framing the custom protocol sends base64 encoded.
+//!
+//! The server only listens on `127.0.0.1`. Each [`WryBindgen`](crate::wry::WryBindgen)
+//! gets a random token, and connections whose path does not start with it are refused,
+//! so other local processes cannot talk to the app.
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::Mutex;
+
+use base64::Engine;
+
+use crate::ipc::IPCMessage;
+use crate::runtime::WryBindgenEvent;
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+/// The GUID every server appends to the client's key, from RFC 6455
+const HANDSHAKE_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The longest opening handshake accepted from a client
+const MAX_HANDSHAKE_LEN: usize = 16 * 1024;
+
+/// Sends events from the socket threads to the thread that owns the `WryBindgen`
+pub(crate) type EventProxy = Arc<dyn Fn(WryBindgenEvent) + Send + Sync>;
+
+/// A WebSocket server accepting connections from the app's webviews.
+pub(crate) struct SocketServer {
+    address: SocketAddr,
+    token: String,
+    closed: Arc<AtomicBool>,
+}
+
+impl SocketServer {
+    /// Listen on `127.0.0.1:port` and forward messages from webviews to `proxy`.
+    ///
+    /// Port 0 picks a free port. Messages larger than `max_message_size` bytes close
+    /// the connection.
+    pub(crate) fn start(
+        port: u16,
+        proxy: EventProxy,
+        max_message_size: Arc<AtomicUsize>,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        let address = listener.local_addr()?;
+        let token = random_token();
+        let closed = Arc::new(AtomicBool::new(false));
+
+        let accept = {
+            let token = token.clone();
+            let closed = closed.clone();
+            move || {
+                static CONNECTIONS: AtomicU64 = AtomicU64::new(0);
+                for stream in listener.incoming() {
+                    if closed.load(Ordering::Acquire) {
+                        break;
+                    }
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+                    let connection = CONNECTIONS.fetch_add(1, Ordering::Relaxed);
+                    let token = token.clone();
+                    let proxy = proxy.clone();
+                    let max_message_size = max_message_size.clone();
+                    let serve = move || {
+                        if let Err(err) =
+                            serve_connection(stream, &token, connection, &proxy, &max_message_size)
+                        {
+                            log::debug!("WebSocket connection {connection} ended: {err}");
+                        }
+                    };
+                    if let Err(err) = std::thread::Builder::new()
+                        .name("wry-bindgen-socket".into())
+                        .spawn(serve)
+                    {
+                        log::warn!("Failed to start a WebSocket connection thread: {err}");
+                    }
+                }
+            }
+        };
+        std::thread::Builder::new()
+            .name("wry-bindgen-socket-accept".into())
+            .spawn(accept)?;
+
+        Ok(Self {
+            address,
+            token,
+            closed,
+        })
+    }
+
+    /// The URL the webview with the given id connects to.
+    pub(crate) fn url(&self, webview_id: u64) -> String {
+        format!("ws://{}/{}/{webview_id}", self.address, self.token)
+    }
+}
+
+impl Drop for SocketServer {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::Release);
+        // Wake the accept thread so it sees the flag and exits
+        let _ = TcpStream::connect(self.address);
+    }
+}
+
+/// The sending half of a webview's WebSocket connection.
+#[derive(Clone)]
+pub(crate) struct SocketWriter {
+    stream: Arc<Mutex<TcpStream>>,
+    connection: u64,
+}
+
+impl SocketWriter {
+    /// Send `payload` as one binary message.
+    pub(crate) fn send(&self, payload: &[u8]) -> io::Result<()> {
+        let mut stream = self.stream.lock().unwrap();
+        write_frame(&mut *stream, OP_BINARY, payload)
+    }
+
+    /// A number identifying the connection this writer sends over.
+    pub(crate) fn connection(&self) -> u64 {
+        self.connection
+    }
+}
+
+impl core::fmt::Debug for SocketWriter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SocketWriter")
+            .field("connection", &self.connection)
+            .finish()
+    }
+}
+
+fn serve_connection(
+    stream: TcpStream,
+    token: &str,
+    connection: u64,
+    proxy: &EventProxy,
+    max_message_size: &AtomicUsize,
+) -> io::Result<()> {
+    let mut reader = stream.try_clone()?;
+    let (path, key) = read_handshake(&mut reader)?;
+    let webview_id = path
+        .strip_prefix('/')
+        .and_then(|path| path.split_once('/'))
+        .filter(|(path_token, _)| *path_token == token)
+        .and_then(|(_, id)| id.parse::<u64>().ok());
+    let Some(webview_id) = webview_id else {
+        (&stream).write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n")?;
+        return Err(invalid_data("refused a connection without the app's token"));
+    };
+
+    let writer = SocketWriter {
+        stream: Arc::new(Mutex::new(stream)),
+        connection,
+    };
+    {
+        // Hold the lock until the handshake is answered so the app cannot send a frame
+        // before it, and register the socket before the client can see the answer
+        let mut stream = writer.stream.lock().unwrap();
+        proxy(WryBindgenEvent::socket_connected(
+            webview_id,
+            writer.clone(),
+        ));
+        write!(
+            stream,
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(&key)
+        )?;
+    }
+
+    let result = read_messages(
+        &mut reader,
+        &writer,
+        |message| {
+            proxy(WryBindgenEvent::socket_message(
+                webview_id,
+                IPCMessage::new(message),
+            ))
+        },
+        max_message_size,
+    );
+    proxy(WryBindgenEvent::socket_closed(webview_id, connection));
+    result
+}
+
+/// Read messages until the client closes the connection.
+fn read_messages(
+    reader: &mut impl Read,
+    writer: &SocketWriter,
+    mut on_message: impl FnMut(Vec<u8>),
+    max_message_size: &AtomicUsize,
+) -> io::Result<()> {
+    let mut message = Vec::new();
+    loop {
+        let limit = max_message_size.load(Ordering::Relaxed);
+        let (fin, opcode, payload) = read_frame(reader, limit.saturating_sub(message.len()))?;
+        match opcode {
+            OP_BINARY | OP_CONTINUATION => {
+                message.extend_from_slice(&payload);
+                if fin {
+                    on_message(core::mem::take(&mut message));
+                }
+            }
+            OP_PING => {
+                let mut stream = writer.stream.lock().unwrap();
+                write_frame(&mut *stream, OP_PONG, &payload)?;
+            }
+            OP_PONG => {}
+            OP_CLOSE => {
+                let mut stream = writer.stream.lock().unwrap();
+                let _ = write_frame(&mut *stream, OP_CLOSE, &[]);
+                return Ok(());
+            }
+            _ => return Err(invalid_data("only binary messages are accepted")),
+        }
+    }
+}
+
+/// Read the client's opening handshake and return the requested path and key.
+fn read_handshake(reader: &mut impl Read) -> io::Result<(String, String)> {
+    let mut head = Vec::new();
+    let mut byte = [0u8];
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_HANDSHAKE_LEN {
+            return Err(invalid_data("handshake too long"));
+        }
+        reader.read_exact(&mut byte)?;
+        head.push(byte[0]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    let mut lines = head.split("\r\n");
+    let path = lines
+        .next()
+        .and_then(|line| line.strip_prefix("GET "))
+        .and_then(|line| line.split(' ').next())
+        .ok_or_else(|| invalid_data("expected a GET request"))?;
+    let key = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("sec-websocket-key"))
+        .map(|(_, value)| value.trim())
+        .ok_or_else(|| invalid_data("missing Sec-WebSocket-Key"))?;
+    Ok((path.into(), key.into()))
+}
+
+/// Read one frame, returning whether it is the last of its message, its opcode and
+/// its unmasked payload.
+fn read_frame(reader: &mut impl Read, limit: usize) -> io::Result<(bool, u8, Vec<u8>)> {
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head)?;
+    let fin = head[0] & 0x80 != 0;
+    let opcode = head[0] & 0x0F;
+    if head[1] & 0x80 == 0 {
+        return Err(invalid_data("frames from the client must be masked"));
+    }
+    let len = match head[1] & 0x7F {
+        126 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len)?;
+            u64::from(u16::from_be_bytes(len))
+        }
+        127 => {
+            let mut len = [0u8; 8];
+            reader.read_exact(&mut len)?;
+            u64::from_be_bytes(len)
+        }
+        len => u64::from(len),
+    };
+    let len = usize::try_from(len)
+        .ok()
+        .filter(|len| *len <= limit)
+        .ok_or_else(|| invalid_data("message larger than the message size limit"))?;
+    let mut mask = [0u8; 4];
+    reader.read_exact(&mut mask)?;
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload)?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+    Ok((fin, opcode, payload))
+}
+
+/// Write `payload` as a single unmasked frame.
+fn write_frame(writer: &mut impl Write, opcode: u8, payload: &[u8]) -> io::Result<()> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode);
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= usize::from(u16::MAX) => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame)
+}
+
+/// The `Sec-WebSocket-Accept` value for a client's `Sec-WebSocket-Key`.
+fn accept_key(key: &str) -> String {
+    let digest = sha1(format!("{key}{HANDSHAKE_GUID}").as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(digest)
+}
+
+/// A token that is hard to guess, from the randomly seeded std hasher.
+fn random_token() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+
+    let high = RandomState::new().hash_one(0u8);
+    let low = RandomState::new().hash_one(1u8);
+    format!("{high:016x}{low:016x}")
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// SHA-1, which the handshake needs to prove the server understood the request.
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    let bit_len = (data.len() as u64).wrapping_mul(8);
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut words = [0u32; 80];
+        for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in words.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let next = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = next;
+        }
+        for (value, add) in state.iter_mut().zip([a, b, c, d, e]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (bytes, value) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
@@ -5,23 +5,29 @@
 //! to enable wry-bindgen functionality.
 
 use alloc::boxed::Box;
-use alloc::rc::Rc;
+use alloc::rc::{Rc, Weak};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use base64::Engine;
 use core::cell::RefCell;
 use core::future::poll_fn;
 use core::pin::{Pin, pin};
+use core::sync::atomic::{AtomicUsize, Ordering};
 use futures_util::FutureExt;
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use http::Response;
 
-use crate::batch::{Runtime, in_runtime};
+use crate::batch::{Runtime, current_webview_id, in_runtime, with_runtime};
+#[cfg(feature = "test-harness")]
+use crate::console_capture::{ConsoleLevel, ConsoleSink};
 use crate::function_registry::FUNCTION_REGISTRY;
-use crate::ipc::{DecodedVariant, IPCMessage, MessageType, decode_data};
-use crate::runtime::{AppEventVariant, IPCSenders, WryBindgenEvent, WryIPC, handle_callbacks};
+use crate::ipc::{DecodeError, DecodedVariant, IPCMessage, MessageType, decode_data};
+use crate::runtime::{
+    AppEventVariant, IPCSenders, WryBindgenEvent, WryIPC, handle_callbacks, run_spawned_tasks,
+};
+use crate::websocket::{SocketServer, SocketWriter};
 
 pub trait ImplWryBindgenResponder {
     fn respond(self: Box<Self>, response: Response<Vec<u8>>);
@@ -68,14 +74,137 @@ impl WryBindgenResponder {
     }
 }
 
-/// Decode request data from the dioxus-data header.
-fn decode_request_data(request: &http::Request<Vec<u8>>) -> Option<IPCMessage> {
-    if let Some(header_value) = request.headers().get("dioxus-data") {
-        return decode_data(header_value.as_bytes());
+/// The default maximum size of a decoded IPC message sent from JS: 64 MiB.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// How Rust sends calls to JS and JS answers them.
+///
+/// Calls from JS into Rust always go through the custom protocol handler as
+/// synchronous requests, because JS has to wait for their results. The transport only
+/// changes how calls that Rust starts reach the webview.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Transport {
+    /// Run each call with `evaluate_script`, which passes it to JS as base64 inside a
+    /// script. Works everywhere the custom protocol does.
+    #[default]
+    CustomProtocol,
+    /// Send calls as binary WebSocket messages, skipping script evaluation and base64.
+    ///
+    /// The server listens on `127.0.0.1` at `port`, or on a free port if it is 0, and
+    /// refuses connections that do not carry the app's random token. Webviews connect
+    /// when the initialization script runs and use `evaluate_script` until they have.
+    /// The page must be allowed to open `ws://127.0.0.1` connections.
+    WebSocket { port: u16 },
+}
+
+/// The URL prefixes a webview uses for `protocol` and `host` on each platform.
+///
+/// Custom protocols are served as `{protocol}://{host}` on macOS and Linux,
+/// `https://{protocol}.{host}` on Android and `http://{protocol}.{host}` on Windows.
+/// By default the host is `index.html`. Pass the result to
+/// [`WryBindgen::set_url_prefixes`] to serve the app from a different host.
+pub fn url_prefixes(protocol: &str, host: &str) -> [String; 3] {
+    [
+        format!("{protocol}://{host}"),
+        format!("https://{protocol}.{host}"),
+        format!("http://{protocol}.{host}"),
+    ]
+}
+
+/// IPC settings shared between [`WryBindgen`] and its protocol handlers.
+struct IpcConfig {
+    /// The largest decoded message accepted from JS, in bytes, shared with the socket server
+    max_message_size: Arc<AtomicUsize>,
+    /// Called when a message from JS cannot be decoded
+    on_ipc_error: RefCell<Option<Rc<dyn Fn(&DecodeError)>>>,
+    /// Receives console output forwarded from JS
+    #[cfg(feature = "test-harness")]
+    console_sink: RefCell<Option<Rc<dyn ConsoleSink>>>,
+    /// URL prefixes stripped before matching `__wbg__/` paths, or `None` for the
+    /// `index.html` defaults of the request's protocol
+    url_prefixes: RefCell<Option<Vec<String>>>,
+    /// The WebSocket server webviews connect to, with [`Transport::WebSocket`]
+    socket: Option<SocketServer>,
+}
+
+impl IpcConfig {
+    fn new(max_message_size: Arc<AtomicUsize>, socket: Option<SocketServer>) -> Self {
+        Self {
+            max_message_size,
+            on_ipc_error: RefCell::new(None),
+            #[cfg(feature = "test-harness")]
+            console_sink: RefCell::new(None),
+            url_prefixes: RefCell::new(None),
+            socket,
+        }
     }
-    None
+
+    fn max_message_size(&self) -> usize {
+        self.max_message_size.load(Ordering::Relaxed)
+    }
+
+    /// Strip the first matching URL prefix from `uri`, or return it unchanged.
+    fn strip_url_prefix<'a>(&self, protocol: &str, uri: &'a str) -> &'a str {
+        let strip = |prefixes: &[String]| {
+            prefixes
+                .iter()
+                .find_map(|prefix| uri.strip_prefix(prefix.as_str()))
+        };
+        let stripped = match &*self.url_prefixes.borrow() {
+            Some(prefixes) => strip(prefixes),
+            None => strip(&url_prefixes(protocol, "index.html")),
+        };
+        stripped.unwrap_or(uri)
+    }
+
+    fn report_error(&self, error: &DecodeError) {
+        // Clone the handler out so it can replace itself without a double borrow
+        let handler = self.on_ipc_error.borrow().clone();
+        match handler {
+            Some(handler) => handler(error),
+            None => log::warn!("Rejected a malformed IPC request: {error}"),
+        }
+    }
+
+    #[cfg(feature = "test-harness")]
+    fn write_console(&self, level: ConsoleLevel, message: &str) {
+        // Clone the sink out so the borrow is not held while it runs
+        let sink = self.console_sink.borrow().clone();
+        if let Some(sink) = sink {
+            sink.write(level, message);
+        }
+    }
+}
+
+/// Decode a console message forwarded from JS.
+#[cfg(feature = "test-harness")]
+fn decode_console_message(request: &http::Request<Vec<u8>>) -> Option<String> {
+    let header_value = request.headers().get("dioxus-data")?;
+    let engine = base64::engine::general_purpose::STANDARD;
+    let bytes = engine.decode(header_value.as_bytes()).ok()?;
+    Some(String::from_utf8_lossy(&bytes).into_owned())
 }
 
+/// Decode request data from the dioxus-data header, along with its message type.
+fn decode_request_data(
+    request: &http::Request<Vec<u8>>,
+    max_message_size: usize,
+) -> Result<(MessageType, IPCMessage), DecodeError> {
+    let Some(header_value) = request.headers().get("dioxus-data") else {
+        return Err(DecodeError::Custom(
+            "missing dioxus-data header".to_string(),
+        ));
+    };
+    let msg = decode_data(header_value.as_bytes(), max_message_size)?;
+    // Reading the type also validates the header, so a truncated message is rejected here
+    let msg_type = msg.ty()?;
+    Ok((msg_type, msg))
+}
+
+/// The script that makes a loaded webview forward its console output to Rust.
+#[cfg(feature = "test-harness")]
+const ENABLE_CONSOLE_SINK: &str = "window.enableConsoleSink()";
+
 /// Tracks the loading state of the webview.
 enum WebviewLoadingState {
     /// Webview is still loading, messages are queued.
@@ -103,6 +232,10 @@ struct WebviewState {
     loading_state: WebviewLoadingState,
     // A function that evaluates scripts in the webview
     evaluate_script: Box<dyn FnMut(&str)>,
+    // Whether the app future has completed. Requests from JS are answered immediately once it has.
+    app_finished: bool,
+    // The webview's WebSocket connection, with the WebSocket transport once it connected
+    socket: Option<SocketWriter>,
 }
 
 impl WebviewState {
@@ -115,6 +248,8 @@ impl WebviewState {
             sender,
             loading_state: WebviewLoadingState::default(),
             evaluate_script: Box::new(evaluate_script),
+            app_finished: false,
+            socket: None,
         }
     }
 
@@ -156,9 +291,33 @@ impl WebviewState {
     fn evaluate_script(&mut self, script: &str) {
         (self.evaluate_script)(script);
     }
+
+    /// Forget the page after the webview was recreated or reloaded.
+    ///
+    /// The old page can no longer receive a response or answer a call, so the calls the
+    /// app thread is blocked on fail with [`DecodeError::WebviewRecreated`].
+    fn forget_page(&mut self) {
+        self.ongoing_request = None;
+        self.socket = None;
+        let mut unanswered = core::mem::take(&mut self.pending_js_evaluates);
+        if let WebviewLoadingState::Pending { queued } = &mut self.loading_state {
+            // Queued calls were never sent, the app thread waits for the last of them
+            unanswered += queued
+                .drain(..)
+                .filter(|msg| matches!(msg.ty(), Ok(MessageType::Evaluate)))
+                .count();
+        }
+        self.pending_rust_evaluates = 0;
+        // Bump the generation first so the app thread sees the empty responses as failures
+        self.sender.webview_recreated();
+        for _ in 0..unanswered {
+            self.sender
+                .start_send(MessageType::Respond, IPCMessage::new_respond(|_| {}));
+        }
+    }
 }
 
-fn unique_id() -> u64 {
+pub(crate) fn unique_id() -> u64 {
     use core::sync::atomic::{AtomicU64, Ordering};
     static COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -193,16 +352,20 @@ impl PreparedApp {
 pub struct ProtocolHandler {
     id: u64,
     webview: Rc<RefCell<HashMap<u64, WebviewState>>>,
+    config: Rc<IpcConfig>,
 }
 
 impl ProtocolHandler {
     /// Create a protocol handler closure suitable for `WebViewBuilder::with_asynchronous_custom_protocol`.
     ///
     /// The returned closure handles this subset of "{protocol}://" requests:
-    /// - "/__wbg__/initialized" - signals webview loaded
+    /// - "/__wbg__/initialized" - signals webview loaded, or reloaded if it had loaded before
     /// - "/__wbg__/snippets/{path}" - serves inline JS modules
     /// - "/__wbg__/init.js" - serves the initialization script
     /// - "/__wbg__/handler" - main IPC endpoint
+    /// - "/__wbg__/transport" - the WebSocket URL to connect to, or an empty body
+    /// - "/__wbg__/console/{level}" - console output forwarded to the console sink, with
+    ///   the `test-harness` feature
     ///
     /// # Arguments
     /// * `protocol` - The protocol scheme (e.g., "wry")
@@ -220,16 +383,8 @@ impl ProtocolHandler {
         let webviews = &self.webview;
         let webview_id = self.id;
 
-        let protocol_prefix = format!("{protocol}://index.html");
-        let android_prefix = format!("https://{protocol}.index.html");
-        let windows_prefix = format!("http://{protocol}.index.html");
-
         let uri = request.uri().to_string();
-        let real_path = uri
-            .strip_prefix(&protocol_prefix)
-            .or_else(|| uri.strip_prefix(&windows_prefix))
-            .or_else(|| uri.strip_prefix(&android_prefix))
-            .unwrap_or(&uri);
+        let real_path = self.config.strip_url_prefix(protocol, &uri);
         let real_path = real_path.trim_matches('/');
 
         let Some(path_without_wbg) = real_path.strip_prefix("__wbg__/") else {
@@ -250,17 +405,57 @@ impl ProtocolHandler {
 
         if path_without_wbg == "init.js" {
             let responder = responder.into();
-            responder.respond(module_response(&init_script()));
+            responder.respond(module_response(&initialization_script()));
             return None;
         }
 
         if path_without_wbg == "initialized" {
-            proxy(WryBindgenEvent::webview_loaded(webview_id));
+            // A webview that already loaded once has navigated or reloaded
+            let reloaded = webviews.borrow().get(&webview_id).is_some_and(|webview| {
+                matches!(webview.loading_state, WebviewLoadingState::Loaded)
+            });
+            if reloaded {
+                proxy(WryBindgenEvent::webview_reloaded(webview_id));
+            } else {
+                proxy(WryBindgenEvent::webview_loaded(webview_id));
+            }
             let responder = responder.into();
             responder.respond(blank_response());
             return None;
         }
 
+        if path_without_wbg == "transport" {
+            let responder = responder.into();
+            let url = match &self.config.socket {
+                Some(socket) => socket.url(webview_id),
+                None => String::new(),
+            };
+            responder.respond(
+                http::Response::builder()
+                    .status(200)
+                    .header("Content-Type", "text/plain")
+                    .body(url.into_bytes())
+                    .expect("Failed to build response"),
+            );
+            return None;
+        }
+
+        #[cfg(feature = "test-harness")]
+        if let Some(level) = path_without_wbg.strip_prefix("console/") {
+            let responder = responder.into();
+            match (
+                ConsoleLevel::from_name(level),
+                decode_console_message(request),
+            ) {
+                (Some(level), Some(message)) => {
+                    self.config.write_console(level, &message);
+                    responder.respond(blank_response());
+                }
+                _ => responder.respond(error_response()),
+            }
+            return None;
+        }
+
         // Js sent us either an Evaluate or Respond message
         if path_without_wbg == "handler" {
             let responder = responder.into();
@@ -269,11 +464,20 @@ impl ProtocolHandler {
                 responder.respond(error_response());
                 return None;
             };
-            let Some(msg) = decode_request_data(request) else {
-                responder.respond(error_response());
+            // Nobody is left to answer the request, so unblock the webview right away
+            if webview_state.app_finished {
+                responder.respond(shutdown_response());
                 return None;
+            }
+            let (msg_type, msg) = match decode_request_data(request, self.config.max_message_size())
+            {
+                Ok(decoded) => decoded,
+                Err(err) => {
+                    self.config.report_error(&err);
+                    responder.respond(error_response());
+                    return None;
+                }
             };
-            let msg_type = msg.ty().unwrap();
             match msg_type {
                 // New call from JS - save responder and wait for the js application thread to respond
                 MessageType::Evaluate => {
@@ -295,7 +499,7 @@ impl ProtocolHandler {
                     }
                 }
             }
-            webview_state.sender.start_send(msg);
+            webview_state.sender.start_send(msg_type, msg);
             return None;
         }
 
@@ -306,11 +510,21 @@ impl ProtocolHandler {
 /// Get the initialization script that must be evaluated in the webview.
 ///
 /// This script sets up the JavaScript function registry and IPC infrastructure.
-fn init_script() -> String {
+/// It is served from "/__wbg__/init.js", but it can also be registered with
+/// `WebViewBuilder::with_initialization_script` so it runs before any of the
+/// page's own scripts instead of racing with them after navigation.
+///
+/// Running the script again in the same page does nothing, so it is safe to both
+/// register it and load "/__wbg__/init.js".
+pub fn initialization_script() -> String {
     /// The script you need to include in the initialization of your webview.
     const INITIALIZATION_SCRIPT: &str = include_str!("./js/main.js");
+    /// Set by the first run so later runs keep the existing heap and registry
+    const INITIALIZED_FLAG: &str = "window.__wbg_initialized";
     let collect_functions = FUNCTION_REGISTRY.script();
-    format!("{INITIALIZATION_SCRIPT}\n{collect_functions}")
+    format!(
+        "if (!{INITIALIZED_FLAG}) {{\n{INITIALIZED_FLAG} = true;\n{INITIALIZATION_SCRIPT}\n{collect_functions}\n}}\n"
+    )
 }
 
 /// Reusable wry-bindgen state for integrating with existing wry applications.
@@ -343,14 +557,84 @@ pub struct WryBindgen {
     event_loop_proxy: Arc<dyn Fn(WryBindgenEvent) + Send + Sync>,
     // State that is unique to each webview
     webview: Rc<RefCell<HashMap<u64, WebviewState>>>,
+    // IPC settings shared with every protocol handler
+    config: Rc<IpcConfig>,
 }
 
 impl WryBindgen {
-    /// Create a new WryBindgen instance.
+    /// Create a new WryBindgen instance that uses [`Transport::CustomProtocol`].
     pub fn new(event_loop_proxy: impl Fn(WryBindgenEvent) + Send + Sync + 'static) -> Self {
-        Self {
-            event_loop_proxy: Arc::new(event_loop_proxy),
+        Self::new_with_transport(event_loop_proxy, Transport::CustomProtocol)
+            .expect("the custom protocol transport does not open any sockets")
+    }
+
+    /// Create a new WryBindgen instance that sends calls to JS over `transport`.
+    ///
+    /// Fails if the WebSocket server cannot listen on the requested port.
+    pub fn new_with_transport(
+        event_loop_proxy: impl Fn(WryBindgenEvent) + Send + Sync + 'static,
+        transport: Transport,
+    ) -> std::io::Result<Self> {
+        let event_loop_proxy: Arc<dyn Fn(WryBindgenEvent) + Send + Sync> =
+            Arc::new(event_loop_proxy);
+        let max_message_size = Arc::new(AtomicUsize::new(DEFAULT_MAX_MESSAGE_SIZE));
+        let socket = match transport {
+            Transport::CustomProtocol => None,
+            Transport::WebSocket { port } => Some(SocketServer::start(
+                port,
+                event_loop_proxy.clone(),
+                max_message_size.clone(),
+            )?),
+        };
+        Ok(Self {
+            event_loop_proxy,
             webview: Rc::new(RefCell::new(HashMap::new())),
+            config: Rc::new(IpcConfig::new(max_message_size, socket)),
+        })
+    }
+
+    /// Set the largest decoded message, in bytes, that JS may send to Rust.
+    ///
+    /// Larger messages are rejected with an error response before they are decoded,
+    /// so a buggy or malicious page cannot make the Rust process allocate an arbitrary
+    /// amount of memory. Defaults to [`DEFAULT_MAX_MESSAGE_SIZE`].
+    pub fn set_max_message_size(&self, bytes: usize) {
+        self.config.max_message_size.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Set the URL prefixes the webview's pages are served under.
+    ///
+    /// Requests are matched against the first prefix they start with, and the rest of the
+    /// path must start with `__wbg__/` to be handled by wry-bindgen. Defaults to
+    /// [`url_prefixes`] with the `index.html` host for the protocol of each request. Use
+    /// `url_prefixes("wry", "app")` to serve the app from `wry://app/` instead.
+    pub fn set_url_prefixes(&self, prefixes: impl IntoIterator<Item = impl Into<String>>) {
+        let prefixes = prefixes.into_iter().map(Into::into).collect();
+        *self.config.url_prefixes.borrow_mut() = Some(prefixes);
+    }
+
+    /// Set a handler that is called whenever a message from JS is rejected or cannot be decoded.
+    ///
+    /// Without a handler, the error is logged at warn level through the `log` crate.
+    pub fn set_on_ipc_error(&self, handler: impl Fn(&DecodeError) + 'static) {
+        *self.config.on_ipc_error.borrow_mut() = Some(Rc::new(handler));
+    }
+
+    /// Forward the webview's console output to `sink`.
+    ///
+    /// This is intended for tests that need to assert on logged output. Once a sink is
+    /// installed, every console call makes a blocking round-trip to Rust, so avoid it in
+    /// production. Webviews start forwarding once they have loaded, so output logged
+    /// while the page is still loading is not captured.
+    ///
+    /// This is only available with the `test-harness` feature.
+    #[cfg(feature = "test-harness")]
+    pub fn set_console_sink(&self, sink: impl ConsoleSink + 'static) {
+        *self.config.console_sink.borrow_mut() = Some(Rc::new(sink));
+        for webview_state in self.webview.borrow_mut().values_mut() {
+            if let WebviewLoadingState::Loaded = webview_state.loading_state {
+                webview_state.evaluate_script(ENABLE_CONSOLE_SINK);
+            }
         }
     }
 
@@ -374,6 +658,7 @@ impl WryBindgen {
             webview_id,
             bindgen: self,
             ipc,
+            attached: Vec::new(),
         }
     }
 
@@ -395,6 +680,10 @@ impl WryBindgen {
                 let Some(webview_state) = state.get_mut(&id) else {
                     return;
                 };
+                #[cfg(feature = "test-harness")]
+                if self.config.console_sink.borrow().is_some() {
+                    webview_state.evaluate_script(ENABLE_CONSOLE_SINK);
+                }
                 if let WebviewLoadingState::Pending { queued } = std::mem::replace(
                     &mut webview_state.loading_state,
                     WebviewLoadingState::Loaded,
@@ -404,9 +693,116 @@ impl WryBindgen {
                     }
                 }
             }
+            AppEventVariant::WebviewReloaded => {
+                log::debug!("Webview {id} reloaded, JS values from the old page are now stale");
+                let mut state = self.webview.borrow_mut();
+                let Some(webview_state) = state.get_mut(&id) else {
+                    return;
+                };
+                #[cfg(feature = "test-harness")]
+                if self.config.console_sink.borrow().is_some() {
+                    webview_state.evaluate_script(ENABLE_CONSOLE_SINK);
+                }
+                webview_state.forget_page();
+            }
+            AppEventVariant::AppFinished => {
+                let mut state = self.webview.borrow_mut();
+                let Some(webview_state) = state.get_mut(&id) else {
+                    return;
+                };
+                webview_state.app_finished = true;
+                // JS may be blocked in a sync request that the app will never answer
+                if let Some(responder) = webview_state.take_ongoing_request() {
+                    responder.respond(shutdown_response());
+                }
+            }
+            AppEventVariant::SocketConnected(writer) => {
+                let mut state = self.webview.borrow_mut();
+                if let Some(webview_state) = state.get_mut(&id) {
+                    webview_state.socket = Some(writer);
+                }
+            }
+            AppEventVariant::SocketClosed { connection } => {
+                let mut state = self.webview.borrow_mut();
+                let Some(webview_state) = state.get_mut(&id) else {
+                    return;
+                };
+                // A reloaded page may already have replaced the connection
+                if webview_state
+                    .socket
+                    .as_ref()
+                    .is_some_and(|socket| socket.connection() == connection)
+                {
+                    webview_state.socket = None;
+                }
+            }
+            AppEventVariant::SocketMessage(msg) => self.handle_socket_message(id, msg),
+        }
+    }
+
+    /// Handle a message JS sent over its WebSocket connection.
+    fn handle_socket_message(&self, id: u64, msg: IPCMessage) {
+        let mut state = self.webview.borrow_mut();
+        let Some(webview_state) = state.get_mut(&id) else {
+            return;
+        };
+        if webview_state.app_finished {
+            return;
+        }
+        match msg.ty() {
+            // JS answered a call Rust sent over the socket
+            Ok(MessageType::Respond) => {
+                webview_state.pending_js_evaluates =
+                    webview_state.pending_js_evaluates.saturating_sub(1);
+                webview_state.sender.start_send(MessageType::Respond, msg);
+            }
+            // Calls from JS need an answer before JS continues, so they must use the handler
+            Ok(MessageType::Evaluate) => self.config.report_error(&DecodeError::Custom(
+                "calls from JS must go through the handler, not the WebSocket".to_string(),
+            )),
+            Err(err) => self.config.report_error(&err),
+        }
+    }
+
+    /// Remove and return the messages queued for a webview that has not finished loading.
+    ///
+    /// Messages the app sends before the webview loads are held back until it does. If the
+    /// load is aborted, the host can take them here to replay, drop or inspect them. Returns
+    /// an empty list if the webview is unknown or has already loaded.
+    pub fn drain_queued(&self, id: u64) -> Vec<IPCMessage> {
+        let mut state = self.webview.borrow_mut();
+        match state.get_mut(&id).map(|webview| &mut webview.loading_state) {
+            Some(WebviewLoadingState::Pending { queued }) => std::mem::take(queued),
+            _ => Vec::new(),
         }
     }
 
+    /// Point an app at a new webview after its old webview was destroyed.
+    ///
+    /// Use this to recover when a webview is recreated, for example after its GPU or
+    /// content process crashed. The app future, its channels and the exported objects
+    /// keep running. Calls are held back until the new webview loads and signals
+    /// "/__wbg__/initialized", then they go through `evaluate_script`. Serve the new
+    /// webview from the same [`ProtocolHandler`] as the old one.
+    ///
+    /// The new webview starts with an empty JS heap, so `JsValue`s created before the
+    /// recreation become stale and resolve to `undefined`. Calls the old webview never
+    /// answered fail with [`DecodeError::WebviewRecreated`], which panics unless the
+    /// import can return the error, like `catch` imports. Reloads of the same webview are
+    /// handled the same way automatically, see [`WryBindgenEvent::is_webview_reload`].
+    ///
+    /// Returns false if no app is bound to a webview with this id.
+    pub fn rebind_webview(&self, id: u64, evaluate_script: impl FnMut(&str) + 'static) -> bool {
+        let mut state = self.webview.borrow_mut();
+        let Some(webview_state) = state.get_mut(&id) else {
+            return false;
+        };
+        webview_state.evaluate_script = Box::new(evaluate_script);
+        webview_state.forget_page();
+        webview_state.loading_state = WebviewLoadingState::default();
+        true
+    }
+
     fn handle_ipc_message(&self, id: u64, ipc_msg: IPCMessage) {
         let mut state = self.webview.borrow_mut();
         let Some(webview_state) = state.get_mut(&id) else {
@@ -444,6 +840,17 @@ impl WryBindgen {
             return;
         }
 
+        // Otherwise send calls over the webview's WebSocket connection, if it has one
+        if let (MessageType::Evaluate, Some(socket)) = (ty, &webview_state.socket) {
+            match socket.send(ipc_msg.data()) {
+                Ok(()) => return,
+                Err(err) => {
+                    log::debug!("Falling back to evaluate_script after a WebSocket error: {err}");
+                    webview_state.socket = None;
+                }
+            }
+        }
+
         // Otherwise call into js through evaluate_script
         let decoded = ipc_msg.decoded().unwrap();
 
@@ -458,11 +865,17 @@ impl WryBindgen {
     }
 }
 
+/// A future factory that runs on the app thread, started inside the runtime.
+type AppFactory =
+    Box<dyn FnOnce() -> Pin<Box<dyn core::future::Future<Output = ()> + 'static>> + Send>;
+
 /// A builder for the application future and protocol handler.
 pub struct AppBuilder<'a> {
     webview_id: u64,
     bindgen: &'a WryBindgen,
     ipc: WryIPC,
+    // Additional futures that share the webview with the main app future
+    attached: Vec<AppFactory>,
 }
 
 impl<'a> AppBuilder<'a> {
@@ -471,9 +884,34 @@ impl<'a> AppBuilder<'a> {
         ProtocolHandler {
             id: self.webview_id,
             webview: self.bindgen.webview.clone(),
+            config: self.bindgen.config.clone(),
         }
     }
 
+    /// Attach another future that shares this webview with the app.
+    ///
+    /// Attached futures run on the app thread in the same runtime as the main app future,
+    /// so plugins can make independent JS calls over the same IPC channel. Every call that
+    /// needs a response flushes and waits for it before another future is polled, so calls
+    /// from different futures can interleave without their responses being mixed up. The
+    /// app finishes once the main future and every attached future have completed.
+    ///
+    /// The JS functions imported with `#[wasm_bindgen]` are shared by every app on the
+    /// webview, but each attached future has its own scope for the Rust side it registers
+    /// with JS. Closures passed to JS and exported objects created while it runs, or while
+    /// one of its closures is called, belong to it and are dropped when it completes. JS
+    /// calls to them afterwards fail, so keep the future pending for as long as JS may
+    /// call back into it. Tasks started with `spawn_local` belong to the main app.
+    pub fn attach<F>(mut self, app: impl FnOnce() -> F + Send + 'static) -> Self
+    where
+        F: core::future::Future<Output = ()> + 'static,
+    {
+        self.attached.push(Box::new(move || {
+            Box::pin(app()) as Pin<Box<dyn core::future::Future<Output = ()> + 'static>>
+        }));
+        self
+    }
+
     /// Consume the builder and get the prepared app future.
     pub fn build<F>(
         self,
@@ -492,30 +930,53 @@ impl<'a> AppBuilder<'a> {
             webview_state.evaluate_script = Box::new(evaluate_script);
         }
 
+        let attached = self.attached;
         let start_future = move || {
             let run_app_in_runtime = async move {
-                let run_app = app();
+                let run_attached = futures_util::future::join_all(
+                    attached
+                        .into_iter()
+                        .zip(1..)
+                        .map(|(start, scope)| run_in_app_scope(scope, start())),
+                );
+                let run_app = futures_util::future::join(app(), run_attached);
                 let wait_for_events = handle_callbacks();
+                let run_spawned = run_spawned_tasks();
 
                 futures_util::select! {
                     _ = run_app.fuse() => {},
                     _ = wait_for_events.fuse() => {},
+                    _ = run_spawned.fuse() => {},
                 }
             };
 
+            let proxy = self.ipc.proxy.clone();
+            let webview_id = self.webview_id;
             let runtime = Runtime::new(self.ipc, self.webview_id);
-            let mut maybe_runtime = Some(runtime);
+            let slot: RuntimeSlot = Rc::new(RefCell::new(Some(runtime)));
+            // Let other apps on this thread reach the webview through a WindowHandle
+            WINDOWS.with(|windows| {
+                windows
+                    .borrow_mut()
+                    .insert(webview_id, Rc::downgrade(&slot))
+            });
             let poll_in_runtime = async move {
                 let mut run_app_in_runtime = pin!(run_app_in_runtime);
-                poll_fn(move |ctx| {
+                poll_fn(|ctx| {
+                    let runtime = slot
+                        .borrow_mut()
+                        .take()
+                        .expect("The app was polled while its window was in use");
                     let (new_runtime, poll_result) =
-                        in_runtime(maybe_runtime.take().unwrap(), || {
-                            run_app_in_runtime.as_mut().poll(ctx)
-                        });
-                    maybe_runtime = Some(new_runtime);
+                        in_runtime(runtime, || run_app_in_runtime.as_mut().poll(ctx));
+                    *slot.borrow_mut() = Some(new_runtime);
                     poll_result
                 })
-                .await
+                .await;
+                WINDOWS.with(|windows| windows.borrow_mut().remove(&webview_id));
+
+                // Let the main thread answer any request JS is still waiting on
+                proxy(WryBindgenEvent::app_finished(webview_id));
             };
 
             Box::pin(poll_in_runtime) as Pin<Box<dyn Future<Output = ()> + 'static>>
@@ -528,6 +989,136 @@ impl<'a> AppBuilder<'a> {
     }
 }
 
+/// Run an attached app future in its own scope, then drop what it registered with JS.
+async fn run_in_app_scope(scope: u32, mut app: Pin<Box<dyn Future<Output = ()> + 'static>>) {
+    poll_fn(|ctx| {
+        let outer_scope = with_runtime(|runtime| runtime.enter_app_scope(scope));
+        let poll_result = app.as_mut().poll(ctx);
+        with_runtime(|runtime| runtime.enter_app_scope(outer_scope));
+        poll_result
+    })
+    .await;
+    crate::object_store::release_app_scope(scope);
+}
+
+/// The runtime of a webview, shared between its app future and [`WindowHandle`]s.
+/// It is `None` while the runtime is in use further up the stack.
+type RuntimeSlot = Rc<RefCell<Option<Runtime>>>;
+
+thread_local! {
+    /// The runtimes of the apps running on this thread, keyed by webview id. The app
+    /// future owns the runtime, so it is freed when the future is dropped.
+    static WINDOWS: RefCell<HashMap<u64, Weak<RefCell<Option<Runtime>>>>> = RefCell::new(HashMap::new());
+}
+
+/// A handle to the JS context of one webview.
+///
+/// Every webview created with [`WryBindgen::app_builder`] has its own runtime with
+/// its own JS heap, object store and borrow stack. JS calls normally go to the webview
+/// whose app is currently running. [`WindowHandle::run`] switches to another webview
+/// for the duration of a closure, so one app can drive several windows when their app
+/// futures run on the same thread.
+///
+/// Heap ids are only meaningful in the webview that created them. A [`JsValue`] used
+/// inside another window is stale there and acts as `undefined`. Dropping it there
+/// frees it in its own window the next time that window runs.
+///
+/// [`JsValue`]: crate::JsValue
+///
+/// # Example
+///
+/// ```ignore
+/// let main_window = WindowHandle::current();
+/// let settings_window = WindowHandle::from_id(settings_app.id()).unwrap();
+/// settings_window.run(|| set_title("Settings"));
+/// ```
+#[derive(Clone)]
+pub struct WindowHandle {
+    id: u64,
+    runtime: Weak<RefCell<Option<Runtime>>>,
+}
+
+impl WindowHandle {
+    /// Get a handle to the webview of the app that is currently running.
+    ///
+    /// # Panics
+    /// Panics if called outside of an app future.
+    pub fn current() -> Self {
+        let id = current_webview_id().expect("No runtime available");
+        Self::from_id(id).expect("The current app is not registered on this thread")
+    }
+
+    /// Get a handle to the webview with the given id, as returned by [`PreparedApp::id`].
+    ///
+    /// Returns `None` if no app for that webview is running on this thread.
+    pub fn from_id(id: u64) -> Option<Self> {
+        WINDOWS.with(|windows| {
+            windows
+                .borrow()
+                .get(&id)
+                .filter(|runtime| runtime.strong_count() > 0)
+                .map(|runtime| Self {
+                    id,
+                    runtime: runtime.clone(),
+                })
+        })
+    }
+
+    /// The id of the webview this handle targets.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Whether the app for this webview is still running.
+    pub fn is_open(&self) -> bool {
+        self.runtime.strong_count() > 0
+    }
+
+    /// Run `f` with JS calls, exported objects and callbacks scoped to this webview.
+    ///
+    /// # Panics
+    /// Panics if the app for this webview has finished, or if the webview is already
+    /// in use further up the stack, for example when window A runs a closure in window
+    /// B that tries to run a closure in window A again.
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        if current_webview_id() == Some(self.id) {
+            return f();
+        }
+        let slot = self
+            .runtime
+            .upgrade()
+            .expect("The app for this window has finished");
+        let runtime = slot
+            .borrow_mut()
+            .take()
+            .expect("The window is already in use further up the stack");
+        let (runtime, out) = in_runtime(runtime, f);
+        *slot.borrow_mut() = Some(runtime);
+        out
+    }
+
+    /// Send every queued `JsValue` drop for this webview to JS and wait until JS has
+    /// freed them.
+    ///
+    /// Drops are normally sent together with the next call into JS. Call this to make
+    /// the JS heap reflect every value dropped so far, for example before a test counts
+    /// the live heap objects.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`WindowHandle::run`].
+    pub fn flush_pending_drops(&self) {
+        self.run(crate::batch::flush_drops);
+    }
+}
+
+impl core::fmt::Debug for WindowHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WindowHandle")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
 /// Create a blank HTTP response.
 pub fn blank_response() -> http::Response<Vec<u8>> {
     http::Response::builder()
@@ -544,6 +1135,14 @@ pub fn error_response() -> http::Response<Vec<u8>> {
         .expect("Failed to build error response")
 }
 
+/// Create the HTTP response sent to JS requests once the app has finished.
+pub fn shutdown_response() -> http::Response<Vec<u8>> {
+    http::Response::builder()
+        .status(503)
+        .body(vec![])
+        .expect("Failed to build shutdown response")
+}
+
 /// Create a JavaScript module HTTP response.
 pub fn module_response(content: &str) -> http::Response<Vec<u8>> {
     http::Response::builder()
@@ -0,0 +1,155 @@
+//! Bindings to `requestAnimationFrame` and helpers for driving animations from Rust.
+
+use std::cell::RefCell;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use futures_util::Stream;
+use wasm_bindgen::{Closure, JsValue, wasm_bindgen};
+
+#[wasm_bindgen(inline_js = r#"
+export function request_animation_frame(callback) {
+    return window.requestAnimationFrame(callback);
+}
+
+export function cancel_animation_frame(id) {
+    window.cancelAnimationFrame(id);
+}
+
+export function start_animation_loop(callback) {
+    const state = { id: 0, start: null };
+    const frame = (timestamp) => {
+        if (state.start === null) {
+            state.start = timestamp;
+        }
+        state.id = 0;
+        if (callback(timestamp - state.start)) {
+            state.id = window.requestAnimationFrame(frame);
+        }
+    };
+    state.id = window.requestAnimationFrame(frame);
+    return state;
+}
+
+export function stop_animation_loop(state) {
+    if (state.id !== 0) {
+        window.cancelAnimationFrame(state.id);
+        state.id = 0;
+    }
+}
+"#)]
+extern "C" {
+    /// Call `callback` before the next repaint with `window.requestAnimationFrame`.
+    ///
+    /// The callback receives the frame timestamp in milliseconds. Returns an id that
+    /// can be passed to [`cancel_animation_frame`].
+    pub fn request_animation_frame(callback: &Closure<dyn FnMut(f64)>) -> i32;
+
+    /// Cancel a callback scheduled with [`request_animation_frame`].
+    pub fn cancel_animation_frame(id: i32);
+
+    fn start_animation_loop(callback: &Closure<dyn FnMut(Duration) -> bool>) -> JsValue;
+
+    fn stop_animation_loop(state: &JsValue);
+}
+
+/// An animation that calls a Rust closure once per frame.
+///
+/// Each frame is rescheduled in JS, so the loop costs one call into Rust per frame.
+/// The loop runs until the closure returns `false` or the `AnimationLoop` is dropped.
+pub struct AnimationLoop {
+    state: JsValue,
+    _closure: Closure<dyn FnMut(Duration) -> bool>,
+}
+
+impl AnimationLoop {
+    /// Start calling `frame` on every animation frame with the time elapsed since the
+    /// first frame. Return `false` from `frame` to stop the loop.
+    pub fn start(frame: impl FnMut(Duration) -> bool + 'static) -> AnimationLoop {
+        let closure: Closure<dyn FnMut(Duration) -> bool> = Closure::new(frame);
+        let state = start_animation_loop(&closure);
+        AnimationLoop {
+            state,
+            _closure: closure,
+        }
+    }
+}
+
+impl Drop for AnimationLoop {
+    fn drop(&mut self) {
+        stop_animation_loop(&self.state);
+    }
+}
+
+/// Create a stream that yields the frame timestamp, in milliseconds, on every animation frame.
+///
+/// Frames are requested one at a time while the stream is polled, so a consumer that
+/// stops polling does not queue up frames. Dropping the stream cancels the pending frame.
+///
+/// ```ignore
+/// let mut frames = animation_frames();
+/// while let Some(timestamp) = frames.next().await {
+///     draw(timestamp);
+/// }
+/// ```
+pub fn animation_frames() -> AnimationFrames {
+    let state = Rc::new(RefCell::new(FrameState::default()));
+    let closure: Closure<dyn FnMut(f64)> = Closure::new({
+        let state = state.clone();
+        move |timestamp: f64| {
+            let mut state = state.borrow_mut();
+            state.request_id = None;
+            state.timestamp = Some(timestamp);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        }
+    });
+    AnimationFrames { state, closure }
+}
+
+#[derive(Default)]
+struct FrameState {
+    /// The id of the frame that has been requested but not delivered yet
+    request_id: Option<i32>,
+    /// A timestamp delivered by JS that has not been yielded yet
+    timestamp: Option<f64>,
+    waker: Option<Waker>,
+}
+
+/// A stream of animation frame timestamps created by [`animation_frames`].
+///
+/// The stream never ends on its own. Dropping it cancels the pending frame.
+pub struct AnimationFrames {
+    state: Rc<RefCell<FrameState>>,
+    closure: Closure<dyn FnMut(f64)>,
+}
+
+impl Stream for AnimationFrames {
+    type Item = f64;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<f64>> {
+        let mut state = self.state.borrow_mut();
+        if let Some(timestamp) = state.timestamp.take() {
+            return Poll::Ready(Some(timestamp));
+        }
+        state.waker = Some(cx.waker().clone());
+        if state.request_id.is_none() {
+            // Release the borrow while calling into JS in case the frame runs immediately
+            drop(state);
+            let id = request_animation_frame(&self.closure);
+            self.state.borrow_mut().request_id = Some(id);
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for AnimationFrames {
+    fn drop(&mut self) {
+        if let Some(id) = self.state.borrow_mut().request_id.take() {
+            cancel_animation_frame(id);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Minimal bindings to the Web Audio API for playing tones.
+
+use js_sys::Promise;
+use wasm_bindgen::{JsValue, wasm_bindgen};
+
+#[wasm_bindgen(inline_js = r#"
+export function new_audio_context() {
+    const Context = window.AudioContext || window.webkitAudioContext;
+    return new Context();
+}
+
+export function oscillator_frequency(oscillator) {
+    return oscillator.frequency.value;
+}
+
+export function set_oscillator_frequency(oscillator, hz) {
+    oscillator.frequency.value = hz;
+}
+"#)]
+extern "C" {
+    /// A graph of audio nodes that renders to the system's audio output.
+    pub type AudioContext;
+
+    /// A node in an audio graph.
+    pub type AudioNode;
+
+    /// The final node of a context's graph, usually the speakers.
+    #[wasm_bindgen(extends = AudioNode)]
+    pub type AudioDestinationNode;
+
+    /// A source node that generates a periodic waveform, a sine wave by default.
+    #[wasm_bindgen(extends = AudioNode)]
+    pub type OscillatorNode;
+
+    /// The time in seconds since the context was created, on the audio hardware clock.
+    ///
+    /// `start_at` and `stop_at` times are measured on this clock. It does not advance
+    /// while the context is suspended.
+    #[wasm_bindgen(method, getter = currentTime)]
+    pub fn current_time(this: &AudioContext) -> f64;
+
+    /// The node that sends audio to the output device.
+    #[wasm_bindgen(method, getter)]
+    pub fn destination(this: &AudioContext) -> AudioDestinationNode;
+
+    /// Create a sine wave oscillator at 440 Hz. Connect it to a destination to hear it.
+    #[wasm_bindgen(method, js_name = createOscillator)]
+    pub fn create_oscillator(this: &AudioContext) -> OscillatorNode;
+
+    /// Resume a suspended context.
+    ///
+    /// Webviews may create contexts suspended until the user interacts with the page,
+    /// so call this from an input handler before playing sound. The promise rejects if
+    /// the context is closed.
+    #[wasm_bindgen(method)]
+    pub fn resume(this: &AudioContext) -> Promise;
+
+    /// Close the context and release its audio resources.
+    #[wasm_bindgen(method)]
+    pub fn close(this: &AudioContext) -> Promise;
+
+    /// Send this node's output to `destination`.
+    ///
+    /// Returns the exception thrown in JS if the nodes belong to different contexts.
+    #[wasm_bindgen(method, catch)]
+    pub fn connect(this: &AudioNode, destination: &AudioNode) -> Result<(), JsValue>;
+
+    /// Disconnect every output of this node.
+    #[wasm_bindgen(method)]
+    pub fn disconnect(this: &AudioNode);
+
+    /// Start the oscillator immediately.
+    ///
+    /// An oscillator can only be started once. Returns the `InvalidStateError` thrown in
+    /// JS on a second call.
+    #[wasm_bindgen(method, catch)]
+    pub fn start(this: &OscillatorNode) -> Result<(), JsValue>;
+
+    /// Start the oscillator at `when`, in seconds on the context's
+    /// [`current_time`](AudioContext::current_time) clock.
+    #[wasm_bindgen(method, catch, js_name = start)]
+    pub fn start_at(this: &OscillatorNode, when: f64) -> Result<(), JsValue>;
+
+    /// Stop the oscillator immediately.
+    ///
+    /// Returns the `InvalidStateError` thrown in JS if the oscillator was never started.
+    #[wasm_bindgen(method, catch)]
+    pub fn stop(this: &OscillatorNode) -> Result<(), JsValue>;
+
+    /// Stop the oscillator at `when`, in seconds on the context's
+    /// [`current_time`](AudioContext::current_time) clock.
+    #[wasm_bindgen(method, catch, js_name = stop)]
+    pub fn stop_at(this: &OscillatorNode, when: f64) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch)]
+    fn new_audio_context() -> Result<AudioContext, JsValue>;
+
+    fn oscillator_frequency(oscillator: &OscillatorNode) -> f64;
+
+    fn set_oscillator_frequency(oscillator: &OscillatorNode, hz: f64);
+}
+
+impl AudioContext {
+    /// Create a context with `new AudioContext()`.
+    ///
+    /// Returns the exception thrown in JS if the webview has no Web Audio support or no
+    /// audio device is available.
+    pub fn new() -> Result<AudioContext, JsValue> {
+        new_audio_context()
+    }
+}
+
+impl OscillatorNode {
+    /// The oscillator's frequency in hertz.
+    pub fn frequency(&self) -> f64 {
+        oscillator_frequency(self)
+    }
+
+    /// Set the oscillator's frequency in hertz. The change takes effect immediately,
+    /// including while the oscillator is playing.
+    pub fn set_frequency(&self, hz: f64) {
+        set_oscillator_frequency(self, hz);
+    }
+}
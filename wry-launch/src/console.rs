@@ -0,0 +1,135 @@
+//! Bindings to the devtools `console` for structured debugging.
+//!
+//! These calls return nothing, so they are queued into the current batch instead of
+//! waiting for a round-trip to JS.
+//!
+//! Calls below the level set with [`set_console_level`] return without encoding their
+//! arguments or touching IPC. Use [`enabled`] to skip building expensive arguments too.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+pub use wasm_bindgen::console_capture::ConsoleLevel;
+use wasm_bindgen::{JsValue, wasm_bindgen};
+
+#[wasm_bindgen(inline_js = r#"
+export function dir(value) {
+    console.dir(value);
+}
+
+export function table(value) {
+    console.table(value);
+}
+
+export function log_with_format(format, args) {
+    console.log(format, ...args);
+}
+
+export function log_at(level, args) {
+    console[level](...args);
+}
+"#)]
+extern "C" {
+    #[wasm_bindgen(js_name = dir)]
+    fn js_dir(value: &JsValue);
+
+    #[wasm_bindgen(js_name = table)]
+    fn js_table(value: &JsValue);
+
+    #[wasm_bindgen(js_name = log_with_format)]
+    fn js_log_with_format(format: &str, args: &[JsValue]);
+
+    fn log_at(level: &str, args: &[JsValue]);
+}
+
+/// The least severe level that is sent to JS. Everything is sent by default.
+static LEVEL: AtomicU8 = AtomicU8::new(ConsoleLevel::Debug as u8);
+
+/// Only send console calls at `level` or above to JS.
+///
+/// Calls below the threshold become no-ops. `dir`, `table` and `log_with_format` are
+/// filtered as [`ConsoleLevel::Log`].
+pub fn set_console_level(level: ConsoleLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// The threshold set with [`set_console_level`].
+pub fn console_level() -> ConsoleLevel {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => ConsoleLevel::Debug,
+        1 => ConsoleLevel::Log,
+        2 => ConsoleLevel::Info,
+        3 => ConsoleLevel::Warn,
+        _ => ConsoleLevel::Error,
+    }
+}
+
+/// Whether a console call at `level` would be sent to JS.
+///
+/// Check this before formatting arguments that are only needed for logging.
+pub fn enabled(level: ConsoleLevel) -> bool {
+    level as u8 >= LEVEL.load(Ordering::Relaxed)
+}
+
+/// Display an interactive list of the properties of `value` with `console.dir`.
+pub fn dir(value: &JsValue) {
+    if enabled(ConsoleLevel::Log) {
+        js_dir(value);
+    }
+}
+
+/// Display an array or object as a table with `console.table`.
+pub fn table(value: &JsValue) {
+    if enabled(ConsoleLevel::Log) {
+        js_table(value);
+    }
+}
+
+/// Log `args` with `console.log`, substituted into the format string `format`.
+///
+/// The format string supports the browser's specifiers: `%s` for a string, `%d`
+/// or `%i` for an integer, `%f` for a float, `%o` or `%O` for an object and `%c`
+/// to apply the CSS in the matching argument to the text that follows.
+pub fn log_with_format(format: &str, args: &[JsValue]) {
+    if enabled(ConsoleLevel::Log) {
+        js_log_with_format(format, args);
+    }
+}
+
+/// Log `args` with `console.debug`.
+pub fn debug(args: &[JsValue]) {
+    log_level(ConsoleLevel::Debug, args);
+}
+
+/// Log `args` with `console.log`.
+pub fn log(args: &[JsValue]) {
+    log_level(ConsoleLevel::Log, args);
+}
+
+/// Log `args` with `console.info`.
+pub fn info(args: &[JsValue]) {
+    log_level(ConsoleLevel::Info, args);
+}
+
+/// Log `args` with `console.warn`.
+pub fn warn(args: &[JsValue]) {
+    log_level(ConsoleLevel::Warn, args);
+}
+
+/// Log `args` with `console.error`.
+pub fn error(args: &[JsValue]) {
+    log_level(ConsoleLevel::Error, args);
+}
+
+fn log_level(level: ConsoleLevel, args: &[JsValue]) {
+    if !enabled(level) {
+        return;
+    }
+    let method = match level {
+        ConsoleLevel::Debug => "debug",
+        ConsoleLevel::Log => "log",
+        ConsoleLevel::Info => "info",
+        ConsoleLevel::Warn => "warn",
+        ConsoleLevel::Error => "error",
+    };
+    log_at(method, args);
+}
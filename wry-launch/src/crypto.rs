@@ -0,0 +1,39 @@
+//! Bindings to the Web Crypto API exposed on `window.crypto`.
+//!
+//! These let apps get cryptographically secure randomness from the webview
+//! without pulling in a Rust RNG crate.
+
+use wasm_bindgen::wasm_bindgen;
+
+/// The most bytes `crypto.getRandomValues` fills in one call
+const MAX_RANDOM_BYTES: usize = 65536;
+
+#[wasm_bindgen(inline_js = r#"
+export function get_random_values(buf) {
+    window.crypto.getRandomValues(buf);
+}
+
+export function random_uuid() {
+    return window.crypto.randomUUID();
+}
+"#)]
+extern "C" {
+    #[wasm_bindgen(js_name = get_random_values)]
+    fn js_get_random_values(buf: &mut [u8]);
+    #[wasm_bindgen(js_name = random_uuid)]
+    fn js_random_uuid() -> String;
+}
+
+/// Fill `buf` with cryptographically secure random bytes from `crypto.getRandomValues`.
+///
+/// Buffers larger than the 65536 bytes the API accepts at once are filled in chunks.
+pub fn get_random_values(buf: &mut [u8]) {
+    for chunk in buf.chunks_mut(MAX_RANDOM_BYTES) {
+        js_get_random_values(chunk);
+    }
+}
+
+/// Generate a random RFC 4122 version 4 UUID with `crypto.randomUUID`.
+pub fn random_uuid() -> String {
+    js_random_uuid()
+}
@@ -0,0 +1,65 @@
+//! Modal dialogs drawn as an HTML overlay instead of the native `alert`, `confirm`
+//! and `prompt`.
+//!
+//! The native dialogs block the webview's main thread until they are closed and use
+//! the platform's look. These dialogs are plain elements added to the page, so the
+//! page keeps running while they are open and the result is awaited like any other
+//! promise.
+//!
+//! The overlay has the `wry-dialog-overlay` class and the dialog box has the
+//! `wry-dialog` class. Its buttons carry `data-action="ok"` or `data-action="cancel"`
+//! and the prompt input has the `wry-dialog-input` class, so pages can restyle them.
+//! Escape cancels the topmost dialog and Enter in the prompt input accepts it. Key
+//! presses outside the dialogs are left to the page.
+
+use js_sys::Promise;
+use wasm_bindgen::wasm_bindgen;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Defined by the wry-bindgen initialization script
+    #[wasm_bindgen(js_namespace = window, js_name = showWryDialog)]
+    fn show_dialog(kind: &str, message: &str, default_value: &str) -> Promise;
+}
+
+/// Show `message` with an OK button and wait until the user closes it.
+///
+/// The dialog is shown when this is called, not when the future is first polled.
+pub fn alert(message: &str) -> impl Future<Output = ()> + 'static {
+    let closed = JsFuture::from(show_dialog("alert", message, ""));
+    async move {
+        // The promise never rejects
+        let _ = closed.await;
+    }
+}
+
+/// Show `message` with OK and Cancel buttons and wait for the user's choice.
+///
+/// Resolves to `true` if the user pressed OK or Enter and `false` if they pressed
+/// Cancel or Escape. The dialog is shown when this is called, not when the future
+/// is first polled.
+pub fn confirm(message: &str) -> impl Future<Output = bool> + 'static {
+    let choice = JsFuture::from(show_dialog("confirm", message, ""));
+    async move {
+        choice
+            .await
+            .ok()
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false)
+    }
+}
+
+/// Show `message` with a text input prefilled with `default_value` and wait for the
+/// user's answer.
+///
+/// Resolves to the text in the input if the user accepted the dialog, or `None` if
+/// they cancelled it. The dialog is shown when this is called, not when the future
+/// is first polled.
+pub fn prompt(
+    message: &str,
+    default_value: &str,
+) -> impl Future<Output = Option<String>> + 'static {
+    let answer = JsFuture::from(show_dialog("prompt", message, default_value));
+    async move { answer.await.ok().and_then(|value| value.as_string()) }
+}
@@ -0,0 +1,197 @@
+//! Bindings to `document` and DOM elements.
+
+use std::collections::HashMap;
+
+use js_sys::Promise;
+use wasm_bindgen::{JsValue, wasm_bindgen};
+
+use crate::events::{ListenerGuard, add_event_listener};
+
+#[wasm_bindgen(inline_js = r#"
+export function exit_fullscreen() {
+    return document.exitFullscreen();
+}
+
+export function fullscreen_element() {
+    return document.fullscreenElement;
+}
+
+export function document_target() {
+    return document;
+}
+
+export function cookie() {
+    return document.cookie;
+}
+
+export function set_cookie(value) {
+    document.cookie = value;
+}
+
+export function bounding_client_rect(element) {
+    const rect = element.getBoundingClientRect();
+    return [rect.x, rect.y, rect.width, rect.height];
+}
+"#)]
+extern "C" {
+    /// An element in the document.
+    pub type Element;
+
+    /// Show the element in fullscreen with `Element.requestFullscreen`.
+    ///
+    /// The returned promise resolves once the element is fullscreen and can be awaited
+    /// with `JsFuture`. It rejects if the user or webview denies the request.
+    #[wasm_bindgen(method, js_name = requestFullscreen)]
+    pub fn request_fullscreen(this: &Element) -> Promise;
+
+    /// Replace the element's children by parsing `html` with `Element.innerHTML`.
+    ///
+    /// The markup is inserted as is, so never pass untrusted input: it can inject
+    /// elements and event handler attributes that run script (XSS). Escape user content
+    /// or set it as text instead.
+    #[wasm_bindgen(method, setter = innerHTML)]
+    pub fn set_inner_html(this: &Element, html: &str);
+
+    /// Serialize the element's children to HTML with `Element.innerHTML`.
+    #[wasm_bindgen(method, getter = innerHTML)]
+    pub fn inner_html(this: &Element) -> String;
+
+    /// Serialize the element and its children to HTML with `Element.outerHTML`.
+    #[wasm_bindgen(method, getter = outerHTML)]
+    pub fn outer_html(this: &Element) -> String;
+
+    /// Parse `html` and insert it relative to the element with `Element.insertAdjacentHTML`.
+    ///
+    /// `position` is one of `"beforebegin"`, `"afterbegin"`, `"beforeend"` or `"afterend"`.
+    /// Returns the `SyntaxError` thrown in JS for any other position. Like
+    /// [`set_inner_html`](Element::set_inner_html), the markup is not sanitized and must
+    /// not come from untrusted input.
+    #[wasm_bindgen(method, catch, js_name = insertAdjacentHTML)]
+    pub fn insert_adjacent_html(this: &Element, position: &str, html: &str) -> Result<(), JsValue>;
+
+    /// Leave fullscreen with `document.exitFullscreen`.
+    ///
+    /// The returned promise rejects if the document is not fullscreen.
+    pub fn exit_fullscreen() -> Promise;
+
+    /// The element that is currently fullscreen, or `None` if the document is not fullscreen.
+    pub fn fullscreen_element() -> Option<Element>;
+
+    fn document_target() -> JsValue;
+
+    fn bounding_client_rect(element: &Element) -> Vec<f64>;
+
+    /// Read the cookies visible to the document as `"name=value; name2=value2"`.
+    pub fn cookie() -> String;
+
+    /// Write a single cookie with `document.cookie = value`.
+    ///
+    /// Like the browser API this adds or updates the one cookie named in `value`, such
+    /// as `"theme=dark; path=/; max-age=3600"`, and leaves the others untouched.
+    pub fn set_cookie(value: &str);
+}
+
+/// Parse the cookies visible to the document into a map from name to value.
+///
+/// Values are returned as they are stored, without percent-decoding.
+pub fn cookies_map() -> HashMap<String, String> {
+    cookie()
+        .split(';')
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+impl Element {
+    /// Register `handler` for `event` on this element.
+    ///
+    /// The listener is removed and the handler dropped when the returned
+    /// [`ListenerGuard`] is dropped.
+    pub fn add_event_listener(
+        &self,
+        event: &str,
+        handler: impl FnMut(JsValue) + 'static,
+    ) -> ListenerGuard {
+        add_event_listener(self.as_ref(), event, handler)
+    }
+
+    /// Measure the element's size and position relative to the viewport with
+    /// `Element.getBoundingClientRect`.
+    ///
+    /// The whole rect is read in a single call, so measuring does not cost a round-trip
+    /// per coordinate.
+    pub fn get_bounding_client_rect(&self) -> DomRect {
+        let rect = bounding_client_rect(self);
+        DomRect {
+            x: rect[0],
+            y: rect[1],
+            width: rect[2],
+            height: rect[3],
+        }
+    }
+}
+
+/// A rectangle in CSS pixels, as returned by [`Element::get_bounding_client_rect`].
+///
+/// The width and height may be negative, in which case the edges are normalized the
+/// same way `DOMRect` normalizes them in JS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DomRect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl DomRect {
+    /// The x coordinate of the rectangle's origin.
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    /// The y coordinate of the rectangle's origin.
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    /// The width of the rectangle.
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    /// The height of the rectangle.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// The y coordinate of the top edge.
+    pub fn top(&self) -> f64 {
+        self.y.min(self.y + self.height)
+    }
+
+    /// The x coordinate of the left edge.
+    pub fn left(&self) -> f64 {
+        self.x.min(self.x + self.width)
+    }
+
+    /// The x coordinate of the right edge.
+    pub fn right(&self) -> f64 {
+        self.x.max(self.x + self.width)
+    }
+
+    /// The y coordinate of the bottom edge.
+    pub fn bottom(&self) -> f64 {
+        self.y.max(self.y + self.height)
+    }
+}
+
+/// Register a handler for `fullscreenchange` events on the document.
+///
+/// The handler is called when an element enters or leaves fullscreen. The listener is
+/// removed and the handler dropped when the returned [`ListenerGuard`] is dropped. Use
+/// [`fullscreen_element`] inside the handler to check the new state.
+pub fn on_fullscreen_change(handler: impl FnMut(JsValue) + 'static) -> ListenerGuard {
+    add_event_listener(&document_target(), "fullscreenchange", handler)
+}
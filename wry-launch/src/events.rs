@@ -0,0 +1,65 @@
+//! Event listeners that are removed and freed when their guard is dropped.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::{Closure, JsValue, wasm_bindgen};
+
+#[wasm_bindgen(inline_js = r#"
+export function add_listener(target, event, handler) {
+    target.addEventListener(event, handler);
+}
+
+export function remove_listener(target, event, handler) {
+    target.removeEventListener(event, handler);
+}
+"#)]
+extern "C" {
+    fn add_listener(target: &JsValue, event: &str, handler: &Closure<dyn FnMut(JsValue)>);
+
+    fn remove_listener(target: &JsValue, event: &str, handler: &Closure<dyn FnMut(JsValue)>);
+}
+
+/// Register `handler` for `event` on any JS event target with `addEventListener`.
+///
+/// The listener stays registered until the returned [`ListenerGuard`] is dropped, so
+/// `let _guard = add_event_listener(...)` cleans up at the end of the scope.
+pub fn add_event_listener(
+    target: &JsValue,
+    event: &str,
+    handler: impl FnMut(JsValue) + 'static,
+) -> ListenerGuard {
+    let handler: Rc<RefCell<dyn FnMut(JsValue)>> = Rc::new(RefCell::new(handler));
+    // JS only keeps the Rust side of a closure alive until it garbage collects the
+    // wrapper. The registered closure holds a weak reference, so the handler and
+    // everything it captures is freed as soon as the guard drops.
+    let weak = Rc::downgrade(&handler);
+    let closure: Closure<dyn FnMut(JsValue)> = Closure::new(move |event: JsValue| {
+        if let Some(handler) = weak.upgrade() {
+            (handler.borrow_mut())(event);
+        }
+    });
+    add_listener(target, event, &closure);
+    ListenerGuard {
+        target: target.clone(),
+        event: event.to_string(),
+        closure,
+        _handler: handler,
+    }
+}
+
+/// A guard that keeps a handler registered with [`add_event_listener`] alive.
+///
+/// Dropping the guard removes the event listener and drops the handler.
+pub struct ListenerGuard {
+    target: JsValue,
+    event: String,
+    closure: Closure<dyn FnMut(JsValue)>,
+    _handler: Rc<RefCell<dyn FnMut(JsValue)>>,
+}
+
+impl Drop for ListenerGuard {
+    fn drop(&mut self) {
+        remove_listener(&self.target, &self.event, &self.closure);
+    }
+}
@@ -0,0 +1,266 @@
+//! Bindings to the Fetch API with full control over the request.
+
+use js_sys::{Promise, Uint8Array};
+use wasm_bindgen::{JsValue, wasm_bindgen};
+
+#[wasm_bindgen(inline_js = r#"
+export function new_headers() {
+    return new Headers();
+}
+
+export function new_request_init() {
+    return {};
+}
+
+export function new_request(url, init) {
+    return new Request(url, init);
+}
+
+export function fetch_with_request(request) {
+    return window.fetch(request);
+}
+
+export function fetch_with_str(url) {
+    return window.fetch(url);
+}
+
+export function new_abort_controller() {
+    return new AbortController();
+}
+
+export function new_form_data() {
+    return new FormData();
+}
+
+export function new_blob_with_text(text, type) {
+    return new Blob([text], { type });
+}
+"#)]
+extern "C" {
+    /// A set of HTTP headers for a [`Request`] or [`Response`].
+    pub type Headers;
+
+    /// Append a value to a header, keeping any existing values.
+    #[wasm_bindgen(method)]
+    pub fn append(this: &Headers, name: &str, value: &str);
+
+    /// Get all values of a header joined with `", "`, or `None` if it is not set.
+    #[wasm_bindgen(method)]
+    pub fn get(this: &Headers, name: &str) -> Option<String>;
+
+    /// Options used to build a [`Request`].
+    pub type RequestInit;
+
+    /// Set the HTTP method, e.g. `"POST"` or `"PUT"`.
+    #[wasm_bindgen(method, setter)]
+    pub fn set_method(this: &RequestInit, method: &str);
+
+    /// Set the headers sent with the request.
+    #[wasm_bindgen(method, setter)]
+    pub fn set_headers(this: &RequestInit, headers: &Headers);
+
+    /// Set the request body to any value `fetch` accepts as a body.
+    #[wasm_bindgen(method, setter)]
+    pub fn set_body(this: &RequestInit, body: &JsValue);
+
+    /// Set the request body to a string, such as serialized JSON.
+    #[wasm_bindgen(method, setter = body)]
+    pub fn set_body_str(this: &RequestInit, body: &str);
+
+    /// Set the request body to raw bytes.
+    #[wasm_bindgen(method, setter = body)]
+    pub fn set_body_u8_array(this: &RequestInit, body: &Uint8Array);
+
+    /// Set the request body to form data, sent as `multipart/form-data`.
+    ///
+    /// Do not set a `Content-Type` header yourself: `fetch` adds one with the multipart boundary.
+    #[wasm_bindgen(method, setter = body)]
+    pub fn set_body_form_data(this: &RequestInit, body: &FormData);
+
+    /// Set the signal that cancels the request when its [`AbortController`] aborts.
+    #[wasm_bindgen(method, setter)]
+    pub fn set_signal(this: &RequestInit, signal: &AbortSignal);
+
+    /// Cancels one or more requests through its [`AbortSignal`].
+    pub type AbortController;
+
+    /// The signal to pass to [`RequestInit::set_signal`].
+    #[wasm_bindgen(method, getter)]
+    pub fn signal(this: &AbortController) -> AbortSignal;
+
+    /// Abort every request using this controller's signal.
+    ///
+    /// Pending fetches reject with an `AbortError` `DOMException`.
+    #[wasm_bindgen(method)]
+    pub fn abort(this: &AbortController);
+
+    /// Reports whether its [`AbortController`] has aborted.
+    pub type AbortSignal;
+
+    /// Whether the controller has aborted.
+    #[wasm_bindgen(method, getter)]
+    pub fn aborted(this: &AbortSignal) -> bool;
+
+    /// Key/value pairs for a form submission, including files.
+    pub type FormData;
+
+    /// Append a text field, keeping any existing values for `key`.
+    #[wasm_bindgen(method)]
+    pub fn append(this: &FormData, key: &str, value: &str);
+
+    /// Append a file field with the contents of `blob`, sent with the given file name.
+    #[wasm_bindgen(method, js_name = append)]
+    pub fn append_blob(this: &FormData, key: &str, blob: &Blob, filename: &str);
+
+    /// Get the first value for `key`: a JS string for text fields, a `File` for blobs,
+    /// or `null` if the key is not present.
+    #[wasm_bindgen(method)]
+    pub fn get(this: &FormData, key: &str) -> JsValue;
+
+    /// Whether `key` is present.
+    #[wasm_bindgen(method)]
+    pub fn has(this: &FormData, key: &str) -> bool;
+
+    /// Remove every value for `key`.
+    #[wasm_bindgen(method)]
+    pub fn delete(this: &FormData, key: &str);
+
+    /// Immutable binary data, such as the contents of a file to upload.
+    pub type Blob;
+
+    /// The size of the data in bytes.
+    #[wasm_bindgen(method, getter)]
+    pub fn size(this: &Blob) -> f64;
+
+    /// The MIME type of the data, or an empty string if it is unknown.
+    #[wasm_bindgen(method, getter = "type")]
+    pub fn content_type(this: &Blob) -> String;
+
+    /// Read the data as a string. The promise resolves to a JS string.
+    #[wasm_bindgen(method)]
+    pub fn text(this: &Blob) -> Promise;
+
+    /// A request that can be sent with [`fetch_with_request`].
+    pub type Request;
+
+    /// The HTTP method of the request.
+    #[wasm_bindgen(method, getter)]
+    pub fn method(this: &Request) -> String;
+
+    /// The resolved URL of the request.
+    #[wasm_bindgen(method, getter)]
+    pub fn url(this: &Request) -> String;
+
+    /// The headers of the request.
+    #[wasm_bindgen(method, getter)]
+    pub fn headers(this: &Request) -> Headers;
+
+    /// The response to a request, resolved from the promise returned by [`fetch_with_request`].
+    pub type Response;
+
+    /// The HTTP status code of the response.
+    #[wasm_bindgen(method, getter)]
+    pub fn status(this: &Response) -> u16;
+
+    /// Whether the status code is in the 200-299 range.
+    #[wasm_bindgen(method, getter)]
+    pub fn ok(this: &Response) -> bool;
+
+    /// The headers of the response.
+    #[wasm_bindgen(method, getter)]
+    pub fn headers(this: &Response) -> Headers;
+
+    /// Read the response body as a string. The promise resolves to a JS string.
+    #[wasm_bindgen(method)]
+    pub fn text(this: &Response) -> Promise;
+
+    /// Read the response body as parsed JSON.
+    #[wasm_bindgen(method)]
+    pub fn json(this: &Response) -> Promise;
+
+    fn new_headers() -> Headers;
+
+    fn new_request_init() -> RequestInit;
+
+    fn new_request(url: &str, init: &RequestInit) -> Request;
+
+    fn new_abort_controller() -> AbortController;
+
+    fn new_form_data() -> FormData;
+
+    fn new_blob_with_text(text: &str, content_type: &str) -> Blob;
+
+    /// Send a request with `window.fetch`.
+    ///
+    /// The returned promise resolves to a [`Response`] and can be awaited with `JsFuture`.
+    pub fn fetch_with_request(request: &Request) -> Promise;
+
+    /// Send a `GET` request to `url` with `window.fetch`.
+    pub fn fetch_with_str(url: &str) -> Promise;
+}
+
+impl Headers {
+    /// Create an empty set of headers.
+    pub fn new() -> Headers {
+        new_headers()
+    }
+}
+
+impl Default for Headers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RequestInit {
+    /// Create empty request options. `fetch` defaults to a `GET` with no body.
+    pub fn new() -> RequestInit {
+        new_request_init()
+    }
+}
+
+impl Default for RequestInit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AbortController {
+    /// Create a controller that has not aborted yet.
+    pub fn new() -> AbortController {
+        new_abort_controller()
+    }
+}
+
+impl Default for AbortController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormData {
+    /// Create empty form data.
+    pub fn new() -> FormData {
+        new_form_data()
+    }
+}
+
+impl Default for FormData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Blob {
+    /// Create a blob holding `text` encoded as UTF-8, with the given MIME type.
+    pub fn new_with_text(text: &str, content_type: &str) -> Blob {
+        new_blob_with_text(text, content_type)
+    }
+}
+
+impl Request {
+    /// Create a request for `url` with the given options.
+    pub fn new_with_init(url: &str, init: &RequestInit) -> Request {
+        new_request(url, init)
+    }
+}
@@ -0,0 +1,67 @@
+//! Bindings to `navigator.geolocation`.
+
+use js_sys::Promise;
+use wasm_bindgen::{Closure, wasm_bindgen};
+
+#[wasm_bindgen(inline_js = r#"
+export function get_current_position() {
+    return new Promise((resolve, reject) => {
+        if (!navigator.geolocation) {
+            reject(new Error("geolocation is not supported"));
+            return;
+        }
+        navigator.geolocation.getCurrentPosition(resolve, reject);
+    });
+}
+
+export function watch_position(callback) {
+    return navigator.geolocation.watchPosition(callback);
+}
+
+export function clear_watch(id) {
+    navigator.geolocation.clearWatch(id);
+}
+"#)]
+extern "C" {
+    /// A position reported by [`get_current_position`] or [`watch_position`].
+    pub type Position;
+
+    /// The coordinates of the position.
+    #[wasm_bindgen(method, getter)]
+    pub fn coords(this: &Position) -> Coordinates;
+
+    /// The time the position was acquired, in milliseconds since the Unix epoch.
+    #[wasm_bindgen(method, getter)]
+    pub fn timestamp(this: &Position) -> f64;
+
+    /// The coordinates of a [`Position`].
+    pub type Coordinates;
+
+    /// The latitude in decimal degrees.
+    #[wasm_bindgen(method, getter)]
+    pub fn latitude(this: &Coordinates) -> f64;
+
+    /// The longitude in decimal degrees.
+    #[wasm_bindgen(method, getter)]
+    pub fn longitude(this: &Coordinates) -> f64;
+
+    /// The accuracy of the latitude and longitude in meters.
+    #[wasm_bindgen(method, getter)]
+    pub fn accuracy(this: &Coordinates) -> f64;
+
+    /// Get the current position of the device with `navigator.geolocation.getCurrentPosition`.
+    ///
+    /// The returned promise resolves to a [`Position`] and can be awaited with `JsFuture`.
+    /// It rejects if the user denies the permission, the position is unavailable, or the
+    /// webview does not support geolocation.
+    pub fn get_current_position() -> Promise;
+
+    /// Call `callback` each time the position of the device changes.
+    ///
+    /// Returns a watch id that can be passed to [`clear_watch`] to stop watching. The
+    /// closure must be kept alive until the watch is cleared.
+    pub fn watch_position(callback: &Closure<dyn FnMut(Position)>) -> i32;
+
+    /// Stop a watch started with [`watch_position`].
+    pub fn clear_watch(id: i32);
+}
@@ -1,15 +1,32 @@
-pub fn root_response() -> wry::http::Response<Vec<u8>> {
+use wry::http::{Request, Response};
+
+/// Serve a custom protocol request that wry-bindgen does not handle.
+///
+/// Requests are offered to the app's own handler first. Every other path serves the
+/// root page.
+pub fn app_response(
+    request: &Request<Vec<u8>>,
+    handler: Option<&AppProtocolHandler>,
+) -> Response<Vec<u8>> {
+    handler
+        .and_then(|handler| handler(request))
+        .unwrap_or_else(root_response)
+}
+
+/// Answers custom protocol requests for the app, or returns `None` to serve the root page
+pub(crate) type AppProtocolHandler = Box<dyn Fn(&Request<Vec<u8>>) -> Option<Response<Vec<u8>>>>;
+
+pub fn root_response() -> Response<Vec<u8>> {
     let html = r#"<!DOCTYPE html>
 <html>
 <head>
     <title>Wry Test</title>
-    <script src="/__wbg__/init.js"></script>
 </head>
 <body>
 </body>
 </html>"#;
 
-    wry::http::Response::builder()
+    Response::builder()
         .header("Content-Type", "text/html")
         .header("access-control-allow-origin", "*")
         .body(html.as_bytes().to_vec())
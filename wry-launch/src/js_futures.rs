@@ -0,0 +1,33 @@
+//! Combinators for awaiting several JS promises at once.
+//!
+//! Each [`JsFuture`] registers its settle callbacks when it is created, so the
+//! promises run concurrently in JS. The combinators only poll the futures as their
+//! callbacks arrive and never make calls of their own.
+
+use futures_util::future;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+
+/// Wait for every future to settle and collect the results in the order given.
+///
+/// Unlike `Promise.all`, a rejection does not end the wait early: every rejection is
+/// returned as an `Err` in its position.
+pub fn join_all(
+    futs: Vec<JsFuture>,
+) -> impl Future<Output = Vec<Result<JsValue, JsValue>>> + 'static {
+    future::join_all(futs)
+}
+
+/// Wait for the first future to settle and return its result and its index in `futs`.
+///
+/// Like `Promise.race`, a rejection that settles first wins. The remaining futures are
+/// dropped, which ignores their results but does not cancel the promises in JS.
+///
+/// # Panics
+///
+/// Panics if `futs` is empty.
+pub async fn race(futs: Vec<JsFuture>) -> (Result<JsValue, JsValue>, usize) {
+    assert!(!futs.is_empty(), "race needs at least one future");
+    let (result, index, _) = future::select_all(futs).await;
+    (result, index)
+}
@@ -7,13 +7,34 @@ use tao::dpi::LogicalSize;
 use tao::event_loop::EventLoopBuilder;
 
 use wasm_bindgen::Closure;
-use wasm_bindgen::wry::WryBindgen;
+#[cfg(feature = "test-harness")]
+use wasm_bindgen::console_capture::ConsoleSink;
+use wasm_bindgen::wry::{Transport, WryBindgen};
 
+pub mod animation;
+pub mod audio;
 pub mod bindings;
+pub mod console;
+pub mod crypto;
+pub mod dialog;
+pub mod document;
+pub mod events;
+pub mod fetch;
+pub mod geolocation;
 mod home;
+pub mod js_futures;
+pub mod main_thread;
+pub mod media;
+pub mod navigator;
+pub mod notification;
+pub mod observer;
+pub mod shutdown;
+pub mod url;
 mod webview;
+pub mod window;
 
-use webview::{WryEvent, run_event_loop};
+use home::AppProtocolHandler;
+use webview::{AppThreadConfig, AttachedApp, WryEvent, run_event_loop};
 
 // Re-export bindings for convenience
 pub use bindings::set_on_log;
@@ -55,6 +76,13 @@ pub use wry::WebViewBuilder;
 pub struct LaunchBuilder {
     window: WindowBuilder,
     webview: WebViewBuilder<'static>,
+    thread_name: Option<String>,
+    stack_size: Option<usize>,
+    transport: Transport,
+    #[cfg(feature = "test-harness")]
+    console_sink: Option<Box<dyn ConsoleSink>>,
+    attached: Vec<AttachedApp>,
+    app_protocol: Option<AppProtocolHandler>,
 }
 
 impl Default for LaunchBuilder {
@@ -71,6 +99,13 @@ impl LaunchBuilder {
                 .with_title("wry-launch")
                 .with_inner_size(LogicalSize::new(800.0, 600.0)),
             webview: WebViewBuilder::new().with_devtools(true),
+            thread_name: None,
+            stack_size: None,
+            transport: Transport::default(),
+            #[cfg(feature = "test-harness")]
+            console_sink: None,
+            attached: Vec::new(),
+            app_protocol: None,
         }
     }
 
@@ -112,6 +147,73 @@ impl LaunchBuilder {
         self
     }
 
+    /// Set the name of the thread the app runs on.
+    ///
+    /// The name shows up in debuggers, panic messages and crash reports. Defaults to
+    /// `"wry-bindgen-app-{id}"`, where `id` identifies the webview.
+    pub fn thread_name(mut self, name: impl Into<String>) -> Self {
+        self.thread_name = Some(name.into());
+        self
+    }
+
+    /// Set the stack size, in bytes, of the thread the app runs on.
+    ///
+    /// Raise this if deeply nested chains of callbacks between Rust and JS overflow the
+    /// default stack.
+    pub fn stack_size(mut self, bytes: usize) -> Self {
+        self.stack_size = Some(bytes);
+        self
+    }
+
+    /// Set how Rust sends calls to JS.
+    ///
+    /// Defaults to [`Transport::CustomProtocol`]. See [`Transport`] for the trade-offs.
+    pub fn transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Forward the webview's console output to `sink`.
+    ///
+    /// This is intended for tests that assert on logged output, so it is only available
+    /// with the `test-harness` feature. See [`WryBindgen::set_console_sink`] for details.
+    #[cfg(feature = "test-harness")]
+    pub fn console_sink(mut self, sink: impl ConsoleSink + 'static) -> Self {
+        self.console_sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Answer requests to the page's own origin with `handler`.
+    ///
+    /// wry-bindgen's own endpoints are handled first. Every other request is passed to
+    /// `handler`, and the root page is served when it returns `None`. Use this to serve
+    /// assets or fake endpoints for `fetch`.
+    pub fn app_protocol(
+        mut self,
+        handler: impl Fn(&wry::http::Request<Vec<u8>>) -> Option<wry::http::Response<Vec<u8>>> + 'static,
+    ) -> Self {
+        self.app_protocol = Some(Box::new(handler));
+        self
+    }
+
+    /// Run another future next to the app, sharing its webview.
+    ///
+    /// This suits plugin architectures where independent tasks each talk to JS. The
+    /// futures run on the app thread and their JS calls are multiplexed over the same
+    /// IPC channel. The app keeps running until every attached future has completed.
+    /// Closures and exported objects an attached future gives to JS are dropped when it
+    /// completes, see [`AppBuilder::attach`](wasm_bindgen::wry::AppBuilder::attach).
+    pub fn attach<F, Fut>(mut self, app: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + 'static,
+    {
+        self.attached.push(Box::new(move || {
+            Box::pin(app()) as std::pin::Pin<Box<dyn std::future::Future<Output = ()>>>
+        }));
+        self
+    }
+
     /// Run the application with the configured settings.
     pub fn run<F, Fut>(self, app: F) -> wry::Result<()>
     where
@@ -126,7 +228,14 @@ impl LaunchBuilder {
             set_on_log(Closure::new(|msg: String| {
                 println!("[JS] {msg}");
             }));
-            app().await
+
+            // Closing the window stops the app after its before-unload hook has run
+            tokio::select! {
+                _ = app() => {}
+                _ = shutdown::close_requested() => {
+                    shutdown::before_unload().await;
+                }
+            }
         };
 
         let event_loop = EventLoopBuilder::<WryEvent>::with_user_event().build();
@@ -139,9 +248,26 @@ impl LaunchBuilder {
             }
         };
 
-        let wry_bindgen = WryBindgen::new(event_loop_proxy);
+        let wry_bindgen = WryBindgen::new_with_transport(event_loop_proxy, self.transport)?;
+        #[cfg(feature = "test-harness")]
+        if let Some(sink) = self.console_sink {
+            wry_bindgen.set_console_sink(sink);
+        }
 
-        run_event_loop(event_loop, wry_bindgen, app, self.window, self.webview);
+        let app_thread = AppThreadConfig {
+            name: self.thread_name,
+            stack_size: self.stack_size,
+        };
+        run_event_loop(
+            event_loop,
+            wry_bindgen,
+            app,
+            self.attached,
+            app_thread,
+            self.window,
+            self.webview,
+            self.app_protocol,
+        );
 
         Ok(())
     }
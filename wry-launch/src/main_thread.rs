@@ -0,0 +1,101 @@
+//! Running closures on the event loop thread from the app thread.
+//!
+//! The app future runs on its own thread, but some platform APIs (menus, dialogs,
+//! window handles) must be used from the thread that runs the event loop.
+
+use std::fmt;
+use std::sync::Mutex;
+use std::sync::mpsc;
+
+use tao::event_loop::EventLoopProxy;
+
+use crate::webview::WryEvent;
+
+/// The proxy of the running event loop, set once the event loop starts
+static EVENT_LOOP: Mutex<Option<EventLoopProxy<WryEvent>>> = Mutex::new(None);
+
+/// Make tasks sent with [`try_run_on_main_thread`] run on `proxy`'s event loop.
+pub(crate) fn set_event_loop(proxy: EventLoopProxy<WryEvent>) {
+    *EVENT_LOOP.lock().unwrap() = Some(proxy);
+}
+
+/// A closure sent to the event loop thread.
+pub(crate) struct MainThreadTask(Box<dyn FnOnce() + Send>);
+
+impl MainThreadTask {
+    /// Run the task. Called by the event loop.
+    pub(crate) fn run(self) {
+        (self.0)()
+    }
+}
+
+impl fmt::Debug for MainThreadTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MainThreadTask")
+    }
+}
+
+/// Error returned by [`try_run_on_main_thread`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MainThreadError {
+    /// The event loop is not running, or it shut down before the task completed.
+    Disconnected,
+}
+
+impl fmt::Display for MainThreadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MainThreadError::Disconnected => f.write_str("the main thread is no longer running"),
+        }
+    }
+}
+
+impl std::error::Error for MainThreadError {}
+
+/// Run `f` on the event loop thread and wait for its result.
+///
+/// Returns [`MainThreadError::Disconnected`] instead of panicking if the event loop
+/// is not running or shuts down before running `f`, for example when a background
+/// task races with the window closing.
+///
+/// This blocks the calling thread until `f` has run. Do not call it from the event
+/// loop thread itself.
+pub fn try_run_on_main_thread<T, F>(f: F) -> Result<T, MainThreadError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::sync_channel(1);
+    let task = MainThreadTask(Box::new(move || {
+        // The caller may have given up waiting, so a failed send is fine
+        let _ = tx.send(f());
+    }));
+    send_event(WryEvent::RunOnMainThread(task))?;
+    // If the event loop exits first, the task is dropped along with the sender
+    rx.recv().map_err(|_| MainThreadError::Disconnected)
+}
+
+/// Send an event to the running event loop.
+pub(crate) fn send_event(event: WryEvent) -> Result<(), MainThreadError> {
+    let proxy = EVENT_LOOP
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or(MainThreadError::Disconnected)?;
+    proxy
+        .send_event(event)
+        .map_err(|_| MainThreadError::Disconnected)
+}
+
+/// Run `f` on the event loop thread and wait for its result.
+///
+/// # Panics
+/// Panics if the event loop is not running. Use [`try_run_on_main_thread`] where the
+/// event loop may shut down first.
+pub fn run_on_main_thread<T, F>(f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    try_run_on_main_thread(f).expect("Main thread did not complete the task")
+}
@@ -0,0 +1,38 @@
+//! Media query conveniences.
+
+use wasm_bindgen::wasm_bindgen;
+
+#[wasm_bindgen(inline_js = r#"
+export function preferred_color_scheme() {
+    if (window.matchMedia("(prefers-color-scheme: dark)").matches) {
+        return "dark";
+    }
+    if (window.matchMedia("(prefers-color-scheme: light)").matches) {
+        return "light";
+    }
+    return "no-preference";
+}
+"#)]
+extern "C" {
+    fn preferred_color_scheme() -> String;
+}
+
+/// The color scheme the user prefers, from the `prefers-color-scheme` media feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorScheme {
+    /// The user prefers a light theme.
+    Light,
+    /// The user prefers a dark theme.
+    Dark,
+    /// The user has not expressed a preference.
+    NoPreference,
+}
+
+/// Get the user's preferred color scheme without constructing a `MediaQueryList`.
+pub fn prefers_color_scheme() -> ColorScheme {
+    match preferred_color_scheme().as_str() {
+        "dark" => ColorScheme::Dark,
+        "light" => ColorScheme::Light,
+        _ => ColorScheme::NoPreference,
+    }
+}
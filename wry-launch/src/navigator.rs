@@ -0,0 +1,47 @@
+//! Bindings to `window.navigator` APIs.
+
+use js_sys::Promise;
+use wasm_bindgen::wasm_bindgen;
+
+#[wasm_bindgen(inline_js = r#"
+export function permissions_query(name) {
+    // Denials and unsupported permissions resolve with a "denied" status instead of rejecting
+    const denied = { name, state: "denied" };
+    if (!navigator.permissions) {
+        return Promise.resolve(denied);
+    }
+    return navigator.permissions.query({ name }).catch(() => denied);
+}
+
+export function send_beacon(url, data) {
+    // sendBeacon throws for URLs it cannot send to, such as non-HTTP schemes
+    try {
+        return navigator.sendBeacon(url, data);
+    } catch {
+        return false;
+    }
+}
+"#)]
+extern "C" {
+    /// The status of a permission returned by [`permissions_query`].
+    pub type PermissionStatus;
+
+    /// The state of the permission: `"granted"`, `"denied"` or `"prompt"`.
+    #[wasm_bindgen(method, getter)]
+    pub fn state(this: &PermissionStatus) -> String;
+
+    /// Query the state of a permission by name with `navigator.permissions.query`.
+    ///
+    /// The returned promise resolves to a [`PermissionStatus`] and can be awaited with
+    /// `JsFuture`. Permissions that are denied or not supported by the webview resolve
+    /// with a `"denied"` state instead of rejecting.
+    pub fn permissions_query(name: &str) -> Promise;
+
+    /// Queue a POST of `data` to `url` with `navigator.sendBeacon`.
+    ///
+    /// The request is sent in the background and outlives the page, so it suits
+    /// telemetry that should not delay shutdown. The response is never observed. Returns
+    /// whether the webview queued the request: `false` if the payload is over the
+    /// webview's beacon quota or `url` is not an HTTP(S) URL it can send to.
+    pub fn send_beacon(url: &str, data: &[u8]) -> bool;
+}
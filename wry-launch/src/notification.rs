@@ -0,0 +1,30 @@
+//! Bindings to the `Notification` API.
+
+use js_sys::Promise;
+use wasm_bindgen::wasm_bindgen;
+
+#[wasm_bindgen(inline_js = r#"
+export function request_notification_permission() {
+    if (typeof Notification === "undefined") {
+        return Promise.resolve("denied");
+    }
+    return Promise.resolve(Notification.requestPermission()).catch(() => "denied");
+}
+"#)]
+extern "C" {
+    /// A JavaScript `Notification`.
+    pub type Notification;
+
+    fn request_notification_permission() -> Promise;
+}
+
+impl Notification {
+    /// Ask the user for permission to show notifications with `Notification.requestPermission`.
+    ///
+    /// The returned promise resolves to `"granted"`, `"denied"` or `"default"` and can be
+    /// awaited with `JsFuture`. If the webview does not support notifications it resolves
+    /// with `"denied"`.
+    pub fn request_permission() -> Promise {
+        request_notification_permission()
+    }
+}
@@ -0,0 +1,189 @@
+//! Bindings to `MutationObserver` for DOM change notifications and `IntersectionObserver`
+//! for visibility changes.
+
+use wasm_bindgen::{Closure, JsArray, JsValue, wasm_bindgen};
+
+use crate::document::Element;
+
+#[wasm_bindgen(inline_js = r#"
+export function new_observer_init() {
+    return {};
+}
+
+export function new_mutation_observer(callback) {
+    return new MutationObserver((records) => callback(records));
+}
+
+export function observe(observer, target, options) {
+    observer.observe(target, options);
+}
+
+export function disconnect(observer) {
+    observer.disconnect();
+}
+
+export function new_intersection_observer(callback) {
+    return new IntersectionObserver((entries) => callback(entries));
+}
+
+export function observe_intersection(observer, target) {
+    observer.observe(target);
+}
+
+export function unobserve_intersection(observer, target) {
+    observer.unobserve(target);
+}
+"#)]
+extern "C" {
+    /// Options that choose which changes a [`MutationObserver`] reports.
+    ///
+    /// At least one of `childList`, `attributes` or `characterData` must be set, or
+    /// [`MutationObserver::observe`] throws in JS.
+    pub type ObserverInit;
+
+    /// Report children being added or removed.
+    #[wasm_bindgen(method, setter = childList)]
+    pub fn set_child_list(this: &ObserverInit, value: bool);
+
+    /// Report attribute changes.
+    #[wasm_bindgen(method, setter)]
+    pub fn set_attributes(this: &ObserverInit, value: bool);
+
+    /// Report changes to the text of character data nodes.
+    #[wasm_bindgen(method, setter = characterData)]
+    pub fn set_character_data(this: &ObserverInit, value: bool);
+
+    /// Report changes in the whole subtree, not just the target itself.
+    #[wasm_bindgen(method, setter)]
+    pub fn set_subtree(this: &ObserverInit, value: bool);
+
+    fn new_observer_init() -> ObserverInit;
+
+    fn new_mutation_observer(callback: &Closure<dyn FnMut(JsArray)>) -> JsValue;
+
+    fn observe(observer: &JsValue, target: &Element, options: &ObserverInit);
+
+    fn disconnect(observer: &JsValue);
+
+    /// A change in how much of an element observed by an [`IntersectionObserver`] is visible.
+    ///
+    /// The callback receives these in a [`JsArray`]; cast each element with
+    /// `JsCast::unchecked_into`.
+    pub type IntersectionObserverEntry;
+
+    /// Whether any part of the element is visible.
+    #[wasm_bindgen(method, getter = isIntersecting)]
+    pub fn is_intersecting(this: &IntersectionObserverEntry) -> bool;
+
+    /// The visible fraction of the element, from `0.0` to `1.0`.
+    #[wasm_bindgen(method, getter = intersectionRatio)]
+    pub fn intersection_ratio(this: &IntersectionObserverEntry) -> f64;
+
+    /// The element whose visibility changed.
+    #[wasm_bindgen(method, getter)]
+    pub fn target(this: &IntersectionObserverEntry) -> Element;
+
+    fn new_intersection_observer(callback: &Closure<dyn FnMut(JsArray)>) -> JsValue;
+
+    fn observe_intersection(observer: &JsValue, target: &Element);
+
+    fn unobserve_intersection(observer: &JsValue, target: &Element);
+}
+
+impl ObserverInit {
+    /// Create options that report nothing. Set at least one kind of change to observe.
+    pub fn new() -> ObserverInit {
+        new_observer_init()
+    }
+}
+
+impl Default for ObserverInit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Watches elements for DOM changes with a JS `MutationObserver`.
+///
+/// The observer owns its callback, so the callback stays alive until the observer is
+/// dropped. Dropping the observer disconnects it.
+pub struct MutationObserver {
+    observer: JsValue,
+    _callback: Closure<dyn FnMut(JsArray)>,
+}
+
+impl MutationObserver {
+    /// Create an observer that calls `cb` with an array of `MutationRecord`s.
+    ///
+    /// JS batches mutations, so one call may report several changes.
+    pub fn new(cb: Closure<dyn FnMut(JsArray)>) -> MutationObserver {
+        let observer = new_mutation_observer(&cb);
+        MutationObserver {
+            observer,
+            _callback: cb,
+        }
+    }
+
+    /// Start reporting the changes selected by `options` on `target`.
+    ///
+    /// Observing the same target again replaces its options.
+    pub fn observe(&self, target: &Element, options: &ObserverInit) {
+        observe(&self.observer, target, options);
+    }
+
+    /// Stop reporting changes on every target. Records that were not delivered yet
+    /// are discarded.
+    pub fn disconnect(&self) {
+        disconnect(&self.observer);
+    }
+}
+
+impl Drop for MutationObserver {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+/// Watches elements entering and leaving the viewport with a JS `IntersectionObserver`.
+///
+/// The observer owns its callback, so the callback stays alive until the observer is
+/// dropped. Dropping the observer disconnects it.
+pub struct IntersectionObserver {
+    observer: JsValue,
+    _callback: Closure<dyn FnMut(JsArray)>,
+}
+
+impl IntersectionObserver {
+    /// Create an observer that calls `cb` with an array of [`IntersectionObserverEntry`]s.
+    ///
+    /// The callback also runs once for each element when it starts being observed,
+    /// reporting its initial visibility.
+    pub fn new(cb: Closure<dyn FnMut(JsArray)>) -> IntersectionObserver {
+        let observer = new_intersection_observer(&cb);
+        IntersectionObserver {
+            observer,
+            _callback: cb,
+        }
+    }
+
+    /// Start reporting visibility changes of `target`.
+    pub fn observe(&self, target: &Element) {
+        observe_intersection(&self.observer, target);
+    }
+
+    /// Stop reporting visibility changes of `target`.
+    pub fn unobserve(&self, target: &Element) {
+        unobserve_intersection(&self.observer, target);
+    }
+
+    /// Stop reporting visibility changes of every target.
+    pub fn disconnect(&self) {
+        disconnect(&self.observer);
+    }
+}
+
+impl Drop for IntersectionObserver {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
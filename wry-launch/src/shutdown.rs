@@ -0,0 +1,91 @@
+//! Coordinating window close with async cleanup in the app.
+//!
+//! When the user closes the window, the event loop asks the app to run the hook
+//! registered with [`on_before_unload`] before the webview is torn down.
+
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+use crate::main_thread::MainThreadError;
+use crate::webview::WryEvent;
+
+type BeforeUnloadHook = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()>>> + Send>;
+
+/// The registered hook and its timeout. Any thread can register it, and the app thread
+/// runs it when the window closes.
+static BEFORE_UNLOAD: Mutex<Option<(Duration, BeforeUnloadHook)>> = Mutex::new(None);
+
+/// Told when the hook has finished, one for each close waiting for it
+static UNLOAD_FINISHED: Mutex<Vec<mpsc::Sender<()>>> = Mutex::new(Vec::new());
+
+/// Notified by the event loop when the user asks to close the window
+static CLOSE_REQUESTED: Notify = Notify::const_new();
+
+/// Register an async hook that runs when the user closes the window.
+///
+/// Closing the window waits for the hook to finish, or for `timeout` to elapse,
+/// before the webview is torn down and the app future is dropped. The hook runs on
+/// the app thread, so it can call into JS to save state. Registering a new hook
+/// replaces the previous one.
+///
+/// # Example
+///
+/// ```ignore
+/// wry_launch::shutdown::on_before_unload(Duration::from_secs(2), || async {
+///     save_document().await;
+/// });
+/// ```
+pub fn on_before_unload<F, Fut>(timeout: Duration, hook: F)
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + 'static,
+{
+    let hook: BeforeUnloadHook = Box::new(move || Box::pin(hook()));
+    *BEFORE_UNLOAD.lock().unwrap() = Some((timeout, hook));
+}
+
+/// Run the hook registered with [`on_before_unload`], if any.
+///
+/// This is what the close flow awaits. The hook is removed, so it runs at most once.
+/// Returns `false` if the hook did not finish within its timeout.
+pub async fn before_unload() -> bool {
+    let registered = BEFORE_UNLOAD.lock().unwrap().take();
+    let finished = match registered {
+        Some((timeout, hook)) => tokio::time::timeout(timeout, hook()).await.is_ok(),
+        None => true,
+    };
+    // Let a waiting close go ahead without waiting for the rest of its timeout
+    for unload_finished in UNLOAD_FINISHED.lock().unwrap().drain(..) {
+        let _ = unload_finished.send(());
+    }
+    finished
+}
+
+/// Close the window as if the user had clicked its close button.
+///
+/// The hook registered with [`on_before_unload`] runs first, so this suits an in-app
+/// "Quit" action that should save state like closing the window does.
+pub fn request_window_close() -> Result<(), MainThreadError> {
+    crate::main_thread::send_event(WryEvent::CloseRequested)
+}
+
+/// Ask the app thread to run its before-unload hook.
+///
+/// Returns the hook's timeout and a receiver that is told when the hook has finished,
+/// or `None` if no hook is registered.
+pub(crate) fn request_close() -> Option<(Duration, mpsc::Receiver<()>)> {
+    let timeout = BEFORE_UNLOAD.lock().unwrap().as_ref()?.0;
+    let (unload_finished, wait_for_unload) = mpsc::channel();
+    UNLOAD_FINISHED.lock().unwrap().push(unload_finished);
+    CLOSE_REQUESTED.notify_one();
+    Some((timeout, wait_for_unload))
+}
+
+/// Wait until the event loop asks the app to close.
+pub(crate) async fn close_requested() {
+    CLOSE_REQUESTED.notified().await;
+}
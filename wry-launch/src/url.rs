@@ -0,0 +1,56 @@
+//! Bindings to `URLSearchParams` for parsing and building query strings.
+
+use wasm_bindgen::wasm_bindgen;
+
+#[wasm_bindgen(inline_js = r#"
+export function new_url_search_params(init) {
+    return new URLSearchParams(init);
+}
+"#)]
+extern "C" {
+    /// The key/value pairs of a query string, parsed and serialized per the URL spec.
+    pub type URLSearchParams;
+
+    /// Get the first value for `key`, or `None` if it is not present.
+    #[wasm_bindgen(method)]
+    pub fn get(this: &URLSearchParams, key: &str) -> Option<String>;
+
+    /// Get every value for `key` in the order they appear.
+    #[wasm_bindgen(method, js_name = getAll)]
+    pub fn get_all(this: &URLSearchParams, key: &str) -> Vec<String>;
+
+    /// Whether `key` is present.
+    #[wasm_bindgen(method)]
+    pub fn has(this: &URLSearchParams, key: &str) -> bool;
+
+    /// Set `key` to `value`, replacing any existing values.
+    #[wasm_bindgen(method)]
+    pub fn set(this: &URLSearchParams, key: &str, value: &str);
+
+    /// Add a value for `key`, keeping any existing values.
+    #[wasm_bindgen(method)]
+    pub fn append(this: &URLSearchParams, key: &str, value: &str);
+
+    /// Remove every value for `key`.
+    #[wasm_bindgen(method)]
+    pub fn delete(this: &URLSearchParams, key: &str);
+
+    /// Serialize the parameters as a query string without the leading `?`.
+    #[wasm_bindgen(method, js_name = toString)]
+    pub fn to_string(this: &URLSearchParams) -> String;
+
+    fn new_url_search_params(init: &str) -> URLSearchParams;
+}
+
+impl URLSearchParams {
+    /// Parse a query string such as `"a=1&b=2"`. A leading `?` is ignored.
+    pub fn new(init: &str) -> URLSearchParams {
+        new_url_search_params(init)
+    }
+}
+
+impl Default for URLSearchParams {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
@@ -1,13 +1,16 @@
+use std::pin::Pin;
+
 use tao::{
     event::{Event, WindowEvent},
-    event_loop::{ControlFlow, EventLoop},
+    event_loop::{ControlFlow, EventLoop, EventLoopProxy},
     window::WindowBuilder,
 };
 use wry::WebViewBuilder;
 
-use wasm_bindgen::{runtime::WryBindgenEvent, wry::WryBindgen};
+use wasm_bindgen::{initialization_script, runtime::WryBindgenEvent, wry::WryBindgen};
 
-use crate::home::root_response;
+use crate::home::{AppProtocolHandler, app_response};
+use crate::main_thread::MainThreadTask;
 
 /// Event type for the wry-launch event loop.
 /// Wraps wry-bindgen's AppEvent and adds application-level events.
@@ -17,6 +20,10 @@ pub(crate) enum WryEvent {
     App(WryBindgenEvent),
     /// Shutdown the event loop
     Shutdown,
+    /// Run a closure sent from another thread on the event loop thread
+    RunOnMainThread(MainThreadTask),
+    /// The app asked to close the window, like the user clicking its close button
+    CloseRequested,
 }
 
 // Each platform has a different custom protocol scheme
@@ -31,25 +38,48 @@ pub const BASE_URL: &str = "wry://index.html";
 
 const PROTOCOL_SCHEME: &str = "wry";
 
+/// Extra time given to the app thread to shut down after the before-unload hook times out
+const CLOSE_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// A future that shares the webview with the main app future
+pub(crate) type AttachedApp = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()>>> + Send>;
+
+/// Settings for the thread the app future runs on
+pub(crate) struct AppThreadConfig {
+    pub(crate) name: Option<String>,
+    pub(crate) stack_size: Option<usize>,
+}
+
 pub(crate) fn run_event_loop<F>(
     event_loop: EventLoop<WryEvent>,
     wry_bindgen: WryBindgen,
     app: impl FnOnce() -> F + Send + 'static,
+    attached: Vec<AttachedApp>,
+    app_thread: AppThreadConfig,
     window_builder: WindowBuilder,
     webview_builder: WebViewBuilder<'static>,
+    app_protocol: Option<AppProtocolHandler>,
 ) where
     F: Future<Output = ()> + 'static,
 {
     let window = window_builder.build(&event_loop).unwrap();
 
     let proxy = event_loop.create_proxy();
+    crate::main_thread::set_event_loop(proxy.clone());
     let proxy_clone = proxy.clone();
+    let proxy_for_close = proxy.clone();
 
-    let app_builder = wry_bindgen.app_builder();
+    let app_builder = attached
+        .into_iter()
+        .fold(wry_bindgen.app_builder(), |app_builder, attached| {
+            app_builder.attach(attached)
+        });
     let protocol_handler = app_builder.protocol_handler();
 
-    // Add the required protocol handler and URL to the user-provided webview builder
+    // Add the required protocol handler, init script and URL to the user-provided webview builder.
+    // The init script is registered as an initialization script so it runs before any page scripts.
     let builder = webview_builder
+        .with_initialization_script(initialization_script())
         .with_asynchronous_custom_protocol(PROTOCOL_SCHEME.into(), move |_, request, responder| {
             let responder = |response| responder.respond(response);
             let send_app_event = |event| {
@@ -65,7 +95,7 @@ pub(crate) fn run_event_loop<F>(
                 return;
             };
 
-            responder(root_response())
+            responder(app_response(&request, app_protocol.as_ref()))
         })
         .with_url(BASE_URL);
 
@@ -85,15 +115,24 @@ pub(crate) fn run_event_loop<F>(
     };
     let run_app = app_builder.build(app, evaluate_script);
 
-    std::thread::spawn(move || {
-        tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .unwrap()
-            .block_on(run_app.into_future());
-        // Signal the event loop to exit after app completes
-        let _ = proxy.send_event(WryEvent::Shutdown);
-    });
+    let thread_name = app_thread
+        .name
+        .unwrap_or_else(|| format!("wry-bindgen-app-{}", run_app.id()));
+    let mut thread_builder = std::thread::Builder::new().name(thread_name);
+    if let Some(stack_size) = app_thread.stack_size {
+        thread_builder = thread_builder.stack_size(stack_size);
+    }
+    thread_builder
+        .spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(run_app.into_future());
+            // Signal the event loop to exit after app completes
+            let _ = proxy.send_event(WryEvent::Shutdown);
+        })
+        .expect("Failed to spawn the app thread");
 
     event_loop.run(move |event, _, control_flow| {
         *control_flow = ControlFlow::Wait;
@@ -102,9 +141,7 @@ pub(crate) fn run_event_loop<F>(
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 ..
-            } => {
-                std::process::exit(0);
-            }
+            } => close_window(&proxy_for_close),
             Event::UserEvent(wry_event) => match wry_event {
                 WryEvent::Shutdown => {
                     *control_flow = ControlFlow::Exit;
@@ -112,8 +149,23 @@ pub(crate) fn run_event_loop<F>(
                 WryEvent::App(app_event) => {
                     wry_bindgen.handle_user_event(app_event);
                 }
+                WryEvent::RunOnMainThread(task) => task.run(),
+                WryEvent::CloseRequested => close_window(&proxy_for_close),
             },
             _ => {}
         }
     });
 }
+
+/// Let the app run its before-unload hook, then shut down the event loop.
+fn close_window(proxy: &EventLoopProxy<WryEvent>) {
+    let Some((timeout, wait_for_unload)) = crate::shutdown::request_close() else {
+        std::process::exit(0);
+    };
+    // Close as soon as the hook finishes, or anyway if it hangs
+    let proxy = proxy.clone();
+    std::thread::spawn(move || {
+        let _ = wait_for_unload.recv_timeout(timeout + CLOSE_GRACE_PERIOD);
+        let _ = proxy.send_event(WryEvent::Shutdown);
+    });
+}
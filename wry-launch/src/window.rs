@@ -0,0 +1,78 @@
+//! Cross-window messaging with `window.postMessage` and JS popups opened with `window.open`.
+
+use wasm_bindgen::{JsCast, JsValue, wasm_bindgen};
+
+use crate::events::{ListenerGuard, add_event_listener};
+
+#[wasm_bindgen(inline_js = r#"
+export function post_message(msg, target_origin) {
+    window.postMessage(msg, target_origin);
+}
+
+export function window_target() {
+    return window;
+}
+
+export function open(url, target, features) {
+    return window.open(url, target, features);
+}
+"#)]
+extern "C" {
+    /// The event delivered to handlers registered with [`on_message`].
+    pub type MessageEvent;
+
+    /// The data sent by the other window.
+    #[wasm_bindgen(method, getter)]
+    pub fn data(this: &MessageEvent) -> JsValue;
+
+    /// The origin of the window that sent the message.
+    #[wasm_bindgen(method, getter)]
+    pub fn origin(this: &MessageEvent) -> String;
+
+    /// Send `msg` to this window with `window.postMessage`.
+    ///
+    /// `target_origin` restricts delivery to windows with a matching origin, or `"*"`
+    /// to deliver regardless of origin.
+    pub fn post_message(msg: &JsValue, target_origin: &str);
+
+    fn window_target() -> JsValue;
+
+    /// A handle to a popup opened with [`open`].
+    pub type WindowProxy;
+
+    /// Close the popup.
+    #[wasm_bindgen(method)]
+    pub fn close(this: &WindowProxy);
+
+    /// Bring the popup to the front and give it focus.
+    #[wasm_bindgen(method)]
+    pub fn focus(this: &WindowProxy);
+
+    /// Whether the popup has been closed, either by [`WindowProxy::close`] or by the user.
+    #[wasm_bindgen(method, getter)]
+    pub fn closed(this: &WindowProxy) -> bool;
+
+    /// Send `msg` to the popup with `postMessage`.
+    ///
+    /// `target_origin` restricts delivery to a popup with a matching origin, or `"*"`
+    /// to deliver regardless of origin.
+    #[wasm_bindgen(method, js_name = postMessage)]
+    pub fn post_message(this: &WindowProxy, msg: &JsValue, target_origin: &str);
+
+    /// Open a JS popup with `window.open`.
+    ///
+    /// `target` is the browsing context name, such as `"_blank"`, and `features` is the
+    /// comma-separated feature list, such as `"width=400,height=300"`. Returns `None` if
+    /// the popup was blocked. This opens a popup inside the webview, not a native window.
+    pub fn open(url: &str, target: &str, features: &str) -> Option<WindowProxy>;
+}
+
+/// Register a handler for `message` events sent to this window.
+///
+/// The listener is removed and the handler dropped when the returned [`ListenerGuard`]
+/// is dropped.
+pub fn on_message(mut handler: impl FnMut(MessageEvent) + 'static) -> ListenerGuard {
+    add_event_listener(&window_target(), "message", move |event: JsValue| {
+        handler(event.unchecked_into())
+    })
+}
@@ -0,0 +1,64 @@
+//! Values paired with the JSON that JS sees for them.
+//!
+//! The test harness decodes and encodes values with its own copy of the JS codec. Both
+//! the webview tests and the harness tests check this fixture, so the copy has to agree
+//! with the real one in `types.ts` on every value listed here.
+
+use std::collections::BTreeMap;
+
+use wasm_bindgen::{Either, wasm_bindgen};
+
+macro_rules! codec_fixture {
+    ($($stringify:ident, $identity:ident: $ty:ty = $value:expr => $json:expr;)*) => {
+        #[wasm_bindgen]
+        extern "C" {
+            $(
+                #[wasm_bindgen(js_namespace = JSON, js_name = stringify)]
+                fn $stringify(value: $ty) -> String;
+            )*
+        }
+
+        #[wasm_bindgen(inline_js = "export function identity(value) { return value; }")]
+        extern "C" {
+            $(
+                #[wasm_bindgen(js_name = identity)]
+                fn $identity(value: $ty) -> $ty;
+            )*
+        }
+
+        /// Check the JSON of every fixture value in JS, and that it decodes back unchanged
+        pub(crate) fn check_codec_fixture() {
+            $(
+                let value: $ty = $value;
+                assert_eq!($stringify(value.clone()), $json, "{}", stringify!($ty));
+                assert_eq!($identity(value.clone()), value, "{}", stringify!($ty));
+            )*
+        }
+    };
+}
+
+codec_fixture! {
+    stringify_bool, identity_bool: bool = true => "true";
+    stringify_u8, identity_u8: u8 = 200 => "200";
+    stringify_i32, identity_i32: i32 = -70000 => "-70000";
+    stringify_u32, identity_u32: u32 = 4_000_000_000 => "4000000000";
+    stringify_u64, identity_u64: u64 = 1 << 40 => "1099511627776";
+    stringify_i64, identity_i64: i64 = -(1 << 40) => "-1099511627776";
+    stringify_f32, identity_f32: f32 = 0.25 => "0.25";
+    stringify_f64, identity_f64: f64 = -1.5 => "-1.5";
+    stringify_string, identity_string: String = "héllo \"quoted\"".to_string()
+        => r#""héllo \"quoted\"""#;
+    stringify_some, identity_some: Option<u32> = Some(3) => "3";
+    stringify_none, identity_none: Option<String> = None => "null";
+    stringify_strings, identity_strings: Vec<String> = vec!["a".to_string(), "b".to_string()]
+        => r#"["a","b"]"#;
+    stringify_options, identity_options: Vec<Option<bool>> = vec![Some(true), None]
+        => "[true,null]";
+    stringify_tuple, identity_tuple: (String, f64) = ("a".to_string(), 2.5) => r#"["a",2.5]"#;
+    stringify_map, identity_map: BTreeMap<String, u32> =
+        [("a".to_string(), 1), ("b".to_string(), 2)].into_iter().collect()
+        => r#"{"a":1,"b":2}"#;
+    stringify_left, identity_left: Either<u32, String> = Either::Left(9) => "9";
+    stringify_right, identity_right: Either<u32, String> = Either::Right("x".to_string())
+        => r#""x""#;
+}
@@ -0,0 +1,50 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use wry_launch::animation::{AnimationLoop, animation_frames};
+
+/// Test that an animation loop runs until the closure returns false with increasing elapsed time
+pub(crate) async fn test_animation_loop_elapsed_increases() {
+    let frames = Rc::new(RefCell::new(Vec::new()));
+    let (done_tx, mut done_rx) = futures_channel::mpsc::unbounded();
+
+    let animation = AnimationLoop::start({
+        let frames = frames.clone();
+        move |elapsed: Duration| {
+            let mut frames = frames.borrow_mut();
+            frames.push(elapsed);
+            let keep_going = frames.len() < 5;
+            if !keep_going {
+                done_tx.unbounded_send(()).unwrap();
+            }
+            keep_going
+        }
+    });
+    done_rx.next().await.unwrap();
+    drop(animation);
+
+    let frames = frames.borrow();
+    assert_eq!(frames.len(), 5);
+    assert_eq!(frames[0], Duration::ZERO);
+    for pair in frames.windows(2) {
+        assert!(
+            pair[0] <= pair[1],
+            "elapsed time went backwards: {frames:?}"
+        );
+    }
+    assert!(frames[4] > frames[0]);
+}
+
+/// Test that the animation frame stream yields increasing timestamps
+pub(crate) async fn test_animation_frames_stream() {
+    let timestamps: Vec<f64> = animation_frames().take(3).collect().await;
+    assert_eq!(timestamps.len(), 3);
+    for pair in timestamps.windows(2) {
+        assert!(
+            pair[0] < pair[1],
+            "frame timestamps did not increase: {timestamps:?}"
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use wasm_bindgen::wry::WryBindgen;
+use wasm_bindgen::{BinaryEncode, EncodedData};
+use wry_launch::wry::http;
+
+/// Build a request for the IPC endpoint carrying an Evaluate message from JS
+fn evaluate_request() -> http::Request<Vec<u8>> {
+    use base64::Engine;
+
+    let mut data = EncodedData::new();
+    // Message type 0 is Evaluate
+    0u8.encode(&mut data);
+    let header = base64::engine::general_purpose::STANDARD.encode(data.to_bytes());
+    http::Request::builder()
+        .uri("wry://index.html/__wbg__/handler")
+        .header("dioxus-data", header)
+        .body(Vec::new())
+        .unwrap()
+}
+
+/// Test that a sync call from JS is answered when the app future completes instead of hanging
+pub(crate) fn test_pending_request_answered_after_app_finishes() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let wry_bindgen = WryBindgen::new({
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event)
+    });
+    let app_builder = wry_bindgen.app_builder();
+    let protocol_handler = app_builder.protocol_handler();
+    let prepared = app_builder.build(|| async {}, |_| {});
+
+    // Run the app to completion on its own thread, like the launcher does
+    std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(prepared.into_future());
+    })
+    .join()
+    .unwrap();
+
+    // JS issues a call while the app is finishing
+    let status = Rc::new(Cell::new(None));
+    let responder = {
+        let status = status.clone();
+        move |response: http::Response<Vec<u8>>| status.set(Some(response.status().as_u16()))
+    };
+    protocol_handler.handle_request("wry", |_| {}, &evaluate_request(), responder);
+    assert_eq!(status.get(), None, "the call waits for the app to respond");
+
+    // The main thread learns that the app finished and unblocks the call
+    for event in events.lock().unwrap().drain(..) {
+        wry_bindgen.handle_user_event(event);
+    }
+    assert_eq!(status.get(), Some(503));
+
+    // Later calls are answered immediately
+    let later_status = Rc::new(Cell::new(None));
+    let responder = {
+        let later_status = later_status.clone();
+        move |response: http::Response<Vec<u8>>| later_status.set(Some(response.status().as_u16()))
+    };
+    protocol_handler.handle_request("wry", |_| {}, &evaluate_request(), responder);
+    assert_eq!(later_status.get(), Some(503));
+}
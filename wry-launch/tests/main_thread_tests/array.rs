@@ -0,0 +1,162 @@
+use wasm_bindgen::{JsArray, JsObject, JsValue, wasm_bindgen};
+
+/// Test sorting a JS array with a Rust comparator
+pub(crate) fn test_array_sort_by_descending() {
+    #[wasm_bindgen(inline_js = "export function make_number_array() {
+        return [3, 1, 4, 1, 5, 9, 2, 6];
+    }
+    export function number_array_to_vec(arr) {
+        return arr;
+    }")]
+    extern "C" {
+        fn make_number_array() -> JsArray;
+        fn number_array_to_vec(arr: &JsArray) -> Vec<f64>;
+    }
+
+    let array = make_number_array();
+    array.sort_by(|a, b| {
+        let a = a.as_f64().unwrap();
+        let b = b.as_f64().unwrap();
+        b.partial_cmp(&a).unwrap()
+    });
+    assert_eq!(
+        number_array_to_vec(&array),
+        vec![9.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0, 1.0]
+    );
+}
+
+/// Test converting a large JS array to a Vec in one call
+pub(crate) fn test_array_to_vec() {
+    #[wasm_bindgen(inline_js = "export function make_range_array(len) {
+        return Array.from({ length: len }, (_, i) => i);
+    }")]
+    extern "C" {
+        fn make_range_array(len: u32) -> JsArray;
+    }
+
+    let values = make_range_array(500).to_vec();
+    assert_eq!(values.len(), 500);
+    for (i, value) in values.iter().enumerate() {
+        assert_eq!(value.as_f64(), Some(i as f64));
+    }
+}
+
+/// Test converting a JS object with many keys to a HashMap in one call
+pub(crate) fn test_object_to_hashmap() {
+    #[wasm_bindgen(inline_js = "export function make_keyed_object(len) {
+        const obj = {};
+        for (let i = 0; i < len; i++) {
+            obj[`key${i}`] = `value${i}`;
+        }
+        return obj;
+    }")]
+    extern "C" {
+        fn make_keyed_object(len: u32) -> JsObject;
+    }
+
+    let map = make_keyed_object(100).to_hashmap();
+    assert_eq!(map.len(), 100);
+    for i in 0..100 {
+        let value = &map[&format!("key{i}")];
+        assert_eq!(value.as_string(), Some(format!("value{i}")));
+    }
+}
+
+/// Test sending pairs to JS and decoding the pairs it returns
+pub(crate) fn test_pair_roundtrip() {
+    #[wasm_bindgen(inline_js = "export function swap(pair) {
+        return [pair[1], pair[0]];
+    }
+    export function nest(pair) {
+        return [[pair[0], pair[0] + 1], { wrapped: pair[1] }];
+    }")]
+    extern "C" {
+        fn swap(pair: (u32, String)) -> (String, u32);
+        fn nest(pair: (f64, &str)) -> ((f64, f64), JsValue);
+    }
+
+    assert_eq!(swap((7, "seven".to_string())), ("seven".to_string(), 7));
+
+    let ((first, second), object) = nest((1.5, "inner"));
+    assert_eq!((first, second), (1.5, 2.5));
+    let wrapped = js_sys::Reflect::get(&object, &JsValue::from_str("wrapped")).unwrap();
+    assert_eq!(wrapped.as_string().as_deref(), Some("inner"));
+}
+
+/// Test building a JS array from a Vec<String> in one batched call
+pub(crate) fn test_array_from_rust() {
+    use wasm_bindgen::batch::metrics;
+
+    let strings: Vec<String> = (0..200).map(|i| format!("item{i}")).collect();
+
+    let before = metrics();
+    let array = JsArray::from_rust(strings.clone());
+    let after = metrics();
+    // All conversions and the construction are sent together, if at all before returning
+    assert!(after.flushes - before.flushes <= 1);
+
+    let values = array.to_vec();
+    assert_eq!(values.len(), strings.len());
+    for (value, expected) in values.iter().zip(&strings) {
+        assert_eq!(value.as_string().as_ref(), Some(expected));
+    }
+
+    // Numbers work through the same From impls, and collect() builds an array too
+    let numbers: JsArray = [1.5, 2.5, 3.5].into_iter().collect();
+    let numbers: Vec<f64> = numbers.to_vec().iter().filter_map(|v| v.as_f64()).collect();
+    assert_eq!(numbers, vec![1.5, 2.5, 3.5]);
+}
+
+/// Test building a large array element by element and reading it back
+pub(crate) fn test_array_push_get_set() {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen::batch::{batch, metrics};
+
+    let array = JsArray::new();
+    let before = metrics();
+    let lengths = batch(|| {
+        (0..1000)
+            .map(|i| array.push(&JsValue::from(i)))
+            .collect::<Vec<_>>()
+    });
+    let after = metrics();
+    // The pushes are queued and sent together when the batch ends
+    assert!(after.flushes - before.flushes <= 1);
+    assert_eq!(array.length(), 1000);
+    // Each placeholder resolves to the length after its own push
+    assert_eq!(lengths[0].get(), 1);
+    assert_eq!(lengths[999].get(), 1000);
+
+    assert_eq!(array.push(&JsValue::from("last")), 1001);
+    assert_eq!(array.get(0).as_f64(), Some(0.0));
+    assert_eq!(array.get(999).as_f64(), Some(999.0));
+    assert_eq!(array.get(1000).as_string().as_deref(), Some("last"));
+    assert!(array.get(5000).is_undefined());
+
+    array.set(1, &JsValue::from("one"));
+    assert_eq!(array.get(1).as_string().as_deref(), Some("one"));
+    assert_eq!(array.length(), 1001);
+
+    // A length that is not a number is reported instead of read as zero
+    #[wasm_bindgen(inline_js = "export function make_fake_array() {
+        return { push() { return 'not a length'; } };
+    }")]
+    extern "C" {
+        fn make_fake_array() -> JsArray;
+    }
+    let length = make_fake_array().push(&JsValue::from("ignored"));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| length.get()));
+    let message = result
+        .expect_err("a length that is not a number should panic")
+        .downcast::<String>()
+        .expect("panic payload should be a formatted string");
+    assert!(
+        message.starts_with("push returned"),
+        "unexpected panic message: {message}"
+    );
+
+    // Casting uses Array.isArray
+    let value: JsValue = array.into();
+    assert!(value.dyn_ref::<JsArray>().is_some());
+    assert!(JsValue::from("not an array").dyn_ref::<JsArray>().is_none());
+}
@@ -1,7 +1,8 @@
 use std::time::Instant;
 
-use futures_util::{StreamExt, stream::futures_unordered};
+use futures_util::{FutureExt, StreamExt, stream::futures_unordered};
 use wasm_bindgen::wasm_bindgen;
+use wasm_bindgen_futures::JsFuture;
 use wry_launch::JsValue;
 
 pub(crate) async fn test_call_async() {
@@ -210,3 +211,53 @@ pub(crate) async fn test_join_many_async() {
         expected.remove(index);
     }
 }
+
+/// Test that awaiting a promise yields to other work and maps rejections, including
+/// rejections with values that are not `Error`s, to `Err`
+pub(crate) async fn test_promise_resolve_and_reject() {
+    #[wasm_bindgen(inline_js = "export function settle_later(ok, value) {
+        return new Promise((resolve, reject) => {
+            setTimeout(() => ok ? resolve(value) : reject(value), 20);
+        });
+    }
+    export function is_error(value) {
+        return value instanceof Error;
+    }")]
+    extern "C" {
+        fn settle_later(ok: bool, value: &JsValue) -> js_sys::Promise;
+        fn is_error(value: &JsValue) -> bool;
+    }
+
+    // Other work keeps running while the promise is pending
+    let ticks = std::cell::Cell::new(0);
+    let pending = JsFuture::from(settle_later(true, &JsValue::from("done"))).map(|result| {
+        // The other future finished while the promise was pending
+        assert_eq!(ticks.get(), 3);
+        result
+    });
+    let count_ticks = async {
+        for _ in 0..3 {
+            ticks.set(ticks.get() + 1);
+            futures_util::future::ready(()).await;
+        }
+    };
+    let (resolved, ()) = futures_util::join!(pending, count_ticks);
+    assert_eq!(ticks.get(), 3);
+    assert_eq!(resolved.unwrap().as_string().as_deref(), Some("done"));
+
+    let error = js_sys::Error::new("boom");
+    let rejected = JsFuture::from(settle_later(false, &error))
+        .await
+        .unwrap_err();
+    assert!(is_error(&rejected));
+
+    let rejected = JsFuture::from(settle_later(false, &JsValue::from(42)))
+        .await
+        .unwrap_err();
+    assert_eq!(rejected.as_f64(), Some(42.0));
+
+    let rejected = JsFuture::from(settle_later(false, &JsValue::UNDEFINED))
+        .await
+        .unwrap_err();
+    assert!(rejected.is_undefined());
+}
@@ -0,0 +1,177 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen(inline_js = "export function sleep(ms) {
+    return new Promise((resolve) => setTimeout(resolve, ms));
+}
+export async function call_scale_later(scaler, x) {
+    const promise = scaler.scale_later(x);
+    if (!(promise instanceof Promise)) {
+        throw new Error('async export did not return a promise');
+    }
+    return await promise;
+}
+export async function call_add_later(a, b) {
+    return await Scaler.add_later(a, b);
+}
+export async function call_twice_concurrently(scaler) {
+    const first = scaler.scale_later(2);
+    const second = scaler.scale_later(3).then(
+        (value) => `resolved: ${value}`,
+        (e) => `${e.name}: ${e.message}`,
+    );
+    return `${await first} ${await second}`;
+}
+export async function call_while_mutating(scaler) {
+    const first = scaler.scale_later(2);
+    const second = scaler.set_factor_later(5).then(
+        () => 'resolved',
+        (e) => `${e.name}: ${e.message}`,
+    );
+    return `${await first} ${await second}`;
+}
+export async function free_while_pending(tracked) {
+    const pending = tracked.value_later();
+    tracked.free();
+    const value = await pending;
+    try {
+        tracked.value();
+        return `${value} still usable`;
+    } catch (e) {
+        return `${value} ${e.name}`;
+    }
+}
+export async function call_settled(scaler, method) {
+    try {
+        return `resolved: ${await scaler[method]()}`;
+    } catch (e) {
+        return e instanceof Error ? `${e.name}: ${e.message}` : `rejected: ${e}`;
+    }
+}")]
+extern "C" {
+    fn sleep(ms: u32) -> js_sys::Promise;
+    async fn call_scale_later(scaler: &JsValue, x: u32) -> Result<JsValue, JsValue>;
+    async fn call_add_later(a: u32, b: u32) -> Result<JsValue, JsValue>;
+    async fn call_twice_concurrently(scaler: &JsValue) -> Result<JsValue, JsValue>;
+    async fn call_while_mutating(scaler: &JsValue) -> Result<JsValue, JsValue>;
+    async fn free_while_pending(tracked: &JsValue) -> Result<JsValue, JsValue>;
+    async fn call_settled(scaler: &JsValue, method: &str) -> Result<JsValue, JsValue>;
+}
+
+#[wasm_bindgen]
+pub struct Scaler {
+    factor: u32,
+}
+
+#[wasm_bindgen]
+impl Scaler {
+    pub async fn scale_later(&self, x: u32) -> u32 {
+        JsFuture::from(sleep(5)).await.unwrap();
+        x * self.factor
+    }
+
+    pub async fn set_factor_later(&mut self, factor: u32) {
+        JsFuture::from(sleep(5)).await.unwrap();
+        self.factor = factor;
+    }
+
+    pub async fn add_later(a: u32, b: u32) -> u32 {
+        JsFuture::from(sleep(5)).await.unwrap();
+        a + b
+    }
+
+    pub async fn fail_later(&self) -> Result<u32, JsValue> {
+        JsFuture::from(sleep(5)).await.unwrap();
+        Err(JsValue::from_str("no result"))
+    }
+
+    pub async fn panic_later(&self) -> u32 {
+        JsFuture::from(sleep(5)).await.unwrap();
+        panic!("scaler broke")
+    }
+}
+
+static TRACKED_DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[wasm_bindgen]
+pub struct Tracked {
+    value: u32,
+}
+
+#[wasm_bindgen]
+impl Tracked {
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    pub async fn value_later(&self) -> u32 {
+        JsFuture::from(sleep(5)).await.unwrap();
+        self.value
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        TRACKED_DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Test that async exports return promises that JS can await for the future's output
+pub(crate) async fn test_async_export_resolves() {
+    let scaler = JsValue::from(Scaler { factor: 3 });
+
+    let scaled = call_scale_later(&scaler, 7).await.unwrap();
+    assert_eq!(scaled.as_f64(), Some(21.0));
+
+    let sum = call_add_later(2, 5).await.unwrap();
+    assert_eq!(sum.as_f64(), Some(7.0));
+}
+
+/// Test that async exports reject their promise when they return `Err` or panic
+pub(crate) async fn test_async_export_rejects() {
+    let scaler = JsValue::from(Scaler { factor: 3 });
+
+    let failed = call_settled(&scaler, "fail_later").await.unwrap();
+    assert_eq!(failed.as_string().as_deref(), Some("rejected: no result"));
+
+    let panicked = call_settled(&scaler, "panic_later").await.unwrap();
+    let message = panicked.as_string().unwrap();
+    assert!(message.starts_with("Error: "), "{message}");
+    assert!(message.contains("Scaler::panic_later"), "{message}");
+    assert!(message.contains("scaler broke"), "{message}");
+
+    // The object is returned to the store after the panic and still usable
+    let scaled = call_scale_later(&scaler, 2).await.unwrap();
+    assert_eq!(scaled.as_f64(), Some(6.0));
+}
+
+/// Test that `&self` calls on an object whose async method is still pending share it,
+/// while a `&mut self` call rejects instead of panicking, and that the object is usable
+/// once the calls complete
+pub(crate) async fn test_async_export_concurrent_calls() {
+    let scaler = JsValue::from(Scaler { factor: 3 });
+
+    let settled = call_twice_concurrently(&scaler).await.unwrap();
+    assert_eq!(settled.as_string().as_deref(), Some("6 resolved: 9"));
+
+    let settled = call_while_mutating(&scaler).await.unwrap();
+    let message = settled.as_string().unwrap();
+    assert!(message.starts_with("6 Error: "), "{message}");
+    assert!(message.contains("already borrowed"), "{message}");
+
+    let scaled = call_scale_later(&scaler, 4).await.unwrap();
+    assert_eq!(scaled.as_f64(), Some(12.0));
+}
+
+/// Test that an object freed while an async method holds it is dropped once the call
+/// completes, and that the call still resolves
+pub(crate) async fn test_async_export_free_while_pending() {
+    let tracked = JsValue::from(Tracked { value: 7 });
+    let before = TRACKED_DROPS.load(Ordering::SeqCst);
+
+    let settled = free_while_pending(&tracked).await.unwrap();
+    assert_eq!(settled.as_string().as_deref(), Some("7 Error"));
+    assert_eq!(TRACKED_DROPS.load(Ordering::SeqCst) - before, 1);
+}
@@ -0,0 +1,96 @@
+use std::sync::Mutex;
+
+use futures_channel::{mpsc, oneshot};
+use futures_util::StreamExt;
+use wasm_bindgen::{Closure, wasm_bindgen};
+
+#[wasm_bindgen(inline_js = "export function double(x) { return x * 2; }
+export function square(x) { return x * x; }")]
+extern "C" {
+    fn double(x: u32) -> u32;
+    fn square(x: u32) -> u32;
+}
+
+/// A value for the plugin to double in JS, and where to send the result
+pub(crate) type Job = (u32, oneshot::Sender<u32>);
+
+/// Sends jobs to the plugin future while the tests run
+static PLUGIN_JOBS: Mutex<Option<mpsc::UnboundedSender<Job>>> = Mutex::new(None);
+
+/// Create the job queue for [`plugin`] and return its receiving end.
+pub(crate) fn plugin_jobs() -> mpsc::UnboundedReceiver<Job> {
+    let (sender, receiver) = mpsc::unbounded();
+    *PLUGIN_JOBS.lock().unwrap() = Some(sender);
+    receiver
+}
+
+/// Stop the plugin once the tests are done, so the app can finish.
+pub(crate) fn stop_plugin() {
+    PLUGIN_JOBS.lock().unwrap().take();
+}
+
+/// A future attached next to the test app that makes its own JS calls on the same webview
+pub(crate) async fn plugin(mut jobs: mpsc::UnboundedReceiver<Job>) {
+    while let Some((value, reply)) = jobs.next().await {
+        let _ = reply.send(double(value));
+    }
+}
+
+#[wasm_bindgen(inline_js = "let registered = null;
+export function register_callback(cb) { registered = cb; }
+export function call_registered(x) {
+    try {
+        return `returned ${registered(x)}`;
+    } catch (e) {
+        return 'threw';
+    }
+}")]
+extern "C" {
+    fn register_callback(cb: Closure<dyn FnMut(u32) -> u32>);
+    fn call_registered(x: u32) -> String;
+}
+
+/// Ends the short-lived plugin, which answers once it has finished
+static SHORT_LIVED_PLUGIN: Mutex<Option<oneshot::Sender<oneshot::Sender<()>>>> = Mutex::new(None);
+
+/// Create the stop signal for [`short_lived_plugin`] and return its receiving end.
+pub(crate) fn short_lived_plugin_stop() -> oneshot::Receiver<oneshot::Sender<()>> {
+    let (sender, receiver) = oneshot::channel();
+    *SHORT_LIVED_PLUGIN.lock().unwrap() = Some(sender);
+    receiver
+}
+
+/// An attached future that gives JS a callback, then finishes when told to
+pub(crate) async fn short_lived_plugin(stop: oneshot::Receiver<oneshot::Sender<()>>) {
+    register_callback(Closure::new(|x: u32| x + 1));
+    if let Ok(finished) = stop.await {
+        let _ = finished.send(());
+    }
+}
+
+/// Test that JS calls from the app and an attached future interleave and get their own responses
+pub(crate) async fn test_attached_future_interleaves_calls() {
+    let jobs = PLUGIN_JOBS.lock().unwrap().clone().unwrap();
+    for value in 0..5 {
+        let (reply, result) = oneshot::channel();
+        jobs.unbounded_send((value, reply)).unwrap();
+
+        // Call JS from this future before the plugin has had a chance to run
+        assert_eq!(square(value), value * value);
+        assert_eq!(result.await.unwrap(), value * 2);
+        assert_eq!(square(value + 1), (value + 1) * (value + 1));
+    }
+}
+
+/// Test that the callbacks an attached future registered with JS are dropped when it finishes
+pub(crate) async fn test_attached_future_scope_released() {
+    assert_eq!(call_registered(1), "returned 2");
+
+    let stop = SHORT_LIVED_PLUGIN.lock().unwrap().take().unwrap();
+    let (finished, plugin_done) = oneshot::channel();
+    stop.send(finished).unwrap();
+    plugin_done.await.unwrap();
+
+    // The plugin's scope is released as soon as it completes, before this resumes
+    assert_eq!(call_registered(1), "threw");
+}
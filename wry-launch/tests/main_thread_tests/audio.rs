@@ -0,0 +1,26 @@
+use wasm_bindgen_futures::JsFuture;
+use wry_launch::audio::AudioContext;
+
+/// Test creating an audio context and driving an oscillator without listening to it
+pub(crate) async fn test_audio_context_oscillator() {
+    let context = AudioContext::new().expect("webview should support Web Audio");
+    let time = context.current_time();
+    assert!(
+        time.is_finite() && time >= 0.0,
+        "unexpected current time: {time}"
+    );
+
+    let oscillator = context.create_oscillator();
+    assert_eq!(oscillator.frequency(), 440.0);
+    oscillator.set_frequency(880.0);
+    assert_eq!(oscillator.frequency(), 880.0);
+
+    oscillator.connect(&context.destination()).unwrap();
+    oscillator.start().unwrap();
+    // Oscillators can only be started once
+    assert!(oscillator.start().is_err());
+    oscillator.stop().unwrap();
+    oscillator.disconnect();
+
+    JsFuture::from(context.close()).await.unwrap();
+}
@@ -0,0 +1,110 @@
+use wasm_bindgen::batch::{batch, force_flush, metrics};
+use wasm_bindgen::wasm_bindgen;
+
+/// Test that calls made inside a batch are counted as coalesced
+pub(crate) fn test_batch_metrics_coalesced() {
+    #[wasm_bindgen(
+        inline_js = "export function record_value(value) { window.lastRecordedValue = value; }"
+    )]
+    extern "C" {
+        fn record_value(value: u32);
+    }
+
+    let before = metrics();
+    batch(|| {
+        for i in 0..10 {
+            record_value(i);
+        }
+    });
+    // Make sure the batch is sent even if this test runs inside an outer batch
+    force_flush();
+    let after = metrics();
+
+    assert!(after.calls - before.calls >= 10);
+    assert!(after.calls_coalesced - before.calls_coalesced > 1);
+    assert!(after.flushes > before.flushes);
+    assert!(after.round_trips_saved() > before.round_trips_saved());
+    assert!(after.bytes_saved() > before.bytes_saved());
+}
+
+/// Test that dropping many values frees them in JS with a handful of messages
+pub(crate) fn test_drops_coalesced() {
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen::batch::flush_drops;
+
+    #[wasm_bindgen(inline_js = "export function make_value(i) { return { i }; }")]
+    extern "C" {
+        fn make_value(i: u32) -> JsValue;
+    }
+
+    let values: Vec<JsValue> = batch(|| (0..500).map(make_value).collect());
+    force_flush();
+    let alive_before = crate::heap_objects_alive();
+
+    let before = metrics();
+    drop(values);
+    flush_drops();
+    let after = metrics();
+    assert!(
+        after.flushes - before.flushes <= 2,
+        "dropping 500 values took {} flushes",
+        after.flushes - before.flushes
+    );
+
+    assert_eq!(crate::heap_objects_alive(), alive_before - 500);
+}
+
+/// Test that flush_pending_drops frees every dropped value before returning
+pub(crate) fn test_flush_pending_drops() {
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen::wry::WindowHandle;
+
+    #[wasm_bindgen(inline_js = "export function make_value(i) { return { i }; }")]
+    extern "C" {
+        fn make_value(i: u32) -> JsValue;
+    }
+
+    let window = WindowHandle::current();
+    window.flush_pending_drops();
+    let baseline = crate::heap_objects_alive();
+
+    let values: Vec<JsValue> = (0..50).map(make_value).collect();
+    assert_eq!(crate::heap_objects_alive(), baseline + 50);
+    drop(values);
+
+    let before = metrics();
+    window.flush_pending_drops();
+    let after = metrics();
+    assert!(after.flushes > before.flushes);
+
+    // Nothing is left to send once the drops are flushed
+    window.flush_pending_drops();
+    assert_eq!(metrics().flushes, after.flushes);
+
+    assert_eq!(crate::heap_objects_alive(), baseline);
+}
+
+/// Test that values dropped inside a callback are freed before it returns to JS
+pub(crate) fn test_callback_drops_freed_on_return() {
+    use wasm_bindgen::{Closure, JsValue};
+
+    #[wasm_bindgen(inline_js = r#"
+        export function make_value(i) { return { i }; }
+        export function heap_growth_during(callback) {
+            const before = window.jsHeap.heapObjectsAlive();
+            callback();
+            return window.jsHeap.heapObjectsAlive() - before;
+        }
+    "#)]
+    extern "C" {
+        fn make_value(i: u32) -> JsValue;
+        fn heap_growth_during(callback: &Closure<dyn FnMut()>) -> i32;
+    }
+
+    let callback: Closure<dyn FnMut()> = Closure::new(|| {
+        let values: Vec<JsValue> = (0..20).map(make_value).collect();
+        drop(values);
+    });
+    // JS counts the heap right after the callback, without another call from Rust
+    assert_eq!(heap_growth_during(&callback), 0);
+}
@@ -0,0 +1,55 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::time::Duration;
+
+use wry_launch::navigator::send_beacon;
+
+/// Accept one request on `listener` and answer it, returning the request line and body
+fn serve_one_request(listener: TcpListener) -> (String, Vec<u8>) {
+    let (stream, _) = listener.accept().unwrap();
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).unwrap();
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        if header.trim_end().is_empty() {
+            break;
+        }
+        match header.split_once(':') {
+            Some((name, value)) if name.eq_ignore_ascii_case("content-length") => {
+                content_length = value.trim().parse().unwrap();
+            }
+            _ => {}
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    reader
+        .get_mut()
+        .write_all(b"HTTP/1.1 204 No Content\r\nAccess-Control-Allow-Origin: *\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+        .unwrap();
+    (request_line.trim_end().to_string(), body)
+}
+
+/// Test that a beacon is queued and reaches a local HTTP server with its payload
+pub(crate) async fn test_send_beacon() {
+    // Beacons only go to HTTP(S) URLs, so serve this one from a local socket
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/collect", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || serve_one_request(listener));
+
+    assert!(send_beacon(&url, b"event=launch"), "the beacon is queued");
+
+    // The webview sends the beacon in the background
+    while !server.is_finished() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let (request_line, body) = server.join().unwrap();
+    assert_eq!(request_line, "POST /collect HTTP/1.1");
+    assert_eq!(body, b"event=launch");
+
+    // The page is served from a custom protocol, which beacons cannot be sent to
+    assert!(!send_beacon("wry://index.html/collect", b"x"));
+}
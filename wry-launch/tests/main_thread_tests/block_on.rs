@@ -0,0 +1,27 @@
+use js_sys::Promise;
+use wasm_bindgen::wasm_bindgen;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen(inline_js = r#"
+    export function delayed_value(value) {
+        return new Promise((resolve) => setTimeout(() => resolve(value), 10));
+    }
+"#)]
+extern "C" {
+    fn delayed_value(value: u32) -> Promise;
+}
+
+/// Test that block_on handles the JS callback that resolves a promise instead of deadlocking
+pub(crate) fn test_block_on_js_future() {
+    // This runs synchronously, so the app future cannot handle callbacks until it returns
+    let value = wasm_bindgen::block_on(async { JsFuture::from(delayed_value(7)).await.unwrap() });
+    assert_eq!(value.as_f64(), Some(7.0));
+
+    // Several round-trips in one blocking call
+    let sum = wasm_bindgen::block_on(async {
+        let a = JsFuture::from(delayed_value(1)).await.unwrap();
+        let b = JsFuture::from(delayed_value(2)).await.unwrap();
+        a.as_f64().unwrap() + b.as_f64().unwrap()
+    });
+    assert_eq!(sum, 3.0);
+}
@@ -318,3 +318,102 @@ pub(crate) fn test_borrowed_ref_deep_nesting() {
         "Level 4 ref should be valid"
     );
 }
+
+/// Test passing an optional borrowed ref to JS
+pub(crate) fn test_optional_borrowed_ref_argument() {
+    #[wasm_bindgen(inline_js = r#"
+        export function describe_optional(val) {
+            return val === null ? "null" : `x=${val.x}`;
+        }
+        export function make_value() { return { x: 7 }; }
+    "#)]
+    extern "C" {
+        fn describe_optional(val: Option<&JsValue>) -> String;
+        fn make_value() -> JsValue;
+    }
+
+    let val = make_value();
+    assert_eq!(describe_optional(None), "null");
+    assert_eq!(describe_optional(Some(&val)), "x=7");
+    // The value is only borrowed, so it is still usable afterwards
+    assert_eq!(describe_optional(Some(&val)), "x=7");
+}
+
+/// Test callbacks that receive an optional borrowed ref from JS
+pub(crate) fn test_optional_borrowed_ref_in_callback() {
+    use wasm_bindgen::Closure;
+
+    #[wasm_bindgen(inline_js = r#"
+        export function call_with_optional(cb) {
+            return [cb(null), cb(undefined), cb({ x: 3 })].join(",");
+        }
+        export function get_x(val) { return val.x; }
+    "#)]
+    extern "C" {
+        fn call_with_optional(cb: &Closure<dyn FnMut(Option<&JsValue>) -> String>) -> String;
+        fn get_x(val: &JsValue) -> f64;
+    }
+
+    let callback = Closure::new(|v: Option<&JsValue>| match v {
+        Some(v) => get_x(v).to_string(),
+        None => "none".to_string(),
+    });
+    assert_eq!(call_with_optional(&callback), "none,none,3");
+}
+
+/// Test callbacks that borrow two arguments and return a JsValue
+pub(crate) fn test_two_borrowed_refs_in_callback() {
+    use wasm_bindgen::Closure;
+
+    #[wasm_bindgen(inline_js = r#"
+        export function call_with_two(cb) {
+            const sum = cb({ x: 2 }, { x: 5 });
+            return sum.x;
+        }
+        export function get_x(val) { return val.x; }
+        export function make_point(x) { return { x }; }
+    "#)]
+    extern "C" {
+        fn call_with_two(cb: &Closure<dyn FnMut(&JsValue, &JsValue) -> JsValue>) -> f64;
+        fn get_x(val: &JsValue) -> f64;
+        fn make_point(x: f64) -> JsValue;
+    }
+
+    let alive_before = crate::heap_objects_alive();
+    let mut calls = 0;
+    let callback = Closure::new(move |a: &JsValue, b: &JsValue| {
+        calls += 1;
+        make_point(get_x(a) * 10.0 + get_x(b) + calls as f64 * 100.0)
+    });
+    assert_eq!(call_with_two(&callback), 125.0);
+    assert_eq!(call_with_two(&callback), 225.0);
+    drop(callback);
+
+    // The arguments were only borrowed, so nothing is left on the heap
+    assert_eq!(crate::heap_objects_alive(), alive_before);
+}
+
+/// Test callbacks that borrow three arguments, in the order JS passed them
+pub(crate) fn test_three_borrowed_refs_in_callback() {
+    use wasm_bindgen::Closure;
+
+    #[wasm_bindgen(inline_js = r#"
+        export function call_with_three(cb) {
+            return cb("a", ["b"], { c: 1 });
+        }
+        export function describe(val) {
+            return Array.isArray(val) ? "array" : typeof val;
+        }
+    "#)]
+    extern "C" {
+        fn call_with_three(
+            cb: Closure<dyn FnMut(&JsValue, &JsValue, &JsValue) -> String>,
+        ) -> String;
+        fn describe(val: &JsValue) -> String;
+    }
+
+    let callback = Closure::new(|a: &JsValue, b: &JsValue, c: &JsValue| {
+        format!("{},{},{}", describe(a), describe(b), describe(c))
+    });
+    assert_eq!(call_with_three(callback), "string,array,object");
+}
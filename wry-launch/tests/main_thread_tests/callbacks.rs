@@ -206,3 +206,24 @@ pub(crate) fn test_mut_dyn_fnmut_many_arity() {
     });
     assert!(called);
 }
+
+pub(crate) fn test_js_value_from_closure() {
+    #[wasm_bindgen(
+        inline_js = "export function call_handler_property(obj, value) { return obj.handler(value); }"
+    )]
+    extern "C" {
+        fn call_handler_property(obj: &JsValue, value: u32) -> u32;
+    }
+
+    let mut calls = 0;
+    let handler = JsValue::from_closure(move |x: u32| {
+        calls += 1;
+        x * 2 + calls
+    });
+
+    let obj = js_sys::Object::new();
+    js_sys::Reflect::set(&obj, &JsValue::from_str("handler"), &handler).unwrap();
+
+    assert_eq!(call_handler_property(&obj, 10), 21);
+    assert_eq!(call_handler_property(&obj, 10), 22);
+}
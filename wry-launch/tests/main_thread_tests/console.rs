@@ -0,0 +1,113 @@
+#[cfg(feature = "test-harness")]
+use std::sync::LazyLock;
+
+use wasm_bindgen::batch::{force_flush, metrics};
+use wasm_bindgen::console_capture::ConsoleLevel;
+#[cfg(feature = "test-harness")]
+use wasm_bindgen::console_capture::{ConsoleBuffer, ConsoleMessage};
+use wasm_bindgen::{JsValue, wasm_bindgen};
+use wry_launch::console;
+
+#[wasm_bindgen(inline_js = r#"
+    export function make_rows() {
+        return [{ name: "a", value: 1 }, { name: "b", value: 2 }];
+    }
+    export function make_object() {
+        return { nested: { list: [1, 2, 3] }, label: "hello" };
+    }
+"#)]
+extern "C" {
+    fn make_rows() -> JsValue;
+    fn make_object() -> JsValue;
+}
+
+/// Smoke test that console.dir and console.table accept objects and arrays
+pub(crate) fn test_console_dir_and_table() {
+    let rows = make_rows();
+    let object = make_object();
+    console::dir(&object);
+    console::dir(&rows);
+    console::table(&rows);
+    console::table(&object);
+    console::dir(&JsValue::from_str("not an object"));
+}
+
+/// Smoke test that console.log accepts format specifiers with their arguments
+pub(crate) fn test_console_log_with_format() {
+    let object = make_object();
+    console::log_with_format("object: %o", &[object]);
+    console::log_with_format(
+        "%cstyled%c %s",
+        &[
+            JsValue::from_str("color: red"),
+            JsValue::from_str(""),
+            JsValue::from_str("plain"),
+        ],
+    );
+    console::log_with_format("no arguments", &[]);
+}
+
+/// Console output from the whole test run, installed as the console sink in `main`
+#[cfg(feature = "test-harness")]
+static CAPTURED: LazyLock<ConsoleBuffer> = LazyLock::new(ConsoleBuffer::new);
+
+#[cfg(feature = "test-harness")]
+pub(crate) fn captured_console() -> ConsoleBuffer {
+    CAPTURED.clone()
+}
+
+#[cfg(feature = "test-harness")]
+pub(crate) fn test_console_sink_captures_log() {
+    #[wasm_bindgen(inline_js = r#"
+        export function log_messages(message) {
+            console.log(message, { answer: 42 });
+            console.warn("careful");
+            // Return a value so the calls are flushed before the buffer is checked
+            return true;
+        }
+    "#)]
+    extern "C" {
+        fn log_messages(message: &str) -> bool;
+    }
+
+    CAPTURED.take();
+    assert!(log_messages("hello from the test"));
+
+    let captured = CAPTURED.take();
+    assert!(captured.contains(&ConsoleMessage {
+        level: ConsoleLevel::Log,
+        message: r#"hello from the test {"answer":42}"#.to_string(),
+    }));
+    assert!(captured.contains(&ConsoleMessage {
+        level: ConsoleLevel::Warn,
+        message: "careful".to_string(),
+    }));
+}
+
+/// Test that console calls below the configured level are dropped without any IPC
+pub(crate) fn test_console_level_filters_calls() {
+    console::set_console_level(ConsoleLevel::Error);
+    force_flush();
+
+    let before = metrics();
+    console::log(&[JsValue::from_str("filtered")]);
+    console::info(&[JsValue::from_str("filtered")]);
+    console::warn(&[JsValue::from_str("filtered")]);
+    console::log_with_format("%s", &[JsValue::from_str("filtered")]);
+    console::dir(&JsValue::from_str("filtered"));
+    force_flush();
+    assert_eq!(metrics().calls, before.calls);
+    assert!(!console::enabled(ConsoleLevel::Info));
+
+    console::error(&[JsValue::from_str("not filtered")]);
+    force_flush();
+    assert_eq!(metrics().calls, before.calls + 1);
+    #[cfg(feature = "test-harness")]
+    assert!(CAPTURED.take().contains(&ConsoleMessage {
+        level: ConsoleLevel::Error,
+        message: "not filtered".to_string(),
+    }));
+
+    console::set_console_level(ConsoleLevel::Debug);
+    assert!(console::enabled(ConsoleLevel::Debug));
+}
@@ -0,0 +1,26 @@
+use wry_launch::document::{cookie, cookies_map, set_cookie};
+
+/// Test that cookies set one at a time both appear in the parsed cookie map
+pub(crate) fn test_set_cookies_and_parse() {
+    set_cookie("session=abc123; path=/");
+    set_cookie("theme=dark; path=/");
+
+    let raw = cookie();
+    assert!(raw.contains("session=abc123"), "cookie string was {raw:?}");
+
+    let cookies = cookies_map();
+    assert_eq!(cookies.get("session").map(String::as_str), Some("abc123"));
+    assert_eq!(cookies.get("theme").map(String::as_str), Some("dark"));
+
+    // Setting a cookie again updates it in place
+    set_cookie("theme=light; path=/");
+    assert_eq!(
+        cookies_map().get("theme").map(String::as_str),
+        Some("light")
+    );
+
+    // Expire the cookies so the test can run again
+    set_cookie("session=; path=/; max-age=0");
+    set_cookie("theme=; path=/; max-age=0");
+    assert!(!cookies_map().contains_key("session"));
+}
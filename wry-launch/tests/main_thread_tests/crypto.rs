@@ -0,0 +1,45 @@
+use wry_launch::crypto::{get_random_values, random_uuid};
+
+/// Test that get_random_values fills the buffer with random bytes
+pub(crate) fn test_get_random_values() {
+    let mut buf = [0u8; 32];
+    get_random_values(&mut buf);
+    assert!(
+        buf.iter().any(|&b| b != 0),
+        "32 random bytes should not all be zero"
+    );
+}
+
+/// Test that random_uuid returns a version 4 UUID
+pub(crate) fn test_random_uuid() {
+    let uuid = random_uuid();
+    let groups: Vec<&str> = uuid.split('-').collect();
+    let lengths: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+    assert_eq!(
+        lengths,
+        vec![8, 4, 4, 4, 12],
+        "unexpected UUID format: {uuid}"
+    );
+    assert!(
+        groups
+            .iter()
+            .all(|g| g.chars().all(|c| c.is_ascii_hexdigit())),
+        "UUID should only contain hex digits: {uuid}"
+    );
+    assert!(
+        groups[2].starts_with('4'),
+        "UUID should be version 4: {uuid}"
+    );
+}
+
+/// Test that buffers larger than one getRandomValues call are filled completely
+pub(crate) fn test_get_random_values_chunked() {
+    let mut buf = vec![0u8; 65536 * 2 + 100];
+    get_random_values(&mut buf);
+    for chunk in buf.chunks(65536) {
+        assert!(
+            chunk.iter().any(|&b| b != 0),
+            "every chunk should be filled with random bytes"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use wasm_bindgen::wasm_bindgen;
+use wry_launch::dialog::{confirm, prompt};
+
+#[wasm_bindgen(inline_js = r#"
+export function click_dialog_button(action) {
+    const button = document.querySelector(`.wry-dialog [data-action="${action}"]`);
+    if (!button) {
+        throw new Error(`no dialog button ${action}`);
+    }
+    button.click();
+}
+
+export function set_dialog_input(value) {
+    document.querySelector(".wry-dialog-input").value = value;
+}
+
+export function open_dialogs() {
+    return document.querySelectorAll(".wry-dialog-overlay").length;
+}
+
+// Press a key inside the dialog at `index`, counting from the bottom
+export function press_in_dialog(index, key) {
+    const dialog = document.querySelectorAll(".wry-dialog")[index];
+    dialog.dispatchEvent(new KeyboardEvent("keydown", { key, bubbles: true, cancelable: true }));
+}
+
+export function press_in_input(key) {
+    const input = document.querySelector(".wry-dialog-input");
+    input.dispatchEvent(new KeyboardEvent("keydown", { key, bubbles: true, cancelable: true }));
+}
+
+// Press a key on the page itself and report whether it kept its default action
+export function press_on_page(key) {
+    const event = new KeyboardEvent("keydown", { key, bubbles: true, cancelable: true });
+    return document.body.dispatchEvent(event);
+}
+"#)]
+extern "C" {
+    fn click_dialog_button(action: &str);
+    fn set_dialog_input(value: &str);
+    fn open_dialogs() -> u32;
+    fn press_in_dialog(index: u32, key: &str);
+    fn press_in_input(key: &str);
+    fn press_on_page(key: &str) -> bool;
+}
+
+/// Test that a confirm dialog resolves with the button the user clicked
+pub(crate) async fn test_confirm_dialog() {
+    let accepted = confirm("Delete the file?");
+    assert_eq!(open_dialogs(), 1);
+    click_dialog_button("ok");
+    assert!(accepted.await);
+    assert_eq!(open_dialogs(), 0);
+
+    let declined = confirm("Delete the file?");
+    click_dialog_button("cancel");
+    assert!(!declined.await);
+    assert_eq!(open_dialogs(), 0);
+}
+
+/// Test that a prompt dialog resolves with the text entered, or None when cancelled
+pub(crate) async fn test_prompt_dialog() {
+    let answer = prompt("Name?", "guest");
+    set_dialog_input("Ferris");
+    click_dialog_button("ok");
+    assert_eq!(answer.await.as_deref(), Some("Ferris"));
+
+    let cancelled = prompt("Name?", "guest");
+    click_dialog_button("cancel");
+    assert_eq!(cancelled.await, None);
+    assert_eq!(open_dialogs(), 0);
+}
+
+/// Test that keys only reach the topmost dialog and never the rest of the page
+pub(crate) async fn test_dialog_keys_scoped_to_topmost() {
+    let lower = confirm("First?");
+    let upper = confirm("Second?");
+    assert_eq!(open_dialogs(), 2);
+
+    // Enter and Escape on the page are left alone while dialogs are open
+    assert!(press_on_page("Enter"));
+    assert!(press_on_page("Escape"));
+    assert_eq!(open_dialogs(), 2);
+
+    // The dialog underneath ignores keys until it is on top again
+    press_in_dialog(0, "Escape");
+    assert_eq!(open_dialogs(), 2);
+    press_in_dialog(1, "Escape");
+    assert!(!upper.await);
+    assert_eq!(open_dialogs(), 1);
+
+    press_in_dialog(0, "Escape");
+    assert!(!lower.await);
+    assert_eq!(open_dialogs(), 0);
+
+    // Enter only accepts a prompt from its input, focused buttons handle it themselves
+    let answer = prompt("Name?", "guest");
+    press_in_dialog(0, "Enter");
+    assert_eq!(open_dialogs(), 1);
+    press_in_input("Enter");
+    assert_eq!(answer.await.as_deref(), Some("guest"));
+    assert_eq!(open_dialogs(), 0);
+}
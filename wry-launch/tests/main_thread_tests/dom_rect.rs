@@ -0,0 +1,36 @@
+use wasm_bindgen::wasm_bindgen;
+use wry_launch::document::Element;
+
+#[wasm_bindgen(inline_js = r#"
+    export function create_sized_box() {
+        const element = document.createElement("div");
+        element.style.position = "absolute";
+        element.style.left = "30px";
+        element.style.top = "20px";
+        element.style.width = "120px";
+        element.style.height = "45px";
+        document.body.appendChild(element);
+        return element;
+    }
+    export function remove_element(element) {
+        element.remove();
+    }
+"#)]
+extern "C" {
+    fn create_sized_box() -> Element;
+    fn remove_element(element: &Element);
+}
+
+/// Test that get_bounding_client_rect measures an element with known CSS dimensions
+pub(crate) fn test_get_bounding_client_rect() {
+    let element = create_sized_box();
+    let rect = element.get_bounding_client_rect();
+    remove_element(&element);
+
+    assert_eq!(rect.width(), 120.0);
+    assert_eq!(rect.height(), 45.0);
+    assert_eq!(rect.left(), rect.x());
+    assert_eq!(rect.top(), rect.y());
+    assert_eq!(rect.right(), rect.left() + 120.0);
+    assert_eq!(rect.bottom(), rect.top() + 45.0);
+}
@@ -0,0 +1,118 @@
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use wry_launch::fetch::{
+    AbortController, Blob, FormData, Headers, Request, RequestInit, Response, fetch_with_request,
+};
+use wry_launch::wry::http;
+
+/// Send the body and content type of requests to `/__echo__` straight back
+pub(crate) fn echo_endpoint(request: &http::Request<Vec<u8>>) -> Option<http::Response<Vec<u8>>> {
+    if request.uri().path().trim_matches('/') != "__echo__" {
+        return None;
+    }
+    let content_type = request
+        .headers()
+        .get("Content-Type")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let response = http::Response::builder()
+        .header("Content-Type", content_type)
+        .header("access-control-allow-origin", "*")
+        .body(request.body().clone())
+        .expect("Failed to build echo response");
+    Some(response)
+}
+
+/// Test POSTing a JSON body to the echo endpoint and reading it back
+pub(crate) async fn test_fetch_post_json() {
+    let body = r#"{"hello":"world","count":3}"#;
+
+    let headers = Headers::new();
+    headers.append("Content-Type", "application/json");
+    assert_eq!(
+        headers.get("content-type").as_deref(),
+        Some("application/json")
+    );
+    assert_eq!(headers.get("x-missing"), None);
+
+    let init = RequestInit::new();
+    init.set_method("POST");
+    init.set_headers(&headers);
+    init.set_body_str(body);
+
+    let request = Request::new_with_init("/__echo__", &init);
+    assert_eq!(request.method(), "POST");
+
+    let response = JsFuture::from(fetch_with_request(&request))
+        .await
+        .expect("fetch should resolve");
+    let response: Response = response.unchecked_into();
+    assert!(response.ok());
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").as_deref(),
+        Some("application/json")
+    );
+
+    let text = JsFuture::from(response.text())
+        .await
+        .expect("reading the body should resolve");
+    assert_eq!(text.as_string().as_deref(), Some(body));
+}
+
+/// Test that aborting a pending fetch rejects it with an AbortError
+pub(crate) async fn test_fetch_abort() {
+    let controller = AbortController::new();
+    let signal = controller.signal();
+    assert!(!signal.aborted());
+
+    let init = RequestInit::new();
+    init.set_method("POST");
+    init.set_body_str("never sent");
+    init.set_signal(&signal);
+
+    let request = Request::new_with_init("/__echo__", &init);
+    let pending = JsFuture::from(fetch_with_request(&request));
+    controller.abort();
+    assert!(signal.aborted());
+
+    let error = pending.await.expect_err("an aborted fetch should reject");
+    let name = js_sys::Reflect::get(&error, &"name".into()).unwrap();
+    assert_eq!(name.as_string().as_deref(), Some("AbortError"));
+}
+
+/// Test building form data with a text field and a file, and sending it as a body
+pub(crate) async fn test_form_data_entries() {
+    let blob = Blob::new_with_text("hello from a file", "text/plain");
+    assert_eq!(blob.size(), 17.0);
+    assert_eq!(blob.content_type(), "text/plain");
+
+    let form = FormData::new();
+    form.append("name", "ferris");
+    form.append_blob("upload", &blob, "notes.txt");
+    assert!(form.has("name"));
+    assert!(!form.has("missing"));
+    assert!(form.get("missing").is_null());
+
+    assert_eq!(form.get("name").as_string().as_deref(), Some("ferris"));
+    let file = form.get("upload");
+    let file_name = js_sys::Reflect::get(&file, &"name".into()).unwrap();
+    assert_eq!(file_name.as_string().as_deref(), Some("notes.txt"));
+    let file: Blob = file.unchecked_into();
+    assert_eq!(file.size(), 17.0);
+    let text = JsFuture::from(file.text()).await.unwrap();
+    assert_eq!(text.as_string().as_deref(), Some("hello from a file"));
+
+    let init = RequestInit::new();
+    init.set_method("POST");
+    init.set_body_form_data(&form);
+    let request = Request::new_with_init("/__echo__", &init);
+    let content_type = request.headers().get("content-type").unwrap();
+    assert!(
+        content_type.starts_with("multipart/form-data; boundary="),
+        "unexpected content type {content_type}"
+    );
+
+    form.delete("name");
+    assert!(!form.has("name"));
+}
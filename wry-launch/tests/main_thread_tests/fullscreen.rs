@@ -0,0 +1,84 @@
+use futures_util::StreamExt;
+use wasm_bindgen::{JsCast, JsValue, wasm_bindgen};
+use wasm_bindgen_futures::JsFuture;
+use wry_launch::document::{Element, exit_fullscreen, fullscreen_element, on_fullscreen_change};
+
+#[wasm_bindgen(inline_js = r#"
+    // Headless webviews reject fullscreen without a user gesture, so emulate it
+    let originalRequestFullscreen = null;
+    export function install_fullscreen_stub() {
+        originalRequestFullscreen = Element.prototype.requestFullscreen;
+        let current = null;
+        const change = (element) => {
+            current = element;
+            document.dispatchEvent(new Event("fullscreenchange"));
+        };
+        Object.defineProperty(document, "fullscreenElement", {
+            configurable: true,
+            get: () => current,
+        });
+        Element.prototype.requestFullscreen = function() {
+            change(this);
+            return Promise.resolve();
+        };
+        document.exitFullscreen = function() {
+            if (current === null) {
+                return Promise.reject(new TypeError("Not in fullscreen mode"));
+            }
+            change(null);
+            return Promise.resolve();
+        };
+    }
+    export function remove_fullscreen_stub() {
+        delete document.fullscreenElement;
+        Element.prototype.requestFullscreen = originalRequestFullscreen;
+        delete document.exitFullscreen;
+    }
+    export function create_fullscreen_target() {
+        const element = document.createElement("div");
+        document.body.appendChild(element);
+        return element;
+    }
+    export function remove_element(element) {
+        element.remove();
+    }
+"#)]
+extern "C" {
+    fn install_fullscreen_stub();
+    fn remove_fullscreen_stub();
+    fn create_fullscreen_target() -> JsValue;
+    fn remove_element(element: &JsValue);
+}
+
+/// Test requesting fullscreen on an element and reading back the fullscreen element
+pub(crate) async fn test_request_fullscreen() {
+    install_fullscreen_stub();
+    let (tx, mut rx) = futures_channel::mpsc::unbounded();
+    let listener = on_fullscreen_change(move |_| {
+        tx.unbounded_send(fullscreen_element().is_some()).unwrap();
+    });
+
+    let target = create_fullscreen_target();
+    assert!(fullscreen_element().is_none());
+
+    let element: &Element = target.unchecked_ref();
+    JsFuture::from(element.request_fullscreen())
+        .await
+        .expect("the stubbed request should resolve");
+    assert_eq!(rx.next().await, Some(true));
+    let current = fullscreen_element().expect("an element should be fullscreen");
+    assert!(current.as_ref().loose_eq(&target));
+
+    JsFuture::from(exit_fullscreen())
+        .await
+        .expect("exiting fullscreen should resolve");
+    assert_eq!(rx.next().await, Some(false));
+    assert!(fullscreen_element().is_none());
+
+    // Exiting when nothing is fullscreen surfaces the rejection as an error
+    assert!(JsFuture::from(exit_fullscreen()).await.is_err());
+
+    drop(listener);
+    remove_element(&target);
+    remove_fullscreen_stub();
+}
@@ -0,0 +1,84 @@
+use futures_util::StreamExt;
+use wasm_bindgen::{Closure, JsCast, wasm_bindgen};
+use wasm_bindgen_futures::JsFuture;
+use wry_launch::geolocation::{Position, clear_watch, get_current_position, watch_position};
+
+#[wasm_bindgen(inline_js = r#"
+    export function install_geolocation_stub(deny) {
+        const position = {
+            coords: { latitude: 51.5007, longitude: -0.1246, accuracy: 12.5 },
+            timestamp: 1700000000000,
+        };
+        window.clearedWatches = [];
+        Object.defineProperty(navigator, "geolocation", {
+            configurable: true,
+            value: {
+                getCurrentPosition(success, error) {
+                    if (deny) {
+                        error({ code: 1, message: "User denied Geolocation" });
+                    } else {
+                        success(position);
+                    }
+                },
+                watchPosition(success) {
+                    setTimeout(() => success(position), 0);
+                    return 7;
+                },
+                clearWatch(id) {
+                    window.clearedWatches.push(id);
+                },
+            },
+        });
+    }
+    export function remove_geolocation_stub() {
+        delete navigator.geolocation;
+    }
+    export function cleared_watches() { return window.clearedWatches; }
+"#)]
+extern "C" {
+    fn install_geolocation_stub(deny: bool);
+    fn remove_geolocation_stub();
+    fn cleared_watches() -> Vec<i32>;
+}
+
+/// Test resolving a stubbed position and reading its coordinates
+pub(crate) async fn test_get_current_position() {
+    install_geolocation_stub(false);
+    let position = JsFuture::from(get_current_position())
+        .await
+        .expect("the stubbed position should resolve");
+    remove_geolocation_stub();
+
+    let position: Position = position.unchecked_into();
+    let coords = position.coords();
+    assert_eq!(coords.latitude(), 51.5007);
+    assert_eq!(coords.longitude(), -0.1246);
+    assert_eq!(coords.accuracy(), 12.5);
+    assert_eq!(position.timestamp(), 1700000000000.0);
+}
+
+/// Test that a denied permission rejects the promise
+pub(crate) async fn test_get_current_position_denied() {
+    install_geolocation_stub(true);
+    let result = JsFuture::from(get_current_position()).await;
+    remove_geolocation_stub();
+
+    assert!(result.is_err(), "a denied permission should reject");
+}
+
+/// Test watching the position and clearing the watch
+pub(crate) async fn test_watch_position() {
+    install_geolocation_stub(false);
+    let (tx, mut rx) = futures_channel::mpsc::unbounded();
+    let callback: Closure<dyn FnMut(Position)> = Closure::new(move |position: Position| {
+        tx.unbounded_send(position.coords().latitude()).unwrap();
+    });
+
+    let id = watch_position(&callback);
+    assert_eq!(id, 7);
+    assert_eq!(rx.next().await, Some(51.5007));
+
+    clear_watch(id);
+    assert_eq!(cleared_watches(), vec![7]);
+    remove_geolocation_stub();
+}
@@ -0,0 +1,29 @@
+use wasm_bindgen::{initialization_script, wasm_bindgen};
+
+/// Test that the initialization script sets up the entrypoint Rust evaluates through
+pub(crate) fn test_initialization_script_defines_entrypoint() {
+    let script = initialization_script();
+    assert!(
+        script.contains("window.evaluate_from_rust_binary"),
+        "initialization script should define window.evaluate_from_rust_binary"
+    );
+}
+
+/// Test that running the initialization script again keeps the page's existing state
+pub(crate) fn test_initialization_script_runs_once() {
+    #[wasm_bindgen(inline_js = "export function rerun_keeps_heap(script) {
+        const heap = window.jsHeap;
+        (0, eval)(script);
+        return window.jsHeap === heap;
+    }
+    export function add_one(x) { return x + 1; }")]
+    extern "C" {
+        fn rerun_keeps_heap(script: &str) -> bool;
+        fn add_one(x: u32) -> u32;
+    }
+
+    // The webview already ran the script as an initialization script
+    assert!(rerun_keeps_heap(&initialization_script()));
+    assert!(rerun_keeps_heap(&initialization_script()));
+    assert_eq!(add_one(1), 2);
+}
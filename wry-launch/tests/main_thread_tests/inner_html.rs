@@ -0,0 +1,52 @@
+use wasm_bindgen::wasm_bindgen;
+use wry_launch::document::Element;
+
+#[wasm_bindgen(inline_js = r#"
+    export function create_list() {
+        const element = document.createElement("ul");
+        document.body.appendChild(element);
+        return element;
+    }
+    export function remove_element(element) {
+        element.remove();
+    }
+    export function child_count(element) {
+        return element.children.length;
+    }
+"#)]
+extern "C" {
+    fn create_list() -> Element;
+    fn remove_element(element: &Element);
+    fn child_count(element: &Element) -> u32;
+}
+
+/// Test that HTML set with set_inner_html is parsed and reads back the same
+pub(crate) fn test_inner_html_roundtrip() {
+    let list = create_list();
+    list.set_inner_html("<li>one</li><li>two</li>");
+
+    assert_eq!(child_count(&list), 2);
+    assert_eq!(list.inner_html(), "<li>one</li><li>two</li>");
+    assert_eq!(list.outer_html(), "<ul><li>one</li><li>two</li></ul>");
+
+    list.set_inner_html("");
+    assert_eq!(child_count(&list), 0);
+    remove_element(&list);
+}
+
+/// Test inserting HTML at the end of an element, and that an unknown position is an error
+pub(crate) fn test_insert_adjacent_html() {
+    let list = create_list();
+    list.set_inner_html("<li>first</li>");
+
+    list.insert_adjacent_html("beforeend", "<li>last</li>")
+        .unwrap();
+    assert_eq!(list.inner_html(), "<li>first</li><li>last</li>");
+
+    let error = list
+        .insert_adjacent_html("inside", "<li>nope</li>")
+        .unwrap_err();
+    assert!(error.as_debug_string().contains("SyntaxError"));
+    assert_eq!(child_count(&list), 2);
+    remove_element(&list);
+}
@@ -0,0 +1,66 @@
+use wasm_bindgen::{JsCast, JsValue, wasm_bindgen};
+
+#[wasm_bindgen(inline_js = r#"
+    export function define_shape() {
+        window.Shape = class Shape {};
+        return new window.Shape();
+    }
+    export function create_div() {
+        return document.createElement("div");
+    }
+    export function create_iframe_div() {
+        const iframe = document.createElement("iframe");
+        document.body.appendChild(iframe);
+        const div = iframe.contentDocument.createElement("div");
+        iframe.remove();
+        return div;
+    }
+    export function create_object() {
+        return {};
+    }
+"#)]
+extern "C" {
+    type Shape;
+
+    type HTMLElement;
+
+    type HTMLDivElement;
+
+    // No global with this name exists
+    type NotAGlobalClass;
+
+    // `Math` is a global, but not a constructor
+    #[wasm_bindgen(js_name = Math)]
+    type MathNamespace;
+
+    fn define_shape() -> JsValue;
+    fn create_div() -> JsValue;
+    fn create_iframe_div() -> JsValue;
+    fn create_object() -> JsValue;
+}
+
+/// Test that casts to imported classes run instanceof against the named global
+pub(crate) fn test_instanceof_imported_classes() {
+    let shape = define_shape();
+    let div = create_div();
+    let object = create_object();
+
+    assert!(shape.dyn_ref::<Shape>().is_some());
+    assert!(object.dyn_ref::<Shape>().is_none());
+    assert!(div.dyn_ref::<HTMLElement>().is_some());
+    assert!(div.dyn_ref::<HTMLDivElement>().is_some());
+    assert!(shape.dyn_ref::<HTMLElement>().is_none());
+    assert!(JsValue::from("div").dyn_ref::<HTMLElement>().is_none());
+
+    // Missing and non-constructor globals are false instead of throwing
+    assert!(div.dyn_ref::<NotAGlobalClass>().is_none());
+    assert!(object.dyn_ref::<MathNamespace>().is_none());
+}
+
+/// Test that values from another realm, like an iframe, match by class name
+pub(crate) fn test_instanceof_other_realm() {
+    let div = create_iframe_div();
+    assert!(div.dyn_ref::<HTMLDivElement>().is_some());
+    assert!(div.dyn_ref::<HTMLElement>().is_some());
+    assert!(div.dyn_ref::<Shape>().is_none());
+}
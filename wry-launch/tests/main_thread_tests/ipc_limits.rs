@@ -0,0 +1,49 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use wasm_bindgen::DecodeError;
+use wasm_bindgen::wry::WryBindgen;
+use wry_launch::wry::http;
+
+/// Test that messages over the configured size are rejected before they are decoded
+pub(crate) fn test_max_message_size_rejects_oversized() {
+    let wry_bindgen = WryBindgen::new(|_| {});
+    wry_bindgen.set_max_message_size(1024);
+
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    wry_bindgen.set_on_ipc_error({
+        let errors = errors.clone();
+        move |err: &DecodeError| errors.borrow_mut().push(err.clone())
+    });
+
+    let app_builder = wry_bindgen.app_builder();
+    let protocol_handler = app_builder.protocol_handler();
+
+    // 64 KiB of base64 decodes to roughly 48 KiB, well over the 1 KiB limit
+    let payload = "A".repeat(64 * 1024);
+    let request = http::Request::builder()
+        .uri("wry://index.html/__wbg__/handler")
+        .header("dioxus-data", payload)
+        .body(Vec::new())
+        .unwrap();
+
+    let status = Rc::new(Cell::new(None));
+    let responder = {
+        let status = status.clone();
+        move |response: http::Response<Vec<u8>>| status.set(Some(response.status().as_u16()))
+    };
+    let unhandled = protocol_handler.handle_request("wry", |_| {}, &request, responder);
+
+    assert!(
+        unhandled.is_none(),
+        "the handler request should be answered"
+    );
+    assert_eq!(status.get(), Some(400));
+    let errors = errors.borrow();
+    assert_eq!(errors.len(), 1);
+    let DecodeError::MessageTooLarge { limit, actual } = errors[0] else {
+        panic!("expected MessageTooLarge, got {:?}", errors[0]);
+    };
+    assert_eq!(limit, 1024);
+    assert!(actual > limit);
+}
@@ -0,0 +1,26 @@
+use wasm_bindgen::{DecodeError, DecodedData, EncodedData, IPC_VERSION};
+
+/// Test that a buffer from a different protocol version is rejected with a clear error
+pub(crate) fn test_ipc_version_mismatch() {
+    let mut bytes = EncodedData::new().to_bytes();
+    assert!(DecodedData::from_bytes(&bytes).is_ok());
+
+    // The version byte follows the three magic bytes
+    bytes[3] = IPC_VERSION.wrapping_add(1);
+    let err = DecodedData::from_bytes(&bytes).unwrap_err();
+    assert_eq!(
+        err,
+        DecodeError::VersionMismatch {
+            expected: IPC_VERSION,
+            got: IPC_VERSION.wrapping_add(1),
+        }
+    );
+}
+
+/// Test that a buffer without the magic prefix is rejected
+pub(crate) fn test_ipc_invalid_magic() {
+    let mut bytes = EncodedData::new().to_bytes();
+    bytes[0] = b'X';
+    let err = DecodedData::from_bytes(&bytes).unwrap_err();
+    assert!(matches!(err, DecodeError::InvalidMagic { .. }));
+}
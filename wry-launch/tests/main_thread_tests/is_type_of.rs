@@ -129,3 +129,56 @@ pub(crate) fn test_has_type_with_is_type_of() {
         "has_type should return false for number when checking for string"
     );
 }
+
+#[wasm_bindgen(inline_js = r#"
+    export function install_tracked_class() {
+        window.instanceofCalls = 0;
+        window.TrackedClass = class {
+            static [Symbol.hasInstance](_value) {
+                window.instanceofCalls++;
+                return false;
+            }
+        };
+    }
+    export function instanceof_calls() { return window.instanceofCalls; }
+"#)]
+extern "C" {
+    // Final types trust the declaration and never call instanceof
+    #[wasm_bindgen(final, js_name = TrackedClass)]
+    type FinalTracked;
+
+    #[wasm_bindgen(js_name = TrackedClass)]
+    type CheckedTracked;
+
+    fn install_tracked_class();
+    fn instanceof_calls() -> u32;
+}
+
+pub(crate) fn test_final_skips_instanceof() {
+    install_tracked_class();
+    let obj_val = create_object();
+
+    // The final type accepts the value without asking JS
+    let final_result: Result<FinalTracked, _> = obj_val.clone().dyn_into();
+    assert!(
+        final_result.is_ok(),
+        "dyn_into should succeed for final type"
+    );
+    assert_eq!(
+        instanceof_calls(),
+        0,
+        "final type should not call instanceof"
+    );
+
+    // A regular type with the same JS class still runs the instanceof check
+    let checked_result: Result<CheckedTracked, _> = obj_val.dyn_into();
+    assert!(checked_result.is_err(), "dyn_into should run instanceof");
+    assert_eq!(instanceof_calls(), 1, "regular type should call instanceof");
+}
+
+pub(crate) fn test_unchecked_dyn_into() {
+    let str_val = create_string();
+    let js_string: JsString = str_val.unchecked_dyn_into();
+    let value: &JsValue = js_string.as_ref();
+    assert!(value.is_string());
+}
@@ -0,0 +1,49 @@
+use wasm_bindgen::wasm_bindgen;
+use wasm_bindgen_futures::JsFuture;
+use wry_launch::js_futures::{join_all, race};
+
+#[wasm_bindgen(inline_js = "export function settle_after(ms, ok, value) {
+    return new Promise((resolve, reject) => {
+        setTimeout(() => ok ? resolve(value) : reject(value), ms);
+    });
+}")]
+extern "C" {
+    fn settle_after(ms: u32, ok: bool, value: &str) -> js_sys::Promise;
+}
+
+/// Test that join_all waits for promises settling at different times and keeps their order
+pub(crate) async fn test_join_all_promises() {
+    let futs = vec![
+        JsFuture::from(settle_after(30, true, "slow")),
+        JsFuture::from(settle_after(5, false, "failed")),
+        JsFuture::from(settle_after(15, true, "medium")),
+    ];
+    let results = join_all(futs).await;
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap(), "slow");
+    assert_eq!(results[1].as_ref().unwrap_err(), "failed");
+    assert_eq!(results[2].as_ref().unwrap(), "medium");
+
+    assert!(join_all(Vec::new()).await.is_empty());
+}
+
+/// Test that race returns the first promise to settle, whether it resolves or rejects
+pub(crate) async fn test_race_promises() {
+    let (result, index) = race(vec![
+        JsFuture::from(settle_after(40, true, "slow")),
+        JsFuture::from(settle_after(5, true, "fast")),
+        JsFuture::from(settle_after(20, true, "medium")),
+    ])
+    .await;
+    assert_eq!(index, 1);
+    assert_eq!(result.unwrap(), "fast");
+
+    let (result, index) = race(vec![
+        JsFuture::from(settle_after(30, true, "slow")),
+        JsFuture::from(settle_after(5, false, "failed")),
+    ])
+    .await;
+    assert_eq!(index, 1);
+    assert_eq!(result.unwrap_err(), "failed");
+}
@@ -499,6 +499,27 @@ pub(crate) fn test_instanceof_dyn_ref() {
     assert!(num_ref.is_none(), "dyn_ref should return None for non-Date");
 }
 
+pub(crate) fn test_instanceof_value() {
+    // Test instanceof against a constructor obtained at runtime
+    #[wasm_bindgen(inline_js = r#"
+        export function get_array_constructor() { return Array; }
+        export function get_array() { return [1, 2, 3]; }
+        export function get_plain_object() { return {}; }
+    "#)]
+    extern "C" {
+        fn get_array_constructor() -> JsValue;
+        fn get_array() -> JsValue;
+        fn get_plain_object() -> JsValue;
+    }
+
+    let array_ctor = get_array_constructor();
+    assert!(get_array().instanceof_value(&array_ctor));
+    assert!(!get_plain_object().instanceof_value(&array_ctor));
+
+    // A value that is not a constructor never matches
+    assert!(!get_array().instanceof_value(&JsValue::from_f64(1.0)));
+}
+
 pub(crate) fn test_partial_eq_bool() {
     // Test PartialEq<bool> for JsValue
     let js_true = JsValue::from_bool(true);
@@ -657,6 +678,49 @@ pub(crate) fn test_try_from_string() {
     );
 }
 
+pub(crate) fn test_try_from_bool_and_number_precision() {
+    // Test TryFrom<JsValue> for bool
+    let result: Result<bool, _> = JsValue::from(true).try_into();
+    assert_eq!(result.ok(), Some(true));
+    let result: Result<bool, _> = (&JsValue::from(false)).try_into();
+    assert_eq!(result.ok(), Some(false));
+    let result: Result<bool, _> = JsValue::from(1.0).try_into();
+    assert!(
+        result.is_err(),
+        "TryFrom<JsValue> for bool should fail for number"
+    );
+
+    // Test TryFrom<&JsValue> for String
+    let js_str = JsValue::from("borrowed");
+    let result: Result<String, _> = (&js_str).try_into();
+    assert_eq!(result.ok().as_deref(), Some("borrowed"));
+    let result: Result<String, _> = (&JsValue::from(false)).try_into();
+    assert!(
+        result.is_err(),
+        "TryFrom<&JsValue> for String should fail for bool"
+    );
+
+    // Conversions from Rust primitives
+    assert_eq!(JsValue::from(-7i32).as_f64(), Some(-7.0));
+    assert_eq!(JsValue::from(2.5f64).as_f64(), Some(2.5));
+    assert_eq!(
+        JsValue::from(String::from("owned")).as_string().as_deref(),
+        Some("owned")
+    );
+
+    // Integers are exact up to 2^53 and rounded beyond that
+    let max_exact = 1u64 << 53;
+    assert_eq!(JsValue::from(max_exact).as_f64(), Some(max_exact as f64));
+    assert_eq!(
+        JsValue::from(-(max_exact as i64)).as_f64(),
+        Some(-(max_exact as f64))
+    );
+    assert_eq!(
+        JsValue::from(max_exact + 1).as_f64(),
+        Some(max_exact as f64)
+    );
+}
+
 pub(crate) fn test_owned_arithmetic_operators() {
     // Test arithmetic operators with owned JsValue
     #[wasm_bindgen(inline_js = r#"
@@ -787,3 +851,584 @@ pub(crate) fn test_as_ref_jsvalue() {
         "AsRef<JsValue> should return self"
     );
 }
+
+#[cfg(debug_assertions)]
+pub(crate) fn test_debug_assert_alive() {
+    let value = JsValue::from_str("alive");
+    value.debug_assert_alive();
+
+    // Copy the raw heap index out and use it after the original value is dropped
+    let alias = std::mem::ManuallyDrop::new(JsValue { idx: value.idx });
+    drop(value);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        alias.debug_assert_alive();
+    }));
+    let message = result
+        .expect_err("using a dropped JsValue should panic")
+        .downcast::<String>()
+        .expect("panic payload should be a formatted string");
+    assert!(
+        message.starts_with("use of dropped JsValue #"),
+        "unexpected panic message: {message}"
+    );
+}
+
+pub(crate) fn test_stale_generation_is_inert() {
+    // Test that a value from an earlier webview generation acts as undefined
+    #[wasm_bindgen(inline_js = r#"
+        export function type_of(value) { return typeof value; }
+    "#)]
+    extern "C" {
+        fn type_of(value: &JsValue) -> String;
+    }
+
+    let generation = wasm_bindgen::batch::webview_generation();
+    let value = JsValue::from_str("current");
+    assert!(!value.is_stale());
+    assert!(!JsValue::UNDEFINED.is_stale());
+    assert_eq!(type_of(&value), "string");
+
+    // Stamp the same heap slot with another generation, as if the page had reloaded
+    let alive_before = crate::heap_objects_alive();
+    let stale = JsValue {
+        idx: value.idx + (1 << 32),
+    };
+    assert!(stale.is_stale());
+    assert_ne!(stale, value);
+    assert_eq!(type_of(&stale), "undefined");
+    assert!(stale.clone().is_undefined());
+    drop(stale);
+
+    // Dropping the stale value must not free the live value in the same slot
+    assert_eq!(crate::heap_objects_alive(), alive_before);
+    assert_eq!(value.as_string().as_deref(), Some("current"));
+    assert_eq!(wasm_bindgen::batch::webview_generation(), generation);
+}
+
+pub(crate) fn test_drop_before_reuse_ordering() {
+    // Test that a drop queued before a new allocation never frees the new value
+    #[wasm_bindgen(inline_js = r#"
+        export function make_tagged(tag) { return { tag }; }
+        export function read_tag(obj) { return obj.tag; }
+    "#)]
+    extern "C" {
+        fn make_tagged(tag: u32) -> JsValue;
+        fn read_tag(obj: &JsValue) -> u32;
+    }
+
+    for i in 0..100 {
+        let old = make_tagged(i);
+        drop(old);
+        let new = make_tagged(i + 1000);
+        assert_eq!(read_tag(&new), i + 1000, "new value was affected by a drop");
+    }
+
+    // Interleave drops and allocations without flushing in between
+    let values: Vec<JsValue> = (0..50).map(make_tagged).collect();
+    let mut replacements = Vec::new();
+    for (i, value) in values.into_iter().enumerate() {
+        drop(value);
+        replacements.push(make_tagged(i as u32 + 500));
+    }
+    for (i, value) in replacements.iter().enumerate() {
+        assert_eq!(read_tag(value), i as u32 + 500);
+    }
+}
+
+pub(crate) fn test_new_object_and_array() {
+    use js_sys::Reflect;
+
+    // Test creating an empty object and setting a property on it
+    let obj = JsValue::new_object();
+    assert!(obj.is_object());
+    let key = JsValue::from_str("answer");
+    assert!(Reflect::get(&obj, &key).unwrap().is_undefined());
+    Reflect::set(&obj, &key, &JsValue::from_f64(42.0)).unwrap();
+    assert_eq!(Reflect::get(&obj, &key).unwrap().as_f64(), Some(42.0));
+
+    // Test creating an empty array and pushing through an index
+    let arr = JsValue::new_array();
+    let length = JsValue::from_str("length");
+    assert_eq!(Reflect::get(&arr, &length).unwrap().as_f64(), Some(0.0));
+    Reflect::set(&arr, &JsValue::from_f64(0.0), &JsValue::from_str("first")).unwrap();
+    assert_eq!(Reflect::get(&arr, &length).unwrap().as_f64(), Some(1.0));
+}
+
+pub(crate) fn test_identity_token() {
+    // Clones are separate heap entries for the same object
+    let obj = JsValue::new_object();
+    let clone = obj.clone();
+    assert_ne!(obj.id(), clone.id());
+    assert_eq!(obj.identity_token(), clone.identity_token());
+    assert_eq!(obj.identity_token(), obj.identity_token());
+
+    // Distinct objects get distinct tokens, even when they look the same
+    let other = JsValue::new_object();
+    assert_ne!(obj.identity_token(), other.identity_token());
+
+    // Primitives are identified by value
+    assert_eq!(
+        JsValue::from_str("same").identity_token(),
+        JsValue::from_str("same").identity_token()
+    );
+    assert_ne!(
+        JsValue::from_str("1").identity_token(),
+        JsValue::from_f64(1.0).identity_token()
+    );
+    assert_ne!(
+        JsValue::from_str("same").identity_token(),
+        obj.identity_token()
+    );
+
+    // Primitive tokens are not unique: different symbols that print the same collide
+    #[wasm_bindgen(inline_js = "export function make_symbol(description) {
+        return Symbol(description);
+    }")]
+    extern "C" {
+        fn make_symbol(description: &str) -> JsValue;
+    }
+    let first = make_symbol("token");
+    let second = make_symbol("token");
+    assert!(first.is_symbol());
+    assert!(!first.loose_eq(&second));
+    assert_eq!(first.identity_token(), second.identity_token());
+}
+
+pub(crate) fn test_deep_eq() {
+    #[wasm_bindgen(inline_js = r#"
+        export function make_nested(label) {
+            return { label, items: [1, 2, { deep: [NaN, "x"] }], created: new Date(0) };
+        }
+        export function make_cycle() {
+            const value = { name: "cycle" };
+            value.self = value;
+            return value;
+        }
+        export function make_shared(share) {
+            const leaf = () => ({ items: [1, 2] });
+            const first = leaf();
+            return { first, second: share ? first : leaf() };
+        }
+        export function make_map(label) {
+            return new Map([["key", { label }], [1, [NaN]]]);
+        }
+        export function make_set(label) {
+            return new Set(["a", label]);
+        }
+        export function make_object_set() {
+            return new Set([{}]);
+        }
+    "#)]
+    extern "C" {
+        fn make_nested(label: &str) -> JsValue;
+        fn make_cycle() -> JsValue;
+        fn make_shared(share: bool) -> JsValue;
+        fn make_map(label: &str) -> JsValue;
+        fn make_set(label: &str) -> JsValue;
+        fn make_object_set() -> JsValue;
+    }
+
+    // Structurally equal but distinct objects
+    let a = make_nested("same");
+    let b = make_nested("same");
+    assert!(a.deep_eq(&b));
+    assert!(!a.loose_eq(&b), "the objects are distinct references");
+    assert!(!a.deep_eq(&make_nested("different")));
+
+    // Arrays and objects with the same keys are not equal
+    assert!(!JsValue::new_array().deep_eq(&JsValue::new_object()));
+
+    // Cycles are reported as unequal instead of recursing forever
+    let cycle = make_cycle();
+    assert!(!cycle.deep_eq(&make_cycle()));
+    // The same reference is still equal to itself
+    assert!(cycle.deep_eq(&cycle));
+
+    // An object reached twice without a cycle is compared like a copy
+    assert!(make_shared(true).deep_eq(&make_shared(false)));
+
+    // Maps and sets have no own enumerable properties, so their entries are compared
+    assert!(make_map("same").deep_eq(&make_map("same")));
+    assert!(!make_map("same").deep_eq(&make_map("different")));
+    assert!(make_set("same").deep_eq(&make_set("same")));
+    assert!(!make_set("same").deep_eq(&make_set("different")));
+    assert!(!make_set("same").deep_eq(&make_map("same")));
+    // Set items are matched by identity, so equal-looking objects differ
+    assert!(!make_object_set().deep_eq(&make_object_set()));
+}
+
+pub(crate) fn test_try_iter() {
+    #[wasm_bindgen(inline_js = r#"
+        export function make_iterable_array() {
+            return [1, undefined, "three"];
+        }
+    "#)]
+    extern "C" {
+        fn make_iterable_array() -> JsValue;
+    }
+
+    // Arrays are iterable, and a yielded undefined does not end iteration
+    let items: Vec<JsValue> = make_iterable_array()
+        .try_iter()
+        .expect("arrays are iterable")
+        .collect();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].as_f64(), Some(1.0));
+    assert!(items[1].is_undefined());
+    assert_eq!(items[2].as_string().as_deref(), Some("three"));
+
+    // Plain objects and missing values are not iterable
+    assert!(JsValue::new_object().try_iter().is_none());
+    assert!(JsValue::undefined().try_iter().is_none());
+    assert!(JsValue::null().try_iter().is_none());
+}
+
+pub(crate) fn test_construct() {
+    use wasm_bindgen::JsCast;
+
+    #[wasm_bindgen(inline_js = r#"
+        export function date_constructor() {
+            return Date;
+        }
+    "#)]
+    extern "C" {
+        fn date_constructor() -> JsValue;
+    }
+
+    // Construct a Date through a reference to its constructor
+    let epoch_ms = 1_700_000_000_123.0;
+    let date = date_constructor()
+        .construct(&[JsValue::from_f64(epoch_ms)])
+        .expect("Date is a constructor")
+        .dyn_into::<js_sys::Date>()
+        .expect("constructing Date should produce a Date");
+    assert_eq!(date.get_time(), epoch_ms);
+
+    // Values that are not constructors return the thrown error
+    assert!(JsValue::from_f64(1.0).construct(&[]).is_err());
+    assert!(JsValue::new_object().construct(&[]).is_err());
+}
+
+pub(crate) fn test_as_array_and_object() {
+    #[wasm_bindgen(inline_js = r#"
+        export function make_numbers() {
+            return [1, 2, 3];
+        }
+    "#)]
+    extern "C" {
+        fn make_numbers() -> JsValue;
+    }
+
+    // Borrow an array value without consuming it
+    let value = make_numbers();
+    let array = value.as_array().expect("the value is an array");
+    assert_eq!(array.to_vec().len(), 3);
+    assert!(value.as_object().is_some(), "arrays are objects too");
+
+    let object = JsValue::new_object();
+    assert!(object.as_object().is_some());
+    assert!(object.as_array().is_none());
+
+    // Primitives are neither
+    let number = JsValue::from_f64(1.0);
+    assert!(number.as_array().is_none());
+    assert!(number.as_object().is_none());
+}
+
+/// Test that a snapshot is a deep copy unaffected by mutating the original
+pub(crate) fn test_snapshot_rehydrate() {
+    #[wasm_bindgen(inline_js = "export function make_settings() {
+        return { theme: 'dark', sizes: [1, 2, 3], nested: { enabled: true } };
+    }
+    export function mutate_settings(s) {
+        s.theme = 'light';
+        s.sizes.push(4);
+        s.nested.enabled = false;
+    }
+    export function describe(s) {
+        return `${s.theme} ${s.sizes.join(',')} ${s.nested.enabled}`;
+    }
+    export function make_cycle() {
+        const a = {};
+        a.self = a;
+        return a;
+    }
+    export function make_function() {
+        return () => 1;
+    }")]
+    extern "C" {
+        fn make_settings() -> JsValue;
+        fn mutate_settings(s: &JsValue);
+        fn describe(s: &JsValue) -> String;
+        fn make_cycle() -> JsValue;
+        fn make_function() -> JsValue;
+    }
+
+    let settings = make_settings();
+    let snapshot = settings.snapshot().unwrap();
+    mutate_settings(&settings);
+    assert_eq!(describe(&settings), "light 1,2,3,4 false");
+
+    let restored = snapshot.rehydrate();
+    assert_eq!(describe(&restored), "dark 1,2,3 true");
+    // Rehydrated values are independent copies
+    mutate_settings(&restored);
+    assert_eq!(describe(&snapshot.rehydrate()), "dark 1,2,3 true");
+
+    // Values without a JSON form are rejected
+    assert!(make_cycle().snapshot().is_err());
+    assert!(make_function().snapshot().is_err());
+    assert!(JsValue::undefined().snapshot().is_err());
+    assert_eq!(JsValue::from(3.5).snapshot().unwrap().as_json(), "3.5");
+}
+
+pub(crate) fn test_structured_clone() {
+    #[wasm_bindgen(inline_js = "export function make_record() {
+        return { when: new Date(86400000), bytes: new Uint8Array([1, 2, 3]) };
+    }
+    export function describe_record(r) {
+        return `${r.when instanceof Date} ${r.when.getTime()} ${r.bytes instanceof Uint8Array} ${r.bytes.join(',')}`;
+    }
+    export function mutate_record(r) {
+        r.when.setTime(0);
+        r.bytes[0] = 9;
+    }
+    export function make_function() {
+        return () => 1;
+    }")]
+    extern "C" {
+        fn make_record() -> JsValue;
+        fn describe_record(r: &JsValue) -> String;
+        fn mutate_record(r: &JsValue);
+        fn make_function() -> JsValue;
+    }
+
+    let record = make_record();
+    let clone = record.structured_clone().unwrap();
+    // Dates and typed arrays keep their types
+    assert_eq!(describe_record(&clone), "true 86400000 true 1,2,3");
+
+    // The clone is independent of the original
+    mutate_record(&record);
+    assert_eq!(describe_record(&record), "true 0 true 9,2,3");
+    assert_eq!(describe_record(&clone), "true 86400000 true 1,2,3");
+
+    // Functions cannot be cloned
+    assert!(make_function().structured_clone().is_err());
+    assert_eq!(JsValue::from("text").structured_clone().unwrap(), "text");
+}
+
+/// Test that as_f64, as_bool and as_string only accept their own JS type
+pub(crate) fn test_typed_accessors_do_not_coerce() {
+    #[wasm_bindgen(inline_js = "export function js_number() { return 5; }
+    export function js_numeric_string() { return '5'; }
+    export function js_bool() { return true; }
+    export function js_boxed_false() { return new Boolean(false); }
+    export function js_object() { return { toString() { return 'object'; } }; }")]
+    extern "C" {
+        fn js_number() -> JsValue;
+        fn js_numeric_string() -> JsValue;
+        fn js_bool() -> JsValue;
+        fn js_boxed_false() -> JsValue;
+        fn js_object() -> JsValue;
+    }
+
+    let number = js_number();
+    assert_eq!(number.as_f64(), Some(5.0));
+    assert_eq!(number.as_bool(), None);
+    assert_eq!(number.as_string(), None);
+
+    let string = js_numeric_string();
+    assert_eq!(string.as_f64(), None);
+    assert_eq!(string.as_bool(), None);
+    assert_eq!(string.as_string().as_deref(), Some("5"));
+
+    let bool = js_bool();
+    assert_eq!(bool.as_bool(), Some(true));
+    assert_eq!(bool.as_f64(), None);
+    assert_eq!(bool.as_string(), None);
+    assert_eq!(JsValue::FALSE.as_bool(), Some(false));
+
+    // Boxed primitives and objects are not their primitive type
+    assert_eq!(js_boxed_false().as_bool(), None);
+    let object = js_object();
+    assert_eq!(object.as_string(), None);
+    assert_eq!(object.as_f64(), None);
+    assert_eq!(JsValue::NULL.as_f64(), None);
+    assert_eq!(JsValue::UNDEFINED.as_string(), None);
+
+    // Values created in Rust read back the same way
+    assert_eq!(JsValue::from(2.5).as_f64(), Some(2.5));
+    assert_eq!(JsValue::from("text").as_string().as_deref(), Some("text"));
+}
+
+/// Test calling JS functions received at runtime through JsFunction
+pub(crate) fn test_js_function_call() {
+    use wasm_bindgen::{JsArray, JsCast, JsFunction};
+
+    #[wasm_bindgen(inline_js = "export function make_adder() {
+        return function (a, b) { return (this?.base ?? 0) + a + (b ?? 0); };
+    }
+    export function make_thrower() {
+        return () => { throw new Error('boom'); };
+    }
+    export function make_context() {
+        return { base: 100 };
+    }")]
+    extern "C" {
+        fn make_adder() -> JsValue;
+        fn make_thrower() -> JsValue;
+        fn make_context() -> JsValue;
+    }
+
+    let add: JsFunction = make_adder().dyn_into().unwrap();
+    let undefined = JsValue::undefined();
+    let context = make_context();
+    let one = JsValue::from(1.0);
+    let two = JsValue::from(2.0);
+
+    assert!(add.call0(&undefined).unwrap().as_f64().unwrap().is_nan());
+    assert_eq!(add.call1(&undefined, &one).unwrap().as_f64(), Some(1.0));
+    assert_eq!(
+        add.call2(&context, &one, &two).unwrap().as_f64(),
+        Some(103.0)
+    );
+    let args = JsArray::from_rust([5.0, 6.0]);
+    assert_eq!(add.apply(&context, &args).unwrap().as_f64(), Some(111.0));
+
+    // Exceptions are returned instead of panicking
+    let thrower: JsFunction = make_thrower().dyn_into().unwrap();
+    let error = thrower.call0(&undefined).unwrap_err();
+    assert!(error.as_debug_string().contains("boom"));
+
+    // Only functions pass the cast, and calling a non-function is an error
+    assert!(
+        JsValue::from("not a function")
+            .dyn_into::<JsFunction>()
+            .is_err()
+    );
+    let not_a_function: JsFunction = context.unchecked_into();
+    assert!(not_a_function.call0(&undefined).is_err());
+}
+
+/// Test dispatching on the kind of arbitrary values with a visitor
+pub(crate) fn test_jsvalue_visitor() {
+    use wasm_bindgen::{JsArray, JsFunction, JsObject, JsValueVisitor};
+
+    // Formats each kind differently, recursing into arrays and objects
+    struct Stringify;
+
+    impl JsValueVisitor for Stringify {
+        type Output = String;
+
+        fn visit_undefined(self) -> String {
+            "undefined".to_string()
+        }
+
+        fn visit_null(self) -> String {
+            "null".to_string()
+        }
+
+        fn visit_bool(self, value: bool) -> String {
+            format!("bool:{value}")
+        }
+
+        fn visit_number(self, value: f64) -> String {
+            format!("number:{value}")
+        }
+
+        fn visit_string(self, value: String) -> String {
+            format!("{value:?}")
+        }
+
+        fn visit_array(self, value: &JsArray) -> String {
+            let items: Vec<String> = value
+                .to_vec()
+                .iter()
+                .map(|item| item.visit(Stringify))
+                .collect();
+            format!("[{}]", items.join(","))
+        }
+
+        fn visit_function(self, _: &JsFunction) -> String {
+            "<function>".to_string()
+        }
+
+        fn visit_object(self, value: &JsObject) -> String {
+            let mut entries: Vec<(String, JsValue)> = value.to_hashmap().into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let fields: Vec<String> = entries
+                .iter()
+                .map(|(key, value)| format!("{key}={}", value.visit(Stringify)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+
+        fn visit_other(self, value: &JsValue) -> String {
+            format!("<{}>", value.js_typeof().as_string().unwrap())
+        }
+    }
+
+    #[wasm_bindgen(inline_js = "export function mixed_values() {
+        return [undefined, null, false, 1.5, 'text', [2, 'x'], () => 1, { b: true, a: null }, Symbol('s'), 10n];
+    }
+    export function heap_true() { return new Boolean(true).valueOf(); }")]
+    extern "C" {
+        fn mixed_values() -> JsValue;
+        fn heap_true() -> JsValue;
+    }
+
+    assert_eq!(
+        mixed_values().visit(Stringify),
+        "[undefined,null,bool:false,number:1.5,\"text\",[number:2,\"x\"],<function>,{a=null,b=bool:true},<symbol>,<bigint>]"
+    );
+
+    // Constants are visited without asking JS
+    assert_eq!(JsValue::UNDEFINED.visit(Stringify), "undefined");
+    assert_eq!(JsValue::NULL.visit(Stringify), "null");
+    assert_eq!(JsValue::TRUE.visit(Stringify), "bool:true");
+    assert_eq!(heap_true().visit(Stringify), "bool:true");
+    assert_eq!(JsValue::from("quoted").visit(Stringify), "\"quoted\"");
+}
+
+/// Test that the array, array buffer, date and typed array predicates each match only
+/// their own kind of value
+pub(crate) fn test_jsvalue_kind_predicates() {
+    #[wasm_bindgen(inline_js = r#"
+        export function kind_values() {
+            return [
+                [1, 2],
+                new ArrayBuffer(4),
+                new Date(0),
+                new Uint8Array(4),
+                new DataView(new ArrayBuffer(4)),
+                { length: 0 },
+                "text",
+            ];
+        }
+    "#)]
+    extern "C" {
+        fn kind_values() -> Vec<JsValue>;
+    }
+
+    let values = kind_values();
+    let expected = [
+        [true, false, false, false],
+        [false, true, false, false],
+        [false, false, true, false],
+        [false, false, false, true],
+        [false, false, false, false],
+        [false, false, false, false],
+        [false, false, false, false],
+    ];
+    assert_eq!(values.len(), expected.len());
+    for (i, (value, expected)) in values.iter().zip(expected).enumerate() {
+        let actual = [
+            value.is_array(),
+            value.is_array_buffer(),
+            value.is_date(),
+            value.is_typed_array(),
+        ];
+        assert_eq!(actual, expected, "value {i}: {value:?}");
+    }
+}
@@ -0,0 +1,55 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::wasm_bindgen;
+use wry_launch::document::Element;
+
+#[wasm_bindgen(inline_js = r#"
+    export function create_button() {
+        return document.createElement("button");
+    }
+    export function click(element) {
+        element.dispatchEvent(new Event("click"));
+    }
+"#)]
+extern "C" {
+    fn create_button() -> Element;
+    fn click(element: &Element);
+}
+
+/// Sets a flag when dropped, to observe when the handler is freed
+struct DropFlag(Rc<Cell<bool>>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.set(true);
+    }
+}
+
+/// Test that dropping a ListenerGuard removes the listener and frees the handler
+pub(crate) fn test_listener_guard_removes_and_drops() {
+    let button = create_button();
+    let clicks = Rc::new(Cell::new(0));
+    let dropped = Rc::new(Cell::new(false));
+
+    let guard = button.add_event_listener("click", {
+        let clicks = clicks.clone();
+        let flag = DropFlag(dropped.clone());
+        move |_| {
+            let _ = &flag;
+            clicks.set(clicks.get() + 1);
+        }
+    });
+    click(&button);
+    click(&button);
+    assert_eq!(clicks.get(), 2);
+    assert!(!dropped.get());
+
+    drop(guard);
+    assert!(
+        dropped.get(),
+        "the handler should be dropped with the guard"
+    );
+    click(&button);
+    assert_eq!(clicks.get(), 2, "the listener should no longer fire");
+}
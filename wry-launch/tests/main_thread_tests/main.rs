@@ -2,21 +2,65 @@ use tokio::select;
 use wasm_bindgen::{batch::batch_async, wasm_bindgen};
 
 mod add_number_js;
+mod animation;
+mod app_shutdown;
+mod array;
 #[allow(clippy::redundant_closure)]
 mod async_bindings;
+mod async_exports;
+mod attached_futures;
+mod audio;
+mod batch_metrics;
+mod beacon;
+mod block_on;
 mod borrow_stack;
 mod callbacks;
 mod catch_attribute;
 mod clamped;
+#[path = "../codec_fixture/mod.rs"]
+mod codec_fixture;
+mod console;
+mod cookie;
+mod crypto;
+mod dialog;
+mod dom_rect;
+mod fetch;
+mod fullscreen;
+mod geolocation;
 mod indexing;
+mod init_script;
+mod inner_html;
+mod instanceof;
+mod ipc_limits;
+mod ipc_version;
 mod is_type_of;
+mod js_futures;
 mod jsvalue;
+mod listener_guard;
+mod main_thread;
+mod malformed_ipc;
+mod media;
 mod module_import;
+mod observer;
+mod permissions;
 mod reentrant_callbacks;
 mod roundtrip;
+mod serde_bridge;
+mod shutdown;
+mod spawn_local;
 mod string_enum;
 mod structs;
 mod thread_local;
+mod thread_name;
+mod typed_array;
+mod unit;
+mod url;
+mod url_prefix;
+mod websocket;
+mod webview_queue;
+mod window;
+mod window_handle;
+mod zero_copy;
 
 #[wasm_bindgen(inline_js = "export function heap_objects_alive(f) {
     return window.jsHeap.heapObjectsAlive();
@@ -71,13 +115,85 @@ async fn async_test_with_js_context<Fut: std::future::Future<Output = ()>, F: Fn
 }
 
 fn main() {
-    wry_launch::run_headless(|| async {
+    // Tasks sent to the main thread fail cleanly until the event loop is running
+    main_thread::test_try_run_before_event_loop();
+
+    // Same as `run_headless`, but capturing console output for the console tests
+    let window = wry_launch::WindowBuilder::new().with_visible(false);
+    let plugin_jobs = attached_futures::plugin_jobs();
+    let plugin_stop = attached_futures::short_lived_plugin_stop();
+    // Deny popups so the window tests see the blocked path every time
+    let webview = wry_launch::WebViewBuilder::new()
+        .with_devtools(true)
+        .with_new_window_req_handler(|_, _| wry_launch::wry::NewWindowResponse::Deny);
+    let launch = wry_launch::LaunchBuilder::new()
+        .window(window)
+        .webview(webview)
+        .app_protocol(fetch::echo_endpoint)
+        .thread_name(thread_name::APP_THREAD_NAME)
+        .stack_size(thread_name::APP_STACK_SIZE)
+        .attach(move || attached_futures::plugin(plugin_jobs))
+        .attach(move || attached_futures::short_lived_plugin(plugin_stop));
+    // Capturing console output needs wry-launch's `test-harness` feature
+    #[cfg(feature = "test-harness")]
+    let launch = launch.console_sink(console::captured_console());
+    wry_launch::LaunchBuilder::run(launch, || async {
         // Adding numbers with and without batching
         test_with_js_context(add_number_js::test_add_number_js).await;
         test_with_js_context(add_number_js::test_add_number_js_batch).await;
 
+        // Batch metrics tests
+        test_with_js_context(batch_metrics::test_batch_metrics_coalesced).await;
+        test_with_js_context(batch_metrics::test_drops_coalesced).await;
+        test_with_js_context(batch_metrics::test_flush_pending_drops).await;
+        test_with_js_context(batch_metrics::test_callback_drops_freed_on_return).await;
+
+        // App thread tests
+        test_with_js_context(thread_name::test_app_thread_name).await;
+        test_with_js_context(thread_name::test_app_thread_stack_size).await;
+        test_with_js_context(main_thread::test_run_on_main_thread).await;
+
+        // Initialization script tests
+        test_with_js_context(init_script::test_initialization_script_defines_entrypoint).await;
+        test_with_js_context(init_script::test_initialization_script_runs_once).await;
+
+        // IPC protocol version tests
+        test_with_js_context(ipc_version::test_ipc_version_mismatch).await;
+        test_with_js_context(ipc_version::test_ipc_invalid_magic).await;
+        test_with_js_context(ipc_limits::test_max_message_size_rejects_oversized).await;
+        test_with_js_context(malformed_ipc::test_malformed_request_rejected).await;
+        test_with_js_context(malformed_ipc::test_truncated_callback_frame_dropped).await;
+        test_with_js_context(malformed_ipc::test_truncated_callback_args_dropped).await;
+        test_with_js_context(url_prefix::test_default_url_prefixes).await;
+        test_with_js_context(url_prefix::test_custom_url_prefixes).await;
+        test_with_js_context(app_shutdown::test_pending_request_answered_after_app_finishes).await;
+        test_with_js_context(webview_queue::test_drain_queued_before_load).await;
+        test_with_js_context(webview_queue::test_rebind_webview_after_recreation).await;
+        test_with_js_context(webview_queue::test_rebind_webview_fails_unanswered_call).await;
+        test_with_js_context(webview_queue::test_webview_reload).await;
+        test_with_js_context(websocket::test_websocket_transport).await;
+        test_with_js_context(zero_copy::test_take_bytes_ref_borrows_buffer).await;
+
         // Roundtrip tests
         test_with_js_context(roundtrip::test_roundtrip).await;
+        test_with_js_context(codec_fixture::check_codec_fixture).await;
+        test_with_js_context(roundtrip::test_roundtrip_chrono).await;
+        test_with_js_context(roundtrip::test_roundtrip_phantom_data).await;
+        test_with_js_context(roundtrip::test_roundtrip_packed_slices).await;
+        test_with_js_context(roundtrip::test_roundtrip_vecs).await;
+        test_with_js_context(roundtrip::test_roundtrip_box).await;
+        test_with_js_context(roundtrip::test_roundtrip_cow_bytes).await;
+        test_with_js_context(roundtrip::test_roundtrip_codec_rename).await;
+        test_with_js_context(roundtrip::test_roundtrip_codec_tuple_structs).await;
+        test_with_js_context(roundtrip::test_roundtrip_ranges).await;
+        test_with_js_context(roundtrip::test_roundtrip_maps).await;
+        test_with_js_context(roundtrip::test_roundtrip_either).await;
+        test_with_js_context(roundtrip::test_roundtrip_tuples).await;
+        test_with_js_context(roundtrip::test_roundtrip_integer_range).await;
+
+        // Serde bridge tests
+        test_with_js_context(serde_bridge::test_serde_roundtrip).await;
+        test_with_js_context(serde_bridge::test_serde_undefined_and_null).await;
 
         // Callbacks
         test_with_js_context(callbacks::test_call_callback).await;
@@ -89,6 +205,7 @@ fn main() {
         test_with_js_context(callbacks::test_mut_dyn_fnmut).await;
         test_with_js_context(callbacks::test_mut_dyn_fn_many_arity).await;
         test_with_js_context(callbacks::test_mut_dyn_fnmut_many_arity).await;
+        test_with_js_context(callbacks::test_js_value_from_closure).await;
 
         // Reentrant callbacks (dyn Fn)
         test_with_js_context(reentrant_callbacks::test_reentrant_fn_closure).await;
@@ -109,12 +226,15 @@ fn main() {
         test_with_js_context(jsvalue::test_jsvalue_comparisons).await;
         test_with_js_context(jsvalue::test_jsvalue_loose_eq_coercion).await;
         test_with_js_context(jsvalue::test_jsvalue_js_in).await;
+        #[cfg(debug_assertions)]
+        test_with_js_context(jsvalue::test_debug_assert_alive).await;
 
         // instanceof tests
         test_with_js_context(jsvalue::test_instanceof_basic).await;
         test_with_js_context(jsvalue::test_instanceof_is_instance_of).await;
         test_with_js_context(jsvalue::test_instanceof_dyn_into).await;
         test_with_js_context(jsvalue::test_instanceof_dyn_ref).await;
+        test_with_js_context(jsvalue::test_instanceof_value).await;
 
         // Stable API additions tests
         test_with_js_context(jsvalue::test_partial_eq_bool).await;
@@ -122,10 +242,29 @@ fn main() {
         test_with_js_context(jsvalue::test_partial_eq_strings).await;
         test_with_js_context(jsvalue::test_try_from_f64).await;
         test_with_js_context(jsvalue::test_try_from_string).await;
+        test_with_js_context(jsvalue::test_try_from_bool_and_number_precision).await;
         test_with_js_context(jsvalue::test_owned_arithmetic_operators).await;
         test_with_js_context(jsvalue::test_owned_bitwise_operators).await;
         test_with_js_context(jsvalue::test_jscast_as_ref).await;
         test_with_js_context(jsvalue::test_as_ref_jsvalue).await;
+        test_with_js_context(jsvalue::test_drop_before_reuse_ordering).await;
+        test_with_js_context(jsvalue::test_stale_generation_is_inert).await;
+        test_with_js_context(jsvalue::test_new_object_and_array).await;
+        test_with_js_context(jsvalue::test_identity_token).await;
+        test_with_js_context(jsvalue::test_deep_eq).await;
+        test_with_js_context(jsvalue::test_try_iter).await;
+        test_with_js_context(jsvalue::test_construct).await;
+        test_with_js_context(jsvalue::test_as_array_and_object).await;
+        test_with_js_context(jsvalue::test_snapshot_rehydrate).await;
+        test_with_js_context(jsvalue::test_structured_clone).await;
+        test_with_js_context(jsvalue::test_typed_accessors_do_not_coerce).await;
+        test_with_js_context(jsvalue::test_jsvalue_kind_predicates).await;
+        test_with_js_context(jsvalue::test_js_function_call).await;
+        test_with_js_context(jsvalue::test_jsvalue_visitor).await;
+
+        // Unit type tests
+        test_with_js_context(unit::test_unit_return_is_empty).await;
+        test_with_js_context(unit::test_unit_callback_is_undefined).await;
 
         // String enum tests
         test_with_js_context(string_enum::test_string_enum_from_str).await;
@@ -142,7 +281,15 @@ fn main() {
         test_with_js_context(catch_attribute::test_catch_method).await;
 
         // Struct bindings tests
+        test_with_js_context(structs::test_struct_accessor_methods).await;
         test_with_js_context(structs::test_struct_bindings).await;
+        test_with_js_context(structs::test_struct_borrowed_argument).await;
+        test_with_js_context(structs::test_struct_borrowed_argument_errors).await;
+        test_with_js_context(structs::test_struct_drop_releases_held_values).await;
+        test_with_js_context(structs::test_struct_iterable).await;
+        test_with_js_context(structs::test_struct_js_class).await;
+        test_with_js_context(structs::test_struct_mut_in_nested_callbacks).await;
+        test_with_js_context(structs::test_struct_result_throws).await;
 
         // Clamped type tests
         test_with_js_context(clamped::test_clamped_is_uint8clampedarray).await;
@@ -152,11 +299,20 @@ fn main() {
         test_with_js_context(clamped::test_clamped_empty).await;
         test_with_js_context(clamped::test_clamped_mut_slice).await;
 
+        // Crypto tests
+        test_with_js_context(crypto::test_get_random_values).await;
+        test_with_js_context(crypto::test_random_uuid).await;
+        test_with_js_context(crypto::test_get_random_values_chunked).await;
+
         // Borrow stack tests
         test_with_js_context(borrow_stack::test_borrowed_ref_in_callback).await;
         test_with_js_context(borrow_stack::test_borrowed_ref_in_callback_with_return).await;
         test_with_js_context(borrow_stack::test_borrowed_ref_nested_frames).await;
         test_with_js_context(borrow_stack::test_borrowed_ref_deep_nesting).await;
+        test_with_js_context(borrow_stack::test_optional_borrowed_ref_argument).await;
+        test_with_js_context(borrow_stack::test_optional_borrowed_ref_in_callback).await;
+        test_with_js_context(borrow_stack::test_two_borrowed_refs_in_callback).await;
+        test_with_js_context(borrow_stack::test_three_borrowed_refs_in_callback).await;
 
         // Thread local tests
         test_with_js_context(thread_local::test_thread_local).await;
@@ -165,6 +321,18 @@ fn main() {
         // Module import test
         test_with_js_context(module_import::test_module_import).await;
 
+        // Array tests
+        test_with_js_context(array::test_array_sort_by_descending).await;
+        test_with_js_context(array::test_array_to_vec).await;
+        test_with_js_context(array::test_object_to_hashmap).await;
+        test_with_js_context(array::test_pair_roundtrip).await;
+        test_with_js_context(array::test_array_from_rust).await;
+        test_with_js_context(array::test_array_push_get_set).await;
+
+        // Typed array tests
+        test_with_js_context(typed_array::test_uint8_array_transfer).await;
+        test_with_js_context(typed_array::test_mut_slice_write_back).await;
+
         // Indexing tests
         test_with_js_context(indexing::test_indexing_getter_array).await;
         test_with_js_context(indexing::test_indexing_setter_array).await;
@@ -176,6 +344,12 @@ fn main() {
         test_with_js_context(is_type_of::test_is_type_of_with_dyn_into).await;
         test_with_js_context(is_type_of::test_is_type_of_with_dyn_ref).await;
         test_with_js_context(is_type_of::test_has_type_with_is_type_of).await;
+        test_with_js_context(is_type_of::test_final_skips_instanceof).await;
+        test_with_js_context(is_type_of::test_unchecked_dyn_into).await;
+
+        // instanceof tests
+        test_with_js_context(instanceof::test_instanceof_imported_classes).await;
+        test_with_js_context(instanceof::test_instanceof_other_realm).await;
 
         // async bindings test
         async_test_with_js_context(async_bindings::test_call_async).await;
@@ -186,6 +360,105 @@ fn main() {
         async_test_with_js_context(async_bindings::test_async_method_with_catch).await;
         async_test_with_js_context(async_bindings::test_async_static_method).await;
         async_test_with_js_context(async_bindings::test_join_many_async).await;
+        async_test_with_js_context(async_bindings::test_promise_resolve_and_reject).await;
+        test_with_js_context(block_on::test_block_on_js_future).await;
+
+        // Async export tests
+        async_test_with_js_context(async_exports::test_async_export_resolves).await;
+        async_test_with_js_context(async_exports::test_async_export_concurrent_calls).await;
+        async_test_with_js_context(async_exports::test_async_export_free_while_pending).await;
+        async_test_with_js_context(async_exports::test_async_export_rejects).await;
+
+        // spawn_local tests
+        async_test_with_js_context(spawn_local::test_spawn_local_runs_concurrently).await;
+        test_with_js_context(spawn_local::test_spawn_local_from_callback).await;
+
+        // Media query tests
+        test_with_js_context(media::test_prefers_color_scheme).await;
+
+        // Promise combinator tests
+        async_test_with_js_context(js_futures::test_join_all_promises).await;
+        async_test_with_js_context(js_futures::test_race_promises).await;
+
+        // Dialog tests
+        async_test_with_js_context(dialog::test_confirm_dialog).await;
+        async_test_with_js_context(dialog::test_prompt_dialog).await;
+        async_test_with_js_context(dialog::test_dialog_keys_scoped_to_topmost).await;
+
+        // Web Audio tests
+        async_test_with_js_context(audio::test_audio_context_oscillator).await;
+
+        // Console tests
+        test_with_js_context(console::test_console_dir_and_table).await;
+        test_with_js_context(console::test_console_level_filters_calls).await;
+        test_with_js_context(console::test_console_log_with_format).await;
+        #[cfg(feature = "test-harness")]
+        test_with_js_context(console::test_console_sink_captures_log).await;
+
+        // Permission tests
+        async_test_with_js_context(permissions::test_permissions_query_state).await;
+        async_test_with_js_context(permissions::test_permissions_query_unknown_is_denied).await;
+
+        // Beacon tests
+        async_test_with_js_context(beacon::test_send_beacon).await;
+
+        // Fetch tests
+        async_test_with_js_context(fetch::test_fetch_post_json).await;
+        async_test_with_js_context(fetch::test_fetch_abort).await;
+        async_test_with_js_context(fetch::test_form_data_entries).await;
+
+        // URL tests
+        test_with_js_context(url::test_url_search_params).await;
+
+        // Geolocation tests
+        async_test_with_js_context(geolocation::test_get_current_position).await;
+        async_test_with_js_context(geolocation::test_get_current_position_denied).await;
+        async_test_with_js_context(geolocation::test_watch_position).await;
+
+        // Fullscreen tests
+        async_test_with_js_context(fullscreen::test_request_fullscreen).await;
+
+        // Animation tests
+        async_test_with_js_context(animation::test_animation_loop_elapsed_increases).await;
+        async_test_with_js_context(animation::test_animation_frames_stream).await;
+
+        // Event listener tests
+        test_with_js_context(listener_guard::test_listener_guard_removes_and_drops).await;
+
+        // Cookie tests
+        test_with_js_context(cookie::test_set_cookies_and_parse).await;
+
+        // Layout measurement tests
+        test_with_js_context(dom_rect::test_get_bounding_client_rect).await;
+
+        // HTML content tests
+        test_with_js_context(inner_html::test_inner_html_roundtrip).await;
+        test_with_js_context(inner_html::test_insert_adjacent_html).await;
+
+        // DOM observer tests
+        async_test_with_js_context(observer::test_mutation_observer_child_list).await;
+        async_test_with_js_context(observer::test_intersection_observer_entries).await;
+
+        // Window messaging tests
+        async_test_with_js_context(window::test_post_message_to_self).await;
+        test_with_js_context(window::test_open_blocked_popup).await;
+
+        // Window handle tests
+        test_with_js_context(window_handle::test_window_handle_current).await;
+        test_with_js_context(window_handle::test_window_values_stay_in_their_window).await;
+
+        // Shutdown tests
+        async_test_with_js_context(shutdown::test_before_unload_awaits_async_save).await;
+        async_test_with_js_context(shutdown::test_before_unload_times_out).await;
+
+        // Attached future tests
+        async_test_with_js_context(attached_futures::test_attached_future_interleaves_calls).await;
+        async_test_with_js_context(attached_futures::test_attached_future_scope_released).await;
+
+        attached_futures::stop_plugin();
+
+        // Closing the window ends the test app, so this runs last
+        shutdown::test_close_request_runs_before_unload().await;
     })
     .unwrap();
 }
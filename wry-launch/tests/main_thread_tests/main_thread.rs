@@ -0,0 +1,21 @@
+use wry_launch::main_thread::{MainThreadError, run_on_main_thread, try_run_on_main_thread};
+
+/// Test that closures run on the event loop thread and return their result
+pub(crate) fn test_run_on_main_thread() {
+    let app_thread = std::thread::current().id();
+    let main_thread =
+        try_run_on_main_thread(|| std::thread::current().id()).expect("the event loop is running");
+    assert_ne!(main_thread, app_thread);
+
+    let sum = run_on_main_thread(|| 2 + 3);
+    assert_eq!(sum, 5);
+}
+
+/// Test that sending a task before the event loop starts reports that it is not running.
+/// Called from `main` before launching.
+pub(crate) fn test_try_run_before_event_loop() {
+    assert_eq!(
+        try_run_on_main_thread(|| 2 + 3),
+        Err(MainThreadError::Disconnected)
+    );
+}
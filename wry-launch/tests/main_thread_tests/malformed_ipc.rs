@@ -0,0 +1,129 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use base64::Engine;
+use wasm_bindgen::wry::WryBindgen;
+use wasm_bindgen::{Closure, DecodeError, IPCMessage, wasm_bindgen};
+use wry_launch::wry::http;
+
+#[wasm_bindgen(inline_js = r#"
+    export function send_raw_frame(data) {
+        const xhr = new XMLHttpRequest();
+        xhr.open("POST", "/__wbg__/handler", false);
+        xhr.setRequestHeader("dioxus-data", data);
+        xhr.send();
+        return xhr.status;
+    }
+    export function still_answers(x) { return x + 1; }
+    export function call_with_truncated_args(f, arg) {
+        // Cut the string argument's bytes off the end of the frame
+        const setRequestHeader = XMLHttpRequest.prototype.setRequestHeader;
+        XMLHttpRequest.prototype.setRequestHeader = function (name, value) {
+            if (name === "dioxus-data") {
+                const bytes = atob(value);
+                value = btoa(bytes.slice(0, bytes.length - arg.length));
+            }
+            return setRequestHeader.call(this, name, value);
+        };
+        try {
+            return `returned ${f(arg)}`;
+        } catch (e) {
+            return "threw";
+        } finally {
+            XMLHttpRequest.prototype.setRequestHeader = setRequestHeader;
+        }
+    }
+    export function call_with(f, arg) { return f(arg); }
+"#)]
+extern "C" {
+    fn send_raw_frame(data: &str) -> u16;
+    fn still_answers(x: u32) -> u32;
+    fn call_with_truncated_args(f: &Closure<dyn FnMut(String) -> u32>, arg: &str) -> String;
+    fn call_with(f: &Closure<dyn FnMut(String) -> u32>, arg: &str) -> u32;
+}
+
+/// An empty message of the given type, with the type byte as its last byte
+fn message_with_type(ty: u8) -> Vec<u8> {
+    let mut bytes = IPCMessage::new_respond(|_| {}).into_data();
+    *bytes.last_mut().unwrap() = ty;
+    bytes
+}
+
+fn encode(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+/// Test that truncated or mistyped messages are rejected by the protocol handler with a 400
+pub(crate) fn test_malformed_request_rejected() {
+    let wry_bindgen = WryBindgen::new(|_| {});
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    wry_bindgen.set_on_ipc_error({
+        let errors = errors.clone();
+        move |err: &DecodeError| errors.borrow_mut().push(err.clone())
+    });
+    let app_builder = wry_bindgen.app_builder();
+    let protocol_handler = app_builder.protocol_handler();
+
+    let valid = message_with_type(1);
+    let payloads = [
+        // Cut off in the middle of the header
+        encode(&valid[..7]),
+        // A full header with an unknown message type
+        encode(&message_with_type(7)),
+    ];
+    for payload in payloads {
+        let request = http::Request::builder()
+            .uri("wry://index.html/__wbg__/handler")
+            .header("dioxus-data", payload)
+            .body(Vec::new())
+            .unwrap();
+        let status = Rc::new(Cell::new(None));
+        let responder = {
+            let status = status.clone();
+            move |response: http::Response<Vec<u8>>| status.set(Some(response.status().as_u16()))
+        };
+        let unhandled = protocol_handler.handle_request("wry", |_| {}, &request, responder);
+        assert!(unhandled.is_none());
+        assert_eq!(status.get(), Some(400));
+    }
+
+    let errors = errors.borrow();
+    assert!(
+        matches!(errors[0], DecodeError::MessageTooShort { .. }),
+        "unexpected error {:?}",
+        errors[0]
+    );
+    assert_eq!(errors[1], DecodeError::InvalidMessageType { value: 7 });
+}
+
+/// Test that a callback frame from JS with its function id cut off is dropped instead of
+/// crashing the runtime
+pub(crate) fn test_truncated_callback_frame_dropped() {
+    // An Evaluate message that ends before the function id
+    let status = send_raw_frame(&encode(&message_with_type(0)));
+    assert_eq!(status, 200, "JS should still get a response");
+
+    // The runtime keeps handling calls afterwards
+    assert_eq!(still_answers(41), 42);
+}
+
+/// Test that a call to a live callback whose arguments are cut short is dropped without
+/// running the callback, and that the callback keeps working afterwards
+pub(crate) fn test_truncated_callback_args_dropped() {
+    let calls = Rc::new(Cell::new(0));
+    let callback: Closure<dyn FnMut(String) -> u32> = Closure::new({
+        let calls = calls.clone();
+        move |arg: String| {
+            calls.set(calls.get() + 1);
+            arg.len() as u32
+        }
+    });
+
+    // JS gets no return value to decode, so the call throws
+    assert_eq!(call_with_truncated_args(&callback, "hello"), "threw");
+    assert_eq!(calls.get(), 0);
+
+    assert_eq!(call_with(&callback, "hello"), 5);
+    assert_eq!(calls.get(), 1);
+    assert_eq!(still_answers(41), 42);
+}
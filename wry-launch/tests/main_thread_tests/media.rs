@@ -0,0 +1,13 @@
+use wry_launch::media::{ColorScheme, prefers_color_scheme};
+
+/// Test that prefers_color_scheme returns one of the color schemes
+pub(crate) fn test_prefers_color_scheme() {
+    let scheme = prefers_color_scheme();
+    assert!(
+        matches!(
+            scheme,
+            ColorScheme::Light | ColorScheme::Dark | ColorScheme::NoPreference
+        ),
+        "unexpected color scheme: {scheme:?}"
+    );
+}
@@ -0,0 +1,97 @@
+use futures_util::StreamExt;
+use wasm_bindgen::{Closure, JsArray, JsCast, JsValue, wasm_bindgen};
+use wry_launch::document::Element;
+use wry_launch::observer::{
+    IntersectionObserver, IntersectionObserverEntry, MutationObserver, ObserverInit,
+};
+
+#[wasm_bindgen(inline_js = r#"
+    export function create_container() {
+        const element = document.createElement("div");
+        document.body.appendChild(element);
+        return element;
+    }
+    export function append_child(element) {
+        element.appendChild(document.createElement("span"));
+    }
+    export function record_type(record) {
+        return record.type;
+    }
+    export function added_node_count(record) {
+        return record.addedNodes.length;
+    }
+    export function remove_element(element) {
+        element.remove();
+    }
+    export function is_same_element(a, b) {
+        return a === b;
+    }
+"#)]
+extern "C" {
+    fn create_container() -> Element;
+    fn append_child(element: &Element);
+    fn record_type(record: &JsValue) -> String;
+    fn added_node_count(record: &JsValue) -> u32;
+    fn remove_element(element: &Element);
+    fn is_same_element(a: &Element, b: &Element) -> bool;
+}
+
+/// Test that a mutation observer reports a child being appended
+pub(crate) async fn test_mutation_observer_child_list() {
+    let (tx, mut rx) = futures_channel::mpsc::unbounded();
+    let observer = MutationObserver::new(Closure::new(move |records: JsArray| {
+        let summary: Vec<(String, u32)> = records
+            .to_vec()
+            .iter()
+            .map(|record| (record_type(record), added_node_count(record)))
+            .collect();
+        tx.unbounded_send(summary).unwrap();
+    }));
+
+    let container = create_container();
+    let options = ObserverInit::new();
+    options.set_child_list(true);
+    observer.observe(&container, &options);
+
+    append_child(&container);
+    let summary = rx.next().await.unwrap();
+    assert_eq!(summary, vec![("childList".to_string(), 1)]);
+
+    // No records are delivered after disconnecting
+    observer.disconnect();
+    append_child(&container);
+    drop(observer);
+    remove_element(&container);
+    assert!(rx.next().await.is_none());
+}
+
+/// Test that an intersection observer delivers an entry for an observed element
+pub(crate) async fn test_intersection_observer_entries() {
+    let container = create_container();
+    let (tx, mut rx) = futures_channel::mpsc::unbounded();
+    let observer = IntersectionObserver::new(Closure::new(move |entries: JsArray| {
+        let entries: Vec<IntersectionObserverEntry> = entries
+            .to_vec()
+            .into_iter()
+            .map(JsCast::unchecked_into)
+            .collect();
+        tx.unbounded_send(entries).unwrap();
+    }));
+
+    // Observing an element reports its initial visibility
+    observer.observe(&container);
+    let entries = rx.next().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert!(is_same_element(&entry.target(), &container));
+    let ratio = entry.intersection_ratio();
+    assert!((0.0..=1.0).contains(&ratio), "ratio out of range: {ratio}");
+    if !entry.is_intersecting() {
+        assert_eq!(ratio, 0.0);
+    }
+
+    observer.unobserve(&container);
+    drop(observer);
+    remove_element(&container);
+    assert!(rx.next().await.is_none());
+}
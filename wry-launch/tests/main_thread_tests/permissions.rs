@@ -0,0 +1,25 @@
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use wry_launch::navigator::{PermissionStatus, permissions_query};
+
+/// Test querying a permission and reading its state
+pub(crate) async fn test_permissions_query_state() {
+    let status = JsFuture::from(permissions_query("notifications"))
+        .await
+        .expect("permission queries should resolve");
+    let status: PermissionStatus = status.unchecked_into();
+    let state = status.state();
+    assert!(
+        ["granted", "denied", "prompt"].contains(&state.as_str()),
+        "unexpected permission state: {state}"
+    );
+}
+
+/// Test that unknown permissions resolve with a denied state instead of rejecting
+pub(crate) async fn test_permissions_query_unknown_is_denied() {
+    let status = JsFuture::from(permissions_query("not-a-real-permission"))
+        .await
+        .expect("unknown permissions should resolve");
+    let status: PermissionStatus = status.unchecked_into();
+    assert_eq!(status.state(), "denied");
+}
@@ -39,7 +39,573 @@ pub(crate) fn test_roundtrip() {
     roundtrip!(Vec<u32>, vec![1u32, 2u32, 3u32, 4u32, 5u32]);
     roundtrip!(Vec<f32>, vec![1f32, 2f32, 3f32, 4f32, 5f32]);
     roundtrip!(Option<Vec<f32>>, Some(vec![1f32, 2f32, 3f32, 4f32, 5f32]));
+    roundtrip!(
+        (i32, String, bool, Option<f64>),
+        (-7i32, "tuple".to_string(), true, Some(2.5f64))
+    );
 
     // Clamped u8 array roundtrip
     roundtrip!(Clamped<Vec<u8>>, Clamped(vec![0u8, 128u8, 255u8]));
 }
+
+pub(crate) fn test_roundtrip_chrono() {
+    use chrono::{DateTime, NaiveDate, Utc};
+    use wasm_bindgen::{BinaryDecode, BinaryEncode, DecodeError, DecodedData, EncodedData};
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function is_date(x) { return x instanceof Date; }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_date_time(x: DateTime<Utc>) -> DateTime<Utc>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_naive_date(x: NaiveDate) -> NaiveDate;
+        fn is_date(x: DateTime<Utc>) -> bool;
+    }
+
+    let date_time = DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+    assert_eq!(identity_date_time(date_time), date_time);
+    assert!(
+        is_date(date_time),
+        "DateTime<Utc> should arrive in JS as a Date"
+    );
+
+    let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+    assert_eq!(identity_naive_date(date), date);
+
+    // An invalid JS date has a NaN timestamp and fails to decode
+    let mut encoder = EncodedData::new();
+    f64::NAN.encode(&mut encoder);
+    let bytes = encoder.to_bytes();
+    let mut decoder = DecodedData::from_bytes(&bytes).unwrap();
+    assert!(matches!(
+        DateTime::<Utc>::decode(&mut decoder),
+        Err(DecodeError::Custom(_))
+    ));
+}
+
+/// Test that PhantomData is skipped on the wire and decodes back to the marker
+pub(crate) fn test_roundtrip_phantom_data() {
+    use std::marker::PhantomData;
+    use wasm_bindgen::{BinaryDecode, BinaryEncode, DecodedData, EncodedData};
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function describe(x) { return JSON.stringify(x); }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_marker(x: PhantomData<String>) -> PhantomData<String>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_tagged(x: (u32, PhantomData<String>)) -> (u32, PhantomData<String>);
+        #[wasm_bindgen(js_name = describe)]
+        fn describe_tagged(x: (u32, PhantomData<String>)) -> String;
+    }
+
+    // The marker adds no bytes to the message
+    let mut plain = EncodedData::new();
+    42u32.encode(&mut plain);
+    let mut tagged = EncodedData::new();
+    (42u32, PhantomData::<String>).encode(&mut tagged);
+    assert_eq!(plain.to_bytes(), tagged.to_bytes());
+
+    let bytes = tagged.to_bytes();
+    let mut decoder = DecodedData::from_bytes(&bytes).unwrap();
+    let (value, PhantomData) = <(u32, PhantomData<String>)>::decode(&mut decoder).unwrap();
+    assert_eq!(value, 42);
+
+    // JS sees the marker as undefined
+    let PhantomData = identity_marker(PhantomData);
+    assert_eq!(identity_tagged((7, PhantomData)), (7, PhantomData));
+    assert_eq!(describe_tagged((7, PhantomData)), "[7,null]");
+}
+
+/// Test that numeric slices are sent as one packed block and arrive in JS as typed arrays
+pub(crate) fn test_roundtrip_packed_slices() {
+    use wasm_bindgen::{BinaryDecode, BinaryEncode, DecodedData, EncodedData};
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function is_float64_array(x) { return x instanceof Float64Array; }
+    export function plain_array() { return [1, -2, 3]; }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_f64(x: &[f64]) -> Vec<f64>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_i32(x: &[i32]) -> Vec<i32>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_u16(x: Vec<u16>) -> Vec<u16>;
+        fn is_float64_array(x: &[f64]) -> bool;
+        fn plain_array() -> Vec<i32>;
+    }
+
+    let values: Vec<f64> = (0..1000).map(|i| i as f64 * 0.5 - 100.0).collect();
+
+    // The elements take exactly 8 bytes each in the u8 buffer, after the u32 length
+    let mut encoded = EncodedData::new();
+    values.as_slice().encode(&mut encoded);
+    let bytes = encoded.to_bytes();
+    let offset = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as usize;
+    let (u8_offset, str_offset) = (offset(8), offset(12));
+    assert_eq!(u8_offset, 16 + 4);
+    assert_eq!(str_offset - u8_offset, values.len() * 8);
+    assert_eq!(bytes.len(), str_offset);
+    let mut decoder = DecodedData::from_bytes(&bytes).unwrap();
+    assert_eq!(Vec::<f64>::decode(&mut decoder).unwrap(), values);
+
+    // Through JS and back
+    assert_eq!(identity_f64(&values), values);
+    assert!(is_float64_array(&values));
+    assert_eq!(
+        identity_i32(&[i32::MIN, -1, 0, 1, i32::MAX]),
+        vec![i32::MIN, -1, 0, 1, i32::MAX]
+    );
+    assert_eq!(identity_u16(vec![0, 1, u16::MAX]), vec![0, 1, u16::MAX]);
+    assert!(identity_f64(&[]).is_empty());
+
+    // Plain JS arrays are packed on the way back too
+    assert_eq!(plain_array(), vec![1, -2, 3]);
+}
+
+/// Test vectors of non-numeric and nested element types, including empty and large ones
+pub(crate) fn test_roundtrip_vecs() {
+    use wasm_bindgen::JsValue;
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function describe(x) {
+        return `${Array.isArray(x)} ${x.length} ${x.map((v) => typeof v).join(',')}`;
+    }
+    export function is_nested_array(x) { return Array.isArray(x) && x.every((v) => v instanceof Int32Array); }
+    export function sum_lengths(x) { return x.reduce((total, v) => total + v.length, 0); }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_strings(x: Vec<String>) -> Vec<String>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_string_slice(x: &[String]) -> Vec<String>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_values(x: Vec<JsValue>) -> Vec<JsValue>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_nested(x: Vec<Vec<i32>>) -> Vec<Vec<i32>>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_bytes(x: Vec<u8>) -> Vec<u8>;
+        fn describe(x: Vec<JsValue>) -> String;
+        #[wasm_bindgen(js_name = describe)]
+        fn describe_strings(x: &[String]) -> String;
+        fn is_nested_array(x: &[Vec<i32>]) -> bool;
+        fn sum_lengths(x: Vec<Vec<i32>>) -> u32;
+    }
+
+    let strings = vec!["a".to_string(), String::new(), "ünïcode".to_string()];
+    assert_eq!(identity_strings(strings.clone()), strings);
+    assert_eq!(identity_string_slice(&strings), strings);
+    assert_eq!(describe_strings(&strings), "true 3 string,string,string");
+
+    let values = vec![JsValue::from(1.5), JsValue::from("two"), JsValue::NULL];
+    assert_eq!(describe(values.clone()), "true 3 number,string,object");
+    let values = identity_values(values);
+    assert_eq!(values.len(), 3);
+    assert_eq!(values[0].as_f64(), Some(1.5));
+    assert_eq!(values[1].as_string().as_deref(), Some("two"));
+    assert!(values[2].is_null());
+
+    let nested = vec![vec![1, -2], vec![], vec![i32::MAX]];
+    assert_eq!(identity_nested(nested.clone()), nested);
+    assert!(is_nested_array(&nested));
+
+    // Empty vectors encode just a zero length
+    assert!(identity_strings(Vec::new()).is_empty());
+    assert!(identity_values(Vec::new()).is_empty());
+    assert!(identity_nested(Vec::new()).is_empty());
+    assert_eq!(identity_nested(vec![Vec::new()]), vec![Vec::<i32>::new()]);
+    assert_eq!(describe(Vec::new()), "true 0 ");
+
+    // Large vectors
+    let bytes: Vec<u8> = (0..10_000).map(|i| (i % 256) as u8).collect();
+    assert_eq!(identity_bytes(bytes.clone()), bytes);
+    let many_strings: Vec<String> = (0..10_000).map(|i| i.to_string()).collect();
+    assert_eq!(identity_strings(many_strings.clone()), many_strings);
+    let many_rows: Vec<Vec<i32>> = (0..10_000).map(|i| vec![i; (i % 3) as usize]).collect();
+    assert_eq!(sum_lengths(many_rows.clone()), 9_999);
+    assert_eq!(identity_nested(many_rows.clone()), many_rows);
+}
+
+/// Test that Cow slices encode a borrowed slice in place and decode to owned bytes
+pub(crate) fn test_roundtrip_cow_bytes() {
+    use std::borrow::Cow;
+    use wasm_bindgen::{BinaryDecode, BinaryEncode, DecodedData, EncodedData};
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_cow(x: Cow<'_, [u8]>) -> Cow<'static, [u8]>;
+    }
+
+    let bytes: Vec<u8> = (0..=255).collect();
+
+    // A borrowed slice is sent exactly like the Vec it borrows from
+    let mut from_cow = EncodedData::new();
+    Cow::Borrowed(bytes.as_slice()).encode(&mut from_cow);
+    let mut from_vec = EncodedData::new();
+    bytes.clone().encode(&mut from_vec);
+    assert_eq!(from_cow.to_bytes(), from_vec.to_bytes());
+
+    // The bytes are one packed block after the length, without per-element overhead
+    let encoded = from_cow.to_bytes();
+    let empty = EncodedData::new().to_bytes();
+    assert_eq!(encoded.len(), empty.len() + 4 + bytes.len());
+
+    let mut decoder = DecodedData::from_bytes(&encoded).unwrap();
+    let decoded = Cow::<[u8]>::decode(&mut decoder).unwrap();
+    assert!(matches!(decoded, Cow::Owned(_)));
+    assert_eq!(&*decoded, bytes.as_slice());
+
+    // Through JS and back
+    let returned = identity_cow(Cow::Borrowed(&bytes));
+    assert!(matches!(returned, Cow::Owned(_)));
+    assert_eq!(&*returned, bytes.as_slice());
+    assert_eq!(&*identity_cow(Cow::Owned(vec![1, 2, 3])), &[1, 2, 3]);
+    assert!(identity_cow(Cow::Borrowed(&[])).is_empty());
+}
+
+/// Test that a Box is marshalled exactly like the value it holds
+pub(crate) fn test_roundtrip_box() {
+    use wasm_bindgen::{BinaryEncode, EncodedData};
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function is_string(x) { return typeof x === 'string'; }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_boxed(x: Box<String>) -> Box<String>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_boxed_option(x: Option<Box<String>>) -> Option<Box<String>>;
+        fn is_string(x: Box<String>) -> bool;
+    }
+
+    // The box adds nothing to the message
+    let mut plain = EncodedData::new();
+    String::from("boxed").encode(&mut plain);
+    let mut boxed = EncodedData::new();
+    Box::new(String::from("boxed")).encode(&mut boxed);
+    assert_eq!(plain.to_bytes(), boxed.to_bytes());
+
+    let value = Box::new(String::from("hello from a box"));
+    assert_eq!(identity_boxed(value.clone()), value);
+    assert!(
+        is_string(value.clone()),
+        "Box<String> should arrive in JS as a string"
+    );
+    assert_eq!(identity_boxed_option(Some(value.clone())), Some(value));
+    assert_eq!(identity_boxed_option(None), None);
+}
+
+/// Test that derived structs are plain JS objects and honor renamed field keys
+pub(crate) fn test_roundtrip_codec_rename() {
+    use wasm_bindgen::BinaryCodec;
+
+    #[derive(BinaryCodec, Debug, Clone, PartialEq)]
+    struct Profile {
+        #[codec(rename = "displayName")]
+        display_name: String,
+        login_count: u32,
+    }
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function keys(x) { return Object.keys(x).join(','); }
+    export function display_name(x) { return x.displayName; }
+    export function make_profile() { return { displayName: 'from js', login_count: 3 }; }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_profile(x: Profile) -> Profile;
+        fn keys(x: Profile) -> String;
+        fn display_name(x: Profile) -> String;
+        fn make_profile() -> Profile;
+    }
+
+    let profile = Profile {
+        display_name: "Ferris".to_string(),
+        login_count: 7,
+    };
+    assert_eq!(identity_profile(profile.clone()), profile);
+
+    // JS sees the renamed key instead of the Rust field name
+    assert_eq!(keys(profile.clone()), "displayName,login_count");
+    assert_eq!(display_name(profile), "Ferris");
+
+    // Objects built in JS decode through the renamed key too
+    assert_eq!(
+        make_profile(),
+        Profile {
+            display_name: "from js".to_string(),
+            login_count: 3,
+        }
+    );
+}
+
+/// Test that ranges arrive in JS as `{ start, end }` objects and decode back
+pub(crate) fn test_roundtrip_ranges() {
+    use std::ops::{Range, RangeInclusive};
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function describe(x) { return JSON.stringify(x); }
+    export function make_range() { return { start: 3, end: 7 }; }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_range(x: Range<u32>) -> Range<u32>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_inclusive(x: RangeInclusive<i32>) -> RangeInclusive<i32>;
+        #[wasm_bindgen(js_name = describe)]
+        fn describe_range(x: Range<u32>) -> String;
+        #[wasm_bindgen(js_name = describe)]
+        fn describe_inclusive(x: RangeInclusive<i32>) -> String;
+        fn make_range() -> Range<u32>;
+    }
+
+    assert_eq!(identity_range(0..10), 0..10);
+    assert_eq!(identity_inclusive(1..=5), 1..=5);
+    assert_eq!(describe_range(0..10), r#"{"start":0,"end":10}"#);
+    assert_eq!(
+        describe_inclusive(1..=5),
+        r#"{"start":1,"end":5,"inclusive":true}"#
+    );
+    assert_eq!(make_range(), 3..7);
+
+    // Reversed ranges are passed through unchanged and are empty, like in Rust
+    let reversed = identity_range(10..0);
+    assert_eq!(reversed, 10..0);
+    assert!(reversed.is_empty());
+}
+
+/// Test that derived newtypes are transparent and tuple structs arrive as arrays
+pub(crate) fn test_roundtrip_codec_tuple_structs() {
+    use wasm_bindgen::BinaryCodec;
+
+    #[derive(BinaryCodec, Debug, Clone, Copy, PartialEq)]
+    struct Id(u64);
+
+    #[derive(BinaryCodec, Debug, Clone, Copy, PartialEq)]
+    struct Point(f32, f32);
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function describe(x) { return `${typeof x}:${JSON.stringify(x)}`; }
+    export function make_point() { return [0.5, -2]; }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_id(x: Id) -> Id;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_point(x: Point) -> Point;
+        #[wasm_bindgen(js_name = describe)]
+        fn describe_id(x: Id) -> String;
+        #[wasm_bindgen(js_name = describe)]
+        fn describe_point(x: Point) -> String;
+        // The newtype has the same wire format as the wrapped type
+        #[wasm_bindgen(js_name = identity)]
+        fn id_from_u64(x: u64) -> Id;
+        fn make_point() -> Point;
+    }
+
+    assert_eq!(identity_id(Id(42)), Id(42));
+    assert_eq!(describe_id(Id(42)), "number:42");
+    assert_eq!(id_from_u64(7), Id(7));
+
+    assert_eq!(identity_point(Point(1.5, 2.5)), Point(1.5, 2.5));
+    assert_eq!(describe_point(Point(1.5, 2.5)), "object:[1.5,2.5]");
+    assert_eq!(make_point(), Point(0.5, -2.0));
+}
+
+/// Test that string-keyed maps arrive in JS as plain objects and decode back
+pub(crate) fn test_roundtrip_maps() {
+    use std::collections::{BTreeMap, HashMap};
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function describe(x) {
+        return `${Object.getPrototypeOf(x) === Object.prototype}:${JSON.stringify(x)}`;
+    }
+    export function make_scores() { return { ferris: 3, corro: 1 }; }
+    export function make_labels() { return { ok: 'yes', [Symbol('hidden')]: 'no' }; }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_hash_map(x: HashMap<String, String>) -> HashMap<String, String>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_btree_map(x: BTreeMap<String, Option<f64>>) -> BTreeMap<String, Option<f64>>;
+        #[wasm_bindgen(js_name = describe)]
+        fn describe_btree_map(x: BTreeMap<String, u32>) -> String;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_btree_map_u32(x: BTreeMap<String, u32>) -> BTreeMap<String, u32>;
+        fn make_scores() -> BTreeMap<String, u32>;
+        fn make_labels() -> HashMap<String, String>;
+    }
+
+    let config: HashMap<String, String> = [("theme", "dark"), ("lang", "en")]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    assert_eq!(identity_hash_map(config.clone()), config);
+    assert!(identity_hash_map(HashMap::new()).is_empty());
+
+    let values: BTreeMap<String, Option<f64>> =
+        [("a".to_string(), Some(1.5)), ("b".to_string(), None)]
+            .into_iter()
+            .collect();
+    assert_eq!(identity_btree_map(values.clone()), values);
+
+    let scores: BTreeMap<String, u32> = [("x".to_string(), 1), ("y".to_string(), 2)]
+        .into_iter()
+        .collect();
+    assert_eq!(describe_btree_map(scores), r#"true:{"x":1,"y":2}"#);
+
+    // Objects built in JS decode from their own string keys
+    let scores = make_scores();
+    assert_eq!(scores.len(), 2);
+    assert_eq!(scores["ferris"], 3);
+    assert_eq!(scores["corro"], 1);
+    let labels = make_labels();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels["ok"], "yes");
+
+    // A "__proto__" key is an ordinary own key, and the object keeps its prototype
+    let tricky: BTreeMap<String, u32> = [("__proto__".to_string(), 1), ("z".to_string(), 2)]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        describe_btree_map(tricky.clone()),
+        r#"true:{"__proto__":1,"z":2}"#
+    );
+    assert_eq!(identity_btree_map_u32(tricky.clone()), tricky);
+}
+
+/// Test that Either roundtrips in both branches and JS sees the bare value
+pub(crate) fn test_roundtrip_either() {
+    use wasm_bindgen::Either;
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function type_of(x) { return typeof x; }
+    export function make_number() { return 7; }
+    export function make_string() { return 'seven'; }")]
+    extern "C" {
+        fn identity(x: Either<u32, String>) -> Either<u32, String>;
+        fn type_of(x: Either<u32, String>) -> String;
+        fn make_number() -> Either<u32, String>;
+        fn make_string() -> Either<u32, String>;
+    }
+
+    let left: Either<u32, String> = Either::Left(42);
+    assert_eq!(identity(left.clone()), left);
+    let right: Either<u32, String> = Either::Right("forty-two".to_string());
+    assert_eq!(identity(right.clone()), right);
+
+    assert_eq!(type_of(Either::Left(1)), "number");
+    assert_eq!(type_of(Either::Right("one".to_string())), "string");
+    assert_eq!(make_number(), Either::Left(7));
+    assert_eq!(make_string(), Either::Right("seven".to_string()));
+}
+
+pub(crate) fn test_roundtrip_tuples() {
+    type Twelve = (u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, bool, String);
+
+    #[wasm_bindgen(
+        inline_js = "export function make_triple() { return [3, 'three', false]; }
+    export function describe(x) { return `${Array.isArray(x)}:${x.length}:${x.join('|')}`; }
+    export function identity(x) { return x; }"
+    )]
+    extern "C" {
+        fn make_triple() -> (i32, String, bool);
+        fn describe(x: (u8, &str, bool, i32)) -> String;
+        fn identity(x: Twelve) -> Twelve;
+    }
+
+    // JS arrays decode into tuples element by element
+    assert_eq!(make_triple(), (3, "three".to_string(), false));
+
+    // Tuples reach JS as arrays of the same length
+    assert_eq!(describe((1, "two", true, 4)), "true:4:1|two|true|4");
+
+    // The largest supported arity
+    let twelve: Twelve = (
+        1u8,
+        2u16,
+        3u32,
+        4u64,
+        -5i8,
+        -6i16,
+        -7i32,
+        -8i64,
+        9.5f32,
+        10.25f64,
+        true,
+        "twelve".to_string(),
+    );
+    assert_eq!(identity(twelve.clone()), twelve);
+}
+
+pub(crate) fn test_roundtrip_integer_range() {
+    use std::num::Wrapping;
+    use wasm_bindgen::JsValue;
+
+    #[wasm_bindgen(inline_js = "export function identity(x) { return x; }
+    export function three_hundred() { return 300; }
+    export function minus_one() { return -1; }
+    export function with_three_hundred() { return [1, 300]; }
+    export function error_message(e) { return e instanceof Error ? e.message : ''; }")]
+    extern "C" {
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_wrapping(x: Wrapping<u16>) -> Wrapping<u16>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_u8(x: u8) -> u8;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_i16(x: i16) -> i16;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_bytes(x: Vec<u8>) -> Vec<u8>;
+        #[wasm_bindgen(js_name = identity)]
+        fn identity_i16s(x: Vec<i16>) -> Vec<i16>;
+        #[wasm_bindgen(js_name = three_hundred)]
+        fn three_hundred_wrapping_u8() -> Wrapping<u8>;
+        #[wasm_bindgen(js_name = three_hundred)]
+        fn three_hundred_u16() -> u16;
+        #[wasm_bindgen(js_name = three_hundred, catch)]
+        fn three_hundred_u8() -> Result<u8, JsValue>;
+        #[wasm_bindgen(js_name = minus_one)]
+        fn minus_one_wrapping_u8() -> Wrapping<u8>;
+        #[wasm_bindgen(js_name = minus_one)]
+        fn minus_one_i8() -> i8;
+        #[wasm_bindgen(js_name = minus_one, catch)]
+        fn minus_one_u16() -> Result<u16, JsValue>;
+        #[wasm_bindgen(js_name = with_three_hundred, catch)]
+        fn with_three_hundred_bytes() -> Result<Vec<u8>, JsValue>;
+        #[wasm_bindgen(js_name = with_three_hundred, catch)]
+        fn with_three_hundred_i8s() -> Result<Vec<i8>, JsValue>;
+        #[wasm_bindgen(js_name = with_three_hundred)]
+        fn with_three_hundred_i16s() -> Vec<i16>;
+        fn error_message(e: &JsValue) -> String;
+    }
+
+    // Wrapping integers travel as the integer they wrap
+    assert_eq!(identity_wrapping(Wrapping(65535)), Wrapping(65535));
+
+    // Narrow integers and sequences of them sent by Rust come back unchanged
+    assert_eq!(identity_u8(255), 255);
+    assert_eq!(identity_i16(-1), -1);
+    assert_eq!(identity_bytes(vec![0, 1, 255]), vec![0, 1, 255]);
+    assert_eq!(
+        identity_i16s(vec![i16::MIN, -1, i16::MAX]),
+        vec![i16::MIN, -1, i16::MAX]
+    );
+
+    // Values that fit are decoded exactly, and Wrapping wraps the ones that do not
+    assert_eq!(three_hundred_u16(), 300);
+    assert_eq!(minus_one_i8(), -1);
+    assert_eq!(three_hundred_wrapping_u8(), Wrapping(44));
+    assert_eq!(minus_one_wrapping_u8(), Wrapping(255));
+    assert_eq!(with_three_hundred_i16s(), vec![1, 300]);
+
+    // Values that do not fit turn into an error for catch imports instead of a panic,
+    // whether they are single values, plain arrays or packed arrays
+    let out_of_range = [
+        three_hundred_u8().unwrap_err(),
+        minus_one_u16().unwrap_err(),
+        with_three_hundred_bytes().unwrap_err(),
+        with_three_hundred_i8s().unwrap_err(),
+    ];
+    for (error, ty) in out_of_range.iter().zip(["u8", "u16", "u8", "i8"]) {
+        let message = error_message(error);
+        assert!(
+            message.contains(&format!("in the range of {ty}")),
+            "{message}"
+        );
+    }
+}
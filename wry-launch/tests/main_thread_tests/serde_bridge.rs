@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{JsValue, wasm_bindgen};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Settings {
+    name: String,
+    volume: f64,
+    tags: Vec<String>,
+    theme: Option<String>,
+}
+
+/// Test that a struct serialized into JS can be read from JS and deserialized back
+pub(crate) fn test_serde_roundtrip() {
+    #[wasm_bindgen(
+        inline_js = "export function describe(x) { return `${x.name}:${x.tags.length}:${x.theme}`; }"
+    )]
+    extern "C" {
+        fn describe(x: &JsValue) -> String;
+    }
+
+    let settings = Settings {
+        name: "main".to_string(),
+        volume: 0.5,
+        tags: vec!["a".to_string(), "b".to_string()],
+        theme: None,
+    };
+    let value = JsValue::from_serde(&settings).unwrap();
+    assert!(value.is_object());
+    // None becomes null in JS
+    assert_eq!(describe(&value), "main:2:null");
+    assert_eq!(value.into_serde::<Settings>().unwrap(), settings);
+}
+
+/// Test how undefined, null and non-finite numbers deserialize
+pub(crate) fn test_serde_undefined_and_null() {
+    #[wasm_bindgen(inline_js = r#"
+        export function make_settings() {
+            return { name: "js", volume: NaN, tags: ["x", undefined], theme: undefined };
+        }
+        export function make_bigint() {
+            return { value: 1n };
+        }
+    "#)]
+    extern "C" {
+        fn make_settings() -> JsValue;
+        fn make_bigint() -> JsValue;
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Loose {
+        name: String,
+        volume: Option<f64>,
+        tags: Vec<Option<String>>,
+        theme: Option<String>,
+    }
+
+    // Undefined fields are dropped, undefined elements and NaN become null
+    assert_eq!(
+        make_settings().into_serde::<Loose>().unwrap(),
+        Loose {
+            name: "js".to_string(),
+            volume: None,
+            tags: vec![Some("x".to_string()), None],
+            theme: None,
+        }
+    );
+
+    // A bare undefined is treated as null
+    assert_eq!(
+        JsValue::undefined().into_serde::<Option<u32>>().unwrap(),
+        None
+    );
+    assert_eq!(JsValue::null().into_serde::<Option<u32>>().unwrap(), None);
+
+    // NaN serializes to null like JSON.stringify does
+    assert!(JsValue::from_serde(&f64::NAN).unwrap().is_null());
+
+    // Values JSON cannot represent are an error, not a panic
+    assert!(make_bigint().into_serde::<serde_json::Value>().is_err());
+}
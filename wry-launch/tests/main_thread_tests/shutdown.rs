@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use js_sys::Promise;
+use wasm_bindgen::wasm_bindgen;
+use wasm_bindgen_futures::JsFuture;
+use wry_launch::shutdown::{before_unload, on_before_unload, request_window_close};
+
+#[wasm_bindgen(inline_js = r#"
+    export function save_draft(text) {
+        return new Promise((resolve) => setTimeout(() => {
+            window.savedDraft = text;
+            resolve();
+        }, 10));
+    }
+    export function saved_draft() { return window.savedDraft; }
+    export function clear_saved_draft() { delete window.savedDraft; }
+"#)]
+extern "C" {
+    fn save_draft(text: &str) -> Promise;
+    fn saved_draft() -> Option<String>;
+    fn clear_saved_draft();
+}
+
+pub(crate) async fn test_before_unload_awaits_async_save() {
+    clear_saved_draft();
+    let finished = Arc::new(AtomicBool::new(false));
+    on_before_unload(Duration::from_secs(5), {
+        let finished = finished.clone();
+        move || async move {
+            JsFuture::from(save_draft("unsaved work")).await.unwrap();
+            finished.store(true, Ordering::SeqCst);
+        }
+    });
+
+    // The close flow waits for the save to finish before shutting down
+    assert!(before_unload().await);
+    assert!(finished.load(Ordering::SeqCst));
+    assert_eq!(saved_draft().as_deref(), Some("unsaved work"));
+
+    // The hook only runs once
+    finished.store(false, Ordering::SeqCst);
+    assert!(before_unload().await);
+    assert!(!finished.load(Ordering::SeqCst));
+    clear_saved_draft();
+}
+
+pub(crate) async fn test_before_unload_times_out() {
+    let finished = Arc::new(AtomicBool::new(false));
+    on_before_unload(Duration::from_millis(10), {
+        let finished = finished.clone();
+        move || async move {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            finished.store(true, Ordering::SeqCst);
+        }
+    });
+
+    // A hung hook does not keep the window open
+    assert!(!before_unload().await);
+    assert!(!finished.load(Ordering::SeqCst));
+}
+
+/// Test that asking to close the window runs the before-unload hook before shutting
+/// down. This closes the test app, so it must run last.
+pub(crate) async fn test_close_request_runs_before_unload() {
+    clear_saved_draft();
+    let (saved, wait_for_save) = mpsc::channel();
+    on_before_unload(Duration::from_secs(5), move || async move {
+        JsFuture::from(save_draft("closing")).await.unwrap();
+        // The webview is still alive once the save finishes
+        if saved_draft().as_deref() == Some("closing") {
+            let _ = saved.send(());
+        }
+    });
+
+    // Fail if the window closes without running the hook, which would take the full
+    // timeout of the close flow
+    std::thread::spawn(move || {
+        if wait_for_save.recv_timeout(Duration::from_secs(3)).is_err() {
+            eprintln!("closing the window did not run the before-unload hook");
+            std::process::exit(1);
+        }
+    });
+
+    request_window_close().unwrap();
+    // The close flow drops the app once the hook has run
+    std::future::pending::<()>().await;
+}
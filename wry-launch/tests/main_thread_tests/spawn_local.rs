@@ -0,0 +1,50 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use futures_channel::oneshot;
+use wasm_bindgen::{Closure, wasm_bindgen};
+
+#[wasm_bindgen(inline_js = r#"
+    let marker = false;
+    export function set_marker() { marker = true; }
+    export function call_and_read_marker(cb) { marker = false; cb(); return marker; }
+    export function double(value) { return value * 2; }
+"#)]
+extern "C" {
+    fn set_marker();
+    fn call_and_read_marker(cb: Closure<dyn FnMut()>) -> bool;
+    fn double(value: u32) -> u32;
+}
+
+/// Test that spawned futures run alongside the app future and can call into JS
+pub(crate) async fn test_spawn_local_runs_concurrently() {
+    let (tx, rx) = oneshot::channel();
+    wasm_bindgen::spawn_local(async move {
+        tx.send(double(21)).unwrap();
+    });
+    assert_eq!(rx.await.unwrap(), 42);
+
+    // Futures spawned from a spawned future run too
+    let counter = Rc::new(Cell::new(0));
+    let (tx, rx) = oneshot::channel();
+    let outer_counter = counter.clone();
+    wasm_bindgen::spawn_local(async move {
+        outer_counter.set(outer_counter.get() + 1);
+        wasm_bindgen::spawn_local(async move {
+            outer_counter.set(outer_counter.get() + 1);
+            tx.send(()).unwrap();
+        });
+    });
+    rx.await.unwrap();
+    assert_eq!(counter.get(), 2);
+}
+
+/// Test that a future spawned in a callback starts before the callback returns to JS
+pub(crate) fn test_spawn_local_from_callback() {
+    let callback = Closure::new(|| {
+        wasm_bindgen::spawn_local(async {
+            set_marker();
+        });
+    });
+    assert!(call_and_read_marker(callback));
+}
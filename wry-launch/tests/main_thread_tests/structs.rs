@@ -1,3 +1,5 @@
+use wasm_bindgen::WryIterable;
+use wasm_bindgen::object_store::with_exported_mut_in_callback;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen(inline_js = "export function increment_by_5(s) {
@@ -9,11 +11,23 @@ export function set_count(s, count) {
 }
 export function get_count(s) {
     return s.count;
+}
+export function sum_counts(a, b) {
+    return a.sum_with(b);
+}
+export function try_sum_counts(a, b) {
+    try {
+        return `ok: ${a.sum_with(b)}`;
+    } catch (e) {
+        return `${e.name}: ${e.message}`;
+    }
 }")]
 extern "C" {
     fn increment_by_5(s: &JsValue);
     fn set_count(s: &JsValue, count: i32);
     fn get_count(s: &JsValue) -> i32;
+    fn sum_counts(a: &JsValue, b: &JsValue) -> i32;
+    fn try_sum_counts(a: &JsValue, b: &JsValue) -> String;
 }
 
 #[wasm_bindgen]
@@ -42,6 +56,10 @@ impl Counter {
     pub fn increment(&mut self) {
         self.count += 1;
     }
+
+    pub fn sum_with(&self, other: &Counter) -> i32 {
+        self.count + other.count
+    }
 }
 
 pub(crate) fn test_struct_bindings() {
@@ -53,3 +71,318 @@ pub(crate) fn test_struct_bindings() {
     set_count(&as_js_value, 10);
     assert_eq!(get_count(&as_js_value), 20);
 }
+
+pub(crate) fn test_struct_borrowed_argument() {
+    let a = JsValue::from(Counter::new(3));
+    let b = JsValue::from(Counter::new(4));
+    assert_eq!(sum_counts(&a, &b), 7);
+    // The borrowed object is returned to the store after the call
+    assert_eq!(get_count(&b), 4);
+    assert_eq!(sum_counts(&b, &a), 7);
+}
+
+/// Test that a borrowed argument that is the receiver itself or an object of another
+/// class throws in JS, and that the objects stay in the store
+pub(crate) fn test_struct_borrowed_argument_errors() {
+    let a = JsValue::from(Counter::new(3));
+    let message = try_sum_counts(&a, &a);
+    assert!(message.starts_with("Error: "), "{message}");
+    assert!(message.contains("already borrowed"), "{message}");
+    assert_eq!(get_count(&a), 3);
+
+    let point = JsValue::from(Point { x: 1 });
+    let message = try_sum_counts(&a, &point);
+    assert!(message.starts_with("Error: "), "{message}");
+    assert!(message.contains("is not a"), "{message}");
+    // The failed borrow did not take the point out of the store
+    assert_eq!(move_point(&point), 6);
+    assert_eq!(try_sum_counts(&a, &JsValue::from(Counter::new(4))), "ok: 7");
+}
+
+#[wasm_bindgen(inline_js = "export function spread(iterable) {
+    return [...iterable];
+}
+export function sum_for_of(iterable) {
+    let sum = 0;
+    for (const value of iterable)
+        sum += value;
+    return sum;
+}
+export function next_after_free(iterable) {
+    const iterator = iterable[Symbol.iterator]();
+    const first = iterator.next().value;
+    iterable.free();
+    try {
+        return `next: ${first}, ${iterator.next().value}`;
+    } catch (e) {
+        return `${e.name}: ${e.message}`;
+    }
+}")]
+extern "C" {
+    fn spread(iterable: &JsValue) -> Vec<f64>;
+    fn sum_for_of(iterable: &JsValue) -> f64;
+    fn next_after_free(iterable: &JsValue) -> String;
+}
+
+#[wasm_bindgen(iterable)]
+pub struct NumberRange {
+    current: u32,
+    end: u32,
+}
+
+impl WryIterable for NumberRange {
+    fn next(&mut self) -> Option<JsValue> {
+        if self.current >= self.end {
+            return None;
+        }
+        self.current += 1;
+        Some(JsValue::from_f64((self.current - 1) as f64))
+    }
+}
+
+pub(crate) fn test_struct_iterable() {
+    let range = JsValue::from(NumberRange { current: 0, end: 5 });
+    assert_eq!(spread(&range), vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    // The Rust iterator is exhausted, so iterating again yields nothing
+    assert_eq!(spread(&range), Vec::<f64>::new());
+
+    let range = JsValue::from(NumberRange {
+        current: 10,
+        end: 20,
+    });
+    assert_eq!(sum_for_of(&range), 145.0);
+
+    // Advancing an iterator whose object was freed throws in JS
+    let range = JsValue::from(NumberRange { current: 0, end: 5 });
+    let message = next_after_free(&range);
+    assert!(message.starts_with("Error: "), "{message}");
+    assert!(message.contains("freed"), "{message}");
+}
+
+#[wasm_bindgen(inline_js = "export function class_name(obj) {
+    return obj.constructor.name;
+}
+export function is_widget(obj) {
+    return obj instanceof window.MyWidget;
+}
+export function widget_size(obj) {
+    return obj.size();
+}")]
+extern "C" {
+    fn class_name(obj: &JsValue) -> String;
+    fn is_widget(obj: &JsValue) -> bool;
+    fn widget_size(obj: &JsValue) -> u32;
+}
+
+#[wasm_bindgen(js_class = "MyWidget")]
+pub struct Widget {
+    size: u32,
+}
+
+#[wasm_bindgen(js_class = "MyWidget")]
+impl Widget {
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+}
+
+pub(crate) fn test_struct_js_class() {
+    let widget = JsValue::from(Widget { size: 3 });
+    assert_eq!(class_name(&widget), "MyWidget");
+    assert!(is_widget(&widget));
+    assert_eq!(widget_size(&widget), 3);
+}
+
+#[wasm_bindgen(inline_js = "export function try_divide(divider, value) {
+    try {
+        return `ok: ${divider.divide(value)}`;
+    } catch (e) {
+        return `${e.name}: ${e.message}`;
+    }
+}
+export function try_create_divider(divisor) {
+    try {
+        return `ok: ${Divider.new(divisor).divisor}`;
+    } catch (e) {
+        return `thrown: ${e}`;
+    }
+}")]
+extern "C" {
+    fn try_divide(divider: &JsValue, value: i32) -> String;
+    fn try_create_divider(divisor: i32) -> String;
+}
+
+#[wasm_bindgen]
+pub struct Divider {
+    divisor: i32,
+}
+
+#[wasm_bindgen]
+impl Divider {
+    #[wasm_bindgen(constructor)]
+    pub fn new(divisor: i32) -> Result<Divider, JsValue> {
+        if divisor == 0 {
+            Err(JsValue::from("divisor must not be zero"))
+        } else {
+            Ok(Divider { divisor })
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn divisor(&self) -> i32 {
+        self.divisor
+    }
+
+    pub fn divide(&self, value: i32) -> Result<i32, JsError> {
+        value
+            .checked_div(self.divisor)
+            .ok_or_else(|| JsError::new("division overflowed"))
+    }
+}
+
+/// Test that exports returning Err throw in JS instead of panicking
+pub(crate) fn test_struct_result_throws() {
+    let divider = JsValue::from(Divider { divisor: 4 });
+    assert_eq!(try_divide(&divider, 20), "ok: 5");
+    let divider = JsValue::from(Divider { divisor: -1 });
+    assert_eq!(try_divide(&divider, i32::MIN), "Error: division overflowed");
+
+    assert_eq!(try_create_divider(2), "ok: 2");
+    assert_eq!(try_create_divider(0), "thrown: divisor must not be zero");
+}
+
+#[wasm_bindgen(inline_js = "export function create_event_target() {
+    return new EventTarget();
+}
+export function listen(target, name, callback) {
+    target.addEventListener(name, () => callback());
+}
+export function fire(target, name) {
+    target.dispatchEvent(new Event(name));
+}")]
+extern "C" {
+    fn create_event_target() -> JsValue;
+    fn listen(target: &JsValue, name: &str, callback: &Closure<dyn FnMut()>);
+    fn fire(target: &JsValue, name: &str);
+}
+
+/// Test that two event handlers can mutate the same exported object even when the
+/// second event fires while the first handler still holds the object
+pub(crate) fn test_struct_mut_in_nested_callbacks() {
+    let counter = JsValue::from(Counter::new(0));
+    let handle = wasm_bindgen::extract_rust_handle(&counter).unwrap();
+    let target = create_event_target();
+
+    let outer: Closure<dyn FnMut()> = Closure::new({
+        let target = target.clone();
+        move || {
+            let target = target.clone();
+            with_exported_mut_in_callback::<Counter>(handle, move |counter| {
+                counter.increment();
+                // The inner handler runs while this borrow is still active
+                fire(&target, "inner");
+                assert_eq!(counter.count, 1);
+            })
+            .unwrap();
+        }
+    });
+    let inner: Closure<dyn FnMut()> = Closure::new(move || {
+        with_exported_mut_in_callback::<Counter>(handle, |counter| counter.count += 10).unwrap();
+    });
+    listen(&target, "outer", &outer);
+    listen(&target, "inner", &inner);
+
+    fire(&target, "outer");
+    assert_eq!(get_count(&counter), 11);
+    fire(&target, "inner");
+    assert_eq!(get_count(&counter), 21);
+}
+
+#[wasm_bindgen(inline_js = "export function move_point(point) {
+    point.x = point.x + 5;
+    return point.x;
+}
+export function read_after_free(point) {
+    point.free();
+    try {
+        return `read: ${point.x}`;
+    } catch (e) {
+        return `${e.name}: ${e.message}`;
+    }
+}
+export function write_after_free(point) {
+    try {
+        point.x = 1;
+        return 'written';
+    } catch (e) {
+        return `${e.name}: ${e.message}`;
+    }
+}")]
+extern "C" {
+    fn move_point(point: &JsValue) -> i32;
+    fn read_after_free(point: &JsValue) -> String;
+    fn write_after_free(point: &JsValue) -> String;
+}
+
+#[wasm_bindgen]
+pub struct Point {
+    x: i32,
+}
+
+#[wasm_bindgen]
+impl Point {
+    #[wasm_bindgen(getter)]
+    pub fn get_x(&self) -> i32 {
+        self.x
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_x(&mut self, x: i32) {
+        self.x = x;
+    }
+}
+
+/// Test that getter and setter methods become JS properties, and that using them after
+/// the object is freed throws in JS
+pub(crate) fn test_struct_accessor_methods() {
+    let point = JsValue::from(Point { x: 2 });
+    assert_eq!(move_point(&point), 7);
+
+    let message = read_after_free(&point);
+    assert!(message.starts_with("Error: "), "{message}");
+    assert!(message.contains("Point::get_x"), "{message}");
+    assert!(message.contains("freed"), "{message}");
+    assert!(write_after_free(&point).contains("freed"));
+}
+
+#[wasm_bindgen(inline_js = "export function make_handle(i) {
+    return { i };
+}
+export function free_and_count(holder) {
+    const before = window.jsHeap.heapObjectsAlive();
+    holder.free();
+    return before - window.jsHeap.heapObjectsAlive();
+}")]
+extern "C" {
+    fn make_handle(i: u32) -> JsValue;
+    fn free_and_count(holder: &JsValue) -> u32;
+}
+
+#[wasm_bindgen]
+pub struct HandleHolder {
+    #[allow(dead_code)]
+    values: Vec<JsValue>,
+}
+
+/// Test that freeing an exported object releases the JS values it holds before `free()`
+/// returns in JS, so no stale heap references outlive the object
+pub(crate) fn test_struct_drop_releases_held_values() {
+    let alive_before = crate::heap_objects_alive();
+    let holder = JsValue::from(HandleHolder {
+        values: (0..3).map(make_handle).collect(),
+    });
+
+    assert_eq!(free_and_count(&holder), 3);
+    drop(holder);
+    assert_eq!(crate::heap_objects_alive(), alive_before);
+}
@@ -0,0 +1,30 @@
+/// The app thread name set in `main`
+pub(crate) const APP_THREAD_NAME: &str = "wry-launch-test-app";
+
+/// The app thread stack size set in `main`, well above the 2 MiB default for threads
+pub(crate) const APP_STACK_SIZE: usize = 32 * 1024 * 1024;
+
+pub(crate) fn test_app_thread_name() {
+    let thread = std::thread::current();
+    assert_eq!(thread.name(), Some(APP_THREAD_NAME));
+}
+
+/// Test that the app thread gets the configured stack by using more than the default
+/// stack would hold. Without it the thread overflows its stack and the process aborts.
+pub(crate) fn test_app_thread_stack_size() {
+    const FRAME: usize = 64 * 1024;
+
+    fn use_stack(depth: usize) -> u8 {
+        let frame = std::hint::black_box([depth as u8; FRAME]);
+        if depth == 0 {
+            frame[0]
+        } else {
+            use_stack(depth - 1).wrapping_add(frame[FRAME - 1])
+        }
+    }
+
+    // A quarter of the configured stack, four times the default
+    let depth = APP_STACK_SIZE / 4 / FRAME;
+    let expected = (0..=depth).fold(0u8, |sum, i| sum.wrapping_add(i as u8));
+    assert_eq!(use_stack(depth), expected);
+}
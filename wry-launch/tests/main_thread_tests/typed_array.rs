@@ -0,0 +1,85 @@
+use wasm_bindgen::JsCast;
+use wasm_bindgen::typed_array::Uint8Array;
+use wasm_bindgen::wasm_bindgen;
+
+#[wasm_bindgen(inline_js = "export function make_pattern(len) {
+    const bytes = new Uint8Array(len);
+    for (let i = 0; i < len; i++) {
+        bytes[i] = (i * 7) % 256;
+    }
+    return bytes;
+}
+export function matches_pattern(bytes, multiplier) {
+    if (!(bytes instanceof Uint8Array)) {
+        return false;
+    }
+    return bytes.every((byte, i) => byte === (i * multiplier) % 256);
+}")]
+extern "C" {
+    fn make_pattern(len: u32) -> Uint8Array;
+    fn matches_pattern(bytes: &Uint8Array, multiplier: u32) -> bool;
+}
+
+/// Test moving a 64 KiB buffer to JS and back as a real Uint8Array
+pub(crate) fn test_uint8_array_transfer() {
+    const LEN: usize = 64 * 1024;
+
+    // Rust to JS
+    let bytes: Vec<u8> = (0..LEN).map(|i| (i * 3 % 256) as u8).collect();
+    let array = Uint8Array::from_slice(&bytes);
+    assert_eq!(array.length() as usize, LEN);
+    assert!(matches_pattern(&array, 3));
+    assert_eq!(array.to_vec(), bytes);
+
+    // JS to Rust
+    let array = make_pattern(LEN as u32);
+    let expected: Vec<u8> = (0..LEN).map(|i| (i * 7 % 256) as u8).collect();
+    assert_eq!(array.to_vec(), expected);
+    let mut dst = vec![0u8; LEN];
+    array.copy_to(&mut dst);
+    assert_eq!(dst, expected);
+
+    let value: wasm_bindgen::JsValue = array.into();
+    assert!(value.is_instance_of::<Uint8Array>());
+    assert!(!wasm_bindgen::JsValue::from(1).is_instance_of::<Uint8Array>());
+
+    assert!(Uint8Array::from_slice(&[]).to_vec().is_empty());
+}
+
+/// Test that changes JS makes to a mutable slice argument are copied back into it
+pub(crate) fn test_mut_slice_write_back() {
+    use wasm_bindgen::batch::batch;
+
+    #[wasm_bindgen(inline_js = r#"
+        export function double_all(values) {
+            for (let i = 0; i < values.length; i++) {
+                values[i] *= 2;
+            }
+        }
+        export function shout_all(names) {
+            for (let i = 0; i < names.length; i++) {
+                names[i] = names[i].toUpperCase();
+            }
+            return names.length;
+        }
+    "#)]
+    extern "C" {
+        fn double_all(values: &mut [f64]);
+        fn shout_all(names: &mut [String]) -> u32;
+    }
+
+    let mut values = [1.5, -2.0, 3.0];
+    double_all(&mut values);
+    assert_eq!(values, [3.0, -4.0, 6.0]);
+
+    let mut names = vec!["ada".to_string(), "grace".to_string()];
+    assert_eq!(shout_all(&mut names), 2);
+    assert_eq!(names, ["ADA", "GRACE"]);
+
+    // Calls with mutable slices run right away even inside a batch
+    let mut inside_batch = [1.0; 4];
+    batch(|| {
+        double_all(&mut inside_batch);
+        assert_eq!(inside_batch, [2.0; 4]);
+    });
+}
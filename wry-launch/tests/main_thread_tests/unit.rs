@@ -0,0 +1,31 @@
+use wasm_bindgen::{Closure, wasm_bindgen};
+
+#[wasm_bindgen(inline_js = r#"
+    export function returns_object_as_unit() { return { ignored: true }; }
+    export function returns_null_as_unit() { return null; }
+    export function add_one(x) { return x + 1; }
+    export function unit_callback_result(cb) {
+        const result = cb();
+        return result === undefined ? "undefined" : String(result);
+    }
+"#)]
+extern "C" {
+    fn returns_object_as_unit();
+    fn returns_null_as_unit();
+    fn add_one(x: u32) -> u32;
+    fn unit_callback_result(cb: &Closure<dyn FnMut()>) -> String;
+}
+
+/// Test that imports returning `()` send no data back, whatever the JS function returns
+pub(crate) fn test_unit_return_is_empty() {
+    // Any data in the response would trip the trailing data check or misalign the next call
+    returns_object_as_unit();
+    returns_null_as_unit();
+    assert_eq!(add_one(41), 42);
+}
+
+/// Test that a Rust callback returning `()` is seen as `undefined` rather than `null` in JS
+pub(crate) fn test_unit_callback_is_undefined() {
+    let callback: Closure<dyn FnMut()> = Closure::new(|| {});
+    assert_eq!(unit_callback_result(&callback), "undefined");
+}
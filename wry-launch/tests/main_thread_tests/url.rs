@@ -0,0 +1,20 @@
+use wry_launch::url::URLSearchParams;
+
+/// Test parsing, reading, editing and serializing a query string
+pub(crate) fn test_url_search_params() {
+    let params = URLSearchParams::new("a=1&a=2&b=3");
+    assert_eq!(params.get("a").as_deref(), Some("1"));
+    assert_eq!(params.get_all("a"), vec!["1".to_string(), "2".to_string()]);
+    assert_eq!(params.get("missing"), None);
+    assert!(params.get_all("missing").is_empty());
+    assert_eq!(params.to_string(), "a=1&a=2&b=3");
+
+    params.append("c", "hello world");
+    params.set("b", "4");
+    params.delete("a");
+    assert!(!params.has("a"));
+    assert_eq!(params.to_string(), "b=4&c=hello+world");
+
+    let reparsed = URLSearchParams::new(&format!("?{}", params.to_string()));
+    assert_eq!(reparsed.get("c").as_deref(), Some("hello world"));
+}
@@ -0,0 +1,57 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::wry::{WryBindgen, url_prefixes};
+use wry_launch::wry::http;
+
+/// Send a request for `uri` and return the response status, or `None` if it was not handled
+fn request_status(wry_bindgen: &WryBindgen, uri: &str) -> Option<u16> {
+    let app_builder = wry_bindgen.app_builder();
+    let protocol_handler = app_builder.protocol_handler();
+    let request = http::Request::builder().uri(uri).body(Vec::new()).unwrap();
+
+    let status = Rc::new(Cell::new(None));
+    let responder = {
+        let status = status.clone();
+        move |response: http::Response<Vec<u8>>| status.set(Some(response.status().as_u16()))
+    };
+    let unhandled = protocol_handler.handle_request("wry", |_| {}, &request, responder);
+    unhandled.is_none().then(|| status.get().unwrap())
+}
+
+/// Test that the default index.html prefixes are matched on every platform URL shape
+pub(crate) fn test_default_url_prefixes() {
+    let wry_bindgen = WryBindgen::new(|_| {});
+    for uri in [
+        "wry://index.html/__wbg__/init.js",
+        "https://wry.index.html/__wbg__/init.js",
+        "http://wry.index.html/__wbg__/init.js",
+    ] {
+        assert_eq!(request_status(&wry_bindgen, uri), Some(200), "{uri}");
+    }
+}
+
+/// Test serving wry-bindgen requests under a custom host on every platform URL shape
+pub(crate) fn test_custom_url_prefixes() {
+    let wry_bindgen = WryBindgen::new(|_| {});
+    wry_bindgen.set_url_prefixes(url_prefixes("wry", "app"));
+
+    for uri in [
+        "wry://app/__wbg__/init.js",
+        "https://wry.app/__wbg__/init.js",
+        "http://wry.app/__wbg__/init.js",
+    ] {
+        assert_eq!(request_status(&wry_bindgen, uri), Some(200), "{uri}");
+    }
+    assert_eq!(
+        request_status(&wry_bindgen, "wry://app/__wbg__/snippets/missing.js"),
+        Some(404)
+    );
+
+    // The default prefixes are replaced, and other paths are left to the app
+    assert_eq!(
+        request_status(&wry_bindgen, "wry://index.html/__wbg__/init.js"),
+        None
+    );
+    assert_eq!(request_status(&wry_bindgen, "wry://app/index.html"), None);
+}
@@ -0,0 +1,176 @@
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use wasm_bindgen::wry::{Transport, WryBindgen};
+use wasm_bindgen::{IPCMessage, JsValue, MessageType};
+use wry_launch::wry::http;
+
+/// Open a connection to `url` and send the opening handshake with the key from RFC 6455
+fn handshake(url: &str) -> (BufReader<TcpStream>, String) {
+    let (address, path) = url
+        .strip_prefix("ws://")
+        .and_then(|rest| rest.split_once('/'))
+        .unwrap();
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "GET /{path} HTTP/1.1\r\nHost: {address}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n"
+    )
+    .unwrap();
+    let mut reader = BufReader::new(stream);
+    let mut head = String::new();
+    while !head.ends_with("\r\n\r\n") {
+        if reader.read_line(&mut head).unwrap() == 0 {
+            break;
+        }
+    }
+    (reader, head)
+}
+
+/// Write a masked binary frame, as a browser would
+fn write_message(stream: &mut TcpStream, opcode: u8, payload: &[u8]) {
+    let mask = [0x12, 0x34, 0x56, 0x78];
+    let mut frame = vec![0x80 | opcode];
+    if payload.len() < 126 {
+        frame.push(0x80 | payload.len() as u8);
+    } else {
+        frame.push(0x80 | 126);
+        frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    }
+    frame.extend_from_slice(&mask);
+    frame.extend(
+        payload
+            .iter()
+            .enumerate()
+            .map(|(i, byte)| byte ^ mask[i % 4]),
+    );
+    stream.write_all(&frame).unwrap();
+}
+
+/// Read an unmasked frame from the server, returning its opcode and payload
+fn read_message(reader: &mut impl Read) -> (u8, Vec<u8>) {
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head).unwrap();
+    assert_eq!(head[1] & 0x80, 0, "server frames must not be masked");
+    let len = match head[1] & 0x7F {
+        126 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len).unwrap();
+            u16::from_be_bytes(len) as usize
+        }
+        127 => {
+            let mut len = [0u8; 8];
+            reader.read_exact(&mut len).unwrap();
+            u64::from_be_bytes(len) as usize
+        }
+        len => len as usize,
+    };
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload).unwrap();
+    (head[0] & 0x0F, payload)
+}
+
+/// Test that calls from Rust reach JS over the WebSocket transport instead of evaluate_script
+pub(crate) fn test_websocket_transport() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let send_event = {
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event)
+    };
+    let wry_bindgen =
+        WryBindgen::new_with_transport(send_event.clone(), Transport::WebSocket { port: 0 })
+            .unwrap();
+    let app_builder = wry_bindgen.app_builder();
+    let protocol_handler = app_builder.protocol_handler();
+    let scripts = Rc::new(RefCell::new(Vec::new()));
+    let prepared = app_builder.build(
+        || async {
+            for _ in 0..3 {
+                wry_launch::console::dir(&JsValue::UNDEFINED);
+            }
+        },
+        {
+            let scripts = scripts.clone();
+            move |script: &str| scripts.borrow_mut().push(script.to_string())
+        },
+    );
+
+    // The page asks where to connect, then signals that it loaded
+    let url = Rc::new(RefCell::new(String::new()));
+    for path in ["transport", "initialized"] {
+        let url = url.clone();
+        protocol_handler.handle_request(
+            "wry",
+            &send_event,
+            &http::Request::builder()
+                .uri(format!("wry://index.html/__wbg__/{path}"))
+                .body(Vec::new())
+                .unwrap(),
+            move |response: http::Response<Vec<u8>>| {
+                if path == "transport" {
+                    *url.borrow_mut() = String::from_utf8(response.into_body()).unwrap();
+                }
+            },
+        );
+    }
+    let url = url.borrow().clone();
+    assert!(url.starts_with("ws://127.0.0.1:"), "{url}");
+
+    // Connections without the app's token are refused
+    let (base, webview_id) = url.rsplit_once('/').unwrap();
+    let (address, _) = base.rsplit_once('/').unwrap();
+    let (_, refused) = handshake(&format!("{address}/not-the-token/{webview_id}"));
+    assert!(refused.starts_with("HTTP/1.1 403"), "{refused}");
+
+    let (mut reader, accepted) = handshake(&url);
+    assert!(accepted.starts_with("HTTP/1.1 101"), "{accepted}");
+    assert!(
+        accepted.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"),
+        "{accepted}"
+    );
+    let mut writer = reader.get_ref().try_clone().unwrap();
+
+    let app = std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(prepared.into_future());
+    });
+
+    // Play the page: answer every call that arrives over the socket until it closes
+    let js = std::thread::spawn(move || {
+        let mut calls = 0;
+        loop {
+            let (opcode, payload) = read_message(&mut reader);
+            if opcode == 0x8 {
+                break calls;
+            }
+            assert_eq!(opcode, 0x2);
+            assert_eq!(
+                IPCMessage::new(payload).ty().unwrap(),
+                MessageType::Evaluate
+            );
+            let answer = IPCMessage::new_respond(|_| {}).into_data();
+            write_message(reader.get_mut(), 0x2, &answer);
+            calls += 1;
+        }
+    });
+
+    while !app.is_finished() {
+        let pending = std::mem::take(&mut *events.lock().unwrap());
+        for event in pending {
+            wry_bindgen.handle_user_event(event);
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    app.join().unwrap();
+
+    // Closing the connection makes the server answer with a close frame
+    write_message(&mut writer, 0x8, &[]);
+    assert_eq!(js.join().unwrap(), 3);
+    assert!(scripts.borrow().is_empty(), "{:?}", scripts.borrow());
+}
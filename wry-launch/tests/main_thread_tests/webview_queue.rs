@@ -0,0 +1,326 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use wasm_bindgen::wry::WryBindgen;
+use wasm_bindgen::{BinaryEncode, EncodedData, JsValue, MessageType};
+use wry_launch::wry::http;
+
+/// Build a request for the IPC endpoint carrying an empty Respond message from JS
+fn respond_request() -> http::Request<Vec<u8>> {
+    use base64::Engine;
+
+    let mut data = EncodedData::new();
+    (MessageType::Respond as u8).encode(&mut data);
+    let header = base64::engine::general_purpose::STANDARD.encode(data.to_bytes());
+    http::Request::builder()
+        .uri("wry://index.html/__wbg__/handler")
+        .header("dioxus-data", header)
+        .body(Vec::new())
+        .unwrap()
+}
+
+/// Test that messages queued before the webview loads can be drained by the host
+pub(crate) fn test_drain_queued_before_load() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let wry_bindgen = WryBindgen::new({
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event)
+    });
+    let app_builder = wry_bindgen.app_builder();
+    let protocol_handler = app_builder.protocol_handler();
+    let prepared = app_builder.build(
+        || async {
+            wry_launch::console::dir(&JsValue::UNDEFINED);
+        },
+        |_| {},
+    );
+    let id = prepared.id();
+
+    let app = std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(prepared.into_future());
+    });
+
+    // Wait for the app to send its call. The webview never loaded, so it is queued.
+    let event = loop {
+        if let Some(event) = events.lock().unwrap().pop() {
+            break event;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    };
+    wry_bindgen.handle_user_event(event);
+
+    let queued = wry_bindgen.drain_queued(id);
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].ty().unwrap(), MessageType::Evaluate);
+    assert!(wry_bindgen.drain_queued(id).is_empty());
+
+    // Answer the dropped call on behalf of JS so the app can finish
+    protocol_handler.handle_request(
+        "wry",
+        |_| {},
+        &respond_request(),
+        |_: http::Response<Vec<u8>>| {},
+    );
+    app.join().unwrap();
+}
+
+/// Build a request from the webview signalling that it has loaded
+fn initialized_request() -> http::Request<Vec<u8>> {
+    http::Request::builder()
+        .uri("wry://index.html/__wbg__/initialized")
+        .body(Vec::new())
+        .unwrap()
+}
+
+/// Test that an app keeps working after its webview is recreated and rebound
+pub(crate) fn test_rebind_webview_after_recreation() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let send_event = {
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event)
+    };
+    let wry_bindgen = WryBindgen::new(send_event.clone());
+    let app_builder = wry_bindgen.app_builder();
+    let protocol_handler = app_builder.protocol_handler();
+
+    let old_scripts = Rc::new(RefCell::new(Vec::new()));
+    let (recreated_tx, recreated_rx) = std::sync::mpsc::channel();
+    let prepared = app_builder.build(
+        move || async move {
+            wry_launch::console::dir(&JsValue::UNDEFINED);
+            recreated_rx.recv().unwrap();
+            wry_launch::console::dir(&JsValue::UNDEFINED);
+        },
+        {
+            let old_scripts = old_scripts.clone();
+            move |script: &str| old_scripts.borrow_mut().push(script.to_string())
+        },
+    );
+    let id = prepared.id();
+    let app = std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(prepared.into_future());
+    });
+
+    // Forward events from the app thread until `done` returns true
+    let pump = |done: &dyn Fn() -> bool| {
+        while !done() {
+            let pending = std::mem::take(&mut *events.lock().unwrap());
+            for event in pending {
+                wry_bindgen.handle_user_event(event);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    };
+    let answer = || {
+        protocol_handler.handle_request(
+            "wry",
+            |_| {},
+            &respond_request(),
+            |_: http::Response<Vec<u8>>| {},
+        );
+    };
+
+    // The first webview loads and answers the first call
+    protocol_handler.handle_request(
+        "wry",
+        &send_event,
+        &initialized_request(),
+        |_: http::Response<Vec<u8>>| {},
+    );
+    pump(&|| !old_scripts.borrow().is_empty());
+    answer();
+
+    // The webview is recreated. The second call goes to the new webview once it loads.
+    let new_scripts = Rc::new(RefCell::new(Vec::new()));
+    assert!(wry_bindgen.rebind_webview(id, {
+        let new_scripts = new_scripts.clone();
+        move |script: &str| new_scripts.borrow_mut().push(script.to_string())
+    }));
+    recreated_tx.send(()).unwrap();
+    protocol_handler.handle_request(
+        "wry",
+        &send_event,
+        &initialized_request(),
+        |_: http::Response<Vec<u8>>| {},
+    );
+    pump(&|| !new_scripts.borrow().is_empty());
+    answer();
+    app.join().unwrap();
+
+    assert_eq!(old_scripts.borrow().len(), 1);
+    assert_eq!(new_scripts.borrow().len(), 1);
+    // The new webview has an empty type cache, so the call is sent exactly as the first time
+    assert_eq!(old_scripts.borrow()[0], new_scripts.borrow()[0]);
+}
+
+/// Test that a call the old webview never answered fails when the webview is rebound,
+/// instead of blocking the app forever
+pub(crate) fn test_rebind_webview_fails_unanswered_call() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let send_event = {
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event)
+    };
+    let wry_bindgen = WryBindgen::new(send_event.clone());
+    let app_builder = wry_bindgen.app_builder();
+    let protocol_handler = app_builder.protocol_handler();
+
+    let old_scripts = Rc::new(RefCell::new(Vec::new()));
+    let (result_tx, result_rx) = std::sync::mpsc::channel();
+    let prepared = app_builder.build(
+        move || async move {
+            let result = js_sys::Reflect::get(&JsValue::UNDEFINED, &JsValue::from_str("key"));
+            result_tx.send(result.is_err()).unwrap();
+        },
+        {
+            let old_scripts = old_scripts.clone();
+            move |script: &str| old_scripts.borrow_mut().push(script.to_string())
+        },
+    );
+    let id = prepared.id();
+    assert!(!wry_bindgen.rebind_webview(id + 1, |_| {}));
+    let app = std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(prepared.into_future());
+    });
+
+    let pump = |done: &dyn Fn() -> bool| {
+        while !done() {
+            let pending = std::mem::take(&mut *events.lock().unwrap());
+            for event in pending {
+                wry_bindgen.handle_user_event(event);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    };
+
+    // The first webview loads and receives the call, but is destroyed before answering
+    protocol_handler.handle_request(
+        "wry",
+        &send_event,
+        &initialized_request(),
+        |_: http::Response<Vec<u8>>| {},
+    );
+    pump(&|| !old_scripts.borrow().is_empty());
+    let new_scripts = Rc::new(RefCell::new(Vec::new()));
+    assert!(wry_bindgen.rebind_webview(id, {
+        let new_scripts = new_scripts.clone();
+        move |script: &str| new_scripts.borrow_mut().push(script.to_string())
+    }));
+
+    // The call fails, and the error it returns is created in the new webview
+    protocol_handler.handle_request(
+        "wry",
+        &send_event,
+        &initialized_request(),
+        |_: http::Response<Vec<u8>>| {},
+    );
+    pump(&|| !new_scripts.borrow().is_empty());
+    protocol_handler.handle_request(
+        "wry",
+        |_| {},
+        &respond_request(),
+        |_: http::Response<Vec<u8>>| {},
+    );
+    assert!(result_rx.recv_timeout(Duration::from_secs(5)).unwrap());
+    pump(&|| app.is_finished());
+    app.join().unwrap();
+    assert_eq!(old_scripts.borrow().len(), 1);
+}
+
+/// Test that loading a page in an already loaded webview reports a reload, fails the call
+/// the old page never answered, and makes the values from the old page stale
+pub(crate) fn test_webview_reload() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let send_event = {
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event)
+    };
+    let wry_bindgen = WryBindgen::new(send_event.clone());
+    let app_builder = wry_bindgen.app_builder();
+    let protocol_handler = app_builder.protocol_handler();
+
+    let scripts = Rc::new(RefCell::new(Vec::new()));
+    let (result_tx, result_rx) = std::sync::mpsc::channel();
+    let prepared = app_builder.build(
+        move || async move {
+            let value = JsValue::from_str("before");
+            let reloaded = wasm_bindgen::batch::webview_reloaded();
+            let result = js_sys::Reflect::get(&value, &JsValue::from_str("length"));
+            let generation = reloaded.await;
+            result_tx
+                .send((result.is_err(), value.is_stale(), generation))
+                .unwrap();
+        },
+        {
+            let scripts = scripts.clone();
+            move |script: &str| scripts.borrow_mut().push(script.to_string())
+        },
+    );
+    let app = std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(prepared.into_future());
+    });
+
+    let reloads = Rc::new(RefCell::new(0));
+    let pump = |done: &dyn Fn() -> bool| {
+        while !done() {
+            let pending = std::mem::take(&mut *events.lock().unwrap());
+            for event in pending {
+                if event.is_webview_reload() {
+                    *reloads.borrow_mut() += 1;
+                }
+                wry_bindgen.handle_user_event(event);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    };
+    let initialized = || {
+        protocol_handler.handle_request(
+            "wry",
+            &send_event,
+            &initialized_request(),
+            |_: http::Response<Vec<u8>>| {},
+        );
+    };
+    let answer = || {
+        protocol_handler.handle_request(
+            "wry",
+            |_| {},
+            &respond_request(),
+            |_: http::Response<Vec<u8>>| {},
+        );
+    };
+
+    // The first page loads and answers the first call, but not the second
+    initialized();
+    pump(&|| scripts.borrow().len() == 1);
+    answer();
+    pump(&|| scripts.borrow().len() == 2);
+    assert_eq!(*reloads.borrow(), 0);
+
+    // The page reloads. The second call fails and creates its error in the new page.
+    initialized();
+    pump(&|| scripts.borrow().len() == 3);
+    assert_eq!(*reloads.borrow(), 1);
+    answer();
+
+    let (failed, stale, generation) = result_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(failed);
+    assert!(stale);
+    assert_eq!(generation, 1);
+    pump(&|| app.is_finished());
+    app.join().unwrap();
+}
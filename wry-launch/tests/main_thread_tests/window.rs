@@ -0,0 +1,25 @@
+use futures_util::StreamExt;
+use wasm_bindgen::JsValue;
+use wry_launch::window::{on_message, open, post_message};
+
+/// Test posting a message to this window and receiving it in a registered handler
+pub(crate) async fn test_post_message_to_self() {
+    let (tx, mut rx) = futures_channel::mpsc::unbounded();
+    let _listener = on_message(move |event| {
+        tx.unbounded_send((event.data().as_string(), event.origin()))
+            .unwrap();
+    });
+
+    post_message(&JsValue::from_str("hello from rust"), "*");
+
+    let (data, origin) = rx.next().await.unwrap();
+    assert_eq!(data.as_deref(), Some("hello from rust"));
+    assert!(!origin.is_empty(), "message origin should be set");
+}
+
+/// Test that a popup the webview refuses to open is reported as `None`
+pub(crate) fn test_open_blocked_popup() {
+    // The test webview denies every new window request
+    let popup = open("about:blank", "_blank", "width=200,height=200");
+    assert!(popup.is_none(), "the test webview should block popups");
+}
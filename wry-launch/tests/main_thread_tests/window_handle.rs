@@ -0,0 +1,162 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_channel::oneshot;
+use wasm_bindgen::IPCMessage;
+use wasm_bindgen::batch::metrics;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::wry::{WindowHandle, WryBindgen};
+use wry_launch::wry::http;
+
+#[wasm_bindgen(inline_js = "export function add_one(x) { return x + 1; }")]
+extern "C" {
+    fn add_one(x: u32) -> u32;
+}
+
+/// Test that a window handle scopes JS calls to the current webview
+pub(crate) fn test_window_handle_current() {
+    let window = WindowHandle::current();
+    assert!(window.is_open());
+    assert_eq!(window.run(|| add_one(1)), 2);
+
+    let same_window = WindowHandle::from_id(window.id()).expect("the window is registered");
+    assert_eq!(same_window.id(), window.id());
+    assert_eq!(same_window.run(|| window.run(|| add_one(41))), 42);
+
+    assert!(WindowHandle::from_id(u64::MAX).is_none());
+}
+
+/// Test that a value from one window acts as undefined in another and is freed in its own
+pub(crate) fn test_window_values_stay_in_their_window() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let send_event = {
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(event)
+    };
+    let wry_bindgen = WryBindgen::new(send_event.clone());
+
+    // Two webviews whose apps run on one thread, with both pages played by this thread
+    let app_builder_a = wry_bindgen.app_builder();
+    let protocol_handler_a = app_builder_a.protocol_handler();
+    let app_builder_b = wry_bindgen.app_builder();
+    let protocol_handler_b = app_builder_b.protocol_handler();
+
+    let (b_started_tx, b_started_rx) = oneshot::channel();
+    let (a_done_tx, a_done_rx) = oneshot::channel();
+    let scripts_b = Rc::new(RefCell::new(Vec::new()));
+    let prepared_b = app_builder_b.build(
+        move || async move {
+            b_started_tx.send(()).unwrap();
+            a_done_rx.await.unwrap();
+        },
+        {
+            let scripts_b = scripts_b.clone();
+            move |script: &str| scripts_b.borrow_mut().push(script.to_string())
+        },
+    );
+    let id_b = prepared_b.id();
+
+    let (result_tx, result_rx) = std::sync::mpsc::channel();
+    let scripts_a = Rc::new(RefCell::new(Vec::new()));
+    let prepared_a = app_builder_a.build(
+        move || async move {
+            let value = JsValue::from_str("window a");
+            b_started_rx.await.unwrap();
+            let window_a = WindowHandle::current();
+            let window_b = WindowHandle::from_id(id_b).expect("window b is running");
+
+            let in_b = window_b.run(|| (value.is_stale(), value.id()));
+            let in_a = (value.is_stale(), value.id());
+
+            // Dropping it inside window b frees it in window a, not in window b
+            let calls_a = metrics().calls;
+            let calls_b = window_b.run(metrics).calls;
+            window_b.run(move || drop(value));
+            window_b.flush_pending_drops();
+            window_a.flush_pending_drops();
+            let freed_in_a = metrics().calls - calls_a;
+            let freed_in_b = window_b.run(metrics).calls - calls_b;
+
+            result_tx
+                .send((in_b, in_a, freed_in_a, freed_in_b))
+                .unwrap();
+            a_done_tx.send(()).unwrap();
+        },
+        {
+            let scripts_a = scripts_a.clone();
+            move |script: &str| scripts_a.borrow_mut().push(script.to_string())
+        },
+    );
+
+    let app = std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(futures_util::future::join(
+                prepared_a.into_future(),
+                prepared_b.into_future(),
+            ));
+    });
+
+    // Both pages load, then answer every call until both apps finish
+    for protocol_handler in [&protocol_handler_a, &protocol_handler_b] {
+        protocol_handler.handle_request(
+            "wry",
+            &send_event,
+            &request("initialized", None),
+            |_: http::Response<Vec<u8>>| {},
+        );
+    }
+    let mut answered = [0, 0];
+    while !app.is_finished() {
+        let pending = std::mem::take(&mut *events.lock().unwrap());
+        for event in pending {
+            wry_bindgen.handle_user_event(event);
+        }
+        let pages = [
+            (&scripts_a, &protocol_handler_a),
+            (&scripts_b, &protocol_handler_b),
+        ];
+        for ((scripts, protocol_handler), answered) in pages.into_iter().zip(&mut answered) {
+            while *answered < scripts.borrow().len() {
+                protocol_handler.handle_request(
+                    "wry",
+                    |_| {},
+                    &request("handler", Some(&respond())),
+                    |_: http::Response<Vec<u8>>| {},
+                );
+                *answered += 1;
+            }
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    app.join().unwrap();
+
+    let ((stale_in_b, id_in_b), (stale_in_a, id_in_a), freed_in_a, freed_in_b) =
+        result_rx.recv().unwrap();
+    assert!(stale_in_b);
+    assert_eq!(id_in_b, JsValue::UNDEFINED.id());
+    assert!(!stale_in_a);
+    assert_ne!(id_in_a, JsValue::UNDEFINED.id());
+    assert_eq!(freed_in_a, 1);
+    assert_eq!(freed_in_b, 0);
+}
+
+/// Build a request from a page to one of the bindgen endpoints, with an optional message
+fn request(path: &str, message: Option<&[u8]>) -> http::Request<Vec<u8>> {
+    use base64::Engine;
+
+    let mut request = http::Request::builder().uri(format!("wry://index.html/__wbg__/{path}"));
+    if let Some(message) = message {
+        let header = base64::engine::general_purpose::STANDARD.encode(message);
+        request = request.header("dioxus-data", header);
+    }
+    request.body(Vec::new()).unwrap()
+}
+
+/// An empty Respond message from JS
+fn respond() -> Vec<u8> {
+    IPCMessage::new_respond(|_| {}).into_data()
+}
@@ -0,0 +1,31 @@
+use wasm_bindgen::{BinaryDecode, BinaryEncode, DecodeError, DecodedData, EncodedData};
+
+/// Test decoding a large byte field by reference into the message buffer
+pub(crate) fn test_take_bytes_ref_borrows_buffer() {
+    let payload: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+
+    let mut encoder = EncodedData::new();
+    (payload.len() as u32).encode(&mut encoder);
+    encoder.push_bytes(&payload);
+    let bytes = encoder.to_bytes();
+
+    let mut decoder = DecodedData::from_bytes(&bytes).unwrap();
+    let len = u32::decode(&mut decoder).unwrap() as usize;
+    let slice = decoder.take_bytes_ref(len).unwrap();
+    assert_eq!(slice, payload.as_slice());
+
+    // The slice points into the message buffer instead of a fresh allocation
+    let buffer = bytes.as_ptr_range();
+    let borrowed = slice.as_ptr_range();
+    assert!(buffer.start <= borrowed.start && borrowed.end <= buffer.end);
+
+    // Asking for more bytes than remain is an error, not a panic
+    let err = decoder.take_bytes_ref(1).unwrap_err();
+    assert_eq!(
+        err,
+        DecodeError::U8BufferTooShort {
+            expected: 1,
+            actual: 0,
+        }
+    );
+}
@@ -0,0 +1,164 @@
+//! Bindings driven by the in-process test harness, without a webview or display.
+
+use std::collections::BTreeMap;
+
+use wasm_bindgen::test_harness::{TestHarness, Value};
+use wasm_bindgen::{JsValue, wasm_bindgen};
+
+mod codec_fixture;
+
+#[wasm_bindgen(inline_js = r#"
+    export function add(a, b) { return a + b; }
+    export function shout(text) { return text.toUpperCase(); }
+    export function first_even(values) { return values.find((v) => v % 2 === 0); }
+    export function make_point(x, y) { return { x, y }; }
+    export function point_x(point) { return point.x; }
+    export function pair(a, b) { return [b, a]; }
+"#)]
+extern "C" {
+    fn add(a: f64, b: f64) -> f64;
+    fn shout(text: &str) -> String;
+    fn first_even(values: Vec<u32>) -> Option<u32>;
+    fn make_point(x: f64, y: f64) -> JsValue;
+    fn point_x(point: &JsValue) -> f64;
+    fn pair(a: i32, b: String) -> (String, i32);
+}
+
+fn harness() -> TestHarness {
+    let harness = TestHarness::new();
+    harness.define("add", |args| {
+        Value::Number(args[0].as_f64().unwrap() + args[1].as_f64().unwrap())
+    });
+    harness.define("shout", |args| {
+        Value::String(args[0].as_str().unwrap().to_uppercase())
+    });
+    harness.define("first_even", |args| {
+        let Value::Array(values) = &args[0] else {
+            panic!("expected an array");
+        };
+        values
+            .iter()
+            .find(|v| v.as_f64().unwrap() % 2.0 == 0.0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+    });
+    harness.define("make_point", |args| {
+        let mut point = BTreeMap::new();
+        point.insert("x".to_string(), args[0].clone());
+        point.insert("y".to_string(), args[1].clone());
+        Value::Object(point)
+    });
+    harness.define("point_x", |args| {
+        let Value::Object(point) = &args[0] else {
+            panic!("expected an object");
+        };
+        point["x"].clone()
+    });
+    harness.define("pair", |args| {
+        Value::Array(vec![args[1].clone(), args[0].clone()])
+    });
+    harness
+}
+
+#[test]
+fn roundtrip_values_in_process() {
+    let mut harness = harness();
+    harness.run(|| {
+        assert_eq!(add(1.5, 2.0), 3.5);
+        assert_eq!(shout("hello"), "HELLO");
+        assert_eq!(first_even(vec![1, 3, 4, 6]), Some(4));
+        assert_eq!(first_even(vec![1, 3]), None);
+        assert_eq!(pair(7, "seven".to_string()), ("seven".to_string(), 7));
+    });
+}
+
+#[test]
+fn heap_references_in_process() {
+    let mut harness = harness();
+    harness.run(|| {
+        let point = make_point(3.0, 4.0);
+        assert_eq!(point_x(&point), 3.0);
+
+        // Inside a batch the point is created in a reserved heap slot and read in the same message
+        let x = wasm_bindgen::batch::batch(|| point_x(&make_point(5.0, 6.0)));
+        assert_eq!(x, 5.0);
+    });
+    assert_eq!(harness.heap_objects_alive(), 0);
+}
+
+#[test]
+#[should_panic(expected = "no handler for the JS function `missing`")]
+fn missing_handler_panics_with_code() {
+    #[wasm_bindgen(inline_js = "export function missing(x) { return x; }")]
+    extern "C" {
+        fn missing(x: u32) -> u32;
+    }
+
+    TestHarness::new().run(|| missing(1));
+}
+
+/// The JSON that `JSON.stringify` produces for a value
+fn to_json(value: &Value) -> String {
+    match value {
+        Value::Undefined | Value::Null => "null".to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) => value.to_string(),
+        Value::String(value) => serde_json::to_string(value).unwrap(),
+        Value::Array(values) => {
+            let values: Vec<_> = values.iter().map(to_json).collect();
+            format!("[{}]", values.join(","))
+        }
+        Value::Object(entries) => {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|(key, value)| {
+                    format!("{}:{}", to_json(&Value::from(key.as_str())), to_json(value))
+                })
+                .collect();
+            format!("{{{}}}", entries.join(","))
+        }
+        Value::Callback(_) => panic!("callbacks have no JSON"),
+    }
+}
+
+#[test]
+fn codec_matches_js() {
+    let mut harness = TestHarness::new();
+    harness.define("JSON.stringify", |args| Value::String(to_json(&args[0])));
+    harness.define("identity", |args| args[0].clone());
+    harness.run(codec_fixture::check_codec_fixture);
+}
+
+#[test]
+fn handlers_match_import_names() {
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = Math)]
+        fn max(a: f64, b: f64) -> f64;
+
+        type Counter;
+
+        #[wasm_bindgen(constructor)]
+        fn new() -> Counter;
+
+        #[wasm_bindgen(method)]
+        fn increment(this: &Counter) -> u32;
+
+        #[wasm_bindgen(method, getter)]
+        fn count(this: &Counter) -> u32;
+    }
+
+    let mut harness = TestHarness::new();
+    harness.define("Math.max", |args| {
+        Value::Number(args[0].as_f64().unwrap().max(args[1].as_f64().unwrap()))
+    });
+    harness.define("new Counter", |_| Value::Object(BTreeMap::new()));
+    harness.define("Counter.prototype.increment", |_| Value::Number(1.0));
+    harness.define("get Counter.prototype.count", |_| Value::Number(7.0));
+    harness.run(|| {
+        assert_eq!(max(2.0, 3.0), 3.0);
+        let counter = Counter::new();
+        assert_eq!(counter.increment(), 1);
+        assert_eq!(counter.count(), 7);
+    });
+}